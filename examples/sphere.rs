@@ -1,6 +1,6 @@
 //! Raymarched sphere with orbiting camera - demonstrates world-space effects.
 
-use hoplite::{AppConfig, OrbitCamera, OrbitMode, Vec3, run_with_config};
+use hoplite::{AppConfig, KeyCode, OrbitCamera, OrbitMode, Vec3, run_with_config};
 
 fn main() {
     run_with_config(AppConfig::new().title("Raymarched Sphere"), |ctx| {
@@ -14,6 +14,10 @@ fn main() {
             .mode(OrbitMode::AutoRotate { speed: 0.5 });
 
         move |frame| {
+            if frame.input.key_pressed(KeyCode::Escape) {
+                frame.exit();
+            }
+
             orbit.update(frame.input, frame.dt);
             frame.set_camera(orbit.camera());
         }
@@ -72,32 +72,46 @@
 //! ctx.hot_post_process("shaders/bloom.wgsl");     // Reloads on file change
 //! ```
 
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::window::{Window, WindowAttributes, WindowId};
 
 use crate::assets::{Assets, FontId};
+use crate::billboard::{Billboard, BillboardMode};
 use crate::camera::Camera;
+use crate::clock::Clock;
 use crate::draw2d::SpriteId;
-use crate::draw2d::{Color, Draw2d};
-use crate::ecs::{MeshId, TextureId};
+use crate::draw2d::{Color, Draw2d, Rect, SpriteInstance, TextAlign};
+use crate::ecs::{MaterialId, MeshId, TextureId};
 use crate::effect_pass::EffectPass;
-use crate::geometry::PendingGeometry;
-use crate::gpu::GpuContext;
+use crate::error::Error;
+use crate::geometry::{GeometryError, PendingGeometry};
+use crate::gpu::{GpuContext, GpuOptions};
 use crate::hot_shader::{HotEffectPass, HotPostProcessPass, HotWorldPostProcessPass};
 use crate::input::Input;
-use crate::mesh::{Mesh, Transform};
-use crate::picking::{self, PickResult, Ray, RayHit};
+use crate::mesh::{Mesh, Transform, UvTransform};
+use crate::mesh_pass::CullMode;
+use crate::model::{self, GltfModel, ModelPart};
+use crate::particles::ParticleSystem;
+use crate::picking::{self, Collider, PickResult, Ray, RayHit};
 use crate::post_process::{PostProcessPass, WorldPostProcessPass};
 use crate::render_graph::{
-    EffectNode, HotEffectNode, HotPostProcessNode, HotWorldPostProcessNode, MeshNode, MeshQueue,
-    PostProcessNode, RenderGraph, WorldPostProcessNode,
+    BloomHandle, BloomNode, BloomSettings, EffectNode, HotEffectNode, HotPostProcessNode,
+    HotWorldPostProcessNode, MeshNode, MeshQueue, PostProcessNode, QueuedMesh, RenderGraph,
+    SkyboxNode, WorldPostProcessNode,
 };
+use crate::render_stats::RenderStats;
+use crate::screenshot::CaptureTarget;
+use crate::sprite_animation::SpriteAnimation;
 use crate::texture::{Sprite, Texture};
-use glam::{Quat, Vec3};
+use crate::timestep::{FixedTimestep, FixedUpdate};
+use crate::ui;
+use crate::uniforms::UniformHandle;
+use glam::{Quat, Vec2, Vec3};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -190,7 +204,7 @@ impl<'a> MeshLoader<'a> {
     /// Build the mesh and register it, returning the [`MeshId`].
     ///
     /// Returns an error if loading or parsing failed.
-    pub fn build(self) -> Result<MeshId, crate::geometry::GeometryError> {
+    pub fn build(self) -> Result<MeshId, GeometryError> {
         let mesh = self.pending.upload(self.gpu)?;
         Ok(self.mesh_queue.borrow_mut().add_mesh(mesh))
     }
@@ -272,6 +286,8 @@ pub struct SetupContext<'a> {
     graph_builder: &'a mut Option<RenderGraph>,
     /// Shared mesh queue for 3D rendering.
     mesh_queue: &'a Rc<RefCell<MeshQueue>>,
+    /// Callbacks the app loop runs outside the frame closure.
+    hooks: &'a mut AppHooks,
 }
 
 impl<'a> SetupContext<'a> {
@@ -287,7 +303,8 @@ impl<'a> SetupContext<'a> {
     /// # Returns
     ///
     /// The [`FontId`] for the loaded font, which can also be used with the
-    /// lower-level `Draw2d::text` method if needed.
+    /// lower-level `Draw2d::text` method if needed. It's the same font
+    /// [`font`](Self::font) returns for this size.
     ///
     /// # Example
     ///
@@ -297,70 +314,212 @@ impl<'a> SetupContext<'a> {
     /// frame.text(10.0, 10.0, "Hello!");
     /// ```
     pub fn default_font(&mut self, size: f32) -> FontId {
-        let font = self.assets.default_font(self.gpu, size);
+        let font = self.font(size);
         *self.default_font = Some(font);
         font
     }
 
+    /// Load the default typeface at the specified size, or return it if already loaded.
+    ///
+    /// Unlike [`default_font`](Self::default_font), this doesn't change the font
+    /// used by `frame.text()`, so several sizes can be kept around for HUDs with
+    /// small and large text. Sizes are rounded to whole pixels and each atlas is
+    /// built once: `font(16.0)` returns the same [`FontId`] on every call.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.default_font(16.0);
+    /// let small = ctx.font(12.0);
+    /// let large = ctx.font(32.0);
+    ///
+    /// move |frame| {
+    ///     frame.text(10.0, 10.0, "Score: 100");
+    ///     frame.text_with(large, 10.0, 40.0, "GAME OVER", Color::WHITE);
+    ///     frame.text_with(small, 10.0, 80.0, "Press R to restart", Color::WHITE);
+    /// }
+    /// ```
+    pub fn font(&mut self, size: f32) -> FontId {
+        self.assets.default_font(self.gpu, size)
+    }
+
+    /// Load the default font as a signed distance field and make it the default.
+    ///
+    /// Text drawn with it stays sharp at any size via [`Frame::text_sized`] and
+    /// can have outlines and glows (see [`Draw2d::set_text_effects`]). `size` is
+    /// the size `frame.text()` draws at; 32-64 works well.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.default_font_sdf(48.0);
+    /// // Later in frame:
+    /// frame.text_sized(20.0, 20.0, 128.0, "TITLE", Color::WHITE);
+    /// ```
+    pub fn default_font_sdf(&mut self, size: f32) -> FontId {
+        let font = self.assets.default_font_sdf(self.gpu, size);
+        *self.default_font = Some(font);
+        font
+    }
+
+    /// Load a TTF/OTF font from a file at the specified size.
+    ///
+    /// Unlike [`default_font`](Self::default_font), this doesn't change the font
+    /// used by `frame.text()`. Draw with it via [`Frame::text_with`], or make it
+    /// current for a block of text with [`Frame::push_font`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be read or is not a valid font.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let title = ctx.load_font("fonts/Title.ttf", 48.0);
+    /// let body = ctx.load_font("fonts/Body.ttf", 16.0);
+    /// ```
+    pub fn load_font(&mut self, path: impl AsRef<std::path::Path>, size: f32) -> FontId {
+        self.assets.load_font(self.gpu, path, size)
+    }
+
+    /// Load a TTF/OTF font from raw bytes at the specified size.
+    ///
+    /// Useful with `include_bytes!` to embed a typeface in the binary. See
+    /// [`load_font`](Self::load_font) for how to draw with it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes are not a valid font.
+    pub fn load_font_bytes(&mut self, data: &[u8], size: f32) -> FontId {
+        self.assets.load_font_bytes(self.gpu, data, size)
+    }
+
+    /// Load a TTF/OTF font file to draw the characters `font` lacks.
+    ///
+    /// Fonts only hold ASCII glyphs by default, so accented letters, CJK, and
+    /// symbols render as blanks. The fallback is rasterized at `font`'s size
+    /// with the glyphs it has among `chars`, and text in `font` uses it for any
+    /// character `font` doesn't have. Add several to search them in order. Each
+    /// size of a font has its own fallbacks.
+    ///
+    /// Every glyph is rasterized up front, so prefer the ranges the app
+    /// actually shows over entire scripts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a font, or the
+    /// glyphs don't fit in an atlas.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let font = ctx.default_font(16.0);
+    /// ctx.load_font_fallback(font, "fonts/NotoSans.ttf", '\u{a0}'..='\u{17f}')?; // Latin accents
+    /// ctx.load_font_fallback(font, "fonts/NotoSansJP.ttf", "こんにちは世界".chars())?;
+    ///
+    /// move |frame| {
+    ///     frame.text(10.0, 10.0, "Café — こんにちは世界");
+    /// }
+    /// ```
+    pub fn load_font_fallback(
+        &mut self,
+        font: FontId,
+        path: impl AsRef<std::path::Path>,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<FontId, Error> {
+        self.assets
+            .load_fallback_font_from_file(self.gpu, font, path, chars)
+    }
+
+    /// Draw `sprite` for `ch` in text when no font has a glyph for it.
+    ///
+    /// Use this for color emoji and icons. See [`Draw2d::add_color_glyph`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let star = ctx.sprite_from_file("emoji/star.png")?;
+    /// ctx.color_glyph('⭐', star);
+    /// ```
+    pub fn color_glyph(&mut self, ch: char, sprite: SpriteId) -> &mut Self {
+        self.draw.add_color_glyph(ch, sprite);
+        self
+    }
+
     // ========================================================================
     // Background Color
     // ========================================================================
 
-    /// Set a solid background color for the application.
+    /// Set the color the screen is cleared to at the start of each frame.
     ///
-    /// This is the simplest way to set a background - no shader required.
-    /// Use this when you just want a solid color behind your content.
+    /// The first render pass clears to this color instead of black: the first
+    /// node of the render graph when it has no input to draw over, such as
+    /// [`enable_mesh_rendering`](Self::enable_mesh_rendering), or the 2D pass
+    /// when there is no render graph. No shader or render node is created, so
+    /// it can be called at any point during setup. Effects and skyboxes cover
+    /// the whole screen and draw over it.
     ///
     /// For dynamic backgrounds, use [`effect`](Self::effect) or
     /// [`hot_effect`](Self::hot_effect) instead.
     ///
-    /// # Arguments
-    ///
-    /// * `color` - Background color (use [`Color`](crate::Color) for convenience)
-    ///
     /// # Example
     ///
     /// ```ignore
-    /// ctx.background_color(Color::rgb(0.1, 0.1, 0.15)); // Dark blue-gray
+    /// ctx.clear_color(Color::rgb(0.1, 0.1, 0.15)); // Dark blue-gray
     /// ctx.enable_mesh_rendering();
     ///
     /// move |frame| {
     ///     frame.mesh(cube).at(0.0, 0.0, -5.0).draw();
     /// }
     /// ```
-    pub fn background_color(&mut self, color: Color) -> &mut Self {
-        // Create a minimal shader that just outputs the color
-        let shader = format!(
-            r#"
-@group(0) @binding(0) var<uniform> u: Uniforms;
-
-struct Uniforms {{
-    resolution: vec2f,
-    time: f32,
-}}
-
-@vertex
-fn vs(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4f {{
-    var pos = array<vec2f, 3>(
-        vec2f(-1.0, -1.0),
-        vec2f(3.0, -1.0),
-        vec2f(-1.0, 3.0)
-    );
-    return vec4f(pos[vertex_index], 0.0, 1.0);
-}}
-
-@fragment
-fn fs() -> @location(0) vec4f {{
-    return vec4f({:.6}, {:.6}, {:.6}, {:.6});
-}}
-"#,
-            color.r, color.g, color.b, color.a
-        );
-        let effect = EffectPass::new(self.gpu, &shader);
-        self.add_node(EffectNode::new(effect));
+    pub fn clear_color(&mut self, color: Color) -> &mut Self {
+        self.hooks.clear_color = Some(color);
+        if let Some(graph) = self.graph_builder {
+            graph.set_clear_color(color.into());
+        }
         self
     }
 
+    /// Set a solid background color for the application.
+    ///
+    /// Same as [`clear_color`](Self::clear_color).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.background_color(Color::rgb(0.1, 0.1, 0.15)); // Dark blue-gray
+    /// ```
+    pub fn background_color(&mut self, color: Color) -> &mut Self {
+        self.clear_color(color)
+    }
+
+    /// Set a cubemap skybox as the scene background.
+    ///
+    /// Loads six face images in the order +X, -X, +Y, -Y, +Z, -Z and adds a
+    /// render node that draws them around the camera. Call this first, before
+    /// [`enable_mesh_rendering`](Self::enable_mesh_rendering).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a face can't be loaded or the faces aren't square
+    /// and the same size.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.skybox_from_files([
+    ///     "sky/px.png", "sky/nx.png",
+    ///     "sky/py.png", "sky/ny.png",
+    ///     "sky/pz.png", "sky/nz.png",
+    /// ])?
+    /// .enable_mesh_rendering();
+    /// ```
+    pub fn skybox_from_files(&mut self, paths: [&str; 6]) -> Result<&mut Self, Error> {
+        let cubemap = Texture::cubemap_from_files(self.gpu, paths)?;
+        self.add_node(SkyboxNode::new(self.gpu, cubemap));
+        Ok(self)
+    }
+
     // ========================================================================
     // Shader Effect Methods (Embedded)
     // ========================================================================
@@ -378,17 +537,53 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
     ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
+    ///
     /// # Example
     ///
     /// ```ignore
     /// ctx.effect(include_str!("shaders/gradient.wgsl"));
     /// ```
     pub fn effect(&mut self, shader: &str) -> &mut Self {
-        let effect = EffectPass::new(self.gpu, shader);
-        self.add_node(EffectNode::new(effect));
+        match EffectPass::new(self.gpu, shader) {
+            Ok(effect) => self.add_node(EffectNode::new(effect)),
+            Err(e) => eprintln!("[shader] Failed to create effect: {}", e),
+        }
         self
     }
 
+    /// Add a screen-space shader effect with a user uniform buffer.
+    ///
+    /// Like [`Self::effect`], but the shader also declares a struct matching
+    /// `T` at `@group(1) @binding(0)`, initialized to `initial`. Returns a
+    /// handle for updating the values each frame with [`Frame::set_uniforms`],
+    /// or `None` if the shader fails to compile (the error is printed to stderr).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let waves = ctx.effect_with_uniforms(include_str!("shaders/waves.wgsl"), &Waves::default());
+    /// ```
+    pub fn effect_with_uniforms<T: bytemuck::Pod>(
+        &mut self,
+        shader: &str,
+        initial: &T,
+    ) -> Option<UniformHandle> {
+        match EffectPass::with_uniforms::<T>(self.gpu, shader) {
+            Ok(effect) => {
+                effect.set_uniforms(self.gpu, initial);
+                let handle = effect.uniform_handle();
+                self.add_node(EffectNode::new(effect));
+                handle
+            }
+            Err(e) => {
+                eprintln!("[shader] Failed to create effect: {}", e);
+                None
+            }
+        }
+    }
+
     /// Add a fullscreen world-space shader effect to the render pipeline.
     ///
     /// World-space effects receive camera uniforms (view matrix, projection matrix,
@@ -399,6 +594,9 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
     ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -406,8 +604,10 @@ fn fs() -> @location(0) vec4f {{
     /// ctx.effect_world(include_str!("shaders/raymarching.wgsl"));
     /// ```
     pub fn effect_world(&mut self, shader: &str) -> &mut Self {
-        let effect = EffectPass::new_world(self.gpu, shader);
-        self.add_node(EffectNode::new(effect));
+        match EffectPass::new_world(self.gpu, shader) {
+            Ok(effect) => self.add_node(EffectNode::new(effect)),
+            Err(e) => eprintln!("[shader] Failed to create effect: {}", e),
+        }
         self
     }
 
@@ -421,17 +621,121 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
     ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
+    ///
     /// # Example
     ///
     /// ```ignore
     /// ctx.post_process(include_str!("shaders/vignette.wgsl"));
     /// ```
     pub fn post_process(&mut self, shader: &str) -> &mut Self {
-        let pass = PostProcessPass::new(self.gpu, shader);
-        self.add_node(PostProcessNode::new(pass));
+        match PostProcessPass::new(self.gpu, shader) {
+            Ok(pass) => self.add_node(PostProcessNode::new(pass)),
+            Err(e) => eprintln!("[shader] Failed to create post-process pass: {}", e),
+        }
+        self
+    }
+
+    /// Add a screen-space post-processing effect with a user uniform buffer.
+    ///
+    /// Like [`Self::post_process`], but the shader also declares a struct
+    /// matching `T` at `@group(1) @binding(0)`, initialized to `initial`.
+    /// Returns a handle for updating the values each frame with
+    /// [`Frame::set_uniforms`], or `None` if the shader fails to compile (the
+    /// error is printed to stderr).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let bloom = ctx.post_process_with_uniforms(
+    ///     include_str!("shaders/bloom.wgsl"),
+    ///     &Bloom { threshold: 1.0, intensity: 0.6, _pad: [0.0; 2] },
+    /// );
+    /// ```
+    pub fn post_process_with_uniforms<T: bytemuck::Pod>(
+        &mut self,
+        shader: &str,
+        initial: &T,
+    ) -> Option<UniformHandle> {
+        match PostProcessPass::with_uniforms::<T>(self.gpu, shader) {
+            Ok(pass) => {
+                pass.set_uniforms(self.gpu, initial);
+                let handle = pass.uniform_handle();
+                self.add_node(PostProcessNode::new(pass));
+                handle
+            }
+            Err(e) => {
+                eprintln!("[shader] Failed to create post-process pass: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Add a screen-space post-processing effect that samples extra textures.
+    ///
+    /// Like [`Self::post_process`], but each texture (registered with
+    /// [`Self::add_texture`] or [`Self::texture_from_file`]) is bound after the
+    /// standard bindings together with its sampler: the first at bindings 3
+    /// and 4, the next at 5 and 6, and so on. Useful for color grading LUTs,
+    /// lens dirt, or noise.
+    ///
+    /// If a texture id is unknown or the shader fails to compile, the error is
+    /// printed to stderr and the pass is skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let lut = ctx.texture_from_file("assets/grade_lut.png")?;
+    /// ctx.post_process_with_textures(include_str!("shaders/grade.wgsl"), &[lut]);
+    /// ```
+    pub fn post_process_with_textures(
+        &mut self,
+        shader: &str,
+        textures: &[TextureId],
+    ) -> &mut Self {
+        let result = {
+            let queue = self.mesh_queue.borrow();
+            let resolved: Option<Vec<&Texture>> =
+                textures.iter().map(|id| queue.texture(*id)).collect();
+            resolved.map(|resolved| PostProcessPass::new_with_textures(self.gpu, shader, &resolved))
+        };
+        match result {
+            Some(Ok(pass)) => self.add_node(PostProcessNode::new(pass)),
+            Some(Err(e)) => eprintln!("[shader] Failed to create post-process pass: {}", e),
+            None => eprintln!("[shader] Failed to create post-process pass: unknown texture id"),
+        }
         self
     }
 
+    /// Add a built-in bloom effect.
+    ///
+    /// Bright parts of the previous pass (brightness above `threshold`) are
+    /// blurred over `iterations` rounds at half resolution and added back,
+    /// scaled by `intensity`. Returns a handle for tuning the parameters live.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.effect_world(include_str!("shaders/scene.wgsl"));
+    /// let bloom = ctx.bloom(0.8, 0.6, 4);
+    /// // later, in the frame closure:
+    /// bloom.set_intensity(1.0);
+    /// ```
+    pub fn bloom(&mut self, threshold: f32, intensity: f32, iterations: u32) -> BloomHandle {
+        let node = BloomNode::new(
+            self.gpu,
+            BloomSettings {
+                threshold,
+                intensity,
+                iterations,
+            },
+        );
+        let handle = node.handle();
+        self.add_node(node);
+        handle
+    }
+
     /// Add a world-space post-processing effect.
     ///
     /// Similar to [`Self::post_process`], but also receives camera uniforms.
@@ -442,6 +746,9 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
     ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
+    ///
     /// # Example
     ///
     /// ```ignore
@@ -449,8 +756,10 @@ fn fs() -> @location(0) vec4f {{
     /// ctx.post_process_world(include_str!("shaders/lensing.wgsl"));
     /// ```
     pub fn post_process_world(&mut self, shader: &str) -> &mut Self {
-        let pass = WorldPostProcessPass::new(self.gpu, shader);
-        self.add_node(WorldPostProcessNode::new(pass));
+        match WorldPostProcessPass::new(self.gpu, shader) {
+            Ok(pass) => self.add_node(WorldPostProcessNode::new(pass)),
+            Err(e) => eprintln!("[shader] Failed to create post-process pass: {}", e),
+        }
         self
     }
 
@@ -556,12 +865,15 @@ fn fs() -> @location(0) vec4f {{
     /// Lazily initializes the render graph on first use, then appends
     /// subsequent nodes to the existing graph.
     fn add_node<N: crate::render_graph::RenderNode + 'static>(&mut self, node: N) {
-        if self.graph_builder.is_none() {
-            *self.graph_builder = Some(RenderGraph::builder().node(node).build(self.gpu));
-        } else {
-            // Rebuild with new node - could optimize later with incremental updates
-            let old = self.graph_builder.take().unwrap();
-            *self.graph_builder = Some(old.with_node(node, self.gpu));
+        match self.graph_builder {
+            Some(graph) => graph.push_node(node, self.gpu),
+            None => {
+                let mut graph = RenderGraph::builder().node(node).build(self.gpu);
+                if let Some(color) = self.hooks.clear_color {
+                    graph.set_clear_color(color.into());
+                }
+                *self.graph_builder = Some(graph);
+            }
         }
     }
 
@@ -594,6 +906,198 @@ fn fs() -> @location(0) vec4f {{
         self
     }
 
+    /// Register a callback that runs at a fixed rate, independent of rendering.
+    ///
+    /// Every frame, the elapsed time is accumulated and `update` is called once
+    /// per whole `step` seconds (zero or more times) before the frame closure
+    /// runs. Use it for physics and other deterministic logic, and
+    /// [`Frame::fixed_alpha`] to interpolate when drawing. To avoid a spiral of
+    /// catch-up after a stall, at most
+    /// [`MAX_FIXED_STEPS_PER_FRAME`](crate::MAX_FIXED_STEPS_PER_FRAME) steps run
+    /// per frame. Registering again replaces the previous callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - Fixed step length in seconds (e.g. `1.0 / 60.0`)
+    /// * `update` - Called with the ECS world and `step`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.fixed_update(1.0 / 60.0, |world, dt| {
+    ///     for (_, (transform, velocity)) in world.query_mut::<(&mut Transform, &Velocity)>() {
+    ///         transform.position += velocity.0 * dt;
+    ///     }
+    /// });
+    /// ```
+    pub fn fixed_update(
+        &mut self,
+        step: f32,
+        update: impl FnMut(&mut hecs::World, f32) + 'static,
+    ) -> &mut Self {
+        self.hooks.fixed_update = Some(FixedUpdate {
+            timestep: FixedTimestep::new(step),
+            callback: Box::new(update),
+        });
+        self
+    }
+
+    /// Register a callback that runs whenever the window is resized.
+    ///
+    /// The callback runs right after the surface is resized, before the next
+    /// frame, with the GPU context and the new size in pixels. Use it to rebuild
+    /// size-dependent resources such as custom render targets or UI layouts.
+    /// Minimizing the window is not reported. Multiple callbacks run in
+    /// registration order.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let target = Rc::new(RefCell::new(RenderTarget::new(ctx.gpu, "Minimap")));
+    /// let resized = Rc::clone(&target);
+    /// ctx.on_resize(move |gpu, width, height| {
+    ///     println!("resized to {width}x{height}");
+    ///     resized.borrow_mut().ensure_size(gpu, "Minimap");
+    /// });
+    /// ```
+    pub fn on_resize(
+        &mut self,
+        callback: impl FnMut(&GpuContext, u32, u32) + 'static,
+    ) -> &mut Self {
+        self.hooks.resize.push(Box::new(callback));
+        self
+    }
+
+    /// Show shader compilation errors from hot-reload nodes on screen.
+    ///
+    /// While any hot-reloaded shader fails to compile, the frame is dimmed and
+    /// the errors are drawn over it, so a typo shows up without watching the
+    /// terminal. The last working shader keeps rendering underneath, and the
+    /// overlay disappears once the shader compiles again. Errors are also
+    /// available programmatically from [`RenderGraph::shader_errors`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.hot_effect_world("shaders/raymarch.wgsl")
+    ///     .shader_error_overlay();
+    /// ```
+    pub fn shader_error_overlay(&mut self) -> &mut Self {
+        let font = self.assets.default_font(self.gpu, 14.0);
+        self.hooks.error_overlay = Some(font);
+        self
+    }
+
+    /// Skip meshes that are entirely outside the camera's view.
+    ///
+    /// Each queued mesh's bounding sphere (see [`Mesh::bounding_sphere`]) is
+    /// transformed to world space and tested against the camera frustum before
+    /// any draw calls are built. Off by default. Use [`Frame::culled_count`] to
+    /// see how many meshes were skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.enable_mesh_rendering()
+    ///    .set_frustum_culling(true);
+    /// ```
+    pub fn set_frustum_culling(&mut self, enabled: bool) -> &mut Self {
+        self.mesh_queue.borrow_mut().frustum_culling = enabled;
+        self
+    }
+
+    /// Render opaque meshes' depth first so each pixel is shaded only once.
+    ///
+    /// Opaque meshes are first drawn writing depth only, then drawn again with
+    /// full shading wherever they match the nearest depth, so hidden surfaces
+    /// never run the fragment shader. This pays off in scenes with a lot of
+    /// overlapping geometry or expensive materials, at the cost of drawing
+    /// each mesh's vertices twice. Meshes with a translucent color or an
+    /// [alpha cutout](MeshBuilder::alpha_cutout) skip the prepass and draw
+    /// after the opaque ones. Off by default.
+    ///
+    /// Custom materials should mark their `@builtin(position)` output
+    /// `@invariant`, as the built-in shader does, so both passes compute
+    /// exactly the same depth.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.enable_mesh_rendering()
+    ///    .enable_depth_prepass();
+    /// ```
+    pub fn enable_depth_prepass(&mut self) -> &mut Self {
+        self.mesh_queue.borrow_mut().depth_prepass = true;
+        self
+    }
+
+    /// Record GPU timestamps around every render graph node.
+    ///
+    /// Per-node timings become available through [`Frame::gpu_timings`] and
+    /// [`RenderGraph::last_timings`]. Reading them back waits for the GPU each
+    /// frame, so only enable this while profiling. Logs a `[profiler]` message
+    /// and does nothing if the adapter doesn't support timestamp queries.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.enable_gpu_profiling();
+    /// ```
+    pub fn enable_gpu_profiling(&mut self) -> &mut Self {
+        self.gpu.set_profiling(true);
+        self
+    }
+
+    /// Set the directional "sun" light for mesh rendering.
+    ///
+    /// Until any light is set, meshes use a fixed default key light. Setting a
+    /// sun or point light switches to the configured lights.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - Direction the light travels (`-Y` shines straight down)
+    /// * `color` - Light color; components above 1.0 make it brighter
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.set_sun(Vec3::new(-0.4, -1.0, -0.2), Color::rgb(1.0, 0.95, 0.85));
+    /// ```
+    pub fn set_sun(&mut self, direction: Vec3, color: Color) -> &mut Self {
+        self.mesh_queue
+            .borrow_mut()
+            .lighting
+            .set_sun(direction, color);
+        self
+    }
+
+    /// Set the ambient light intensity applied alongside configured lights.
+    ///
+    /// Defaults to 0.15.
+    pub fn set_ambient(&mut self, ambient: f32) -> &mut Self {
+        self.mesh_queue.borrow_mut().lighting.ambient = ambient;
+        self
+    }
+
+    /// Add a point light for mesh rendering.
+    ///
+    /// The light fades smoothly to zero at `range`. Up to
+    /// [`MAX_POINT_LIGHTS`](crate::MAX_POINT_LIGHTS) point lights are used;
+    /// extras are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.add_point_light(Vec3::new(0.0, 2.0, 0.0), Color::rgb(1.0, 0.6, 0.2), 8.0);
+    /// ```
+    pub fn add_point_light(&mut self, position: Vec3, color: Color, range: f32) -> &mut Self {
+        self.mesh_queue
+            .borrow_mut()
+            .lighting
+            .add_point_light(position, color, range);
+        self
+    }
+
     /// Create a unit cube mesh (1x1x1, centered at origin).
     ///
     /// # Returns
@@ -663,30 +1167,118 @@ fn fs() -> @location(0) vec4f {{
         self.mesh_queue.borrow_mut().add_mesh(mesh)
     }
 
-    /// Add a custom mesh to the mesh queue.
+    /// Register a custom WGSL material for meshes.
     ///
-    /// Use this to add meshes created manually or loaded from external sources.
+    /// Attach it per draw with [`MeshBuilder::material`] or per entity with
+    /// [`RenderMesh::with_material`](crate::RenderMesh::with_material); other
+    /// meshes keep the built-in shader. Draws are grouped by material so each
+    /// pipeline is bound once per frame.
     ///
-    /// # Arguments
+    /// The shader must define `vs` and `fs` entry points and may use the same
+    /// bindings as the built-in mesh shader (`src/shaders/mesh.wgsl`):
     ///
-    /// * `mesh` - A [`Mesh`] instance with vertex and index data
+    /// - `@group(0) @binding(0)` camera uniforms, `@binding(1)` light uniforms
+    /// - `@group(1) @binding(0)` per-instance storage buffer (model, normal matrix, color),
+    ///   indexed by `@builtin(instance_index)`, `@binding(1)` each instance's
+    ///   texture array layer (`array<u32>`), `@binding(2)` each instance's
+    ///   UV offset, scale, and rotation (vertex stage only), and `@binding(3)`
+    ///   each instance's alpha cutoff (`array<f32>`, fragment stage only)
+    /// - `@group(2)` texture (`@binding(0)`) and sampler (`@binding(1)`), plus the
+    ///   texture array (`@binding(4)`, `texture_2d_array<f32>`)
     ///
-    /// # Returns
+    /// Vertex inputs are position (`@location(0)`), normal (`@location(1)`),
+    /// and UV (`@location(2)`). If the shader fails to compile, the error is
+    /// printed to stderr and meshes using it fall back to the built-in shader.
     ///
-    /// A type-safe [`MeshId`] for use with [`Frame::mesh`] or [`Frame::draw_mesh`].
-    pub fn add_mesh(&mut self, mesh: Mesh) -> MeshId {
-        self.mesh_queue.borrow_mut().add_mesh(mesh)
-    }
-
-    // ========================================================================
-    // Geometry Loading
-    // ========================================================================
+    /// # Example
+    ///
+    /// ```ignore
+    /// let water = ctx.add_material(include_str!("shaders/water.wgsl"));
+    /// let lake = ctx.mesh_plane(20.0);
+    ///
+    /// move |frame| {
+    ///     frame.mesh(lake).material(water).draw();
+    /// }
+    /// ```
+    pub fn add_material(&mut self, wgsl: &str) -> MaterialId {
+        self.mesh_queue.borrow_mut().add_material(wgsl)
+    }
+
+    /// Create a capped cylinder mesh along the Y axis, centered at the origin.
+    ///
+    /// See [`Mesh::cylinder`] for geometry details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let pillar = ctx.mesh_cylinder(0.5, 3.0, 24);
+    /// ```
+    pub fn mesh_cylinder(&mut self, radius: f32, height: f32, segments: u32) -> MeshId {
+        let mesh = Mesh::cylinder(self.gpu, radius, height, segments);
+        self.mesh_queue.borrow_mut().add_mesh(mesh)
+    }
+
+    /// Create a cone mesh along the Y axis with its tip pointing up.
+    ///
+    /// See [`Mesh::cone`] for geometry details.
+    pub fn mesh_cone(&mut self, radius: f32, height: f32, segments: u32) -> MeshId {
+        let mesh = Mesh::cone(self.gpu, radius, height, segments);
+        self.mesh_queue.borrow_mut().add_mesh(mesh)
+    }
+
+    /// Create a torus mesh lying flat on the XZ plane.
+    ///
+    /// See [`Mesh::torus`] for geometry details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let donut = ctx.mesh_torus(1.0, 0.3, 32, 16);
+    /// ```
+    pub fn mesh_torus(
+        &mut self,
+        major_radius: f32,
+        minor_radius: f32,
+        segments: u32,
+        rings: u32,
+    ) -> MeshId {
+        let mesh = Mesh::torus(self.gpu, major_radius, minor_radius, segments, rings);
+        self.mesh_queue.borrow_mut().add_mesh(mesh)
+    }
+
+    /// Create a capsule mesh along the Y axis; `height` includes both end caps.
+    ///
+    /// See [`Mesh::capsule`] for geometry details.
+    pub fn mesh_capsule(&mut self, radius: f32, height: f32, segments: u32, rings: u32) -> MeshId {
+        let mesh = Mesh::capsule(self.gpu, radius, height, segments, rings);
+        self.mesh_queue.borrow_mut().add_mesh(mesh)
+    }
+
+    /// Add a custom mesh to the mesh queue.
+    ///
+    /// Use this to add meshes created manually or loaded from external sources.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - A [`Mesh`] instance with vertex and index data
+    ///
+    /// # Returns
+    ///
+    /// A type-safe [`MeshId`] for use with [`Frame::mesh`] or [`Frame::draw_mesh`].
+    pub fn add_mesh(&mut self, mesh: Mesh) -> MeshId {
+        self.mesh_queue.borrow_mut().add_mesh(mesh)
+    }
+
+    // ========================================================================
+    // Geometry Loading
+    // ========================================================================
 
     /// Load 3D geometry from a file with a fluent builder API.
     ///
     /// This is the fun, ergonomic way to load models! The format is detected
     /// from the file extension. Currently supports:
     /// - `.stl` - STL files (binary and ASCII)
+    /// - `.obj` - Wavefront OBJ files
     ///
     /// # The Fluent API
     ///
@@ -763,6 +1355,80 @@ fn fs() -> @location(0) vec4f {{
         }
     }
 
+    /// Load a static mesh from a Wavefront OBJ file.
+    ///
+    /// Positions, normals, and UVs are read and polygonal faces are triangulated.
+    /// Faces without normals get flat normals computed from their winding, and
+    /// missing UVs default to `[0.0, 0.0]`.
+    ///
+    /// Use [`Self::load`] instead if you want to center, scale, or otherwise
+    /// transform the geometry before upload.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the `.obj` file
+    ///
+    /// # Returns
+    ///
+    /// A type-safe [`MeshId`] on success, or a [`GeometryError`] on failure.
+    /// Malformed files report the offending line via [`GeometryError::Parse`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let teapot = ctx.mesh_from_obj("assets/teapot.obj")?;
+    /// // In frame:
+    /// frame.mesh(teapot).at(0.0, 0.0, -5.0).draw();
+    /// ```
+    pub fn mesh_from_obj(&mut self, path: &str) -> Result<MeshId, GeometryError> {
+        let geometry = PendingGeometry::load_obj_file(Path::new(path))?;
+        Ok(self.add_mesh(geometry.upload(self.gpu)))
+    }
+
+    /// Load every mesh in a glTF model (`.gltf` or `.glb`).
+    ///
+    /// Each triangle primitive becomes a registered mesh, and each base-color
+    /// texture a registered texture. The node hierarchy of the default scene is
+    /// flattened, so every returned [`ModelPart`] carries its world-space
+    /// transform, base color, and texture. Animations, skins, and PBR
+    /// parameters other than base color are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the `.gltf` or `.glb` file
+    ///
+    /// # Returns
+    ///
+    /// The model's parts on success, or a [`GeometryError`] if the file can't
+    /// be read or contains no triangle meshes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let helmet = ctx.model_from_gltf("assets/helmet.glb")?;
+    /// // In frame:
+    /// frame.draw_model(&helmet, Transform::new().position(Vec3::new(0.0, 0.0, -5.0)));
+    /// ```
+    pub fn model_from_gltf(&mut self, path: &str) -> Result<Vec<ModelPart>, GeometryError> {
+        let model = GltfModel::load(Path::new(path))?;
+        Ok(model.upload(self.gpu, &mut self.mesh_queue.borrow_mut()))
+    }
+
+    /// Load a glTF model from embedded bytes.
+    ///
+    /// Accepts binary `.glb` data or `.gltf` JSON whose buffers and images are
+    /// embedded as data URIs. See [`Self::model_from_gltf`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let ship = ctx.model_from_gltf_bytes(include_bytes!("../assets/ship.glb"))?;
+    /// ```
+    pub fn model_from_gltf_bytes(&mut self, bytes: &[u8]) -> Result<Vec<ModelPart>, GeometryError> {
+        let model = GltfModel::from_slice(bytes)?;
+        Ok(model.upload(self.gpu, &mut self.mesh_queue.borrow_mut()))
+    }
+
     // ========================================================================
     // 3D Texture Methods
     // ========================================================================
@@ -782,9 +1448,12 @@ fn fs() -> @location(0) vec4f {{
         self.mesh_queue.borrow_mut().add_texture(texture)
     }
 
-    /// Load a texture from a file path.
+    /// Load a texture from a file path with linear (smooth) filtering.
     ///
     /// Supports common image formats (PNG, JPEG, etc.) via the `image` crate.
+    /// The texture repeats when UVs leave `[0, 1]`; use
+    /// [`Texture::with_address_mode`] and [`Self::add_texture`] to clamp it
+    /// instead. For pixel art, use [`Self::texture_from_file_nearest`].
     ///
     /// # Arguments
     ///
@@ -792,7 +1461,7 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// # Returns
     ///
-    /// A type-safe [`TextureId`] on success, or an [`image::ImageError`] on failure.
+    /// A type-safe [`TextureId`] on success, or an [`Error`] on failure.
     ///
     /// # Example
     ///
@@ -803,12 +1472,37 @@ fn fs() -> @location(0) vec4f {{
     /// // Or classic style:
     /// frame.draw_mesh_textured(cube, transform, Color::WHITE, brick_tex);
     /// ```
-    pub fn texture_from_file(&mut self, path: &str) -> Result<TextureId, image::ImageError> {
+    pub fn texture_from_file(&mut self, path: &str) -> Result<TextureId, Error> {
         let texture = Texture::from_file(self.gpu, path)?;
         Ok(self.add_texture(texture))
     }
 
-    /// Load a texture from embedded bytes.
+    /// Load a texture from a file with nearest-neighbor (pixelated) filtering.
+    ///
+    /// Texels stay sharp squares however close the camera gets, for
+    /// pixel-art and voxel worlds.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the image file
+    ///
+    /// # Returns
+    ///
+    /// A type-safe [`TextureId`] on success, or an [`Error`] on failure.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let crate_tex = ctx.texture_from_file_nearest("assets/crate_16x16.png")?;
+    /// // In frame:
+    /// frame.mesh(cube).texture(crate_tex).draw();
+    /// ```
+    pub fn texture_from_file_nearest(&mut self, path: &str) -> Result<TextureId, Error> {
+        let texture = Texture::from_file_nearest(self.gpu, path)?;
+        Ok(self.add_texture(texture))
+    }
+
+    /// Load a texture from embedded bytes with linear filtering.
     ///
     /// Useful for bundling textures directly in the executable via `include_bytes!`.
     ///
@@ -819,7 +1513,7 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// # Returns
     ///
-    /// A type-safe [`TextureId`] on success, or an [`image::ImageError`] on failure.
+    /// A type-safe [`TextureId`] on success, or an [`Error`] on failure.
     ///
     /// # Example
     ///
@@ -829,12 +1523,73 @@ fn fs() -> @location(0) vec4f {{
     ///     "brick texture"
     /// )?;
     /// ```
-    pub fn texture_from_bytes(
+    pub fn texture_from_bytes(&mut self, bytes: &[u8], label: &str) -> Result<TextureId, Error> {
+        let texture = Texture::from_bytes(self.gpu, bytes, label)?;
+        Ok(self.add_texture(texture))
+    }
+
+    /// Load a texture from embedded bytes with nearest-neighbor filtering.
+    ///
+    /// Combines embedded loading with pixel-art-friendly filtering.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Raw image file bytes (PNG, JPEG, etc.)
+    /// * `label` - Debug label for the texture (shown in GPU debugging tools)
+    ///
+    /// # Returns
+    ///
+    /// A type-safe [`TextureId`] on success, or an [`Error`] on failure.
+    pub fn texture_from_bytes_nearest(
         &mut self,
         bytes: &[u8],
         label: &str,
-    ) -> Result<TextureId, image::ImageError> {
-        let texture = Texture::from_bytes(self.gpu, bytes, label)?;
+    ) -> Result<TextureId, Error> {
+        let texture = Texture::from_bytes_nearest(self.gpu, bytes, label)?;
+        Ok(self.add_texture(texture))
+    }
+
+    /// Load a texture array from images of equal size, one layer per file.
+    ///
+    /// Select a layer per draw with [`MeshBuilder::texture_layer`]. See
+    /// [`Texture::array_from_files`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let blocks = ctx.texture_array_from_files(&["dirt.png", "grass.png", "stone.png"])?;
+    /// // In frame:
+    /// frame.mesh(cube).texture_layer(blocks, 2).draw(); // stone
+    /// ```
+    pub fn texture_array_from_files(&mut self, paths: &[&str]) -> Result<TextureId, Error> {
+        let texture = Texture::array_from_files(self.gpu, paths)?;
+        Ok(self.add_texture(texture))
+    }
+
+    /// Load a tangent-space normal map from a file path.
+    ///
+    /// Normal maps are stored as linear data rather than sRGB color, so load
+    /// them with this instead of [`Self::texture_from_file`]. Maps are expected
+    /// OpenGL-style, with green pointing up in the image.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the image file
+    ///
+    /// # Returns
+    ///
+    /// A type-safe [`TextureId`] for [`MeshBuilder::normal_map`], or an
+    /// [`Error`] on failure.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let bricks_normal = ctx.normal_map_from_file("assets/bricks_normal.png")?;
+    /// // In frame:
+    /// frame.mesh(wall).texture(bricks).normal_map(bricks_normal).draw();
+    /// ```
+    pub fn normal_map_from_file(&mut self, path: &str) -> Result<TextureId, Error> {
+        let texture = Texture::normal_map_from_file(self.gpu, path)?;
         Ok(self.add_texture(texture))
     }
 
@@ -922,7 +1677,7 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// # Returns
     ///
-    /// A [`SpriteId`] on success, or an [`image::ImageError`] on failure.
+    /// A [`SpriteId`] on success, or an [`Error`] on failure.
     ///
     /// # Example
     ///
@@ -931,7 +1686,7 @@ fn fs() -> @location(0) vec4f {{
     /// // In frame:
     /// frame.sprite(player, 100.0, 200.0);
     /// ```
-    pub fn sprite_from_file(&mut self, path: &str) -> Result<SpriteId, image::ImageError> {
+    pub fn sprite_from_file(&mut self, path: &str) -> Result<SpriteId, Error> {
         let sprite = Sprite::from_file(self.gpu, path)?;
         Ok(self.add_sprite(sprite))
     }
@@ -947,7 +1702,7 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// # Returns
     ///
-    /// A [`SpriteId`] on success, or an [`image::ImageError`] on failure.
+    /// A [`SpriteId`] on success, or an [`Error`] on failure.
     ///
     /// # Example
     ///
@@ -956,7 +1711,7 @@ fn fs() -> @location(0) vec4f {{
     /// // Scale up without blur:
     /// frame.sprite_scaled(character, x, y, 64.0, 64.0);
     /// ```
-    pub fn sprite_from_file_nearest(&mut self, path: &str) -> Result<SpriteId, image::ImageError> {
+    pub fn sprite_from_file_nearest(&mut self, path: &str) -> Result<SpriteId, Error> {
         let sprite = Sprite::from_file_nearest(self.gpu, path)?;
         Ok(self.add_sprite(sprite))
     }
@@ -972,7 +1727,7 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// # Returns
     ///
-    /// A [`SpriteId`] on success, or an [`image::ImageError`] on failure.
+    /// A [`SpriteId`] on success, or an [`Error`] on failure.
     ///
     /// # Example
     ///
@@ -982,11 +1737,7 @@ fn fs() -> @location(0) vec4f {{
     ///     "app icon"
     /// )?;
     /// ```
-    pub fn sprite_from_bytes(
-        &mut self,
-        bytes: &[u8],
-        label: &str,
-    ) -> Result<SpriteId, image::ImageError> {
+    pub fn sprite_from_bytes(&mut self, bytes: &[u8], label: &str) -> Result<SpriteId, Error> {
         let sprite = Sprite::from_bytes(self.gpu, bytes, label)?;
         Ok(self.add_sprite(sprite))
     }
@@ -1002,12 +1753,12 @@ fn fs() -> @location(0) vec4f {{
     ///
     /// # Returns
     ///
-    /// A [`SpriteId`] on success, or an [`image::ImageError`] on failure.
+    /// A [`SpriteId`] on success, or an [`Error`] on failure.
     pub fn sprite_from_bytes_nearest(
         &mut self,
         bytes: &[u8],
         label: &str,
-    ) -> Result<SpriteId, image::ImageError> {
+    ) -> Result<SpriteId, Error> {
         let sprite = Sprite::from_bytes_nearest(self.gpu, bytes, label)?;
         Ok(self.add_sprite(sprite))
     }
@@ -1160,32 +1911,60 @@ pub struct Frame<'a> {
     /// components, or despawn entities based on game logic.
     pub world: &'a mut hecs::World,
 
-    /// Total elapsed time since application start, in seconds.
+    /// Total elapsed game time since application start, in seconds.
     ///
-    /// Useful for animations and time-based effects. Continuously increases;
-    /// never resets during the application lifetime.
+    /// Useful for animations and time-based effects. Never resets during the
+    /// application lifetime, but holds still while [paused](Self::set_paused)
+    /// and runs at the [time scale](Self::set_time_scale).
     pub time: f32,
 
-    /// Time elapsed since the previous frame, in seconds.
+    /// Game time elapsed since the previous frame, in seconds.
     ///
     /// Use this for frame-rate-independent movement and physics:
     /// `position += velocity * frame.dt`
     ///
-    /// Typically around 0.016 (60 FPS) or 0.008 (120 FPS).
+    /// Typically around 0.016 (60 FPS) or 0.008 (120 FPS), multiplied by the
+    /// [time scale](Self::set_time_scale), and 0.0 while [paused](Self::set_paused).
+    /// See [`real_dt`](Self::real_dt) for wall-clock time.
     pub dt: f32,
 
+    /// Wall-clock and game time shared with the app loop.
+    pub(crate) clock: &'a mut Clock,
+
     /// Default font set during setup (if any).
     pub(crate) default_font: Option<FontId>,
 
+    /// Fonts pushed with [`push_font`](Self::push_font); the top overrides `default_font`.
+    pub(crate) font_stack: Vec<FontId>,
+
     /// Shared mesh queue for 3D draw calls.
     pub(crate) mesh_queue: Rc<RefCell<MeshQueue>>,
 
-    /// Window handle for cursor control.
-    pub(crate) window: &'a Window,
+    /// Window handle for cursor control, or `None` when running headless.
+    pub(crate) window: Option<&'a Window>,
 
     /// Scene switch request (used by scene manager).
-    /// When Some, contains (target_scene_name, transition).
-    pub(crate) scene_switch: Option<(String, crate::scene::Transition)>,
+    /// When Some, contains (target_scene_name, transition, data).
+    pub(crate) scene_switch: Option<crate::scene::SceneSwitch>,
+
+    /// Data handed to the current scene by the switch that entered it.
+    /// `None` outside [`run_with_scenes`].
+    pub(crate) scene_data: Option<&'a mut Option<Box<dyn std::any::Any>>>,
+
+    /// Name of the scene running this frame. `None` outside [`run_with_scenes`].
+    pub(crate) current_scene: Option<&'a str>,
+
+    /// Names of all registered scenes, sorted. Empty outside [`run_with_scenes`].
+    pub(crate) scene_ids: &'a [String],
+
+    /// Scene stack pushes and pops requested this frame (used by scene manager).
+    pub(crate) stack_changes: Vec<crate::scene::StackChange>,
+
+    /// Interpolation alpha left over after this frame's fixed updates.
+    pub(crate) fixed_alpha: f32,
+
+    /// Set by [`exit`](Self::exit); the event loop exits after this frame.
+    pub(crate) exit_requested: bool,
 }
 
 impl Frame<'_> {
@@ -1195,12 +1974,13 @@ impl Frame<'_> {
 
     /// Calculate the current frames per second based on delta time.
     ///
-    /// This is a simple reciprocal of `dt`. For smoothed FPS display,
-    /// consider averaging over multiple frames in your application code.
+    /// This is a simple reciprocal of [`real_dt`](Self::real_dt), so it keeps
+    /// reporting while paused. It jitters from frame to frame; for an on-screen
+    /// counter use [`fps_avg`](Self::fps_avg).
     ///
     /// # Returns
     ///
-    /// FPS as a float. Returns 0.0 if `dt` is zero (shouldn't happen in practice).
+    /// FPS as a float. Returns 0.0 if `real_dt` is zero (shouldn't happen in practice).
     ///
     /// # Example
     ///
@@ -1208,7 +1988,184 @@ impl Frame<'_> {
     /// frame.text(10.0, 10.0, &format!("FPS: {:.0}", frame.fps()));
     /// ```
     pub fn fps(&self) -> f32 {
-        if self.dt > 0.0 { 1.0 / self.dt } else { 0.0 }
+        let real_dt = self.real_dt();
+        if real_dt > 0.0 { 1.0 / real_dt } else { 0.0 }
+    }
+
+    /// Average frames per second over the last 60 frames.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.text(10.0, 10.0, &format!(
+    ///     "{:.0} fps ({:.1} ms, min {:.0})",
+    ///     frame.fps_avg(),
+    ///     frame.frame_time_ms(),
+    ///     frame.min_fps(),
+    /// ));
+    /// ```
+    pub fn fps_avg(&self) -> f32 {
+        self.clock.fps_avg()
+    }
+
+    /// Average wall-clock frame time over the last 60 frames, in milliseconds.
+    pub fn frame_time_ms(&self) -> f32 {
+        self.clock.average_frame_time() * 1000.0
+    }
+
+    /// Frame rate of the slowest of the last 60 frames, for spotting hitches.
+    pub fn min_fps(&self) -> f32 {
+        self.clock.min_fps()
+    }
+
+    /// Frame rate of the fastest of the last 60 frames.
+    pub fn max_fps(&self) -> f32 {
+        self.clock.max_fps()
+    }
+
+    /// Wall-clock time elapsed since the previous frame, in seconds.
+    ///
+    /// Unlike [`dt`](Self::dt), this keeps running while paused and ignores the
+    /// time scale, so use it for pause menus and other UI animation.
+    pub fn real_dt(&self) -> f32 {
+        self.clock.real_dt()
+    }
+
+    /// Freeze or resume game time.
+    ///
+    /// While paused, the app keeps rendering and handling input, but
+    /// [`dt`](Self::dt) is 0.0, [`time`](Self::time) and the time seen by
+    /// shaders hold still, and fixed updates stop. Takes effect from the next
+    /// frame.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if frame.input.key_pressed(KeyCode::Escape) {
+    ///     frame.set_paused(!frame.is_paused());
+    /// }
+    /// ```
+    pub fn set_paused(&mut self, paused: bool) {
+        self.clock.set_paused(paused);
+    }
+
+    /// Whether game time is paused. See [`set_paused`](Self::set_paused).
+    pub fn is_paused(&self) -> bool {
+        self.clock.is_paused()
+    }
+
+    /// Set how fast game time runs relative to wall-clock time.
+    ///
+    /// [`dt`](Self::dt) is the real frame time multiplied by `scale`, and
+    /// [`time`](Self::time), shader time, and fixed updates advance by the
+    /// scaled amount, so everything driven by game time slows down or speeds
+    /// up together. [`real_dt`](Self::real_dt) is unaffected. Defaults to 1.0;
+    /// negative values are treated as 0.0. Takes effect from the next frame.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Bullet time while the right mouse button is held
+    /// let slow = frame.input.mouse_down(MouseButton::Right);
+    /// frame.set_time_scale(if slow { 0.2 } else { 1.0 });
+    /// ```
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.clock.set_time_scale(scale);
+    }
+
+    /// Current game time multiplier. See [`set_time_scale`](Self::set_time_scale).
+    pub fn time_scale(&self) -> f32 {
+        self.clock.time_scale()
+    }
+
+    /// Fraction of a fixed step elapsed since the last fixed update, from 0.0 to 1.0.
+    ///
+    /// Blend the previous and current simulation state with this to render
+    /// smoothly between [`SetupContext::fixed_update`] steps. Always 1.0 when no
+    /// fixed update is registered.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let shown = body.previous.lerp(body.current, frame.fixed_alpha());
+    /// frame.mesh(ball).at(shown).draw();
+    /// ```
+    pub fn fixed_alpha(&self) -> f32 {
+        self.fixed_alpha
+    }
+
+    /// Number of meshes skipped by frustum culling in the last rendered frame.
+    ///
+    /// Always zero unless [`SetupContext::set_frustum_culling`] is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.text(10.0, 30.0, &format!("Culled: {}", frame.culled_count()));
+    /// ```
+    pub fn culled_count(&self) -> usize {
+        self.mesh_queue.borrow().culled_count()
+    }
+
+    /// Draw calls, triangles, 2D vertices, and meshes submitted in the last rendered frame.
+    ///
+    /// Counts accumulate as [`MeshNode`] and [`Draw2d`] issue draws, then reset
+    /// when the frame ends, so this frame's drawing shows up next frame. Meshes
+    /// skipped by frustum culling aren't counted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let stats = frame.stats();
+    /// frame.text(
+    ///     10.0,
+    ///     50.0,
+    ///     &format!("{} draws, {} tris", stats.draw_calls, stats.triangles),
+    /// );
+    /// ```
+    pub fn stats(&self) -> RenderStats {
+        self.mesh_queue.borrow().render_stats() + self.draw.render_stats()
+    }
+
+    /// Turn GPU timestamp profiling on or off at runtime.
+    ///
+    /// See [`SetupContext::enable_gpu_profiling`] for details.
+    pub fn set_gpu_profiling(&self, enabled: bool) {
+        self.gpu.set_profiling(enabled);
+    }
+
+    /// GPU time in milliseconds spent in each render graph node last frame.
+    ///
+    /// Returns `(node name, ms)` pairs in execution order, or an empty list
+    /// while profiling is off.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for (i, (name, ms)) in frame.gpu_timings().iter().enumerate() {
+    ///     frame.text(10.0, 50.0 + i as f32 * 20.0, &format!("{name}: {ms:.2} ms"));
+    /// }
+    /// ```
+    pub fn gpu_timings(&self) -> Vec<(String, f32)> {
+        self.gpu.last_timings()
+    }
+
+    /// Update a pass's user uniforms; the new values apply from this frame on.
+    ///
+    /// `handle` comes from [`SetupContext::effect_with_uniforms`] or
+    /// [`SetupContext::post_process_with_uniforms`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't the size of the type the pass was created with.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.set_uniforms(&bloom, &Bloom { threshold: 1.0, intensity: 0.5 + frame.time.sin() * 0.2, _pad: [0.0; 2] });
+    /// ```
+    pub fn set_uniforms<T: bytemuck::Pod>(&self, handle: &UniformHandle, value: &T) {
+        handle.set(self.gpu, value);
     }
 
     /// Get the current window/screen width in pixels.
@@ -1227,6 +2184,20 @@ impl Frame<'_> {
         self.gpu.height()
     }
 
+    /// Width in pixels that effects and meshes render at.
+    ///
+    /// Equal to [`width`](Self::width) unless [`AppConfig::render_scale`] is set.
+    pub fn render_width(&self) -> u32 {
+        self.gpu.render_width()
+    }
+
+    /// Height in pixels that effects and meshes render at.
+    ///
+    /// Equal to [`height`](Self::height) unless [`AppConfig::render_scale`] is set.
+    pub fn render_height(&self) -> u32 {
+        self.gpu.render_height()
+    }
+
     // ========================================================================
     // Cursor Control
     // ========================================================================
@@ -1251,13 +2222,15 @@ impl Frame<'_> {
     /// ```
     pub fn capture_cursor(&self) {
         use winit::window::CursorGrabMode;
-        // Try locked mode first (best for FPS controls - unlimited mouse movement),
+        let Some(window) = self.window else {
+            return;
+        };
+        // Try locked mode first (best for FPS controls - unlimited mouse movement),
         // fall back to confined (keeps cursor in window but hits edges)
-        let _ = self
-            .window
+        let _ = window
             .set_cursor_grab(CursorGrabMode::Locked)
-            .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Confined));
-        self.window.set_cursor_visible(false);
+            .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined));
+        window.set_cursor_visible(false);
     }
 
     /// Release the mouse cursor, restoring normal behavior.
@@ -1266,8 +2239,135 @@ impl Frame<'_> {
     /// and allowing it to leave the window.
     pub fn release_cursor(&self) {
         use winit::window::CursorGrabMode;
-        let _ = self.window.set_cursor_grab(CursorGrabMode::None);
-        self.window.set_cursor_visible(true);
+        let Some(window) = self.window else {
+            return;
+        };
+        let _ = window.set_cursor_grab(CursorGrabMode::None);
+        window.set_cursor_visible(true);
+    }
+
+    // ========================================================================
+    // Window
+    // ========================================================================
+
+    /// Switch between borderless fullscreen and windowed mode.
+    ///
+    /// Fullscreen uses the monitor the window is currently on. The surface and
+    /// all render targets follow the new window size automatically. Does
+    /// nothing when running headless.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Alt+Enter toggles fullscreen
+    /// let alt = frame.input.key_down(KeyCode::AltLeft) || frame.input.key_down(KeyCode::AltRight);
+    /// if alt && frame.input.key_pressed(KeyCode::Enter) {
+    ///     frame.toggle_fullscreen();
+    /// }
+    /// ```
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        if let Some(window) = self.window {
+            window.set_fullscreen(fullscreen_mode(fullscreen));
+        }
+    }
+
+    /// Toggle between borderless fullscreen and windowed mode.
+    ///
+    /// See [`set_fullscreen`](Self::set_fullscreen) for details.
+    pub fn toggle_fullscreen(&self) {
+        self.set_fullscreen(!self.is_fullscreen());
+    }
+
+    /// Returns `true` if the window is currently fullscreen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.window
+            .is_some_and(|window| window.fullscreen().is_some())
+    }
+
+    /// Quit the application after this frame.
+    ///
+    /// The current frame still renders and presents; the event loop then exits
+    /// cleanly, just as if the window had been closed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if frame.input.key_pressed(KeyCode::Escape) {
+    ///     frame.exit();
+    /// }
+    /// ```
+    pub fn exit(&mut self) {
+        self.exit_requested = true;
+    }
+
+    // ========================================================================
+    // Screenshots
+    // ========================================================================
+
+    /// Save this frame to an image file once it has finished rendering.
+    ///
+    /// The capture is taken right before the frame is presented, so it
+    /// includes 3D content, post-processing, and 2D overlays. The image format
+    /// is chosen from the file extension (`.png`, `.jpg`, ...). Failures while
+    /// writing the file are logged with a `[screenshot]` prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the extension isn't a supported image format or if
+    /// the surface can't be read back on this platform.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if frame.input.key_pressed(KeyCode::F12) {
+    ///     frame.capture_screenshot("screenshot.png")?;
+    /// }
+    /// ```
+    pub fn capture_screenshot(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        image::ImageFormat::from_path(path)?;
+        self.request_screenshot(CaptureTarget::File(path.to_path_buf()))
+    }
+
+    /// Capture this frame's pixels into memory once it has finished rendering.
+    ///
+    /// The pixels become available on the next frame via
+    /// [`take_screenshot`](Self::take_screenshot).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the surface can't be read back on this platform.
+    pub fn capture_screenshot_rgba(&self) -> Result<(), Error> {
+        self.request_screenshot(CaptureTarget::Memory)
+    }
+
+    /// Take the most recent capture requested with
+    /// [`capture_screenshot_rgba`](Self::capture_screenshot_rgba).
+    ///
+    /// Returns `None` until a requested frame has been presented. Use
+    /// `into_raw()` on the result for a tightly packed RGBA8 `Vec<u8>`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Some(shot) = frame.take_screenshot() {
+    ///     let (width, height) = shot.dimensions();
+    ///     upload_thumbnail(width, height, shot.into_raw());
+    /// }
+    /// ```
+    pub fn take_screenshot(&self) -> Option<image::RgbaImage> {
+        self.gpu.screenshots.borrow_mut().captured.take()
+    }
+
+    fn request_screenshot(&self, target: CaptureTarget) -> Result<(), Error> {
+        if !self.gpu.supports_screenshots() {
+            return Err(Error::Screenshot(format!(
+                "surface ({:?}) does not support readback",
+                self.gpu.config.format
+            )));
+        }
+        self.gpu.screenshots.borrow_mut().pending.push(target);
+        Ok(())
     }
 
     // ========================================================================
@@ -1283,6 +2383,10 @@ impl Frame<'_> {
     /// This method only works when using [`run_with_scenes`]. It has no effect
     /// when using the standard [`run`] or [`run_with_config`] functions.
     ///
+    /// Switching to a name that isn't registered leaves the current scene
+    /// running and prints a warning listing the registered scenes; use
+    /// [`Self::scene_exists`] to check a name first.
+    ///
     /// # Arguments
     ///
     /// * `scene_name` - The name of the scene to switch to (as defined in setup)
@@ -1306,6 +2410,8 @@ impl Frame<'_> {
     /// - [`Transition::fade_to_black(duration)`] - Fade to black, then fade in
     /// - [`Transition::fade_to_white(duration)`] - Fade to white, then fade in
     /// - [`Transition::crossfade(duration)`] - Blend old and new scenes
+    /// - [`Transition::radial_wipe`](crate::scene::Transition::radial_wipe)`(duration)` - Reveal the new scene in a growing circle
+    /// - [`Transition::shader`](crate::scene::Transition::shader)`(path_or_source, duration, easing)` - Blend with your own WGSL shader
     ///
     /// This method only works when using [`run_with_scenes`].
     ///
@@ -1332,13 +2438,206 @@ impl Frame<'_> {
         scene_name: impl Into<String>,
         transition: crate::scene::Transition,
     ) {
-        self.scene_switch = Some((scene_name.into(), transition));
+        self.scene_switch = Some((scene_name.into(), transition, None));
+    }
+
+    /// Switch to another scene instantly, handing it `data`.
+    ///
+    /// Use this to carry state between scenes, such as the level to load or
+    /// the final score. The target scene retrieves the payload with
+    /// [`Self::take_scene_data`]; it's in place before the target's `on_enter`
+    /// runs and is replaced by the next switch into that scene.
+    ///
+    /// This method only works when using [`run_with_scenes`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the level select scene
+    /// if frame.input.key_pressed(KeyCode::Enter) {
+    ///     frame.switch_to_with_data("game", Box::new(LevelChoice { level: 3 }));
+    /// }
+    ///
+    /// // In the game scene
+    /// if let Some(choice) = frame.take_scene_data::<LevelChoice>() {
+    ///     load_level(choice.level);
+    /// }
+    /// ```
+    pub fn switch_to_with_data(
+        &mut self,
+        scene_name: impl Into<String>,
+        data: Box<dyn std::any::Any>,
+    ) {
+        self.scene_switch = Some((
+            scene_name.into(),
+            crate::scene::Transition::instant(),
+            Some(data),
+        ));
+    }
+
+    /// Take the data passed to this scene by [`Self::switch_to_with_data`].
+    ///
+    /// Returns `None` if there is no data, it was already taken, or it isn't a
+    /// `T` (in which case it stays available for another type). Always `None`
+    /// outside [`run_with_scenes`].
+    pub fn take_scene_data<T: 'static>(&mut self) -> Option<T> {
+        self.scene_data
+            .as_deref_mut()
+            .and_then(crate::scene::scene::take_data)
+    }
+
+    /// Check whether a scene with this name was registered.
+    ///
+    /// Switching to or pushing an unknown scene does nothing but print a
+    /// warning listing the registered scenes, so check first when the name
+    /// comes from data such as a level file. Always `false` outside
+    /// [`run_with_scenes`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let next = format!("level_{}", level + 1);
+    /// if frame.scene_exists(&next) {
+    ///     frame.switch_to(next);
+    /// } else {
+    ///     frame.switch_to("credits");
+    /// }
+    /// ```
+    pub fn scene_exists(&self, scene_name: &str) -> bool {
+        self.scene_ids.iter().any(|id| id == scene_name)
+    }
+
+    /// The scene whose frame logic is running.
+    ///
+    /// For a scene pushed over another this is the pushed scene, not the one
+    /// below it. `None` outside [`run_with_scenes`].
+    pub fn current_scene(&self) -> Option<crate::scene::SceneId> {
+        self.current_scene.map(crate::scene::SceneId::new)
+    }
+
+    /// Names of every registered scene, in alphabetical order.
+    ///
+    /// Empty outside [`run_with_scenes`].
+    pub fn scene_ids(&self) -> Vec<&str> {
+        self.scene_ids.iter().map(String::as_str).collect()
+    }
+
+    /// Push a scene on top of the current one.
+    ///
+    /// The scenes below keep updating and rendering underneath, and the pushed
+    /// scene draws over them. Unless the pushed scene sets a clear color, its
+    /// background is transparent so the scenes below show through. Its
+    /// `on_enter` callback is called when the push is processed at the start of
+    /// the next frame; the scenes below don't exit.
+    ///
+    /// This method only works when using [`run_with_scenes`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the game scene: show the HUD over it
+    /// frame.push_scene("hud");
+    /// ```
+    pub fn push_scene(&mut self, scene_name: impl Into<String>) {
+        self.stack_changes.push(crate::scene::StackChange::Push {
+            name: scene_name.into(),
+            pause_below: false,
+        });
+    }
+
+    /// Push a scene on top of the current one and pause the scenes below.
+    ///
+    /// Like [`Self::push_scene`], but while the pushed scene is on the stack the
+    /// scenes below it run with a zero `dt` and no input. They still draw, so a
+    /// pause menu can show the frozen game underneath.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if frame.input.key_pressed(KeyCode::Escape) {
+    ///     frame.push_scene_paused("pause_menu");
+    /// }
+    /// ```
+    pub fn push_scene_paused(&mut self, scene_name: impl Into<String>) {
+        self.stack_changes.push(crate::scene::StackChange::Push {
+            name: scene_name.into(),
+            pause_below: true,
+        });
+    }
+
+    /// Pop the topmost pushed scene, calling its `on_exit` callback.
+    ///
+    /// Processed at the start of the next frame. The scene at the bottom of the
+    /// stack is never popped; use [`Self::switch_to`] to replace it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // In the pause menu scene
+    /// if frame.input.key_pressed(KeyCode::Escape) {
+    ///     frame.pop_scene();
+    /// }
+    /// ```
+    pub fn pop_scene(&mut self) {
+        self.stack_changes.push(crate::scene::StackChange::Pop);
     }
 
     // ========================================================================
     // Text Rendering
     // ========================================================================
 
+    /// Make `font` the current font for the text methods until the matching
+    /// [`pop_font`](Self::pop_font).
+    ///
+    /// Methods documented as using "the default font" (`text`, `text_color`,
+    /// `text_wrapped`, `measure_text`, ...) use the most recently pushed font
+    /// instead. Pushes nest, and the stack is cleared at the end of each frame.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.push_font(title_font);
+    /// frame.text(20.0, 20.0, "Settings");
+    /// frame.pop_font();
+    /// frame.text(20.0, 80.0, "Volume"); // back to the default font
+    /// ```
+    pub fn push_font(&mut self, font: FontId) {
+        self.font_stack.push(font);
+    }
+
+    /// Restore the font that was current before the last [`push_font`](Self::push_font).
+    ///
+    /// Returns the popped font, or `None` if nothing was pushed.
+    pub fn pop_font(&mut self) -> Option<FontId> {
+        self.font_stack.pop()
+    }
+
+    /// The font used by the text methods: the last pushed font, or the default font.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no font was pushed and no default font was set during setup.
+    pub fn current_font(&self) -> FontId {
+        self.font_stack
+            .last()
+            .copied()
+            .or(self.default_font)
+            .expect("No default font set. Call ctx.default_font() in setup.")
+    }
+
+    /// Draw colored text with a specific font, regardless of the current font.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let title = ctx.load_font("fonts/Title.ttf", 48.0);
+    /// // Later in frame:
+    /// frame.text_with(title, 20.0, 20.0, "Hoplite", Color::WHITE);
+    /// ```
+    pub fn text_with(&mut self, font: FontId, x: f32, y: f32, text: &str, color: Color) {
+        self.draw.text(self.assets, font, x, y, text, color);
+    }
+
     /// Draw white text at the given screen position using the default font.
     ///
     /// Coordinates are in screen pixels with (0, 0) at top-left.
@@ -1383,14 +2682,91 @@ impl Frame<'_> {
     /// frame.text_color(10.0, 70.0, "Health OK", Color::GREEN);
     /// ```
     pub fn text_color(&mut self, x: f32, y: f32, text: &str, color: Color) {
-        let font = self
-            .default_font
-            .expect("No default font set. Call ctx.default_font() in setup.");
+        let font = self.current_font();
         self.draw.text(self.assets, font, x, y, text, color);
     }
 
+    /// Draw colored text at `size` pixels using the default font.
+    ///
+    /// Fonts loaded with [`SetupContext::default_font_sdf`] stay sharp at any
+    /// size; regular fonts are scaled as bitmaps. See [`Draw2d::text_sized`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    pub fn text_sized(&mut self, x: f32, y: f32, size: f32, text: &str, color: Color) {
+        let font = self.current_font();
+        self.draw
+            .text_sized(self.assets, font, x, y, size, text, color);
+    }
+
+    /// Draw white text centered horizontally on `x` using the default font.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.text_centered(frame.width() as f32 / 2.0, 20.0, "GAME OVER");
+    /// ```
+    pub fn text_centered(&mut self, x: f32, y: f32, text: &str) {
+        self.text_aligned(x, y, text, Color::WHITE, TextAlign::Center)
+    }
+
+    /// Draw colored text aligned horizontally relative to `x` using the default font.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Right-align a score against the screen edge
+    /// let right = frame.width() as f32 - 10.0;
+    /// frame.text_aligned(right, 10.0, &score.to_string(), Color::WHITE, TextAlign::Right);
+    /// ```
+    pub fn text_aligned(&mut self, x: f32, y: f32, text: &str, color: Color, align: TextAlign) {
+        let font = self.current_font();
+        self.draw
+            .text_aligned(self.assets, font, x, y, text, color, align);
+    }
+
+    /// Draw white text word-wrapped to `max_width` using the default font.
+    ///
+    /// Returns the total height consumed so following content can be placed below.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.panel(10.0, 300.0, 400.0, 120.0);
+    /// let h = frame.text_wrapped(20.0, 310.0, 380.0, dialog_line);
+    /// frame.text(20.0, 310.0 + h + 8.0, "[Space] Continue");
+    /// ```
+    pub fn text_wrapped(&mut self, x: f32, y: f32, max_width: f32, text: &str) -> f32 {
+        let font = self.current_font();
+        self.draw
+            .text_wrapped(self.assets, font, x, y, max_width, text, Color::WHITE)
+    }
+
+    /// Measure text drawn with the default font, returning `(width, height)` in pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    pub fn measure_text(&self, text: &str) -> Vec2 {
+        let font = self.current_font();
+        self.draw.measure_text(self.assets, font, text)
+    }
+
     // ========================================================================
-    // Rectangle & Panel Rendering
+    // Shape & Panel Rendering
     // ========================================================================
 
     /// Draw a solid colored rectangle.
@@ -1415,6 +2791,123 @@ impl Frame<'_> {
         self.draw.rect(x, y, w, h, color);
     }
 
+    /// Draw a rectangle with a vertical gradient from `top` to `bottom`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Health bar that darkens toward the bottom
+    /// frame.rect_gradient(10.0, 100.0, 200.0, 20.0, Color::rgb(0.2, 0.9, 0.3), Color::rgb(0.0, 0.4, 0.1));
+    /// ```
+    pub fn rect_gradient(&mut self, x: f32, y: f32, w: f32, h: f32, top: Color, bottom: Color) {
+        self.draw.rect_gradient(x, y, w, h, top, bottom);
+    }
+
+    /// Draw a rectangle with a color at each corner (top-left, top-right,
+    /// bottom-left, bottom-right).
+    ///
+    /// See [`Draw2d::rect_gradient_corners`].
+    pub fn rect_gradient_corners(&mut self, x: f32, y: f32, w: f32, h: f32, colors: [Color; 4]) {
+        self.draw.rect_gradient_corners(x, y, w, h, colors);
+    }
+
+    /// Set the 2D layer for subsequent draw calls.
+    ///
+    /// Higher layers draw on top of lower ones. Within a layer, shapes draw
+    /// first, then text, then sprites. The layer resets to `0` every frame.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Sprite behind text behind a highlight rect
+    /// frame.set_layer(0);
+    /// frame.sprite(portrait, 10.0, 10.0);
+    /// frame.set_layer(1);
+    /// frame.text(20.0, 20.0, "Hero");
+    /// frame.set_layer(2);
+    /// frame.rect(10.0, 10.0, 64.0, 4.0, Color::WHITE);
+    /// ```
+    pub fn set_layer(&mut self, layer: i32) {
+        self.draw.set_layer(layer);
+    }
+
+    /// Draw a solid colored rectangle with rounded corners.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.rounded_rect(10.0, 10.0, 120.0, 32.0, 8.0, Color::rgb(0.2, 0.4, 0.8));
+    /// ```
+    pub fn rounded_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        self.draw.rounded_rect(x, y, w, h, radius, color);
+    }
+
+    /// Draw a straight line between two points.
+    ///
+    /// # Arguments
+    ///
+    /// * `x0`, `y0` - Start point in screen pixels
+    /// * `x1`, `y1` - End point in screen pixels
+    /// * `thickness` - Line width in pixels
+    /// * `color` - Line color
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Draw a divider under a header
+    /// frame.line(10.0, 40.0, 310.0, 40.0, 1.0, Color::WHITE);
+    /// ```
+    pub fn line(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, thickness: f32, color: Color) {
+        self.draw.line(x0, y0, x1, y1, thickness, color);
+    }
+
+    /// Draw connected line segments through a list of points.
+    ///
+    /// Joints are not mitered, so very thick lines show notches at sharp corners.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Plot frame times as a graph
+    /// let points: Vec<Vec2> = samples
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(i, ms)| Vec2::new(10.0 + i as f32 * 2.0, 200.0 - ms * 4.0))
+    ///     .collect();
+    /// frame.polyline(&points, 1.5, Color::rgb(0.2, 1.0, 0.4));
+    /// ```
+    pub fn polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        self.draw.polyline(points, thickness, color);
+    }
+
+    /// Draw a filled circle.
+    ///
+    /// Pass `0` for `segments` to pick a smooth segment count from the radius.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Minimap player marker
+    /// frame.circle(player_x, player_y, 4.0, 0, Color::WHITE);
+    /// ```
+    pub fn circle(&mut self, cx: f32, cy: f32, radius: f32, segments: u32, color: Color) {
+        self.draw.circle(cx, cy, radius, segments, color);
+    }
+
+    /// Draw a ring between `inner` and `outer` radii.
+    ///
+    /// Pass `0` for `segments` to pick a smooth segment count from the outer radius.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Cooldown indicator outline
+    /// frame.ring(400.0, 300.0, 28.0, 32.0, 0, Color::rgb(0.9, 0.7, 0.2));
+    /// ```
+    pub fn ring(&mut self, cx: f32, cy: f32, inner: f32, outer: f32, segments: u32, color: Color) {
+        self.draw.ring(cx, cy, inner, outer, segments, color);
+    }
+
     /// Draw a UI panel with a styled background and border.
     ///
     /// Panels provide a consistent look for UI containers. For panels with
@@ -1434,50 +2927,157 @@ impl Frame<'_> {
     /// # Example
     ///
     /// ```ignore
-    /// let content_y = frame.panel(10.0, 10.0, 200.0, 150.0);
-    /// frame.text(20.0, content_y + 10.0, "Panel content here");
+    /// let content_y = frame.panel(10.0, 10.0, 200.0, 150.0);
+    /// frame.text(20.0, content_y + 10.0, "Panel content here");
+    /// ```
+    pub fn panel(&mut self, x: f32, y: f32, w: f32, h: f32) -> f32 {
+        self.draw.panel(x, y, w, h).draw(self.assets);
+        y
+    }
+
+    /// Draw a UI panel with a title bar.
+    ///
+    /// The title bar uses the default font and has a distinct background color.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X position of top-left corner
+    /// * `y` - Y position of top-left corner
+    /// * `w` - Panel width
+    /// * `h` - Total panel height (including title bar)
+    /// * `title` - Title text to display
+    ///
+    /// # Returns
+    ///
+    /// The Y coordinate where content should begin (below the title bar, ~22px offset).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let content_y = frame.panel_titled(10.0, 10.0, 250.0, 200.0, "Inventory");
+    /// frame.text(20.0, content_y + 10.0, "Sword x1");
+    /// frame.text(20.0, content_y + 30.0, "Potion x3");
+    /// ```
+    pub fn panel_titled(&mut self, x: f32, y: f32, w: f32, h: f32, title: &str) -> f32 {
+        let font = self.current_font();
+        self.draw
+            .panel(x, y, w, h)
+            .title(title, font)
+            .draw(self.assets);
+        y + 22.0 // Title bar height
+    }
+
+    // ========================================================================
+    // Widgets
+    // ========================================================================
+
+    /// Draw a button labeled with the current font and return `true` when it's clicked.
+    ///
+    /// A click is a left press and release both on the button. Widgets keep
+    /// no state, so draw them every frame they should be visible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if frame.button(Rect::new(10.0, 10.0, 120.0, 28.0), "Reset") {
+    ///     particles.clear();
+    /// }
+    /// ```
+    pub fn button(&mut self, rect: Rect, label: &str) -> bool {
+        let font = self.current_font();
+        ui::button(self.draw, self.assets, font, self.input, rect, label)
+    }
+
+    /// Draw a horizontal slider for `value` between `min` and `max`.
+    ///
+    /// Pressing the left button on the slider and dragging sets the value from
+    /// the cursor position, even once the cursor leaves the slider. The value
+    /// is printed on the slider when a font is available. Returns `true` on
+    /// frames where `value` changed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.slider(Rect::new(10.0, 50.0, 200.0, 20.0), &mut self.speed, 0.0, 10.0);
     /// ```
-    pub fn panel(&mut self, x: f32, y: f32, w: f32, h: f32) -> f32 {
-        self.draw.panel(x, y, w, h).draw(self.assets);
-        y
+    pub fn slider(&mut self, rect: Rect, value: &mut f32, min: f32, max: f32) -> bool {
+        let font = self.font_stack.last().copied().or(self.default_font);
+        ui::slider(
+            self.draw,
+            self.assets,
+            font,
+            self.input,
+            rect,
+            value,
+            min,
+            max,
+        )
     }
 
-    /// Draw a UI panel with a title bar.
-    ///
-    /// The title bar uses the default font and has a distinct background color.
-    ///
-    /// # Panics
+    /// Draw a checkbox that flips `checked` when clicked.
     ///
-    /// Panics if no default font was set during setup.
+    /// Returns `true` on the frame `checked` changed.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `x` - X position of top-left corner
-    /// * `y` - Y position of top-left corner
-    /// * `w` - Panel width
-    /// * `h` - Total panel height (including title bar)
-    /// * `title` - Title text to display
+    /// ```ignore
+    /// let row = Rect::new(10.0, 80.0, 18.0, 18.0);
+    /// frame.checkbox(row, &mut self.show_hitboxes);
+    /// frame.text(34.0, 80.0, "Show hitboxes");
+    /// ```
+    pub fn checkbox(&mut self, rect: Rect, checked: &mut bool) -> bool {
+        ui::checkbox(self.draw, self.assets, self.input, rect, checked)
+    }
+
+    // ========================================================================
+    // Lighting
+    // ========================================================================
+
+    /// Set the directional "sun" light.
     ///
-    /// # Returns
+    /// Lights persist across frames. See [`SetupContext::set_sun`] for details.
+    pub fn set_sun(&mut self, direction: Vec3, color: Color) {
+        self.mesh_queue
+            .borrow_mut()
+            .lighting
+            .set_sun(direction, color);
+    }
+
+    /// Set the ambient light intensity applied alongside configured lights.
+    pub fn set_ambient(&mut self, ambient: f32) {
+        self.mesh_queue.borrow_mut().lighting.ambient = ambient;
+    }
+
+    /// Add a point light.
     ///
-    /// The Y coordinate where content should begin (below the title bar, ~22px offset).
+    /// Point lights persist across frames, so for moving lights call
+    /// [`clear_point_lights`](Self::clear_point_lights) first each frame.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// let content_y = frame.panel_titled(10.0, 10.0, 250.0, 200.0, "Inventory");
-    /// frame.text(20.0, content_y + 10.0, "Sword x1");
-    /// frame.text(20.0, content_y + 30.0, "Potion x3");
+    /// frame.clear_point_lights();
+    /// let torch = Vec3::new(frame.time.cos() * 3.0, 1.0, frame.time.sin() * 3.0);
+    /// frame.add_point_light(torch, Color::rgb(1.0, 0.6, 0.2), 6.0);
     /// ```
-    pub fn panel_titled(&mut self, x: f32, y: f32, w: f32, h: f32, title: &str) -> f32 {
-        let font = self
-            .default_font
-            .expect("Panel with title requires default font. Call ctx.default_font() in setup.");
-        self.draw
-            .panel(x, y, w, h)
-            .title(title, font)
-            .draw(self.assets);
-        y + 22.0 // Title bar height
+    pub fn add_point_light(&mut self, position: Vec3, color: Color, range: f32) {
+        self.mesh_queue
+            .borrow_mut()
+            .lighting
+            .add_point_light(position, color, range);
+    }
+
+    /// Remove all point lights.
+    pub fn clear_point_lights(&mut self) {
+        self.mesh_queue.borrow_mut().lighting.point_lights.clear();
     }
 
     // ========================================================================
@@ -1547,6 +3147,12 @@ impl Frame<'_> {
             transform: Transform::default(),
             color: Color::WHITE,
             texture: None,
+            layer: 0,
+            uv_transform: UvTransform::IDENTITY,
+            normal_map: None,
+            material: None,
+            cull_mode: CullMode::Back,
+            alpha_cutoff: 0.0,
         }
     }
 
@@ -1584,6 +3190,115 @@ impl Frame<'_> {
         self.draw_mesh(mesh, transform, Color::WHITE);
     }
 
+    /// Draw a world-space line segment for this frame.
+    ///
+    /// Debug lines render after the meshes with the same camera and are hidden
+    /// behind nearer geometry. They need a [`MeshNode`] in the render graph.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // World axes at the origin
+    /// frame.debug_line(Vec3::ZERO, Vec3::X, Color::rgb(1.0, 0.0, 0.0));
+    /// frame.debug_line(Vec3::ZERO, Vec3::Y, Color::rgb(0.0, 1.0, 0.0));
+    /// frame.debug_line(Vec3::ZERO, Vec3::Z, Color::rgb(0.0, 0.0, 1.0));
+    /// ```
+    pub fn debug_line(&mut self, a: Vec3, b: Vec3, color: Color) {
+        self.mesh_queue.borrow_mut().debug_lines.line(a, b, color);
+    }
+
+    /// Draw the wireframe of an axis-aligned box for this frame.
+    ///
+    /// See [`Self::debug_line`].
+    pub fn debug_aabb(&mut self, min: Vec3, max: Vec3, color: Color) {
+        self.mesh_queue
+            .borrow_mut()
+            .debug_lines
+            .aabb(min, max, color);
+    }
+
+    /// Draw the first `length` units of a ray for this frame.
+    ///
+    /// See [`Self::debug_line`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let ray = frame.mouse_ray();
+    /// frame.debug_ray(&ray, 100.0, Color::rgb(1.0, 1.0, 0.0));
+    /// ```
+    pub fn debug_ray(&mut self, ray: &Ray, length: f32, color: Color) {
+        self.mesh_queue
+            .borrow_mut()
+            .debug_lines
+            .ray(ray, length, color);
+    }
+
+    /// Draw the wireframe of every collider in the world for this frame.
+    ///
+    /// Queries entities with both a [`Transform`] and a [`Collider`] and outlines
+    /// each shape as picking sees it: boxes as axis-aligned boxes and spheres as
+    /// three circles. Handy for checking that hitboxes match their meshes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if show_hitboxes {
+    ///     frame.draw_colliders(Color::rgb(0.0, 1.0, 0.0));
+    /// }
+    /// ```
+    pub fn draw_colliders(&mut self, color: Color) {
+        let mut queue = self.mesh_queue.borrow_mut();
+        for (_, (transform, collider)) in self.world.query::<(&Transform, &Collider)>().iter() {
+            queue.debug_lines.collider(collider, transform, color);
+        }
+    }
+
+    /// Draw a textured quad at a 3D position that always faces the camera.
+    ///
+    /// The quad is `size` world units across and is tinted by `tint`. It's drawn
+    /// after the meshes, hidden by anything in front of it, and blended back to
+    /// front with other billboards. Use it for health bars, labels, and
+    /// sprite-like effects that live in the 3D scene.
+    ///
+    /// # Arguments
+    ///
+    /// * `texture` - Handle returned by `SetupContext::texture_*` or `add_texture`
+    /// * `position` - World-space center of the quad
+    /// * `size` - Width and height in world units
+    /// * `tint` - Color multiplied with the texture
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Health bar floating above an enemy
+    /// let above = enemy_pos + Vec3::Y * 2.0;
+    /// frame.billboard(bar, above, Vec2::new(1.0 * health, 0.15), Color::rgb(0.2, 0.9, 0.2));
+    /// ```
+    pub fn billboard(&mut self, texture: TextureId, position: Vec3, size: Vec2, tint: Color) {
+        self.mesh_queue
+            .borrow_mut()
+            .billboards
+            .push(Billboard::new(texture, position, size, tint));
+    }
+
+    /// Draw a camera-facing quad that stays upright, turning only around the Y axis.
+    ///
+    /// Like [`Self::billboard`], but the quad doesn't tilt when the camera looks
+    /// up or down, which suits trees, characters, and other standing sprites.
+    pub fn billboard_cylindrical(
+        &mut self,
+        texture: TextureId,
+        position: Vec3,
+        size: Vec2,
+        tint: Color,
+    ) {
+        self.mesh_queue
+            .borrow_mut()
+            .billboards
+            .push(Billboard::new(texture, position, size, tint).mode(BillboardMode::Cylindrical));
+    }
+
     /// Draw a textured 3D mesh with a color tint.
     ///
     /// The texture is sampled and multiplied by the color. Use `Color::WHITE`
@@ -1641,6 +3356,27 @@ impl Frame<'_> {
         self.draw_mesh_textured(mesh, transform, Color::WHITE, texture);
     }
 
+    /// Draw every part of a model loaded with [`SetupContext::model_from_gltf`].
+    ///
+    /// `transform` places the whole model; each part keeps its position
+    /// relative to the others, along with its own color and texture.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let spin = Transform::new().rotation(Quat::from_rotation_y(frame.time));
+    /// frame.draw_model(&helmet, spin);
+    /// ```
+    pub fn draw_model(&mut self, parts: &[ModelPart], transform: Transform) {
+        for part in parts {
+            let placed = model::compose(transform, part.transform);
+            match part.texture {
+                Some(texture) => self.draw_mesh_textured(part.mesh, placed, part.color, texture),
+                None => self.draw_mesh(part.mesh, placed, part.color),
+            }
+        }
+    }
+
     // ========================================================================
     // 2D Sprite Rendering
     // ========================================================================
@@ -1817,6 +3553,117 @@ impl Frame<'_> {
             .sprite_region(sprite_id, x, y, w, h, src_x, src_y, src_w, src_h, tint);
     }
 
+    /// Draw a sprite as a resizable 9-slice panel.
+    ///
+    /// The sprite is cut `border` pixels in from each edge: corners stay
+    /// unscaled, edges stretch along one axis, and the center fills the rest.
+    /// Use this for textured UI panels and buttons that need to resize cleanly.
+    ///
+    /// # Arguments
+    ///
+    /// * `sprite_id` - ID of the panel sprite
+    /// * `dest` - Destination rectangle on screen
+    /// * `border` - Width of the panel's frame in sprite pixels
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // A 48x48 panel texture with a 16px frame, stretched to a dialog box
+    /// frame.sprite_nine_slice(panel, Rect::new(100.0, 100.0, 400.0, 200.0), 16.0);
+    /// ```
+    pub fn sprite_nine_slice(&mut self, sprite_id: SpriteId, dest: Rect, border: f32) {
+        self.draw
+            .sprite_nine_slice(sprite_id, dest, border, Color::WHITE);
+    }
+
+    /// Draw a 9-slice panel with a color tint.
+    ///
+    /// See [`Self::sprite_nine_slice`].
+    pub fn sprite_nine_slice_tinted(
+        &mut self,
+        sprite_id: SpriteId,
+        dest: Rect,
+        border: f32,
+        tint: Color,
+    ) {
+        self.draw.sprite_nine_slice(sprite_id, dest, border, tint);
+    }
+
+    /// Draw many copies of a sprite in one instanced draw call.
+    ///
+    /// Each instance is centered on its position with its own scale, rotation,
+    /// and tint. Prefer this over calling [`Self::sprite`] in a loop for
+    /// particles and other large crowds of the same sprite.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let flakes: Vec<SpriteInstance> = snow
+    ///     .iter()
+    ///     .map(|f| SpriteInstance::new(f.x, f.y).scale(f.size).rotation(f.spin))
+    ///     .collect();
+    /// frame.sprites_instanced(flake, &flakes);
+    /// ```
+    pub fn sprites_instanced(&mut self, sprite_id: SpriteId, instances: &[SpriteInstance]) {
+        self.draw.sprites_instanced(sprite_id, instances);
+    }
+
+    /// Draw every live particle of a particle system in one instanced draw call.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// sparks.emit(frame.input.mouse_position());
+    /// sparks.update(frame.dt);
+    /// frame.draw_particles(&sparks);
+    /// ```
+    pub fn draw_particles(&mut self, system: &ParticleSystem) {
+        let instances: Vec<SpriteInstance> = system.instances().collect();
+        self.draw.sprites_instanced(system.sprite(), &instances);
+    }
+
+    /// Draw the current frame of a sprite sheet animation at its native frame size.
+    ///
+    /// # Arguments
+    ///
+    /// * `anim` - The animation; advance it with [`SpriteAnimation::update`]
+    /// * `x`, `y` - Top-left corner in screen pixels
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Setup:
+    /// let sheet = ctx.sprite_from_file_nearest("assets/hero_walk.png")?;
+    /// let mut walk = SpriteAnimation::new(sheet, 32.0, 32.0, 8, 4, 12.0);
+    ///
+    /// // Frame loop:
+    /// move |frame| {
+    ///     walk.update(frame.dt);
+    ///     frame.sprite_anim(&walk, 100.0, 100.0);
+    /// }
+    /// ```
+    pub fn sprite_anim(&mut self, anim: &SpriteAnimation, x: f32, y: f32) {
+        let (w, h) = anim.frame_size();
+        self.sprite_anim_scaled(anim, x, y, w, h);
+    }
+
+    /// Draw the current frame of a sprite sheet animation scaled to `w` x `h`.
+    pub fn sprite_anim_scaled(&mut self, anim: &SpriteAnimation, x: f32, y: f32, w: f32, h: f32) {
+        let (src_x, src_y, src_w, src_h) = anim.source_rect();
+        self.draw.sprite_region(
+            anim.sprite(),
+            x,
+            y,
+            w,
+            h,
+            src_x,
+            src_y,
+            src_w,
+            src_h,
+            Color::WHITE,
+        );
+    }
+
     // ========================================================================
     // ECS Rendering
     // ========================================================================
@@ -1850,20 +3697,21 @@ impl Frame<'_> {
         use crate::ecs::RenderMesh;
         use crate::mesh::Transform;
 
+        let mut queue = self.mesh_queue.borrow_mut();
         for (_, (transform, render_mesh)) in self.world.query::<(&Transform, &RenderMesh)>().iter()
         {
-            if let Some(texture) = render_mesh.texture {
-                self.mesh_queue.borrow_mut().draw_textured(
-                    render_mesh.mesh,
-                    *transform,
-                    render_mesh.color,
-                    texture,
-                );
-            } else {
-                self.mesh_queue
-                    .borrow_mut()
-                    .draw(render_mesh.mesh, *transform, render_mesh.color);
-            }
+            queue.draw_queue.push(QueuedMesh {
+                mesh: render_mesh.mesh,
+                transform: *transform,
+                color: render_mesh.color,
+                texture: render_mesh.texture,
+                layer: 0,
+                uv_transform: UvTransform::IDENTITY,
+                normal_map: None,
+                material: render_mesh.material,
+                cull_mode: CullMode::Back,
+                alpha_cutoff: 0.0,
+            });
         }
     }
 
@@ -1901,6 +3749,110 @@ impl Frame<'_> {
         )
     }
 
+    /// Project a world-space point to screen coordinates in pixels.
+    ///
+    /// Returns `None` if the point is behind the camera. Uses the same
+    /// view/projection matrices as [`mouse_ray`](Self::mouse_ray).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Draw a label above an entity
+    /// if let Some(pos) = frame.world_to_screen(entity_pos + Vec3::Y) {
+    ///     frame.text(pos.x, pos.y, "Player");
+    /// }
+    /// ```
+    pub fn world_to_screen(&self, pos: Vec3) -> Option<Vec2> {
+        self.camera.world_to_screen(pos, self.screen_size())
+    }
+
+    /// Unproject screen coordinates to a world-space point.
+    ///
+    /// `depth` is the distance in front of the camera along its forward axis.
+    /// This is the inverse of [`world_to_screen`](Self::world_to_screen).
+    pub fn screen_to_world(&self, screen: Vec2, depth: f32) -> Vec3 {
+        self.camera
+            .screen_to_world(screen, depth, self.screen_size())
+    }
+
+    /// Draw text centered on the screen position of a world-space point.
+    ///
+    /// The text is `size` pixels tall wherever the point is, like an RTS unit
+    /// label. Nothing is drawn when the point is behind the camera. Uses the
+    /// current font.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.text_3d(ship_pos + Vec3::Y * 2.0, "Frigate", Color::WHITE, 16.0);
+    /// ```
+    pub fn text_3d(&mut self, position: Vec3, text: &str, color: Color, size: f32) {
+        self.draw_world_text(position, text, color, size);
+    }
+
+    /// Draw text at a world-space point that shrinks with distance.
+    ///
+    /// Like [`text_3d`](Self::text_3d), but the text is `size` pixels tall when
+    /// the point is `reference_distance` units in front of the camera, and
+    /// scales in proportion to that distance elsewhere, so labels sit in the
+    /// scene like real signs. Orthographic cameras draw it at `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no default font was set during setup.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // 24px at 10 units away, 12px at 20
+    /// frame.text_3d_scaled(station_pos, "Outpost", Color::WHITE, 24.0, 10.0);
+    /// ```
+    pub fn text_3d_scaled(
+        &mut self,
+        position: Vec3,
+        text: &str,
+        color: Color,
+        size: f32,
+        reference_distance: f32,
+    ) {
+        let depth = (position - self.camera.position).dot(self.camera.forward.normalize_or_zero());
+        let size = if self.camera.is_orthographic() || depth <= 0.0 {
+            size
+        } else {
+            size * reference_distance / depth
+        };
+        self.draw_world_text(position, text, color, size);
+    }
+
+    /// Draws `text` at `size` pixels, centered on the projection of `position`.
+    fn draw_world_text(&mut self, position: Vec3, text: &str, color: Color, size: f32) {
+        let font = self.current_font();
+        let Some(screen) = self.world_to_screen(position) else {
+            return;
+        };
+        let Some(font_size) = self.assets.font(font).map(|atlas| atlas.size()) else {
+            return;
+        };
+        let extent = self.draw.measure_text(self.assets, font, text) * (size / font_size);
+        self.draw.text_sized(
+            self.assets,
+            font,
+            screen.x - extent.x * 0.5,
+            screen.y - extent.y * 0.5,
+            size,
+            text,
+            color,
+        );
+    }
+
+    fn screen_size(&self) -> Vec2 {
+        Vec2::new(self.gpu.width() as f32, self.gpu.height() as f32)
+    }
+
     /// Cast a ray from the mouse and find the closest entity with a collider.
     ///
     /// This is the primary method for implementing mouse picking in your game.
@@ -1925,8 +3877,28 @@ impl Frame<'_> {
     /// }
     /// ```
     pub fn pick_collider(&self) -> PickResult {
+        self.pick_collider_masked(Collider::ALL_LAYERS)
+    }
+
+    /// Cast a ray from the mouse and find the closest collider on the given layers.
+    ///
+    /// Like [`pick_collider`](Self::pick_collider), but colliders whose
+    /// [`layer`](Collider::layer) shares no bit with `mask` are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// const ENEMIES: u32 = 1 << 1;
+    /// ctx.world.spawn((transform, Collider::unit_box().with_layer(ENEMIES)));
+    ///
+    /// // Later, in the frame: click enemies only
+    /// if let Some(hit) = frame.pick_collider_masked(ENEMIES) {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn pick_collider_masked(&self, mask: u32) -> PickResult {
         let ray = self.mouse_ray();
-        picking::raycast(self.world, &ray)
+        picking::raycast(self.world, &ray, mask)
     }
 
     /// Cast a ray from the mouse and find all entities with colliders.
@@ -1949,7 +3921,7 @@ impl Frame<'_> {
     /// ```
     pub fn pick_collider_all(&self) -> Vec<RayHit> {
         let ray = self.mouse_ray();
-        picking::raycast_all(self.world, &ray)
+        picking::raycast_all(self.world, &ray, Collider::ALL_LAYERS)
     }
 
     /// Cast a custom ray and find the closest entity with a collider.
@@ -1963,19 +3935,58 @@ impl Frame<'_> {
     ///
     /// # Returns
     ///
-    /// The closest hit entity, or `None` if nothing was hit.
+    /// The closest hit entity, or `None` if nothing was hit.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Check if enemy can see player
+    /// let ray = Ray::new(enemy_pos, (player_pos - enemy_pos).normalize());
+    /// if let Some(hit) = frame.raycast(&ray) {
+    ///     // Something is in the way
+    /// }
+    /// ```
+    pub fn raycast(&self, ray: &Ray) -> PickResult {
+        picking::raycast(self.world, ray, Collider::ALL_LAYERS)
+    }
+
+    /// Find all entities with colliders inside a screen-space rectangle.
+    ///
+    /// Each collider's center is projected to the screen (like
+    /// [`world_to_screen`](Self::world_to_screen)) and kept if it falls inside
+    /// the rectangle. Entities behind the camera are excluded. The corners may
+    /// be given in any order, which suits RTS-style drag selection.
+    ///
+    /// # Returns
+    ///
+    /// The selected entities, sorted by distance from the camera (closest first).
     ///
     /// # Example
     ///
     /// ```ignore
-    /// // Check if enemy can see player
-    /// let ray = Ray::new(enemy_pos, (player_pos - enemy_pos).normalize());
-    /// if let Some(hit) = frame.raycast(&ray) {
-    ///     // Something is in the way
+    /// if frame.input.mouse_pressed(MouseButton::Left) {
+    ///     drag_start = frame.input.mouse_position();
+    /// }
+    /// if frame.input.mouse_released(MouseButton::Left) {
+    ///     selection = frame.select_in_rect(drag_start, frame.input.mouse_position());
     /// }
     /// ```
-    pub fn raycast(&self, ray: &Ray) -> PickResult {
-        picking::raycast(self.world, ray)
+    pub fn select_in_rect(&self, screen_min: Vec2, screen_max: Vec2) -> Vec<hecs::Entity> {
+        picking::select_in_rect(
+            self.world,
+            self.camera,
+            self.screen_size(),
+            screen_min,
+            screen_max,
+            Collider::ALL_LAYERS,
+        )
+    }
+
+    /// Cast a custom ray and find the closest collider on the given layers.
+    ///
+    /// See [`pick_collider_masked`](Self::pick_collider_masked) for details.
+    pub fn raycast_masked(&self, ray: &Ray, mask: u32) -> PickResult {
+        picking::raycast(self.world, ray, mask)
     }
 
     /// Cast a custom ray and find all entities with colliders.
@@ -1988,7 +3999,40 @@ impl Frame<'_> {
     ///
     /// A vector of all hits, sorted by distance (closest first).
     pub fn raycast_all(&self, ray: &Ray) -> Vec<RayHit> {
-        picking::raycast_all(self.world, ray)
+        picking::raycast_all(self.world, ray, Collider::ALL_LAYERS)
+    }
+
+    /// Cast a ray from the mouse against the triangles of rendered meshes.
+    ///
+    /// Unlike [`pick_collider`](Self::pick_collider), this needs no `Collider`:
+    /// it tests the actual geometry of every entity with a `Transform` and
+    /// [`RenderMesh`](crate::RenderMesh). The hit's [`triangle`](RayHit::triangle) holds the
+    /// triangle index, barycentric coordinates, and interpolated UV.
+    ///
+    /// Cost grows with triangle count, so prefer colliders for large scenes.
+    ///
+    /// # Returns
+    ///
+    /// The closest hit, or `None` if no mesh is under the mouse.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Some(hit) = frame.pick_mesh() {
+    ///     let uv = hit.triangle.unwrap().uv;
+    ///     frame.text(10.0, 10.0, &format!("{:?} at uv {:?}", hit.entity, uv));
+    /// }
+    /// ```
+    pub fn pick_mesh(&self) -> PickResult {
+        let ray = self.mouse_ray();
+        self.raycast_mesh(&ray)
+    }
+
+    /// Cast a custom ray against the triangles of rendered meshes.
+    ///
+    /// See [`pick_mesh`](Self::pick_mesh) for details.
+    pub fn raycast_mesh(&self, ray: &Ray) -> PickResult {
+        picking::raycast_meshes(self.world, &self.mesh_queue.borrow(), ray)
     }
 }
 
@@ -2020,6 +4064,12 @@ pub struct MeshBuilder<'a> {
     transform: Transform,
     color: Color,
     texture: Option<TextureId>,
+    layer: u32,
+    uv_transform: UvTransform,
+    normal_map: Option<TextureId>,
+    material: Option<MaterialId>,
+    cull_mode: CullMode,
+    alpha_cutoff: f32,
 }
 
 impl MeshBuilder<'_> {
@@ -2086,17 +4136,172 @@ impl MeshBuilder<'_> {
         self
     }
 
+    /// Apply one layer of a texture array to the mesh.
+    ///
+    /// Meshes sharing the same array are drawn together in one instanced call
+    /// whatever their layers, so thousands of differently textured tiles cost
+    /// a single draw.
+    ///
+    /// # Arguments
+    ///
+    /// * `texture` - Texture array handle from `ctx.texture_array_from_files()`
+    /// * `layer` - Index of the layer to sample
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for (position, block) in &chunk {
+    ///     frame.mesh(cube).position(*position).texture_layer(blocks, *block as u32).draw();
+    /// }
+    /// ```
+    pub fn texture_layer(mut self, texture: TextureId, layer: u32) -> Self {
+        self.texture = Some(texture);
+        self.layer = layer;
+        self
+    }
+
+    /// Shift the mesh's texture coordinates.
+    ///
+    /// Animate it to scroll a repeating texture, for conveyor belts, flowing
+    /// water, or drifting skies, without touching the mesh.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Amount added to every UV, in texture repeats
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.mesh(river).texture(water).uv_offset(Vec2::new(0.0, frame.time * 0.2)).draw();
+    /// ```
+    pub fn uv_offset(mut self, offset: glam::Vec2) -> Self {
+        self.uv_transform.offset = offset;
+        self
+    }
+
+    /// Scale the mesh's texture coordinates.
+    ///
+    /// Values above `1.0` repeat a tiling texture more times across the surface.
+    ///
+    /// # Arguments
+    ///
+    /// * `scale` - Per-axis multiplier, applied before rotation and offset
+    pub fn uv_scale(mut self, scale: glam::Vec2) -> Self {
+        self.uv_transform.scale = scale;
+        self
+    }
+
+    /// Rotate the mesh's texture coordinates counter-clockwise around the UV origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `radians` - Rotation applied after scaling and before the offset
+    pub fn uv_rotation(mut self, radians: f32) -> Self {
+        self.uv_transform.rotation = radians;
+        self
+    }
+
+    /// Set the full UV transform at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `uv_transform` - Offset, scale, and rotation for the mesh's UVs
+    pub fn uv_transform(mut self, uv_transform: UvTransform) -> Self {
+        self.uv_transform = uv_transform;
+        self
+    }
+
+    /// Apply a tangent-space normal map to the mesh.
+    ///
+    /// The normal map adds surface detail to lighting without extra geometry.
+    /// Load it with [`SetupContext::normal_map_from_file`] so its data isn't
+    /// treated as sRGB color. Meshes need tangents, which the built-in
+    /// primitives and loaded models have; call
+    /// [`generate_tangents`](crate::generate_tangents) for custom meshes.
+    ///
+    /// # Arguments
+    ///
+    /// * `normal_map` - Normal map handle from `ctx.normal_map_from_file()`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.mesh(wall).texture(bricks).normal_map(bricks_normal).draw();
+    /// ```
+    pub fn normal_map(mut self, normal_map: TextureId) -> Self {
+        self.normal_map = Some(normal_map);
+        self
+    }
+
+    /// Render the mesh with a custom material instead of the built-in shader.
+    ///
+    /// # Arguments
+    ///
+    /// * `material` - Material handle from `ctx.add_material()`
+    pub fn material(mut self, material: MaterialId) -> Self {
+        self.material = Some(material);
+        self
+    }
+
+    /// Choose which triangle faces are discarded.
+    ///
+    /// Meshes cull back faces by default. Use [`CullMode::Front`] for models
+    /// exported with clockwise winding, which otherwise render inside-out.
+    ///
+    /// # Arguments
+    ///
+    /// * `cull_mode` - The faces to discard
+    pub fn cull(mut self, cull_mode: CullMode) -> Self {
+        self.cull_mode = cull_mode;
+        self
+    }
+
+    /// Draw both sides of every triangle.
+    ///
+    /// Shorthand for `.cull(CullMode::None)`, for open surfaces such as
+    /// foliage, flags, or planes seen from below.
+    pub fn double_sided(self) -> Self {
+        self.cull(CullMode::None)
+    }
+
+    /// Render with alpha testing instead of alpha blending.
+    ///
+    /// Fragments whose alpha (texture alpha times the color's alpha) is below
+    /// `cutoff` are discarded, and the rest are drawn fully opaque with depth
+    /// writes. Grass, leaves, and fences then render correctly in any draw
+    /// order, with no transparency sorting. A cutoff of `0.0` restores blending.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff` - Alpha threshold in `[0, 1]`, typically `0.5`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.mesh(leaves).texture(leaf_texture).alpha_cutout(0.5).double_sided().draw();
+    /// ```
+    pub fn alpha_cutout(mut self, cutoff: f32) -> Self {
+        self.alpha_cutoff = cutoff.clamp(0.0, 1.0);
+        self
+    }
+
     /// Queue the mesh for rendering.
     ///
     /// This must be called to actually draw the mesh. The builder pattern
     /// allows you to configure all options, then draw with a single call.
     pub fn draw(self) {
-        let mut queue = self.queue.borrow_mut();
-        if let Some(texture) = self.texture {
-            queue.draw_textured(self.mesh, self.transform, self.color, texture);
-        } else {
-            queue.draw(self.mesh, self.transform, self.color);
-        }
+        self.queue.borrow_mut().draw_queue.push(QueuedMesh {
+            mesh: self.mesh,
+            transform: self.transform,
+            color: self.color,
+            texture: self.texture,
+            layer: self.layer,
+            uv_transform: self.uv_transform,
+            normal_map: self.normal_map,
+            material: self.material,
+            cull_mode: self.cull_mode,
+            alpha_cutoff: self.alpha_cutoff,
+        });
     }
 }
 
@@ -2130,15 +4335,33 @@ pub struct AppConfig {
     pub width: u32,
     /// Initial window height in pixels.
     pub height: u32,
+    /// Whether presenting waits for the display refresh.
+    pub vsync: bool,
+    /// Optional frame rate cap; the loop sleeps between frames to hold it.
+    pub max_fps: Option<u32>,
+    /// Whether the window starts in borderless fullscreen.
+    pub fullscreen: bool,
+    /// Fraction of the window resolution used for 3D rendering.
+    pub render_scale: f32,
+    /// Which adapter to prefer on machines with more than one GPU.
+    pub power_preference: wgpu::PowerPreference,
+    /// Graphics APIs wgpu may choose from.
+    pub backends: wgpu::Backends,
 }
 
 impl Default for AppConfig {
-    /// Create default configuration: 800x600 window titled "Hoplite".
+    /// Create default configuration: 800x600 window titled "Hoplite", vsync on, no FPS cap.
     fn default() -> Self {
         Self {
             title: "Hoplite".to_string(),
             width: 800,
             height: 600,
+            vsync: true,
+            max_fps: None,
+            fullscreen: false,
+            render_scale: 1.0,
+            power_preference: wgpu::PowerPreference::default(),
+            backends: wgpu::Backends::PRIMARY,
         }
     }
 }
@@ -2187,6 +4410,176 @@ impl AppConfig {
         self.height = height;
         self
     }
+
+    /// Enable or disable vsync (on by default).
+    ///
+    /// With vsync, frames are presented in step with the display refresh rate.
+    /// Disabling it uses a low-latency present mode (`Mailbox`, or `Immediate`
+    /// where supported) and lets the loop run as fast as the GPU allows;
+    /// combine it with [`max_fps`](Self::max_fps) to avoid pegging the CPU and GPU.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// AppConfig::new().vsync(false).max_fps(144)
+    /// ```
+    pub fn vsync(mut self, enabled: bool) -> Self {
+        self.vsync = enabled;
+        self
+    }
+
+    /// Cap the frame rate by sleeping between frames.
+    ///
+    /// Pass 0 to remove the cap. The cap applies on top of vsync, so it only
+    /// has an effect when it is below the display refresh rate or vsync is off.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// AppConfig::new().max_fps(30)  // Save battery on laptops
+    /// ```
+    pub fn max_fps(mut self, fps: u32) -> Self {
+        self.max_fps = (fps > 0).then_some(fps);
+        self
+    }
+
+    /// Start in borderless fullscreen on the primary monitor.
+    ///
+    /// Use [`Frame::set_fullscreen`] or [`Frame::toggle_fullscreen`] to switch
+    /// at runtime. [`size`](Self::size) still sets the windowed size.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// AppConfig::new().title("My Game").fullscreen(true)
+    /// ```
+    pub fn fullscreen(mut self, enabled: bool) -> Self {
+        self.fullscreen = enabled;
+        self
+    }
+
+    /// Render effects and meshes at a fraction of the window resolution.
+    ///
+    /// With a scale of 0.5, a 4K window renders its render graph at 1920x1080
+    /// and upscales the result, quartering the pixel shader cost. 2D drawing
+    /// and text stay at native resolution. Clamped to `0.1..=1.0`; defaults to 1.0.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// AppConfig::new().title("Raymarcher").render_scale(0.5)
+    /// ```
+    pub fn render_scale(mut self, scale: f32) -> Self {
+        self.render_scale = scale;
+        self
+    }
+
+    /// Choose between the integrated and discrete GPU on multi-GPU machines.
+    ///
+    /// `HighPerformance` asks for the discrete GPU, `LowPower` for the
+    /// integrated one. Log [`GpuContext::adapter_info`] to see which was picked.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// AppConfig::new().power_preference(wgpu::PowerPreference::HighPerformance)
+    /// ```
+    pub fn power_preference(mut self, preference: wgpu::PowerPreference) -> Self {
+        self.power_preference = preference;
+        self
+    }
+
+    /// Restrict which graphics APIs wgpu may use.
+    ///
+    /// Defaults to the primary backends (Vulkan, Metal, DX12, and WebGPU).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Force Vulkan on Linux
+    /// AppConfig::new().backends(wgpu::Backends::VULKAN)
+    /// ```
+    pub fn backends(mut self, backends: wgpu::Backends) -> Self {
+        self.backends = backends;
+        self
+    }
+
+    /// GPU options for creating the context described by this config.
+    fn gpu_options(&self) -> GpuOptions {
+        GpuOptions {
+            vsync: self.vsync,
+            power_preference: self.power_preference,
+            backends: self.backends,
+        }
+    }
+
+    /// Window attributes for creating the window described by this config.
+    fn window_attributes(&self) -> WindowAttributes {
+        WindowAttributes::default()
+            .with_title(&self.title)
+            .with_inner_size(winit::dpi::LogicalSize::new(self.width, self.height))
+            .with_fullscreen(fullscreen_mode(self.fullscreen))
+    }
+
+    /// Minimum time between frames implied by [`max_fps`](Self::max_fps).
+    fn frame_interval(&self) -> Option<Duration> {
+        self.max_fps
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+    }
+}
+
+/// The winit fullscreen mode for borderless fullscreen or windowed.
+fn fullscreen_mode(fullscreen: bool) -> Option<winit::window::Fullscreen> {
+    fullscreen.then_some(winit::window::Fullscreen::Borderless(None))
+}
+
+/// Resizes the surface if it no longer matches the window.
+///
+/// `Resized` events can arrive after a redraw when switching fullscreen, and
+/// rendering to a stale surface fails, so the size is also checked every frame.
+fn sync_surface_size(gpu: &mut GpuContext, window: &Window, hooks: &mut AppHooks) {
+    let size = window.inner_size();
+    if size.width != gpu.width() || size.height != gpu.height() {
+        hooks.resize(gpu, size.width, size.height);
+    }
+}
+
+/// Callback run after the surface is resized, with the new width and height.
+type ResizeFn = Box<dyn FnMut(&GpuContext, u32, u32)>;
+
+/// Callbacks registered during setup that the app loop runs outside the frame closure.
+#[derive(Default)]
+struct AppHooks {
+    /// Fixed-rate update (set via [`SetupContext::fixed_update`]).
+    fixed_update: Option<FixedUpdate>,
+    /// Resize handlers (added via [`SetupContext::on_resize`]).
+    resize: Vec<ResizeFn>,
+    /// Font for the shader error overlay (set via [`SetupContext::shader_error_overlay`]).
+    error_overlay: Option<FontId>,
+    /// Background for apps without a render graph (set via [`SetupContext::clear_color`]).
+    clear_color: Option<Color>,
+}
+
+impl AppHooks {
+    /// Resizes the surface and notifies resize handlers.
+    ///
+    /// Zero sizes (a minimized window) leave the surface untouched and are not reported.
+    fn resize(&mut self, gpu: &mut GpuContext, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        gpu.resize(width, height);
+        for callback in &mut self.resize {
+            callback(gpu, width, height);
+        }
+    }
+}
+
+/// Sleeps until at least `interval` has passed since `last_frame`.
+fn wait_for_frame_interval(last_frame: Instant, interval: Option<Duration>) {
+    if let Some(remaining) = interval.and_then(|i| i.checked_sub(last_frame.elapsed())) {
+        std::thread::sleep(remaining);
+    }
 }
 
 /// Run a Hoplite application with default configuration.
@@ -2214,6 +4607,8 @@ impl AppConfig {
 /// - The window cannot be created
 /// - Event loop creation fails
 ///
+/// Use [`run_fallible`] to get these as an [`Error`] instead.
+///
 /// # Example
 ///
 /// ```ignore
@@ -2257,7 +4652,8 @@ where
 ///
 /// # Panics
 ///
-/// Same conditions as [`run`].
+/// Same conditions as [`run`]. Use [`run_fallible_with_config`] to handle
+/// them instead.
 ///
 /// # Example
 ///
@@ -2286,36 +4682,176 @@ where
     S: FnOnce(&mut SetupContext) -> F + 'static,
     F: FnMut(&mut Frame) + 'static,
 {
-    let event_loop = EventLoop::new().unwrap();
-    event_loop.set_control_flow(ControlFlow::Poll);
+    if let Err(e) = run_fallible_with_config(config, setup) {
+        panic!("{}", e);
+    }
+}
+
+/// Run a Hoplite application, returning an error if it can't start.
+///
+/// Like [`run`], but instead of panicking when the event loop, window, or GPU
+/// can't be initialized, returns an [`Error`] so the host can show a message
+/// or fall back. GPU failures come back as [`Error::Gpu`].
+///
+/// # Example
+///
+/// ```ignore
+/// use hoplite::{run_fallible, Error};
+///
+/// fn main() {
+///     let result = run_fallible(|ctx| {
+///         ctx.default_font(16.0);
+///         move |frame| frame.text(10.0, 10.0, "Hello!")
+///     });
+///     if let Err(Error::Gpu(e)) = result {
+///         eprintln!("No usable graphics card: {}", e);
+///     }
+/// }
+/// ```
+pub fn run_fallible<S, F>(setup: S) -> Result<(), Error>
+where
+    S: FnOnce(&mut SetupContext) -> F + 'static,
+    F: FnMut(&mut Frame) + 'static,
+{
+    run_fallible_with_config(AppConfig::default(), setup)
+}
+
+/// Run a Hoplite application with custom configuration, returning an error
+/// if it can't start.
+///
+/// See [`run_fallible`] and [`run_with_config`].
+pub fn run_fallible_with_config<S, F>(config: AppConfig, setup: S) -> Result<(), Error>
+where
+    S: FnOnce(&mut SetupContext) -> F + 'static,
+    F: FnMut(&mut Frame) + 'static,
+{
+    let event_loop = EventLoop::new().map_err(|e| Error::Window(e.to_string()))?;
+    event_loop.set_control_flow(ControlFlow::Poll);
+
+    let mut app = HopliteApp::Pending {
+        config,
+        setup: Some(boxed_setup(setup)),
+    };
+
+    event_loop
+        .run_app(&mut app)
+        .map_err(|e| Error::Window(e.to_string()))?;
+    match app {
+        HopliteApp::Failed(e) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Game time each headless frame advances by, so output doesn't depend on machine speed.
+const HEADLESS_DT: f32 = 1.0 / 60.0;
+
+/// Run a Hoplite application without a window and return its last frame.
+///
+/// Runs `setup`, then `frames` frames rendered into a `width` x `height`
+/// offscreen target, and returns the final framebuffer as tightly packed
+/// sRGB RGBA8 pixels (`width * height * 4` bytes, top row first). Frames
+/// advance game time by a fixed 1/60 s regardless of how long they take, so
+/// the same program produces the same image on every run. [`Frame::exit`]
+/// stops early, and the frame that requested it is returned.
+///
+/// There is no window, so input stays idle and cursor and fullscreen calls
+/// do nothing.
+///
+/// # Panics
+///
+/// Panics if the GPU cannot be initialized or the last frame can't be read
+/// back. Use [`run_headless_fallible`] to handle that instead.
+///
+/// # Example
+///
+/// ```ignore
+/// use hoplite::*;
+///
+/// let pixels = run_headless(320, 240, 10, |ctx| {
+///     ctx.enable_mesh_rendering();
+///     let cube = ctx.mesh_cube();
+///     move |frame| {
+///         frame.mesh(cube).at(0.0, 0.0, -5.0).draw();
+///     }
+/// });
+/// image::RgbaImage::from_raw(320, 240, pixels).unwrap().save("cube.png")?;
+/// ```
+pub fn run_headless<S, F>(width: u32, height: u32, frames: u32, setup: S) -> Vec<u8>
+where
+    S: FnOnce(&mut SetupContext) -> F + 'static,
+    F: FnMut(&mut Frame) + 'static,
+{
+    run_headless_fallible(width, height, frames, setup).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Run a Hoplite application without a window, returning an error if the GPU
+/// can't be initialized.
+///
+/// See [`run_headless`]. GPU failures come back as [`Error::Gpu`], so tests
+/// can skip on machines without an adapter.
+///
+/// # Errors
+///
+/// Returns [`Error::Gpu`] if the GPU cannot be initialized, or
+/// [`Error::Screenshot`] if the last frame can't be read back.
+pub fn run_headless_fallible<S, F>(
+    width: u32,
+    height: u32,
+    frames: u32,
+    setup: S,
+) -> Result<Vec<u8>, Error>
+where
+    S: FnOnce(&mut SetupContext) -> F + 'static,
+    F: FnMut(&mut Frame) + 'static,
+{
+    let gpu = GpuContext::try_new_headless(width, height)?;
+    let mut state = AppState::new(gpu, boxed_setup(setup));
 
-    let mut app = HopliteApp::Pending {
-        config,
-        setup: Some(Box::new(move |gpu, assets, draw, mesh_queue, world| {
-            let mut default_font = None;
-            let mut graph_builder = None;
+    for _ in 0..frames {
+        state.clock.advance(HEADLESS_DT);
+        if state.run_frame(None) {
+            break;
+        }
+    }
 
-            let mut ctx = SetupContext {
-                gpu,
-                assets,
-                draw,
-                world,
-                default_font: &mut default_font,
-                graph_builder: &mut graph_builder,
-                mesh_queue,
-            };
+    let image = state.gpu.read_offscreen().ok_or_else(|| {
+        Error::Screenshot("the headless frame could not be read back".to_string())
+    })?;
+    Ok(image.into_raw())
+}
 
-            let frame_fn = setup(&mut ctx);
+/// Wraps the user's setup closure into the [`SetupFn`] the app loop runs once
+/// the GPU is ready.
+fn boxed_setup<S, F>(setup: S) -> SetupFn
+where
+    S: FnOnce(&mut SetupContext) -> F + 'static,
+    F: FnMut(&mut Frame) + 'static,
+{
+    Box::new(move |gpu, assets, draw, mesh_queue, world| {
+        let mut default_font = None;
+        let mut graph_builder = None;
+        let mut hooks = AppHooks::default();
 
-            (
-                Box::new(frame_fn) as Box<dyn FnMut(&mut Frame)>,
-                default_font,
-                graph_builder,
-            )
-        })),
-    };
+        let mut ctx = SetupContext {
+            gpu,
+            assets,
+            draw,
+            world,
+            default_font: &mut default_font,
+            graph_builder: &mut graph_builder,
+            mesh_queue,
+            hooks: &mut hooks,
+        };
 
-    event_loop.run_app(&mut app).unwrap();
+        let frame_fn = setup(&mut ctx);
+
+        (
+            Box::new(frame_fn) as Box<dyn FnMut(&mut Frame)>,
+            default_font,
+            graph_builder,
+            hooks,
+        )
+    })
 }
 
 // ============================================================================
@@ -2341,6 +4877,54 @@ impl<'a> SceneSetupContext<'a> {
         self.base.default_font(size)
     }
 
+    /// Load the default typeface at the specified size, or return it if already loaded.
+    ///
+    /// See [`SetupContext::font`] for details.
+    pub fn font(&mut self, size: f32) -> FontId {
+        self.base.font(size)
+    }
+
+    /// Load the default font as a signed distance field and make it the default.
+    ///
+    /// See [`SetupContext::default_font_sdf`] for details.
+    pub fn default_font_sdf(&mut self, size: f32) -> FontId {
+        self.base.default_font_sdf(size)
+    }
+
+    /// Load a TTF/OTF font from a file at the specified size.
+    ///
+    /// See [`SetupContext::load_font`] for details.
+    pub fn load_font(&mut self, path: impl AsRef<std::path::Path>, size: f32) -> FontId {
+        self.base.load_font(path, size)
+    }
+
+    /// Load a TTF/OTF font from raw bytes at the specified size.
+    ///
+    /// See [`SetupContext::load_font_bytes`] for details.
+    pub fn load_font_bytes(&mut self, data: &[u8], size: f32) -> FontId {
+        self.base.load_font_bytes(data, size)
+    }
+
+    /// Load a TTF/OTF font file to draw the characters `font` lacks.
+    ///
+    /// See [`SetupContext::load_font_fallback`] for details.
+    pub fn load_font_fallback(
+        &mut self,
+        font: FontId,
+        path: impl AsRef<std::path::Path>,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<FontId, Error> {
+        self.base.load_font_fallback(font, path, chars)
+    }
+
+    /// Draw `sprite` for `ch` in text when no font has a glyph for it.
+    ///
+    /// See [`SetupContext::color_glyph`] for details.
+    pub fn color_glyph(&mut self, ch: char, sprite: SpriteId) -> &mut Self {
+        self.base.color_glyph(ch, sprite);
+        self
+    }
+
     /// Create a unit cube mesh.
     ///
     /// See [`SetupContext::mesh_cube`] for details.
@@ -2362,6 +4946,128 @@ impl<'a> SceneSetupContext<'a> {
         self.base.mesh_plane(size)
     }
 
+    /// Register a callback that runs at a fixed rate, independent of rendering.
+    ///
+    /// The callback runs regardless of which scene is active. See
+    /// [`SetupContext::fixed_update`] for details.
+    pub fn fixed_update(
+        &mut self,
+        step: f32,
+        update: impl FnMut(&mut hecs::World, f32) + 'static,
+    ) -> &mut Self {
+        self.base.fixed_update(step, update);
+        self
+    }
+
+    /// Register a callback that runs whenever the window is resized.
+    ///
+    /// See [`SetupContext::on_resize`] for details.
+    pub fn on_resize(
+        &mut self,
+        callback: impl FnMut(&GpuContext, u32, u32) + 'static,
+    ) -> &mut Self {
+        self.base.on_resize(callback);
+        self
+    }
+
+    /// Show shader compilation errors from the active scene's hot-reload nodes on screen.
+    ///
+    /// See [`SetupContext::shader_error_overlay`] for details.
+    pub fn shader_error_overlay(&mut self) -> &mut Self {
+        self.base.shader_error_overlay();
+        self
+    }
+
+    /// Skip meshes that are entirely outside the camera's view.
+    ///
+    /// See [`SetupContext::set_frustum_culling`] for details.
+    pub fn set_frustum_culling(&mut self, enabled: bool) -> &mut Self {
+        self.base.set_frustum_culling(enabled);
+        self
+    }
+
+    /// Render opaque meshes' depth first so each pixel is shaded only once.
+    ///
+    /// See [`SetupContext::enable_depth_prepass`] for details.
+    pub fn enable_depth_prepass(&mut self) -> &mut Self {
+        self.base.enable_depth_prepass();
+        self
+    }
+
+    /// Record GPU timestamps around every render graph node.
+    ///
+    /// See [`SetupContext::enable_gpu_profiling`] for details.
+    pub fn enable_gpu_profiling(&mut self) -> &mut Self {
+        self.base.enable_gpu_profiling();
+        self
+    }
+
+    /// Set the directional "sun" light.
+    ///
+    /// See [`SetupContext::set_sun`] for details.
+    pub fn set_sun(&mut self, direction: Vec3, color: Color) -> &mut Self {
+        self.base.set_sun(direction, color);
+        self
+    }
+
+    /// Set the ambient light intensity.
+    ///
+    /// See [`SetupContext::set_ambient`] for details.
+    pub fn set_ambient(&mut self, ambient: f32) -> &mut Self {
+        self.base.set_ambient(ambient);
+        self
+    }
+
+    /// Add a point light.
+    ///
+    /// See [`SetupContext::add_point_light`] for details.
+    pub fn add_point_light(&mut self, position: Vec3, color: Color, range: f32) -> &mut Self {
+        self.base.add_point_light(position, color, range);
+        self
+    }
+
+    /// Register a custom WGSL material for meshes.
+    ///
+    /// See [`SetupContext::add_material`] for details.
+    pub fn add_material(&mut self, wgsl: &str) -> MaterialId {
+        self.base.add_material(wgsl)
+    }
+
+    /// Create a cylinder mesh.
+    ///
+    /// See [`SetupContext::mesh_cylinder`] for details.
+    pub fn mesh_cylinder(&mut self, radius: f32, height: f32, segments: u32) -> MeshId {
+        self.base.mesh_cylinder(radius, height, segments)
+    }
+
+    /// Create a cone mesh.
+    ///
+    /// See [`SetupContext::mesh_cone`] for details.
+    pub fn mesh_cone(&mut self, radius: f32, height: f32, segments: u32) -> MeshId {
+        self.base.mesh_cone(radius, height, segments)
+    }
+
+    /// Create a torus mesh.
+    ///
+    /// See [`SetupContext::mesh_torus`] for details.
+    pub fn mesh_torus(
+        &mut self,
+        major_radius: f32,
+        minor_radius: f32,
+        segments: u32,
+        rings: u32,
+    ) -> MeshId {
+        self.base
+            .mesh_torus(major_radius, minor_radius, segments, rings)
+    }
+
+    /// Create a capsule mesh.
+    ///
+    /// See [`SetupContext::mesh_capsule`] for details.
+    pub fn mesh_capsule(&mut self, radius: f32, height: f32, segments: u32, rings: u32) -> MeshId {
+        self.base.mesh_capsule(radius, height, segments, rings)
+    }
+
     /// Add a custom mesh.
     ///
     /// See [`SetupContext::add_mesh`] for details.
@@ -2369,6 +5075,13 @@ impl<'a> SceneSetupContext<'a> {
         self.base.add_mesh(mesh)
     }
 
+    /// Load every mesh in a glTF model.
+    ///
+    /// See [`SetupContext::model_from_gltf`] for details.
+    pub fn model_from_gltf(&mut self, path: &str) -> Result<Vec<ModelPart>, GeometryError> {
+        self.base.model_from_gltf(path)
+    }
+
     /// Add a texture.
     ///
     /// See [`SetupContext::add_texture`] for details.
@@ -2379,10 +5092,39 @@ impl<'a> SceneSetupContext<'a> {
     /// Load a texture from file.
     ///
     /// See [`SetupContext::texture_from_file`] for details.
-    pub fn texture_from_file(&mut self, path: &str) -> Result<TextureId, image::ImageError> {
+    pub fn texture_from_file(&mut self, path: &str) -> Result<TextureId, Error> {
         self.base.texture_from_file(path)
     }
 
+    /// Load a texture from file with nearest-neighbor filtering.
+    ///
+    /// See [`SetupContext::texture_from_file_nearest`] for details.
+    pub fn texture_from_file_nearest(&mut self, path: &str) -> Result<TextureId, Error> {
+        self.base.texture_from_file_nearest(path)
+    }
+
+    /// Load a texture array from files.
+    ///
+    /// See [`SetupContext::texture_array_from_files`] for details.
+    pub fn texture_array_from_files(&mut self, paths: &[&str]) -> Result<TextureId, Error> {
+        self.base.texture_array_from_files(paths)
+    }
+
+    /// Load a normal map from file.
+    ///
+    /// See [`SetupContext::normal_map_from_file`] for details.
+    pub fn normal_map_from_file(&mut self, path: &str) -> Result<TextureId, Error> {
+        self.base.normal_map_from_file(path)
+    }
+
+    /// Set a cubemap skybox as the scene background.
+    ///
+    /// See [`SetupContext::skybox_from_files`] for details.
+    pub fn skybox_from_files(&mut self, paths: [&str; 6]) -> Result<&mut Self, Error> {
+        self.base.skybox_from_files(paths)?;
+        Ok(self)
+    }
+
     /// Create a procedural noise texture.
     ///
     /// See [`SetupContext::texture_blocky_noise`] for details.
@@ -2407,7 +5149,7 @@ impl<'a> SceneSetupContext<'a> {
     /// Load a sprite from file.
     ///
     /// See [`SetupContext::sprite_from_file`] for details.
-    pub fn sprite_from_file(&mut self, path: &str) -> Result<SpriteId, image::ImageError> {
+    pub fn sprite_from_file(&mut self, path: &str) -> Result<SpriteId, Error> {
         self.base.sprite_from_file(path)
     }
 
@@ -2441,19 +5183,25 @@ impl<'a> SceneSetupContext<'a> {
         F: FnMut(&mut Frame) + 'static,
     {
         let mut render_graph = None;
+        let mut assets = crate::scene::SceneAssets::default();
         let mesh_queue = Rc::clone(self.base.mesh_queue);
 
-        let mut scene_ctx =
-            crate::scene::SceneSetupContext::new(self.base.gpu, &mut render_graph, &mesh_queue);
+        let mut scene_ctx = crate::scene::SceneSetupContext::new(
+            self.base.gpu,
+            &mut render_graph,
+            &mesh_queue,
+            &mut assets,
+        );
 
         let frame_fn = setup(&mut scene_ctx);
 
-        let scene = crate::scene::Scene::new(
+        let mut scene = crate::scene::Scene::new(
             crate::scene::SceneId::new(name),
             render_graph,
             mesh_queue,
             Box::new(frame_fn),
         );
+        scene.assets = assets;
 
         let scene_id = scene.id.clone();
         self.scene_manager.register(scene);
@@ -2556,6 +5304,7 @@ where
     let scene_setup: SceneSetupFn = Box::new(move |gpu, assets, draw, mesh_queue, world| {
         let mut default_font = None;
         let mut graph_builder = None;
+        let mut hooks = AppHooks::default();
         let mut scene_manager = crate::scene::SceneManager::new();
 
         // Initialize scene manager GPU resources
@@ -2570,6 +5319,7 @@ where
                 default_font: &mut default_font,
                 graph_builder: &mut graph_builder,
                 mesh_queue,
+                hooks: &mut hooks,
             };
 
             let mut ctx = SceneSetupContext {
@@ -2580,7 +5330,7 @@ where
             setup(&mut ctx);
         }
 
-        (scene_manager, default_font)
+        (scene_manager, default_font, hooks)
     });
 
     let mut app = HopliteSceneApp::Pending {
@@ -2599,7 +5349,7 @@ type SceneSetupFn = Box<
         &mut Draw2d,
         &Rc<RefCell<MeshQueue>>,
         &mut hecs::World,
-    ) -> (crate::scene::SceneManager, Option<FontId>),
+    ) -> (crate::scene::SceneManager, Option<FontId>, AppHooks),
 >;
 
 /// Internal application state machine for scene-based apps.
@@ -2620,20 +5370,22 @@ enum HopliteSceneApp {
         scene_manager: crate::scene::SceneManager,
         default_font: Option<FontId>,
         mesh_queue: Rc<RefCell<MeshQueue>>,
-        start_time: Instant,
-        last_frame: Instant,
+        hooks: AppHooks,
+        clock: Clock,
+        frame_interval: Option<Duration>,
     },
 }
 
 impl ApplicationHandler for HopliteSceneApp {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if let HopliteSceneApp::Pending { config, setup } = self {
-            let window_attrs = WindowAttributes::default()
-                .with_title(&config.title)
-                .with_inner_size(winit::dpi::LogicalSize::new(config.width, config.height));
+            let window_attrs = config.window_attributes();
 
             let window = Arc::new(event_loop.create_window(window_attrs).unwrap());
-            let gpu = GpuContext::new(window.clone());
+            // Let IME-composed text reach `Input::typed_chars`
+            window.set_ime_allowed(true);
+            let mut gpu = GpuContext::new_with_options(window.clone(), config.gpu_options());
+            gpu.set_render_scale(config.render_scale);
             let mut assets = Assets::new();
             let mut draw_2d = Draw2d::new(&gpu);
 
@@ -2642,7 +5394,7 @@ impl ApplicationHandler for HopliteSceneApp {
 
             // Run scene setup
             let setup_fn = setup.take().unwrap();
-            let (scene_manager, default_font) =
+            let (scene_manager, default_font, hooks) =
                 setup_fn(&gpu, &mut assets, &mut draw_2d, &mesh_queue, &mut world);
 
             *self = HopliteSceneApp::Running {
@@ -2655,8 +5407,9 @@ impl ApplicationHandler for HopliteSceneApp {
                 scene_manager,
                 default_font,
                 mesh_queue,
-                start_time: Instant::now(),
-                last_frame: Instant::now(),
+                hooks,
+                clock: Clock::new(),
+                frame_interval: config.frame_interval(),
             };
         }
     }
@@ -2672,8 +5425,9 @@ impl ApplicationHandler for HopliteSceneApp {
             scene_manager,
             default_font,
             mesh_queue,
-            start_time,
-            last_frame,
+            hooks,
+            clock,
+            frame_interval,
         } = self
         else {
             return;
@@ -2686,13 +5440,17 @@ impl ApplicationHandler for HopliteSceneApp {
                 event_loop.exit();
             }
             WindowEvent::Resized(size) => {
-                gpu.resize(size.width, size.height);
+                hooks.resize(gpu, size.width, size.height);
             }
             WindowEvent::RedrawRequested => {
-                let now = Instant::now();
-                let time = start_time.elapsed().as_secs_f32();
-                let dt = now.duration_since(*last_frame).as_secs_f32();
-                *last_frame = now;
+                wait_for_frame_interval(clock.last_frame(), *frame_interval);
+                sync_surface_size(gpu, window, hooks);
+
+                clock.tick(Instant::now());
+                let time = clock.time();
+
+                // Pick up gamepad events since the last frame
+                input.poll_gamepads();
 
                 // Clear draw_2d for new frame
                 draw_2d.clear();
@@ -2701,28 +5459,53 @@ impl ApplicationHandler for HopliteSceneApp {
                 // Clear mesh queue for new frame
                 mesh_queue.borrow_mut().clear_queue();
 
-                // Update scene manager (process transitions)
-                scene_manager.update(time);
+                // Catch the simulation up to real time
+                let fixed_alpha = hooks
+                    .fixed_update
+                    .as_mut()
+                    .map_or(1.0, |fixed| fixed.run(world, clock.dt()));
+
+                // Update scene manager (process transitions), which keep
+                // playing while game time is paused
+                scene_manager.update(clock.real_time());
+                scene_manager.sync_scene_assets(gpu);
 
                 // Run active scene's frame logic
-                scene_manager.run_frame(
+                let exit_requested = scene_manager.run_frame(
                     gpu,
                     assets,
                     draw_2d,
                     input,
                     world,
-                    time,
-                    dt,
+                    clock,
                     mesh_queue,
                     window,
                     *default_font,
+                    fixed_alpha,
                 );
 
+                // Cover the frame with any shader errors from the last reload check
+                if let Some(font) = hooks.error_overlay {
+                    let errors: Vec<&str> = scene_manager
+                        .active_scene_mut()
+                        .and_then(|scene| scene.render_graph.as_ref())
+                        .map(|graph| graph.shader_errors().collect())
+                        .unwrap_or_default();
+                    draw_shader_errors(gpu, draw_2d, assets, font, &errors);
+                }
+
+                // Make room for everything drawn this frame
+                draw_2d.ensure_vertex_capacity(&gpu.device);
+
                 // Render with transition effects if active
                 scene_manager.render(gpu, time, draw_2d, assets);
 
                 input.begin_frame();
-                window.request_redraw();
+                if exit_requested {
+                    event_loop.exit();
+                } else {
+                    window.request_redraw();
+                }
             }
             _ => {}
         }
@@ -2738,9 +5521,7 @@ impl ApplicationHandler for HopliteSceneApp {
             return;
         };
 
-        if let winit::event::DeviceEvent::MouseMotion { delta } = event {
-            input.handle_raw_mouse_motion(delta.0 as f32, delta.1 as f32);
-        }
+        input.handle_device_event(&event);
     }
 }
 
@@ -2761,9 +5542,160 @@ type SetupFn = Box<
         Box<dyn FnMut(&mut Frame)>,
         Option<FontId>,
         Option<RenderGraph>,
+        AppHooks,
     ),
 >;
 
+/// Everything the simple app loop needs to run frames, with or without a window.
+struct AppState {
+    /// GPU context containing device, queue, and surface.
+    gpu: GpuContext,
+    /// Loaded fonts and other managed assets.
+    assets: Assets,
+    /// 2D rendering state (batched draw calls).
+    draw_2d: Draw2d,
+    /// 3D camera state.
+    camera: Camera,
+    /// Input state (keyboard, mouse).
+    input: Input,
+    /// ECS world for entity management.
+    world: hecs::World,
+    /// User's frame closure (called every frame).
+    frame_fn: Box<dyn FnMut(&mut Frame)>,
+    /// Default font ID if one was set during setup.
+    default_font: Option<FontId>,
+    /// Optional render graph for shader effects and 3D rendering.
+    render_graph: Option<RenderGraph>,
+    /// Shared queue of mesh draw calls for the current frame.
+    mesh_queue: Rc<RefCell<MeshQueue>>,
+    /// Fixed-update and resize callbacks registered during setup.
+    hooks: AppHooks,
+    /// Wall-clock and game time (for `Frame::time` and `Frame::dt`).
+    clock: Clock,
+}
+
+impl AppState {
+    /// Creates the asset and 2D drawing systems and runs the user's setup closure.
+    fn new(gpu: GpuContext, setup: SetupFn) -> Self {
+        let mut assets = Assets::new();
+        let mut draw_2d = Draw2d::new(&gpu);
+
+        // Create shared mesh queue for 3D rendering
+        let mesh_queue = Rc::new(RefCell::new(MeshQueue::new()));
+
+        // Create ECS world
+        let mut world = hecs::World::new();
+
+        // Run user's setup closure to get the frame function
+        let (frame_fn, default_font, render_graph, hooks) =
+            setup(&gpu, &mut assets, &mut draw_2d, &mesh_queue, &mut world);
+
+        Self {
+            gpu,
+            assets,
+            draw_2d,
+            camera: Camera::new(),
+            input: Input::new(),
+            world,
+            frame_fn,
+            default_font,
+            render_graph,
+            mesh_queue,
+            hooks,
+            clock: Clock::new(),
+        }
+    }
+
+    /// Runs the frame closure and renders the result, returning `true` if it
+    /// asked to exit.
+    ///
+    /// The clock must already have been advanced for this frame. `window` is
+    /// `None` when running headless.
+    fn run_frame(&mut self, window: Option<&Window>) -> bool {
+        let Self {
+            gpu,
+            assets,
+            draw_2d,
+            camera,
+            input,
+            world,
+            frame_fn,
+            default_font,
+            render_graph,
+            mesh_queue,
+            hooks,
+            clock,
+        } = self;
+        let time = clock.time();
+
+        // Pick up gamepad events since the last frame
+        input.poll_gamepads();
+
+        // Clear draw_2d for new frame and update font bind groups
+        draw_2d.clear();
+        draw_2d.update_font_bind_groups(gpu, assets);
+
+        // Clear mesh queue for new frame
+        mesh_queue.borrow_mut().clear_queue();
+
+        // Catch the simulation up to real time
+        let fixed_alpha = hooks
+            .fixed_update
+            .as_mut()
+            .map_or(1.0, |fixed| fixed.run(world, clock.dt()));
+
+        // Create frame context
+        let mut frame = Frame {
+            gpu,
+            assets,
+            draw: draw_2d,
+            camera,
+            input,
+            world,
+            time,
+            dt: clock.dt(),
+            clock,
+            default_font: *default_font,
+            font_stack: Vec::new(),
+            mesh_queue: Rc::clone(mesh_queue),
+            window,
+            scene_switch: None, // Only used with run_with_scenes
+            scene_data: None,
+            current_scene: None,
+            scene_ids: &[],
+            stack_changes: Vec::new(),
+            fixed_alpha,
+            exit_requested: false,
+        };
+
+        // Run user's frame function
+        frame_fn(&mut frame);
+        let exit_requested = frame.exit_requested;
+
+        // Cover the frame with any shader errors from the last reload check
+        if let (Some(font), Some(graph)) = (hooks.error_overlay, render_graph.as_ref()) {
+            let errors: Vec<&str> = graph.shader_errors().collect();
+            draw_shader_errors(gpu, draw_2d, assets, font, &errors);
+        }
+
+        // Make room for everything drawn this frame
+        draw_2d.ensure_vertex_capacity(&gpu.device);
+
+        // Execute render graph if present, otherwise just render UI
+        if let Some(graph) = render_graph {
+            graph.execute_with_ui(gpu, time, camera, |gpu, pass| {
+                draw_2d.render(gpu, pass, assets);
+            });
+        } else {
+            // No render graph - just render 2D content to screen
+            render_2d_only(gpu, draw_2d, assets, hooks.clear_color);
+        }
+
+        input.begin_frame();
+        exit_requested
+    }
+}
+
 /// Internal application state machine.
 ///
 /// The Hoplite application lifecycle has two states:
@@ -2787,31 +5719,22 @@ enum HopliteApp {
     Running {
         /// Native window handle (Arc for sharing with wgpu surface).
         window: Arc<Window>,
-        /// GPU context containing device, queue, and surface.
-        gpu: GpuContext,
-        /// Loaded fonts and other managed assets.
-        assets: Assets,
-        /// 2D rendering state (batched draw calls).
-        draw_2d: Draw2d,
-        /// 3D camera state.
-        camera: Camera,
-        /// Input state (keyboard, mouse).
-        input: Input,
-        /// ECS world for entity management.
-        world: hecs::World,
-        /// User's frame closure (called every frame).
-        frame_fn: Box<dyn FnMut(&mut Frame)>,
-        /// Default font ID if one was set during setup.
-        default_font: Option<FontId>,
-        /// Optional render graph for shader effects and 3D rendering.
-        render_graph: Option<RenderGraph>,
-        /// Shared queue of mesh draw calls for the current frame.
-        mesh_queue: Rc<RefCell<MeshQueue>>,
-        /// Time when the application started (for `Frame::time`).
-        start_time: Instant,
-        /// Time of the last frame (for `Frame::dt` calculation).
-        last_frame: Instant,
+        /// GPU, assets, and everything else the frames run on.
+        state: Box<AppState>,
+        /// Minimum time between frames from [`AppConfig::max_fps`].
+        frame_interval: Option<Duration>,
     },
+    /// Startup failed; the event loop is exiting and the error is returned
+    /// from [`run_fallible`].
+    Failed(Error),
+}
+
+impl HopliteApp {
+    /// Records a startup error and stops the event loop.
+    fn fail(&mut self, event_loop: &ActiveEventLoop, error: Error) {
+        *self = HopliteApp::Failed(error);
+        event_loop.exit();
+    }
 }
 
 /// Implementation of winit's [`ApplicationHandler`] trait for the Hoplite app.
@@ -2828,40 +5751,28 @@ impl ApplicationHandler for HopliteApp {
     /// 5. Transitioning to the `Running` state
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if let HopliteApp::Pending { config, setup } = self {
-            let window_attrs = WindowAttributes::default()
-                .with_title(&config.title)
-                .with_inner_size(winit::dpi::LogicalSize::new(config.width, config.height));
-
-            let window = Arc::new(event_loop.create_window(window_attrs).unwrap());
-            let gpu = GpuContext::new(window.clone());
-            let mut assets = Assets::new();
-            let mut draw_2d = Draw2d::new(&gpu);
-
-            // Create shared mesh queue for 3D rendering
-            let mesh_queue = Rc::new(RefCell::new(MeshQueue::new()));
+            let window_attrs = config.window_attributes();
 
-            // Create ECS world
-            let mut world = hecs::World::new();
+            let window = match event_loop.create_window(window_attrs) {
+                Ok(window) => Arc::new(window),
+                Err(e) => return self.fail(event_loop, Error::Window(e.to_string())),
+            };
+            // Let IME-composed text reach `Input::typed_chars`
+            window.set_ime_allowed(true);
+            let mut gpu =
+                match GpuContext::try_new_with_options(window.clone(), config.gpu_options()) {
+                    Ok(gpu) => gpu,
+                    Err(e) => return self.fail(event_loop, e.into()),
+                };
+            gpu.set_render_scale(config.render_scale);
 
-            // Run user's setup closure to get the frame function
-            let setup_fn = setup.take().unwrap();
-            let (frame_fn, default_font, render_graph) =
-                setup_fn(&gpu, &mut assets, &mut draw_2d, &mesh_queue, &mut world);
+            let frame_interval = config.frame_interval();
+            let state = AppState::new(gpu, setup.take().unwrap());
 
             *self = HopliteApp::Running {
                 window,
-                gpu,
-                assets,
-                draw_2d,
-                camera: Camera::new(),
-                input: Input::new(),
-                world,
-                frame_fn,
-                default_font,
-                render_graph,
-                mesh_queue,
-                start_time: Instant::now(),
-                last_frame: Instant::now(),
+                state: Box::new(state),
+                frame_interval,
             };
         }
     }
@@ -2877,76 +5788,32 @@ impl ApplicationHandler for HopliteApp {
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         let HopliteApp::Running {
             window,
-            gpu,
-            assets,
-            draw_2d,
-            camera,
-            input,
-            world,
-            frame_fn,
-            default_font,
-            render_graph,
-            mesh_queue,
-            start_time,
-            last_frame,
+            state,
+            frame_interval,
         } = self
         else {
             return;
         };
 
-        input.handle_event(&event);
+        state.input.handle_event(&event);
 
         match event {
             WindowEvent::CloseRequested => {
                 event_loop.exit();
             }
             WindowEvent::Resized(size) => {
-                gpu.resize(size.width, size.height);
+                state.hooks.resize(&mut state.gpu, size.width, size.height);
             }
             WindowEvent::RedrawRequested => {
-                let now = Instant::now();
-                let time = start_time.elapsed().as_secs_f32();
-                let dt = now.duration_since(*last_frame).as_secs_f32();
-                *last_frame = now;
-
-                // Clear draw_2d for new frame and update font bind groups
-                draw_2d.clear();
-                draw_2d.update_font_bind_groups(gpu, assets);
-
-                // Clear mesh queue for new frame
-                mesh_queue.borrow_mut().clear_queue();
-
-                // Create frame context
-                let mut frame = Frame {
-                    gpu,
-                    assets,
-                    draw: draw_2d,
-                    camera,
-                    input,
-                    world,
-                    time,
-                    dt,
-                    default_font: *default_font,
-                    mesh_queue: Rc::clone(mesh_queue),
-                    window,
-                    scene_switch: None, // Only used with run_with_scenes
-                };
-
-                // Run user's frame function
-                frame_fn(&mut frame);
+                wait_for_frame_interval(state.clock.last_frame(), *frame_interval);
+                sync_surface_size(&mut state.gpu, window, &mut state.hooks);
 
-                // Execute render graph if present, otherwise just render UI
-                if let Some(graph) = render_graph {
-                    graph.execute_with_ui(gpu, time, camera, |gpu, pass| {
-                        draw_2d.render(gpu, pass, assets);
-                    });
+                state.clock.tick(Instant::now());
+                if state.run_frame(Some(window)) {
+                    event_loop.exit();
                 } else {
-                    // No render graph - just render 2D content to screen
-                    render_2d_only(gpu, draw_2d, assets);
+                    window.request_redraw();
                 }
-
-                input.begin_frame();
-                window.request_redraw();
             }
             _ => {}
         }
@@ -2964,13 +5831,44 @@ impl ApplicationHandler for HopliteApp {
         _device_id: winit::event::DeviceId,
         event: winit::event::DeviceEvent,
     ) {
-        let HopliteApp::Running { input, .. } = self else {
+        let HopliteApp::Running { state, .. } = self else {
             return;
         };
 
-        if let winit::event::DeviceEvent::MouseMotion { delta } = event {
-            input.handle_raw_mouse_motion(delta.0 as f32, delta.1 as f32);
-        }
+        state.input.handle_device_event(&event);
+    }
+}
+
+/// Dims the screen and lists `errors` over it, or does nothing if there are none.
+fn draw_shader_errors(
+    gpu: &GpuContext,
+    draw_2d: &mut Draw2d,
+    assets: &Assets,
+    font: FontId,
+    errors: &[&str],
+) {
+    const MARGIN: f32 = 16.0;
+    const ERROR_COLOR: Color = Color::rgb(1.0, 0.45, 0.4);
+
+    if errors.is_empty() {
+        return;
+    }
+
+    let (width, height) = (gpu.width() as f32, gpu.height() as f32);
+    draw_2d.rect(0.0, 0.0, width, height, Color::rgba(0.0, 0.0, 0.0, 0.8));
+
+    let mut y = MARGIN;
+    for error in errors {
+        y += draw_2d.text_wrapped(
+            assets,
+            font,
+            MARGIN,
+            y,
+            width - 2.0 * MARGIN,
+            error,
+            ERROR_COLOR,
+        );
+        y += MARGIN;
     }
 }
 
@@ -2978,8 +5876,8 @@ impl ApplicationHandler for HopliteApp {
 ///
 /// When no shader effects or 3D rendering are configured, this function
 /// provides a simple path to render 2D content directly to the screen.
-/// It clears the screen to black and renders all 2D draw calls (text,
-/// rectangles, sprites).
+/// It clears the screen to the configured clear color (black if unset) and
+/// renders all 2D draw calls (text, rectangles, sprites).
 ///
 /// This is used internally when the user doesn't call any effect/post-process
 /// methods during setup.
@@ -2989,12 +5887,11 @@ impl ApplicationHandler for HopliteApp {
 /// * `gpu` - GPU context for accessing device, queue, and surface
 /// * `draw_2d` - 2D drawing context with batched draw calls
 /// * `assets` - Asset manager (needed for font textures)
-fn render_2d_only(gpu: &GpuContext, draw_2d: &Draw2d, assets: &Assets) {
+/// * `clear_color` - Background set with [`SetupContext::clear_color`], black if unset
+fn render_2d_only(gpu: &GpuContext, draw_2d: &Draw2d, assets: &Assets, clear_color: Option<Color>) {
     // Get the next frame's texture to render to
-    let output = gpu.surface.get_current_texture().unwrap();
-    let view = output
-        .texture
-        .create_view(&wgpu::TextureViewDescriptor::default());
+    let output = gpu.acquire_output().unwrap();
+    let view = output.view();
 
     // Create command encoder for this frame
     let mut encoder = gpu
@@ -3003,7 +5900,7 @@ fn render_2d_only(gpu: &GpuContext, draw_2d: &Draw2d, assets: &Assets) {
             label: Some("2D Only Encoder"),
         });
 
-    // Begin render pass: clear to black, then render 2D content
+    // Begin render pass: clear to the background color, then render 2D content
     {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("2D Only Pass"),
@@ -3011,7 +5908,7 @@ fn render_2d_only(gpu: &GpuContext, draw_2d: &Draw2d, assets: &Assets) {
                 view: &view,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    load: wgpu::LoadOp::Clear(clear_color.unwrap_or(Color::BLACK).into()),
                     store: wgpu::StoreOp::Store,
                 },
                 depth_slice: None,
@@ -3027,5 +5924,5 @@ fn render_2d_only(gpu: &GpuContext, draw_2d: &Draw2d, assets: &Assets) {
 
     // Submit commands and present the frame
     gpu.queue.submit(std::iter::once(encoder.finish()));
-    output.present();
+    gpu.present_output(output);
 }
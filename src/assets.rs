@@ -29,6 +29,7 @@
 //! }
 //! ```
 
+use crate::error::Error;
 use crate::gpu::GpuContext;
 use fontdue::{Font, FontSettings};
 use std::collections::HashMap;
@@ -128,6 +129,9 @@ pub struct FontAtlas {
     size: f32,
     /// Recommended line height for this font and size.
     line_height: f32,
+    /// For signed distance field atlases, the distance in pixels covered by
+    /// the full `0..1` range of texel values on each side of a glyph edge.
+    sdf_spread: Option<f32>,
 }
 
 impl FontAtlas {
@@ -144,70 +148,121 @@ impl FontAtlas {
     ///
     /// # Panics
     ///
-    /// Panics if the font data cannot be parsed by `fontdue`.
+    /// Panics if the font data cannot be parsed by `fontdue`, or the glyphs don't
+    /// fit in the largest texture the GPU supports. Use [`try_new`](Self::try_new)
+    /// to handle these cases.
     ///
     /// # Performance
     ///
     /// Atlas creation involves CPU-side rasterization of ~95 glyphs and a texture
     /// upload. This should be done during loading, not per-frame.
     pub fn new(gpu: &GpuContext, font_data: &[u8], size: f32) -> Self {
-        let font =
-            Font::from_bytes(font_data, FontSettings::default()).expect("Failed to parse font");
+        Self::try_new(gpu, font_data, size).expect("Failed to create font atlas")
+    }
 
-        // Characters to pre-rasterize
-        let chars: Vec<char> = (32u8..=126u8).map(|c| c as char).collect();
+    /// Creates a new font atlas from TTF/OTF font data, returning an error
+    /// instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Font`] if the data is not a valid font, the size is not
+    /// positive, or the rasterized glyphs need an atlas larger than the GPU's
+    /// maximum texture size.
+    pub fn try_new(gpu: &GpuContext, font_data: &[u8], size: f32) -> Result<Self, Error> {
+        Self::build(gpu, font_data, size, None, &ascii_printable())
+    }
 
-        // First pass: rasterize all glyphs to get their sizes
+    /// Creates a font atlas holding `chars` instead of the ASCII printable range.
+    ///
+    /// Characters the font has no glyph for are left out, so [`glyph`](Self::glyph)
+    /// returns `None` for them. Use this for fallback fonts covering accented,
+    /// CJK, or symbol characters (see [`Assets::load_fallback_font_from_bytes`]).
+    /// Every glyph is rasterized up front, so large ranges need a large atlas.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_new`](Self::try_new).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let kana = FontAtlas::try_new_with_chars(&gpu, &data, 16.0, '\u{3040}'..='\u{30ff}')?;
+    /// ```
+    pub fn try_new_with_chars(
+        gpu: &GpuContext,
+        font_data: &[u8],
+        size: f32,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Self, Error> {
+        let chars: Vec<char> = chars.into_iter().collect();
+        Self::build(gpu, font_data, size, None, &chars)
+    }
+
+    /// Creates a signed distance field (SDF) font atlas from TTF/OTF font data.
+    ///
+    /// Each texel stores the distance to the nearest glyph edge instead of
+    /// coverage, so [`Draw2d`](crate::Draw2d) can draw the text sharply at
+    /// sizes well above `size` and add outlines and glows from the same data
+    /// (see [`Draw2d::set_text_effects`](crate::Draw2d::set_text_effects)).
+    /// A `size` of 32-64 pixels keeps edges crisp for most on-screen sizes.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_new`](Self::try_new).
+    pub fn try_new_sdf(gpu: &GpuContext, font_data: &[u8], size: f32) -> Result<Self, Error> {
+        let spread = (size / 8.0).ceil().max(4.0);
+        Self::build(gpu, font_data, size, Some(spread), &ascii_printable())
+    }
+
+    /// Rasterizes the glyphs `font_data` has among `chars`, as distance fields
+    /// with the given spread if `sdf_spread` is set.
+    fn build(
+        gpu: &GpuContext,
+        font_data: &[u8],
+        size: f32,
+        sdf_spread: Option<f32>,
+        chars: &[char],
+    ) -> Result<Self, Error> {
+        if !(size > 0.0 && size.is_finite()) {
+            return Err(Error::Font(format!("invalid font size {}", size)));
+        }
+        let font = Font::from_bytes(font_data, FontSettings::default())
+            .map_err(|e| Error::Font(e.to_string()))?;
+
+        // First pass: rasterize all glyphs to get their sizes. Characters the
+        // font lacks are skipped so fallback fonts can supply them.
         let rasterized: Vec<(char, fontdue::Metrics, Vec<u8>)> = chars
             .iter()
+            .filter(|&&c| font.has_glyph(c))
             .map(|&c| {
-                let (metrics, bitmap) = font.rasterize(c, size);
+                let (mut metrics, mut bitmap) = font.rasterize(c, size);
+                if let Some(spread) = sdf_spread.filter(|_| metrics.width > 0 && metrics.height > 0)
+                {
+                    let pad = spread as usize;
+                    bitmap = signed_distance_field(&bitmap, metrics.width, metrics.height, pad);
+                    metrics.width += 2 * pad;
+                    metrics.height += 2 * pad;
+                    metrics.xmin -= pad as i32;
+                    metrics.ymin -= pad as i32;
+                }
                 (c, metrics, bitmap)
             })
             .collect();
 
         // Calculate atlas dimensions using a simple row packing
-        let padding = 1u32;
-        let mut atlas_width = 512u32;
-        let mut atlas_height = 512u32;
-
-        // Try to fit everything, increase size if needed
-        loop {
-            let mut x = padding;
-            let mut y = padding;
-            let mut row_height = 0u32;
-            let mut fits = true;
-
-            for (_, metrics, _) in &rasterized {
-                let glyph_w = metrics.width as u32;
-                let glyph_h = metrics.height as u32;
-
-                if x + glyph_w + padding > atlas_width {
-                    x = padding;
-                    y += row_height + padding;
-                    row_height = 0;
-                }
-
-                if y + glyph_h + padding > atlas_height {
-                    fits = false;
-                    break;
-                }
-
-                x += glyph_w + padding;
-                row_height = row_height.max(glyph_h);
-            }
-
-            if fits {
-                break;
-            }
-
-            // Double the smaller dimension
-            if atlas_width <= atlas_height {
-                atlas_width *= 2;
-            } else {
-                atlas_height *= 2;
-            }
-        }
+        let padding = ATLAS_PADDING;
+        let glyph_sizes: Vec<(u32, u32)> = rasterized
+            .iter()
+            .map(|(_, metrics, _)| (metrics.width as u32, metrics.height as u32))
+            .collect();
+        let max_dimension = gpu.device.limits().max_texture_dimension_2d;
+        let (atlas_width, atlas_height) =
+            atlas_size(&glyph_sizes, max_dimension).ok_or_else(|| {
+                Error::Font(format!(
+                    "glyphs at size {} don't fit in a {}x{} atlas",
+                    size, max_dimension, max_dimension
+                ))
+            })?;
 
         // Create atlas bitmap
         let mut atlas_data = vec![0u8; (atlas_width * atlas_height) as usize];
@@ -313,7 +368,7 @@ impl FontAtlas {
         let line_metrics = font.horizontal_line_metrics(size);
         let line_height = line_metrics.map(|m| m.new_line_size).unwrap_or(size * 1.2);
 
-        Self {
+        Ok(Self {
             texture,
             view,
             sampler,
@@ -321,14 +376,15 @@ impl FontAtlas {
             font,
             size,
             line_height,
-        }
+            sdf_spread,
+        })
     }
 
     /// Returns glyph information for a character.
     ///
-    /// Returns `None` if the character is not in the atlas (i.e., not in ASCII 32-126).
-    /// For missing glyphs, callers typically substitute a fallback like `'?'` or skip
-    /// rendering.
+    /// Returns `None` if the character is not in the atlas (by default, anything
+    /// outside ASCII 32-126, or a character the font has no glyph for).
+    /// [`Assets::glyph_font`] also searches the font's fallbacks.
     #[inline]
     pub fn glyph(&self, c: char) -> Option<&GlyphInfo> {
         self.glyphs.get(&c)
@@ -343,6 +399,18 @@ impl FontAtlas {
         self.size
     }
 
+    /// Returns whether this atlas stores signed distance fields (see [`try_new_sdf`](Self::try_new_sdf)).
+    #[inline]
+    pub fn is_sdf(&self) -> bool {
+        self.sdf_spread.is_some()
+    }
+
+    /// Returns the distance field spread in atlas pixels, or `None` for coverage atlases.
+    #[inline]
+    pub fn sdf_spread(&self) -> Option<f32> {
+        self.sdf_spread
+    }
+
     /// Returns the recommended line height for this font.
     ///
     /// This value comes from the font's horizontal line metrics and represents the
@@ -371,6 +439,178 @@ impl FontAtlas {
     }
 }
 
+/// Empty pixels around each glyph in the atlas, so linear filtering doesn't bleed.
+const ATLAS_PADDING: u32 = 1;
+
+/// The characters atlases hold by default: ASCII 32-126.
+fn ascii_printable() -> Vec<char> {
+    (32u8..=126u8).map(|c| c as char).collect()
+}
+
+/// Smallest atlas, starting at 512x512 and doubling the shorter side, that fits
+/// glyphs of the given sizes with row packing.
+///
+/// Returns `None` if either side would have to exceed `max_dimension`.
+fn atlas_size(glyph_sizes: &[(u32, u32)], max_dimension: u32) -> Option<(u32, u32)> {
+    let padding = ATLAS_PADDING;
+    let mut atlas_width = 512u32.min(max_dimension);
+    let mut atlas_height = 512u32.min(max_dimension);
+
+    loop {
+        let mut x = padding;
+        let mut y = padding;
+        let mut row_height = 0u32;
+        let mut fits = true;
+
+        for &(glyph_w, glyph_h) in glyph_sizes {
+            if x + glyph_w + padding > atlas_width {
+                x = padding;
+                y += row_height + padding;
+                row_height = 0;
+            }
+
+            if x + glyph_w + padding > atlas_width || y + glyph_h + padding > atlas_height {
+                fits = false;
+                break;
+            }
+
+            x += glyph_w + padding;
+            row_height = row_height.max(glyph_h);
+        }
+
+        if fits {
+            return Some((atlas_width, atlas_height));
+        }
+
+        // Double the smaller dimension
+        let side = if atlas_width <= atlas_height {
+            &mut atlas_width
+        } else {
+            &mut atlas_height
+        };
+        if *side >= max_dimension {
+            return None;
+        }
+        *side = (*side * 2).min(max_dimension);
+    }
+}
+
+/// Converts a glyph coverage bitmap into a distance field with `pad` pixels of
+/// margin on each side.
+///
+/// Texels inside the glyph are above `0.5` and texels outside below it, with
+/// `pad` pixels of distance mapping to a change of `0.5`.
+pub(crate) fn signed_distance_field(
+    coverage: &[u8],
+    width: usize,
+    height: usize,
+    pad: usize,
+) -> Vec<u8> {
+    let (w, h) = (width + 2 * pad, height + 2 * pad);
+    let inside = |x: usize, y: usize| {
+        x >= pad
+            && y >= pad
+            && x < pad + width
+            && y < pad + height
+            && coverage[(y - pad) * width + (x - pad)] >= 128
+    };
+
+    // Squared distances to the nearest inside and outside texel
+    let to_inside = squared_distance_transform(w, h, inside);
+    let to_outside = squared_distance_transform(w, h, |x, y| !inside(x, y));
+
+    let scale = 0.5 / pad.max(1) as f32;
+    (0..w * h)
+        .map(|i| {
+            // Put the edge halfway between the last inside and first outside texel
+            let distance = if to_inside[i] == 0.0 {
+                to_outside[i].sqrt() - 0.5
+            } else {
+                0.5 - to_inside[i].sqrt()
+            };
+            ((0.5 + distance * scale).clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+/// Squared Euclidean distance from every texel to the nearest texel where
+/// `is_target` holds, using the separable algorithm of Felzenszwalb and Huttenlocher.
+fn squared_distance_transform(
+    width: usize,
+    height: usize,
+    is_target: impl Fn(usize, usize) -> bool,
+) -> Vec<f32> {
+    const FAR: f32 = 1e20;
+    let mut grid: Vec<f32> = (0..width * height)
+        .map(|i| {
+            if is_target(i % width, i / width) {
+                0.0
+            } else {
+                FAR
+            }
+        })
+        .collect();
+
+    let n = width.max(height);
+    let mut line = vec![0.0; n];
+    let mut out = vec![0.0; n];
+    let mut hull = vec![0usize; n];
+    let mut bounds = vec![0.0f32; n + 1];
+
+    for x in 0..width {
+        for y in 0..height {
+            line[y] = grid[y * width + x];
+        }
+        distance_transform_1d(&line[..height], &mut out, &mut hull, &mut bounds);
+        for y in 0..height {
+            grid[y * width + x] = out[y];
+        }
+    }
+    for y in 0..height {
+        line[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        distance_transform_1d(&line[..width], &mut out, &mut hull, &mut bounds);
+        grid[y * width..(y + 1) * width].copy_from_slice(&out[..width]);
+    }
+    grid
+}
+
+/// One-dimensional squared distance transform of the sampled function `f`
+/// (the lower envelope of parabolas rooted at each sample).
+fn distance_transform_1d(f: &[f32], out: &mut [f32], hull: &mut [usize], bounds: &mut [f32]) {
+    let n = f.len();
+    if n == 0 {
+        return;
+    }
+    let intersection = |q: usize, p: usize| {
+        ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2.0 * (q as f32 - p as f32))
+    };
+
+    let mut k = 0;
+    hull[0] = 0;
+    bounds[0] = f32::NEG_INFINITY;
+    bounds[1] = f32::INFINITY;
+    for q in 1..n {
+        let mut s = intersection(q, hull[k]);
+        while s <= bounds[k] {
+            k -= 1;
+            s = intersection(q, hull[k]);
+        }
+        k += 1;
+        hull[k] = q;
+        bounds[k] = s;
+        bounds[k + 1] = f32::INFINITY;
+    }
+
+    k = 0;
+    for (q, value) in out.iter_mut().enumerate().take(n) {
+        while bounds[k + 1] < q as f32 {
+            k += 1;
+        }
+        let d = q as f32 - hull[k] as f32;
+        *value = d * d + f[hull[k]];
+    }
+}
+
 /// Built-in embedded font data (JetBrains Mono Regular).
 ///
 /// This font is compiled into the binary using `include_bytes!`, eliminating
@@ -388,6 +628,15 @@ const EMBEDDED_FONT: &[u8] = include_bytes!("fonts/JetBrainsMono-Regular.ttf");
 ///
 /// Fonts are stored in an `Arc` to allow shared access from multiple renderers.
 /// Each font loaded at a different size creates a separate atlas (no runtime scaling).
+/// The embedded default font is cached per whole-pixel size, so asking for it
+/// again at the same size returns the existing atlas.
+///
+/// # Font Fallbacks
+///
+/// Atlases only hold ASCII by default. To draw other characters, give a font
+/// fallbacks with [`load_fallback_font_from_file`](Self::load_fallback_font_from_file):
+/// when the font has no glyph for a character, text rendering uses the first
+/// fallback that does.
 ///
 /// # Example
 ///
@@ -407,12 +656,20 @@ const EMBEDDED_FONT: &[u8] = include_bytes!("fonts/JetBrainsMono-Regular.ttf");
 pub struct Assets {
     /// Loaded font atlases, indexed by [`FontId`].
     pub(crate) fonts: Vec<Arc<FontAtlas>>,
+    /// Default font atlases by rounded pixel size (see [`default_font`](Self::default_font)).
+    default_fonts: HashMap<u32, FontId>,
+    /// Fonts searched, in order, for characters a font has no glyph for.
+    fallbacks: HashMap<FontId, Vec<FontId>>,
 }
 
 impl Assets {
     /// Creates a new asset manager.
     pub(crate) fn new() -> Self {
-        Self { fonts: Vec::new() }
+        Self {
+            fonts: Vec::new(),
+            default_fonts: HashMap::new(),
+            fallbacks: HashMap::new(),
+        }
     }
 
     /// Loads a font from a file path.
@@ -436,8 +693,8 @@ impl Assets {
     /// let font_id = assets.load_font(&gpu, "assets/fonts/Roboto.ttf", 24.0);
     /// ```
     pub fn load_font(&mut self, gpu: &GpuContext, path: impl AsRef<Path>, size: f32) -> FontId {
-        let data = std::fs::read(path.as_ref()).expect("Failed to read font file");
-        self.load_font_bytes(gpu, &data, size)
+        self.load_font_from_file(gpu, path, size)
+            .expect("Failed to load font")
     }
 
     /// Loads a font from raw TTF/OTF bytes.
@@ -455,7 +712,188 @@ impl Assets {
     ///
     /// Panics if the font data cannot be parsed.
     pub fn load_font_bytes(&mut self, gpu: &GpuContext, data: &[u8], size: f32) -> FontId {
-        let atlas = FontAtlas::new(gpu, data, size);
+        self.load_font_from_bytes(gpu, data, size)
+            .expect("Failed to load font")
+    }
+
+    /// Loads a TTF/OTF font from a file, returning an error instead of panicking.
+    ///
+    /// The atlas grows past 512x512 as needed for large sizes, up to the GPU's
+    /// maximum texture size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read, or [`Error::Font`] if
+    /// it is not a valid font or the glyphs don't fit in an atlas.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let title = assets.load_font_from_file(&gpu, "fonts/Title.otf", 64.0)?;
+    /// ```
+    pub fn load_font_from_file(
+        &mut self,
+        gpu: &GpuContext,
+        path: impl AsRef<Path>,
+        size: f32,
+    ) -> Result<FontId, Error> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| {
+            Error::Io(std::io::Error::new(
+                e.kind(),
+                format!("{}: {}", path.display(), e),
+            ))
+        })?;
+        self.load_font_from_bytes(gpu, &data, size)
+    }
+
+    /// Loads a TTF/OTF font from raw bytes, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Font`] if the bytes are not a valid font or the glyphs
+    /// don't fit in an atlas.
+    pub fn load_font_from_bytes(
+        &mut self,
+        gpu: &GpuContext,
+        data: &[u8],
+        size: f32,
+    ) -> Result<FontId, Error> {
+        let atlas = FontAtlas::try_new(gpu, data, size)?;
+        Ok(self.add_font(atlas))
+    }
+
+    /// Loads a TTF/OTF font from a file as a signed distance field atlas.
+    ///
+    /// SDF fonts stay sharp when drawn larger than `size` (see
+    /// [`Draw2d::text_sized`](crate::Draw2d::text_sized)) and support outlines
+    /// and glows. See [`FontAtlas::try_new_sdf`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`load_font_from_file`](Self::load_font_from_file).
+    pub fn load_font_sdf_from_file(
+        &mut self,
+        gpu: &GpuContext,
+        path: impl AsRef<Path>,
+        size: f32,
+    ) -> Result<FontId, Error> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| {
+            Error::Io(std::io::Error::new(
+                e.kind(),
+                format!("{}: {}", path.display(), e),
+            ))
+        })?;
+        self.load_font_sdf_from_bytes(gpu, &data, size)
+    }
+
+    /// Loads a TTF/OTF font from raw bytes as a signed distance field atlas.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`load_font_from_bytes`](Self::load_font_from_bytes).
+    pub fn load_font_sdf_from_bytes(
+        &mut self,
+        gpu: &GpuContext,
+        data: &[u8],
+        size: f32,
+    ) -> Result<FontId, Error> {
+        let atlas = FontAtlas::try_new_sdf(gpu, data, size)?;
+        Ok(self.add_font(atlas))
+    }
+
+    /// Loads a font file as a fallback for `font`, holding the glyphs it has among `chars`.
+    ///
+    /// The atlas is built at `font`'s size, as a distance field if `font` is
+    /// one, and added to the end of `font`'s fallbacks (see
+    /// [`add_fallback`](Self::add_fallback)). Returns the fallback's own ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Font`] if `font` isn't loaded, plus the errors of
+    /// [`load_font_from_file`](Self::load_font_from_file).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let body = assets.default_font(&gpu, 16.0);
+    /// assets.load_fallback_font_from_file(&gpu, body, "fonts/NotoSansJP.ttf", '\u{3040}'..='\u{30ff}')?;
+    /// assets.load_fallback_font_from_file(&gpu, body, "fonts/NotoSans.ttf", "éèüñ".chars())?;
+    /// ```
+    pub fn load_fallback_font_from_file(
+        &mut self,
+        gpu: &GpuContext,
+        font: FontId,
+        path: impl AsRef<Path>,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<FontId, Error> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| {
+            Error::Io(std::io::Error::new(
+                e.kind(),
+                format!("{}: {}", path.display(), e),
+            ))
+        })?;
+        self.load_fallback_font_from_bytes(gpu, font, &data, chars)
+    }
+
+    /// Loads font bytes as a fallback for `font`, holding the glyphs it has among `chars`.
+    ///
+    /// See [`load_fallback_font_from_file`](Self::load_fallback_font_from_file).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Font`] if `font` isn't loaded, the bytes are not a valid
+    /// font, or the glyphs don't fit in an atlas.
+    pub fn load_fallback_font_from_bytes(
+        &mut self,
+        gpu: &GpuContext,
+        font: FontId,
+        data: &[u8],
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<FontId, Error> {
+        let primary = self
+            .font(font)
+            .ok_or_else(|| Error::Font(format!("no font with id {}", font.0)))?;
+        let chars: Vec<char> = chars.into_iter().collect();
+        let atlas = FontAtlas::build(gpu, data, primary.size(), primary.sdf_spread(), &chars)?;
+        let fallback = self.add_font(atlas);
+        self.add_fallback(font, fallback);
+        Ok(fallback)
+    }
+
+    /// Makes `fallback` supply the glyphs `font` doesn't have.
+    ///
+    /// Fallbacks are searched in the order they were added, after `font`
+    /// itself. A fallback's own fallbacks are not searched. Adding a font as its
+    /// own fallback does nothing.
+    pub fn add_fallback(&mut self, font: FontId, fallback: FontId) {
+        if font != fallback {
+            self.fallbacks.entry(font).or_default().push(fallback);
+        }
+    }
+
+    /// Returns the fallbacks of `font`, in search order.
+    pub fn fallbacks(&self, font: FontId) -> &[FontId] {
+        self.fallbacks.get(&font).map_or(&[], Vec::as_slice)
+    }
+
+    /// Finds the font that draws `ch` for text in `font`: `font` itself if it
+    /// has the glyph, otherwise its first fallback that does.
+    ///
+    /// Returns `None` if neither `font` nor any fallback has the glyph.
+    pub fn glyph_font(&self, font: FontId, ch: char) -> Option<(FontId, &FontAtlas)> {
+        std::iter::once(font)
+            .chain(self.fallbacks(font).iter().copied())
+            .find_map(|id| {
+                let atlas = self.fonts.get(id.0)?;
+                atlas.glyph(ch).is_some().then_some((id, &**atlas))
+            })
+    }
+
+    /// Stores an atlas and returns its handle.
+    fn add_font(&mut self, atlas: FontAtlas) -> FontId {
         let id = FontId(self.fonts.len());
         self.fonts.push(Arc::new(atlas));
         id
@@ -471,22 +909,37 @@ impl Assets {
     /// * `gpu` - GPU context for creating the font atlas texture
     /// * `size` - Font size in pixels
     ///
-    /// # Note
+    /// # Caching
     ///
-    /// Currently creates a new atlas for each call. For repeated use at the same
-    /// size, store the returned [`FontId`] rather than calling this repeatedly.
+    /// `size` is rounded to whole pixels (at least 1), and the atlas is built
+    /// only the first time each rounded size is requested. Later calls return
+    /// the same [`FontId`], so `default_font(&gpu, 16.0)` can be called freely.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// // Load once during initialization
     /// let debug_font = assets.default_font(&gpu, 14.0);
+    /// assert_eq!(assets.default_font(&gpu, 14.0), debug_font);
     ///
-    /// // Reuse the ID for rendering
     /// let atlas = assets.font(debug_font).unwrap();
     /// ```
     pub fn default_font(&mut self, gpu: &GpuContext, size: f32) -> FontId {
-        self.load_font_bytes(gpu, EMBEDDED_FONT, size)
+        let size = size.round().max(1.0);
+        if let Some(&id) = self.default_fonts.get(&(size as u32)) {
+            return id;
+        }
+        let id = self.load_font_bytes(gpu, EMBEDDED_FONT, size);
+        self.default_fonts.insert(size as u32, id);
+        id
+    }
+
+    /// Loads the default embedded font as a signed distance field atlas.
+    ///
+    /// Use this for titles and zoomable UI that draw the font at several sizes.
+    /// See [`load_font_sdf_from_bytes`](Self::load_font_sdf_from_bytes).
+    pub fn default_font_sdf(&mut self, gpu: &GpuContext, size: f32) -> FontId {
+        self.load_font_sdf_from_bytes(gpu, EMBEDDED_FONT, size)
+            .expect("Failed to load default font")
     }
 
     /// Retrieves a font atlas by its ID.
@@ -501,3 +954,93 @@ impl Assets {
         self.fonts.get(id.0).cloned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atlas_grows_to_fit_glyphs_up_to_the_limit() {
+        assert_eq!(atlas_size(&[(10, 10); 95], 8192), Some((512, 512)));
+
+        // Five 100px glyphs fit per 512px row, so 95 of them need a taller atlas
+        let (w, h) = atlas_size(&[(100, 100); 95], 8192).unwrap();
+        assert!(w > 512 || h > 512);
+        let per_row = (w - ATLAS_PADDING) / (100 + ATLAS_PADDING);
+        let rows = 95u32.div_ceil(per_row);
+        assert!(rows * (100 + ATLAS_PADDING) + ATLAS_PADDING <= h);
+
+        assert_eq!(atlas_size(&[(100, 100); 95], 512), None);
+        assert_eq!(atlas_size(&[(600, 10)], 512), None);
+    }
+
+    #[test]
+    fn distance_field_is_half_at_edges_and_ramps_away_from_them() {
+        // A 4x4 filled square with 4 texels of margin
+        let field = signed_distance_field(&[255; 16], 4, 4, 4);
+        let at = |x: usize, y: usize| field[y * 12 + x];
+        assert_eq!(field.len(), 12 * 12);
+
+        assert_eq!(at(0, 0), 0);
+        assert!((at(4, 5) as i32 - 128).abs() <= 16, "{}", at(4, 5));
+        assert!((at(3, 5) as i32 - 128).abs() <= 16, "{}", at(3, 5));
+        assert!(at(5, 5) > at(4, 5) && at(4, 5) > at(3, 5) && at(3, 5) > at(2, 5));
+
+        let row: Vec<u8> = (0..12).map(|x| at(x, 6)).collect();
+        assert!(row.windows(2).take(6).all(|w| w[0] <= w[1]), "{:?}", row);
+    }
+
+    #[test]
+    fn default_fonts_are_cached_by_rounded_size() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+        let mut assets = Assets::new();
+
+        let body = assets.default_font(&gpu, 16.0);
+        assert_eq!(assets.default_font(&gpu, 16.0), body);
+        assert_eq!(assets.default_font(&gpu, 16.3), body);
+        let title = assets.default_font(&gpu, 32.0);
+        assert_ne!(title, body);
+        assert_eq!(assets.fonts.len(), 2);
+        assert_eq!(assets.font(body).unwrap().size(), 16.0);
+    }
+
+    #[test]
+    fn fallback_fonts_supply_missing_glyphs_in_order() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+        let mut assets = Assets::new();
+        let font = assets.default_font(&gpu, 16.0);
+        assert!(assets.glyph_font(font, 'é').is_none());
+
+        let accents = assets
+            .load_fallback_font_from_bytes(&gpu, font, EMBEDDED_FONT, "éü你".chars())
+            .unwrap();
+        let more = assets
+            .load_fallback_font_from_bytes(&gpu, font, EMBEDDED_FONT, "éñ".chars())
+            .unwrap();
+        assert_eq!(assets.fallbacks(font), [accents, more]);
+        assert_eq!(assets.font(accents).unwrap().size(), 16.0);
+
+        let found = |ch| assets.glyph_font(font, ch).map(|(id, _)| id);
+        assert_eq!(found('a'), Some(font));
+        assert_eq!(found('é'), Some(accents));
+        assert_eq!(found('ñ'), Some(more));
+        // Characters the fallback font lacks aren't rasterized
+        assert_eq!(found('你'), None);
+
+        assert!(
+            assets
+                .load_fallback_font_from_bytes(&gpu, FontId(99), EMBEDDED_FONT, "é".chars())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn embedded_font_parses_and_garbage_does_not() {
+        assert!(Font::from_bytes(EMBEDDED_FONT, FontSettings::default()).is_ok());
+        assert!(Font::from_bytes(&b"not a font"[..], FontSettings::default()).is_err());
+    }
+}
@@ -0,0 +1,403 @@
+//! Textured quads placed in the 3D scene that always face the camera.
+//!
+//! Billboards bridge the 2D and 3D worlds: a health bar, name tag, or
+//! particle can sit at a world position and still read as a flat sprite.
+//! [`MeshNode`](crate::MeshNode) draws them after its meshes with the same
+//! camera, depth-tested against the mesh depth buffer so scenery in front
+//! hides them, and sorted back to front so translucent ones blend correctly.
+//!
+//! Queue billboards through [`Frame::billboard`](crate::Frame::billboard) and
+//! [`Frame::billboard_cylindrical`](crate::Frame::billboard_cylindrical), or
+//! push to [`MeshQueue::billboards`](crate::MeshQueue::billboards) when
+//! driving a render graph by hand.
+
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::camera::Camera;
+use crate::draw2d::Color;
+use crate::ecs::TextureId;
+use crate::texture::Texture;
+
+/// Number of billboards the GPU buffer holds before it first grows.
+const INITIAL_CAPACITY: usize = 256;
+
+/// Vertices per billboard: two triangles.
+const VERTICES_PER_QUAD: usize = 6;
+
+/// How a billboard turns to face the camera.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BillboardMode {
+    /// Faces the camera fully, tilting as the camera looks up or down.
+    #[default]
+    Spherical,
+    /// Turns only around the world Y axis and stays upright, like a tree
+    /// impostor or a standing character.
+    Cylindrical,
+}
+
+/// A camera-facing textured quad queued for the current frame.
+#[derive(Clone, Copy, Debug)]
+pub struct Billboard {
+    /// Texture drawn on the quad, from [`MeshQueue::add_texture`](crate::MeshQueue::add_texture).
+    pub texture: TextureId,
+    /// World-space center of the quad.
+    pub position: Vec3,
+    /// Width and height in world units.
+    pub size: Vec2,
+    /// Tint multiplied with the texture.
+    pub color: Color,
+    /// How the quad turns toward the camera.
+    pub mode: BillboardMode,
+}
+
+impl Billboard {
+    /// Creates a spherical billboard centered at `position`.
+    pub fn new(texture: TextureId, position: Vec3, size: Vec2, color: Color) -> Self {
+        Self {
+            texture,
+            position,
+            size,
+            color,
+            mode: BillboardMode::Spherical,
+        }
+    }
+
+    /// Sets how the quad turns toward the camera.
+    pub fn mode(mut self, mode: BillboardMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// World-space corners facing `camera`, counterclockwise from the bottom left.
+    pub fn corners(&self, camera: &Camera) -> [Vec3; 4] {
+        let (right, up) = match self.mode {
+            BillboardMode::Spherical => (camera.right(), camera.orthogonal_up()),
+            BillboardMode::Cylindrical => {
+                let right = camera.forward.cross(Vec3::Y).normalize_or(Vec3::X);
+                (right, Vec3::Y)
+            }
+        };
+        let right = right * self.size.x * 0.5;
+        let up = up * self.size.y * 0.5;
+        [
+            self.position - right - up,
+            self.position + right - up,
+            self.position + right + up,
+            self.position - right + up,
+        ]
+    }
+}
+
+/// One corner of a billboard quad.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct BillboardVertex {
+    position: [f32; 3],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+impl BillboardVertex {
+    const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<BillboardVertex>() as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2, 2 => Float32x4],
+    };
+}
+
+/// Returns the indices of `billboards` farthest from the camera first.
+///
+/// Distance is measured along the view direction, which suits both
+/// perspective and orthographic cameras.
+fn back_to_front(billboards: &[Billboard], camera: &Camera) -> Vec<usize> {
+    let depth = |b: &Billboard| (b.position - camera.position).dot(camera.forward);
+    let mut order: Vec<usize> = (0..billboards.len()).collect();
+    order.sort_by(|&a, &b| depth(&billboards[b]).total_cmp(&depth(&billboards[a])));
+    order
+}
+
+/// GPU resources for drawing [`Billboard`]s inside a mesh render pass.
+pub(crate) struct BillboardPass {
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    uniform_bind_group: wgpu::BindGroup,
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    vertex_buffer: wgpu::Buffer,
+    /// Number of billboards `vertex_buffer` holds.
+    capacity: usize,
+}
+
+impl BillboardPass {
+    /// Creates a billboard pass for color targets of `format` with a `Depth32Float` depth attachment.
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Billboard Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/billboard.wgsl").into()),
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Billboard Uniforms"),
+            size: std::mem::size_of::<[[f32; 4]; 4]>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let uniform_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Billboard Uniform Bind Group Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Billboard Uniform Bind Group"),
+            layout: &uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let texture_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Billboard Texture Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Billboard Pipeline Layout"),
+            bind_group_layouts: &[&uniform_bind_group_layout, &texture_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Billboard Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs"),
+                buffers: &[BillboardVertex::LAYOUT],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            // Quads face the camera, so there's no back side to cull
+            primitive: wgpu::PrimitiveState::default(),
+            // Sorted back to front and blended, so they're tested against the
+            // meshes' depth but never hide each other through it
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            uniform_bind_group,
+            texture_bind_group_layout,
+            vertex_buffer: Self::create_vertex_buffer(device, INITIAL_CAPACITY),
+            capacity: INITIAL_CAPACITY,
+        }
+    }
+
+    /// Grows the vertex buffer to fit `count` billboards.
+    ///
+    /// The buffer grows to the next power of two and never shrinks. Billboards
+    /// beyond the current capacity are dropped by [`render`](Self::render).
+    pub fn ensure_capacity(&mut self, device: &wgpu::Device, count: usize) {
+        if count > self.capacity {
+            self.capacity = count.next_power_of_two();
+            self.vertex_buffer = Self::create_vertex_buffer(device, self.capacity);
+        }
+    }
+
+    /// Draws `billboards` facing `camera` into a render pass that has a
+    /// `Depth32Float` depth attachment.
+    ///
+    /// Billboards whose texture isn't loaded in `textures` are skipped. Consecutive
+    /// billboards sharing a texture after sorting are drawn together.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        render_pass: &mut wgpu::RenderPass,
+        camera: &Camera,
+        view_proj: Mat4,
+        billboards: &[Billboard],
+        textures: &[Option<Texture>],
+    ) {
+        let mut vertices = Vec::with_capacity(billboards.len() * VERTICES_PER_QUAD);
+        let mut runs: Vec<(TextureId, u32, u32)> = Vec::new();
+        for index in back_to_front(billboards, camera) {
+            let billboard = &billboards[index];
+            if !matches!(textures.get(billboard.texture.0), Some(Some(_))) {
+                continue;
+            }
+            if vertices.len() / VERTICES_PER_QUAD >= self.capacity {
+                break;
+            }
+
+            let corners = billboard.corners(camera);
+            let uvs = [[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]];
+            let color = billboard.color;
+            let start = vertices.len() as u32;
+            vertices.extend([0, 1, 2, 2, 3, 0].map(|i| BillboardVertex {
+                position: corners[i].to_array(),
+                uv: uvs[i],
+                color: [color.r, color.g, color.b, color.a],
+            }));
+            let end = vertices.len() as u32;
+
+            match runs.last_mut() {
+                Some((texture, _, run_end)) if *texture == billboard.texture => *run_end = end,
+                _ => runs.push((billboard.texture, start, end)),
+            }
+        }
+        if vertices.is_empty() {
+            return;
+        }
+
+        queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&view_proj.to_cols_array_2d()),
+        );
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        for (texture, start, end) in runs {
+            let Some(texture) = &textures[texture.0] else {
+                continue;
+            };
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Billboard Texture Bind Group"),
+                layout: &self.texture_bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&texture.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&texture.sampler),
+                    },
+                ],
+            });
+            render_pass.set_bind_group(1, &bind_group, &[]);
+            render_pass.draw(start..end, 0..1);
+        }
+    }
+
+    fn create_vertex_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Billboard Vertices"),
+            size: (capacity * VERTICES_PER_QUAD * std::mem::size_of::<BillboardVertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::capture_shader_errors;
+
+    fn quad(position: Vec3, mode: BillboardMode) -> Billboard {
+        Billboard::new(TextureId(0), position, Vec2::new(2.0, 1.0), Color::WHITE).mode(mode)
+    }
+
+    #[test]
+    fn spherical_billboards_face_the_camera_fully() {
+        let camera = Camera::new()
+            .at([0.0, 5.0, 5.0])
+            .looking_at([0.0, 0.0, 0.0]);
+        let [bl, br, _tr, tl] = quad(Vec3::ZERO, BillboardMode::Spherical).corners(&camera);
+
+        let normal = (br - bl).cross(tl - bl).normalize();
+        assert!((normal + camera.forward).length() < 1e-5, "{}", normal);
+        assert!(((br - bl).length() - 2.0).abs() < 1e-5);
+        assert!(((tl - bl).length() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn cylindrical_billboards_stay_upright() {
+        let camera = Camera::new()
+            .at([3.0, 5.0, 0.0])
+            .looking_at([0.0, 0.0, 0.0]);
+        let [bl, br, _tr, tl] = quad(Vec3::ONE, BillboardMode::Cylindrical).corners(&camera);
+
+        assert!((tl - bl - Vec3::Y).length() < 1e-5);
+        // Facing the camera's position in the XZ plane, i.e. along +X
+        let normal = (br - bl).cross(tl - bl).normalize();
+        assert!((normal - Vec3::X).length() < 1e-5, "{}", normal);
+    }
+
+    #[test]
+    fn billboards_sort_farthest_first() {
+        let camera = Camera::new()
+            .at([0.0, 0.0, 0.0])
+            .looking_at([0.0, 0.0, -1.0]);
+        let billboards = [
+            quad(Vec3::new(0.0, 0.0, -2.0), BillboardMode::Spherical),
+            quad(Vec3::new(0.0, 0.0, -8.0), BillboardMode::Spherical),
+            quad(Vec3::new(3.0, 0.0, -5.0), BillboardMode::Spherical),
+        ];
+        assert_eq!(back_to_front(&billboards, &camera), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn billboard_shader_compiles() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        let result = capture_shader_errors(&gpu.device, || {
+            BillboardPass::new(&gpu.device, wgpu::TextureFormat::Rgba8Unorm)
+        });
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+}
@@ -17,6 +17,21 @@
 //!     .with_fov(60.0);
 //! ```
 //!
+//! # Projection Modes
+//!
+//! Cameras use perspective projection by default. Switch to orthographic
+//! projection for 2.5D games and CAD-style views:
+//!
+//! ```
+//! use hoplite::Camera;
+//!
+//! // Show a 10-unit tall slice of the world, regardless of distance
+//! let camera = Camera::new()
+//!     .at([10.0, 10.0, 10.0])
+//!     .looking_at([0.0, 0.0, 0.0])
+//!     .orthographic(10.0);
+//! ```
+//!
 //! # Coordinate System
 //!
 //! The camera uses a right-handed coordinate system:
@@ -24,7 +39,25 @@
 //! - +Y points up
 //! - -Z points into the screen (forward direction)
 
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
+
+/// How a [`Camera`] projects the scene onto the screen.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ProjectionMode {
+    /// Perspective projection using the camera's [`fov`](Camera::fov).
+    ///
+    /// Distant objects appear smaller.
+    #[default]
+    Perspective,
+    /// Orthographic projection showing a fixed slice of the world.
+    ///
+    /// Objects keep the same on-screen size regardless of distance.
+    Orthographic {
+        /// Visible height of the view volume in world units.
+        /// The width is derived from the aspect ratio.
+        height: f32,
+    },
+}
 
 /// A 3D camera with position, orientation, and projection parameters.
 ///
@@ -61,6 +94,8 @@ pub struct Camera {
     pub near: f32,
     /// Far clipping plane distance.
     pub far: f32,
+    /// Perspective or orthographic projection.
+    pub projection: ProjectionMode,
 }
 
 impl Default for Camera {
@@ -73,6 +108,7 @@ impl Default for Camera {
             fov: std::f32::consts::FRAC_PI_2, // 90 degrees
             near: 0.1,
             far: 1000.0,
+            projection: ProjectionMode::Perspective,
         }
     }
 }
@@ -137,6 +173,62 @@ impl Camera {
         self
     }
 
+    /// Switch to orthographic projection showing `height` world units vertically.
+    ///
+    /// The visible width follows from the aspect ratio. The near and far clip
+    /// planes still apply.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hoplite::Camera;
+    /// let camera = Camera::new().orthographic(20.0);
+    /// assert!(camera.is_orthographic());
+    /// ```
+    pub fn orthographic(mut self, height: f32) -> Self {
+        self.projection = ProjectionMode::Orthographic { height };
+        self
+    }
+
+    /// Switch back to perspective projection using the camera's FOV.
+    pub fn perspective(mut self) -> Self {
+        self.projection = ProjectionMode::Perspective;
+        self
+    }
+
+    /// Returns `true` if the camera uses orthographic projection.
+    pub fn is_orthographic(&self) -> bool {
+        matches!(self.projection, ProjectionMode::Orthographic { .. })
+    }
+
+    /// Move this camera a fraction `t` of the way toward `target`.
+    ///
+    /// Position and field of view are interpolated linearly, and the look
+    /// direction rotates along the shortest arc, so the camera never passes
+    /// through a zero-length forward vector. `t` is clamped to `0.0..=1.0`.
+    /// Other settings such as the projection mode are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hoplite::Camera;
+    /// let mut camera = Camera::new().at([0.0, 0.0, 5.0]);
+    /// let target = Camera::new().at([10.0, 0.0, 5.0]);
+    ///
+    /// camera.lerp_to(&target, 0.5);
+    /// assert_eq!(camera.position.x, 5.0);
+    /// ```
+    pub fn lerp_to(&mut self, target: &Camera, t: f32) {
+        let t = t.clamp(0.0, 1.0);
+        self.position = self.position.lerp(target.position, t);
+        self.fov += (target.fov - self.fov) * t;
+
+        let from = self.forward.normalize_or(Vec3::NEG_Z);
+        let to = target.forward.normalize_or(Vec3::NEG_Z);
+        let rotation = Quat::IDENTITY.slerp(Quat::from_rotation_arc(from, to), t);
+        self.forward = (rotation * from).normalize();
+    }
+
     /// Compute the right vector from forward and up.
     ///
     /// Returns a normalized vector pointing to the camera's right.
@@ -161,10 +253,11 @@ impl Camera {
         Mat4::look_at_rh(self.position, self.position + self.forward, self.up)
     }
 
-    /// Compute a perspective projection matrix.
+    /// Compute the projection matrix for the camera's [`ProjectionMode`].
     ///
-    /// Uses the camera's FOV with the provided aspect ratio and clip planes.
-    /// Uses right-handed coordinates with depth range [0, 1].
+    /// Perspective cameras use their FOV; orthographic cameras use their view
+    /// height. Both use the provided aspect ratio and clip planes, with
+    /// right-handed coordinates and depth range [0, 1].
     ///
     /// # Parameters
     ///
@@ -172,6 +265,226 @@ impl Camera {
     /// - `near`: Near clipping plane distance
     /// - `far`: Far clipping plane distance
     pub fn projection_matrix(&self, aspect: f32, near: f32, far: f32) -> Mat4 {
-        Mat4::perspective_rh(self.fov, aspect, near, far)
+        match self.projection {
+            ProjectionMode::Perspective => Mat4::perspective_rh(self.fov, aspect, near, far),
+            ProjectionMode::Orthographic { height } => {
+                let half_height = height * 0.5;
+                let half_width = half_height * aspect;
+                Mat4::orthographic_rh(
+                    -half_width,
+                    half_width,
+                    -half_height,
+                    half_height,
+                    near,
+                    far,
+                )
+            }
+        }
+    }
+
+    /// Compute the combined view-projection matrix, clipping to the camera's
+    /// own [`near`](Self::near) and [`far`](Self::far) planes.
+    ///
+    /// Its inverse maps NDC back to world space, which is how shaders
+    /// reconstruct positions from depth.
+    pub fn view_projection_matrix(&self, aspect: f32) -> Mat4 {
+        self.projection_matrix(aspect, self.near, self.far) * self.view_matrix()
+    }
+
+    /// World-space directions from the camera through the four screen corners,
+    /// ordered top-left, top-right, bottom-left, bottom-right.
+    ///
+    /// Each direction reaches one unit along [`forward`](Self::forward) and is
+    /// left unnormalized, so interpolating between them with a screen UV
+    /// (`y` pointing down) gives the exact ray through that pixel:
+    ///
+    /// ```wgsl
+    /// let top = mix(u.frustum_corners[0].xyz, u.frustum_corners[1].xyz, uv.x);
+    /// let bottom = mix(u.frustum_corners[2].xyz, u.frustum_corners[3].xyz, uv.x);
+    /// let ray = normalize(mix(top, bottom, uv.y));
+    /// ```
+    ///
+    /// Orthographic rays are parallel, so every corner is `forward`; offset the
+    /// ray origin across the view height instead.
+    pub fn frustum_corners(&self, aspect: f32) -> [Vec3; 4] {
+        let forward = self.forward.normalize_or(Vec3::NEG_Z);
+        if self.is_orthographic() {
+            return [forward; 4];
+        }
+        let half_height = (self.fov * 0.5).tan();
+        let up = self.orthogonal_up() * half_height;
+        let right = self.right() * half_height * aspect;
+        [
+            forward + up - right,
+            forward + up + right,
+            forward - up - right,
+            forward - up + right,
+        ]
+    }
+
+    /// Project a world-space point to screen coordinates in pixels.
+    ///
+    /// Returns `None` if the point is behind the camera. Points outside the
+    /// view frustum still project, landing outside `0..screen_size`.
+    ///
+    /// # Parameters
+    ///
+    /// - `point`: World-space position
+    /// - `screen_size`: Screen dimensions in pixels `[width, height]`
+    pub fn world_to_screen(&self, point: Vec3, screen_size: Vec2) -> Option<Vec2> {
+        let view_pos = self.view_matrix().transform_point3(point);
+        if view_pos.z >= 0.0 {
+            return None;
+        }
+
+        let ndc = self
+            .projection_matrix(screen_size.x / screen_size.y, self.near, self.far)
+            .project_point3(view_pos);
+
+        Some(Vec2::new(
+            (ndc.x + 1.0) * 0.5 * screen_size.x,
+            (1.0 - ndc.y) * 0.5 * screen_size.y,
+        ))
+    }
+
+    /// Unproject screen coordinates back to a world-space point.
+    ///
+    /// `depth` is the distance in front of the camera along its forward axis,
+    /// so `screen_to_world(world_to_screen(p), d)` returns `p` when `d` is the
+    /// forward distance to `p`.
+    ///
+    /// # Parameters
+    ///
+    /// - `screen`: Screen position in pixels
+    /// - `depth`: Distance along the forward axis in world units
+    /// - `screen_size`: Screen dimensions in pixels `[width, height]`
+    pub fn screen_to_world(&self, screen: Vec2, depth: f32, screen_size: Vec2) -> Vec3 {
+        let projection = self.projection_matrix(screen_size.x / screen_size.y, self.near, self.far);
+
+        // Convert the view-space depth to NDC depth
+        let clip = projection * Vec4::new(0.0, 0.0, -depth, 1.0);
+        let ndc = Vec3::new(
+            (2.0 * screen.x / screen_size.x) - 1.0,
+            1.0 - (2.0 * screen.y / screen_size.y), // Y is flipped
+            clip.z / clip.w,
+        );
+
+        (projection * self.view_matrix())
+            .inverse()
+            .project_point3(ndc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orthographic_ignores_depth_but_perspective_does_not() {
+        let perspective = Camera::new().at([0.0, 0.0, 0.0]).with_fov(90.0);
+        let orthographic = perspective.orthographic(4.0);
+
+        let project = |camera: &Camera, point: Vec3| {
+            let clip = camera.projection_matrix(1.0, 0.1, 100.0) * camera.view_matrix();
+            clip.project_point3(point)
+        };
+
+        // A point 1 unit up, at two different depths
+        let near = Vec3::new(0.0, 1.0, -2.0);
+        let far = Vec3::new(0.0, 1.0, -8.0);
+
+        // Perspective: tan(45°) = 1, so y_ndc = 1 / depth
+        assert!((project(&perspective, near).y - 0.5).abs() < 1e-5);
+        assert!((project(&perspective, far).y - 0.125).abs() < 1e-5);
+
+        // Orthographic: 4 units tall, so y_ndc = 1 / 2 at every depth
+        assert!((project(&orthographic, near).y - 0.5).abs() < 1e-5);
+        assert!((project(&orthographic, far).y - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn world_to_screen_round_trips_through_screen_to_world() {
+        let screen_size = Vec2::new(800.0, 600.0);
+        let point = Vec3::new(1.5, -0.5, 2.0);
+
+        for camera in [
+            Camera::new()
+                .at([3.0, 2.0, 10.0])
+                .looking_at([0.0, 0.0, 0.0]),
+            Camera::new()
+                .at([3.0, 2.0, 10.0])
+                .looking_at([0.0, 0.0, 0.0])
+                .orthographic(8.0),
+        ] {
+            let screen = camera.world_to_screen(point, screen_size).unwrap();
+            let depth = (point - camera.position).dot(camera.forward);
+            let back = camera.screen_to_world(screen, depth, screen_size);
+            assert!((back - point).length() < 1e-3, "{back} != {point}");
+        }
+    }
+
+    #[test]
+    fn lerp_to_rotates_forward_along_the_shortest_arc() {
+        let mut camera = Camera::new().at([0.0, 0.0, 0.0]);
+        let mut target = Camera::new().at([4.0, 0.0, 0.0]);
+        target.forward = Vec3::X;
+
+        camera.lerp_to(&target, 0.5);
+        assert!((camera.position - Vec3::new(2.0, 0.0, 0.0)).length() < 1e-5);
+        let halfway = Vec3::new(1.0, 0.0, -1.0).normalize();
+        assert!((camera.forward - halfway).length() < 1e-5);
+
+        camera.lerp_to(&target, 1.0);
+        assert!((camera.forward - Vec3::X).length() < 1e-5);
+    }
+
+    #[test]
+    fn frustum_corners_point_through_the_screen_corners() {
+        let camera = Camera::new()
+            .at([1.0, 2.0, 3.0])
+            .looking_at([4.0, 0.0, -2.0])
+            .with_fov(70.0);
+        let screen_size = Vec2::new(800.0, 600.0);
+        let corners = camera.frustum_corners(screen_size.x / screen_size.y);
+        let expected = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(screen_size.x, 0.0),
+            Vec2::new(0.0, screen_size.y),
+            screen_size,
+        ];
+        for (corner, screen) in corners.into_iter().zip(expected) {
+            assert!((corner.dot(camera.forward) - 1.0).abs() < 1e-5);
+            let projected = camera
+                .world_to_screen(camera.position + corner * 10.0, screen_size)
+                .unwrap();
+            assert!(
+                (projected - screen).length() < 1e-2,
+                "{projected} != {screen}"
+            );
+        }
+
+        // The inverse view-projection sends the NDC corners along the same rays
+        let inverse = camera
+            .view_projection_matrix(screen_size.x / screen_size.y)
+            .inverse();
+        let far_top_left = inverse.project_point3(Vec3::new(-1.0, 1.0, 1.0));
+        let ray = (far_top_left - camera.position).normalize();
+        assert!((ray - corners[0].normalize()).length() < 1e-3);
+
+        let orthographic = camera.orthographic(4.0);
+        assert_eq!(orthographic.frustum_corners(2.0), [orthographic.forward; 4]);
+    }
+
+    #[test]
+    fn world_to_screen_rejects_points_behind_camera() {
+        let camera = Camera::new().at([0.0, 0.0, 0.0]);
+        let screen_size = Vec2::new(800.0, 600.0);
+
+        let center = camera.world_to_screen(Vec3::new(0.0, 0.0, -5.0), screen_size);
+        assert_eq!(center, Some(Vec2::new(400.0, 300.0)));
+        assert_eq!(
+            camera.world_to_screen(Vec3::new(0.0, 0.0, 5.0), screen_size),
+            None
+        );
     }
 }
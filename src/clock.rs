@@ -0,0 +1,245 @@
+//! Frame timing for the app loop, with a game clock that can be paused and scaled.
+//!
+//! The loop tracks two clocks. Wall-clock time always advances at real speed
+//! and drives scene transitions and [`Frame::real_dt`](crate::Frame::real_dt).
+//! Game time is what [`Frame::time`](crate::Frame::time), [`Frame::dt`](crate::Frame::dt),
+//! fixed updates, and shaders see; it runs at the time scale and holds still
+//! while paused.
+//!
+//! The clock also keeps the last [`FRAME_HISTORY`] wall-clock frame times for
+//! smoothed FPS readouts.
+
+use std::time::Instant;
+
+/// Number of recent frames averaged by the frame-time stats.
+pub(crate) const FRAME_HISTORY: usize = 60;
+
+/// Ring buffer of recent wall-clock frame times.
+struct FrameTimes {
+    seconds: [f32; FRAME_HISTORY],
+    /// Slot the next frame time is written to.
+    next: usize,
+    /// Number of filled slots, up to `FRAME_HISTORY`.
+    len: usize,
+}
+
+impl FrameTimes {
+    fn new() -> Self {
+        Self {
+            seconds: [0.0; FRAME_HISTORY],
+            next: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, seconds: f32) {
+        self.seconds[self.next] = seconds;
+        self.next = (self.next + 1) % FRAME_HISTORY;
+        self.len = (self.len + 1).min(FRAME_HISTORY);
+    }
+
+    fn recent(&self) -> &[f32] {
+        &self.seconds[..self.len]
+    }
+}
+
+/// Frames per second for a frame time, or zero for an empty one.
+fn per_second(seconds: f32) -> f32 {
+    if seconds > 0.0 { 1.0 / seconds } else { 0.0 }
+}
+
+/// The app loop's wall-clock and game-time bookkeeping.
+pub(crate) struct Clock {
+    last_frame: Instant,
+    real_time: f32,
+    real_dt: f32,
+    time: f32,
+    dt: f32,
+    paused: bool,
+    scale: f32,
+    frame_times: FrameTimes,
+}
+
+impl Clock {
+    /// Starts both clocks at zero.
+    pub fn new() -> Self {
+        Self {
+            last_frame: Instant::now(),
+            real_time: 0.0,
+            real_dt: 0.0,
+            time: 0.0,
+            dt: 0.0,
+            paused: false,
+            scale: 1.0,
+            frame_times: FrameTimes::new(),
+        }
+    }
+
+    /// When the last frame started.
+    pub fn last_frame(&self) -> Instant {
+        self.last_frame
+    }
+
+    /// Starts a frame at `now`, advancing by the time since the last one.
+    pub fn tick(&mut self, now: Instant) {
+        let real_dt = now.duration_since(self.last_frame).as_secs_f32();
+        self.last_frame = now;
+        self.advance(real_dt);
+    }
+
+    /// Advances wall-clock time by `real_dt` seconds, and game time by that
+    /// times the time scale unless paused.
+    pub fn advance(&mut self, real_dt: f32) {
+        self.real_dt = real_dt;
+        self.real_time += real_dt;
+        self.frame_times.push(real_dt);
+        self.dt = if self.paused {
+            0.0
+        } else {
+            real_dt * self.scale
+        };
+        self.time += self.dt;
+    }
+
+    /// Game time in seconds, scaled and excluding time spent paused.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Game seconds elapsed this frame, scaled; zero while paused.
+    pub fn dt(&self) -> f32 {
+        self.dt
+    }
+
+    /// Wall-clock seconds since the loop started.
+    pub fn real_time(&self) -> f32 {
+        self.real_time
+    }
+
+    /// Wall-clock seconds elapsed this frame.
+    pub fn real_dt(&self) -> f32 {
+        self.real_dt
+    }
+
+    /// Whether game time is frozen.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Freezes or resumes game time from the next frame on.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// How fast game time runs relative to wall-clock time.
+    pub fn time_scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets how fast game time runs from the next frame on. Negative scales are treated as zero.
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.scale = scale.max(0.0);
+    }
+
+    /// Mean wall-clock frame time over recent frames, in seconds.
+    pub fn average_frame_time(&self) -> f32 {
+        let recent = self.frame_times.recent();
+        if recent.is_empty() {
+            return 0.0;
+        }
+        recent.iter().sum::<f32>() / recent.len() as f32
+    }
+
+    /// Frames per second over recent frames.
+    pub fn fps_avg(&self) -> f32 {
+        per_second(self.average_frame_time())
+    }
+
+    /// Lowest frame rate over recent frames, from the slowest frame.
+    pub fn min_fps(&self) -> f32 {
+        per_second(
+            self.frame_times
+                .recent()
+                .iter()
+                .copied()
+                .fold(0.0, f32::max),
+        )
+    }
+
+    /// Highest frame rate over recent frames, from the fastest frame.
+    pub fn max_fps(&self) -> f32 {
+        let fastest = self
+            .frame_times
+            .recent()
+            .iter()
+            .copied()
+            .reduce(f32::min)
+            .unwrap_or(0.0);
+        per_second(fastest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pausing_freezes_game_time_but_not_real_time() {
+        let mut clock = Clock::new();
+        clock.advance(0.5);
+        assert_eq!((clock.time(), clock.dt()), (0.5, 0.5));
+
+        clock.set_paused(true);
+        clock.advance(0.25);
+        assert_eq!((clock.time(), clock.dt()), (0.5, 0.0));
+        assert_eq!((clock.real_time(), clock.real_dt()), (0.75, 0.25));
+
+        // Resuming picks up where game time stopped
+        clock.set_paused(false);
+        clock.advance(0.25);
+        assert_eq!((clock.time(), clock.dt()), (0.75, 0.25));
+        assert_eq!(clock.real_time(), 1.0);
+    }
+
+    #[test]
+    fn time_scale_stretches_game_time_only() {
+        let mut clock = Clock::new();
+        clock.set_time_scale(0.25);
+        clock.advance(0.5);
+        clock.advance(0.5);
+        assert_eq!((clock.time(), clock.dt()), (0.25, 0.125));
+        assert_eq!((clock.real_time(), clock.real_dt()), (1.0, 0.5));
+
+        // Pausing wins over any scale
+        clock.set_time_scale(2.0);
+        clock.set_paused(true);
+        clock.advance(0.5);
+        assert_eq!(clock.dt(), 0.0);
+
+        clock.set_time_scale(-1.0);
+        assert_eq!(clock.time_scale(), 0.0);
+    }
+
+    #[test]
+    fn frame_stats_cover_the_recent_window() {
+        let mut clock = Clock::new();
+        assert_eq!(
+            (clock.fps_avg(), clock.min_fps(), clock.max_fps()),
+            (0.0, 0.0, 0.0)
+        );
+
+        clock.advance(0.01);
+        clock.advance(0.03);
+        assert!((clock.average_frame_time() - 0.02).abs() < 1e-6);
+        assert!((clock.fps_avg() - 50.0).abs() < 1e-3);
+        assert!((clock.min_fps() - 1.0 / 0.03).abs() < 1e-3);
+        assert!((clock.max_fps() - 100.0).abs() < 1e-3);
+
+        // The hitch ages out once a full window of steady frames follows it
+        for _ in 0..FRAME_HISTORY {
+            clock.advance(0.02);
+        }
+        assert!((clock.min_fps() - 50.0).abs() < 1e-3);
+        assert!((clock.max_fps() - 50.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,445 @@
+//! World-space debug lines drawn on top of the 3D scene.
+//!
+//! [`DebugLines`] collects colored line segments for one frame, such as
+//! picking rays, bounding boxes, or skeleton bones. [`MeshNode`](crate::MeshNode)
+//! draws them after its meshes with the same camera, depth-tested against the
+//! mesh depth buffer so lines behind geometry stay hidden.
+//!
+//! Queue lines through [`Frame::debug_line`](crate::Frame::debug_line) and
+//! friends, or through [`MeshQueue::debug_lines`](crate::MeshQueue::debug_lines)
+//! when driving a render graph by hand.
+
+use glam::{Mat4, Vec3};
+
+use crate::draw2d::Color;
+use crate::mesh::Transform;
+use crate::picking::{Collider, ColliderShape, Ray};
+
+/// Line segments per circle of a wireframe sphere.
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Number of line vertices the GPU buffer holds before it first grows.
+const INITIAL_VERTEX_CAPACITY: usize = 1024;
+
+/// One end of a debug line segment.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct LineVertex {
+    position: [f32; 3],
+    color: [f32; 4],
+}
+
+impl LineVertex {
+    const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<LineVertex>() as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x4],
+    };
+}
+
+/// Line segments queued for the current frame.
+///
+/// Lines are cleared along with the rest of the mesh queue at the end of
+/// each frame, so queue them again every frame they should stay visible.
+#[derive(Clone, Debug, Default)]
+pub struct DebugLines {
+    /// Pairs of vertices, one pair per segment.
+    vertices: Vec<LineVertex>,
+}
+
+impl DebugLines {
+    /// Creates an empty line list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a line segment from `a` to `b`.
+    pub fn line(&mut self, a: Vec3, b: Vec3, color: Color) {
+        let color = [color.r, color.g, color.b, color.a];
+        self.vertices.push(LineVertex {
+            position: a.to_array(),
+            color,
+        });
+        self.vertices.push(LineVertex {
+            position: b.to_array(),
+            color,
+        });
+    }
+
+    /// Queues the 12 edges of the axis-aligned box spanning `min` to `max`.
+    pub fn aabb(&mut self, min: Vec3, max: Vec3, color: Color) {
+        let corner = |i: usize| {
+            Vec3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        };
+        // Corners one bit apart share an edge
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    self.line(corner(i), corner(i | bit), color);
+                }
+            }
+        }
+    }
+
+    /// Queues a wireframe sphere as three circles around the X, Y, and Z axes.
+    pub fn sphere(&mut self, center: Vec3, radius: f32, color: Color) {
+        for (u, v) in [(Vec3::Y, Vec3::Z), (Vec3::Z, Vec3::X), (Vec3::X, Vec3::Y)] {
+            let point = |i: usize| {
+                let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+                center + (u * angle.cos() + v * angle.sin()) * radius
+            };
+            for i in 0..CIRCLE_SEGMENTS {
+                self.line(point(i), point(i + 1), color);
+            }
+        }
+    }
+
+    /// Queues the wireframe of a collider placed by `transform`.
+    ///
+    /// The shape matches what picking tests against: boxes stay axis-aligned
+    /// and spheres scale by the transform's average scale.
+    pub fn collider(&mut self, collider: &Collider, transform: &Transform, color: Color) {
+        let position = transform.position;
+        let scale = transform.scale;
+        match collider.shape {
+            ColliderShape::Box { half_extents } => {
+                let half = half_extents * scale;
+                self.aabb(position - half, position + half, color);
+            }
+            ColliderShape::Sphere { radius } => {
+                let avg_scale = (scale.x + scale.y + scale.z) / 3.0;
+                self.sphere(position, radius * avg_scale, color);
+            }
+        }
+    }
+
+    /// Queues the first `length` units of a ray.
+    pub fn ray(&mut self, ray: &Ray, length: f32, color: Color) {
+        self.line(ray.origin, ray.point_at(length), color);
+    }
+
+    /// Number of queued line segments.
+    pub fn len(&self) -> usize {
+        self.vertices.len() / 2
+    }
+
+    /// Whether no lines are queued.
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Removes every queued line.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+}
+
+/// GPU resources for drawing [`DebugLines`] inside a mesh render pass.
+pub(crate) struct DebugLinePass {
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    vertex_buffer: wgpu::Buffer,
+    /// Number of vertices `vertex_buffer` holds.
+    capacity: usize,
+}
+
+impl DebugLinePass {
+    /// Creates a line pass for color targets of `format` with a `Depth32Float` depth attachment.
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Debug Line Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/debug_lines.wgsl").into()),
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Debug Line Uniforms"),
+            size: std::mem::size_of::<[[f32; 4]; 4]>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Debug Line Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Debug Line Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Debug Line Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Debug Line Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs"),
+                buffers: &[LineVertex::LAYOUT],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            },
+            // Test against the meshes' depth but leave it untouched, so lines
+            // never hide each other or anything drawn later
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            vertex_buffer: Self::create_vertex_buffer(device, INITIAL_VERTEX_CAPACITY),
+            capacity: INITIAL_VERTEX_CAPACITY,
+        }
+    }
+
+    /// Grows the vertex buffer to fit `lines`.
+    ///
+    /// The buffer grows to the next power of two and never shrinks. Lines
+    /// beyond the current capacity are dropped by [`render`](Self::render).
+    pub fn ensure_capacity(&mut self, device: &wgpu::Device, lines: &DebugLines) {
+        let count = lines.vertices.len();
+        if count > self.capacity {
+            self.capacity = count.next_power_of_two();
+            self.vertex_buffer = Self::create_vertex_buffer(device, self.capacity);
+        }
+    }
+
+    /// Draws `lines` into a render pass that has a `Depth32Float` depth attachment.
+    pub fn render(
+        &self,
+        queue: &wgpu::Queue,
+        render_pass: &mut wgpu::RenderPass,
+        view_proj: Mat4,
+        lines: &DebugLines,
+    ) {
+        // Whole segments only, in case the list outgrew the buffer
+        let count = lines.vertices.len().min(self.capacity) & !1;
+        if count == 0 {
+            return;
+        }
+
+        queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&view_proj.to_cols_array_2d()),
+        );
+        queue.write_buffer(
+            &self.vertex_buffer,
+            0,
+            bytemuck::cast_slice(&lines.vertices[..count]),
+        );
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(0..count as u32, 0..1);
+    }
+
+    fn create_vertex_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Debug Line Vertices"),
+            size: (capacity * std::mem::size_of::<LineVertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aabb_has_twelve_axis_aligned_edges() {
+        let mut lines = DebugLines::new();
+        lines.aabb(Vec3::ZERO, Vec3::new(1.0, 2.0, 3.0), Color::WHITE);
+        assert_eq!(lines.len(), 12);
+
+        for edge in lines.vertices.chunks(2) {
+            let delta = Vec3::from(edge[1].position) - Vec3::from(edge[0].position);
+            let changed = delta.to_array().iter().filter(|d| **d != 0.0).count();
+            assert_eq!(changed, 1, "edge {:?} isn't axis aligned", edge);
+        }
+    }
+
+    #[test]
+    fn collider_wireframes_match_picking_bounds() {
+        let transform = Transform::new()
+            .position(Vec3::new(1.0, 2.0, 3.0))
+            .scale(Vec3::splat(2.0));
+        let mut lines = DebugLines::new();
+        lines.collider(&Collider::unit_box(), &transform, Color::WHITE);
+        let (min, max) = lines.vertices.iter().fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(min, max), v| (min.min(v.position.into()), max.max(v.position.into())),
+        );
+        assert_eq!(
+            (min, max),
+            (Vec3::new(0.0, 1.0, 2.0), Vec3::new(2.0, 3.0, 4.0))
+        );
+
+        lines.clear();
+        lines.collider(&Collider::sphere(0.5), &transform, Color::WHITE);
+        assert_eq!(lines.len(), 3 * CIRCLE_SEGMENTS);
+        for vertex in &lines.vertices {
+            let distance = Vec3::from(vertex.position).distance(transform.position);
+            assert!((distance - 1.0).abs() < 1e-5, "{}", distance);
+        }
+    }
+
+    #[test]
+    fn ray_line_ends_at_length() {
+        let mut lines = DebugLines::new();
+        lines.ray(
+            &Ray::new(Vec3::ONE, Vec3::new(0.0, 0.0, -2.0)),
+            5.0,
+            Color::rgb(1.0, 0.0, 0.0),
+        );
+        assert_eq!(lines.vertices[1].position, [1.0, 1.0, -4.0]);
+        assert_eq!(lines.vertices[1].color, [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn lines_are_hidden_behind_nearer_depth() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let size = wgpu::Extent3d {
+            width: 16,
+            height: 16,
+            depth_or_array_layers: 1,
+        };
+        let target = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let depth = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth32Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let target_view = target.create_view(&Default::default());
+        let depth_view = depth.create_view(&Default::default());
+
+        // With an identity view-projection, z is the depth directly. The
+        // horizontal line sits in front of the cleared depth, the vertical one behind it
+        let mut lines = DebugLines::new();
+        lines.line(
+            Vec3::new(-1.0, 0.0625, 0.25),
+            Vec3::new(1.0, 0.0625, 0.25),
+            Color::rgb(1.0, 0.0, 0.0),
+        );
+        lines.line(
+            Vec3::new(-0.0625, -1.0, 0.75),
+            Vec3::new(-0.0625, 1.0, 0.75),
+            Color::rgb(0.0, 1.0, 0.0),
+        );
+
+        let mut pass_resources = DebugLinePass::new(&gpu.device, format);
+        pass_resources.ensure_capacity(&gpu.device, &lines);
+
+        let mut encoder = gpu.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0.5),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass_resources.render(&gpu.queue, &mut pass, Mat4::IDENTITY, &lines);
+        }
+
+        gpu.queue.submit([encoder.finish()]);
+        let pixels = crate::screenshot::read_texture_rgba(&gpu.device, &gpu.queue, &target)
+            .expect("Rgba8Unorm targets can be read back");
+
+        // Row and column 7 are just above and left of center
+        let pixel = |x: u32, y: u32| pixels.get_pixel(x, y).0;
+        let row: Vec<_> = (0..16).map(|x| pixel(x, 7)).collect();
+        assert!(
+            row.iter().all(|p| p[0] == 255),
+            "red line missing: {:?}",
+            row
+        );
+        let column: Vec<_> = (0..16).filter(|y| *y != 7).map(|y| pixel(7, y)).collect();
+        assert!(
+            column.iter().all(|p| p[1] == 0),
+            "green line should be occluded: {:?}",
+            column
+        );
+    }
+}
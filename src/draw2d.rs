@@ -7,12 +7,14 @@
 //! # Architecture
 //!
 //! The rendering system uses three separate pipelines:
-//! - **Colored pipeline**: For solid-color rectangles (no texture sampling)
+//! - **Colored pipeline**: For solid-color rectangles, lines, and circles (no texture sampling)
 //! - **Textured pipeline**: For font rendering (R8 alpha mask textures)
+//! - **SDF pipeline**: For signed distance field fonts, with optional outline and glow
 //! - **Sprite pipeline**: For RGBA sprite rendering
+//! - **Instanced sprite pipeline**: For many copies of one sprite, one instance each
 //!
 //! Draw calls are batched by texture to minimize bind group switches. Each frame:
-//! 1. Call drawing methods ([`Draw2d::rect`], [`Draw2d::text`], [`Draw2d::sprite`], etc.)
+//! 1. Call drawing methods ([`Draw2d::rect`], [`Draw2d::line`], [`Draw2d::text`], [`Draw2d::sprite`], etc.)
 //! 2. Call [`Draw2d::render`] to flush all batched geometry to the GPU
 //! 3. Call [`Draw2d::clear`] to reset batches for the next frame
 //!
@@ -34,9 +36,13 @@
 //! draw2d.clear();
 //! ```
 
-use crate::assets::{Assets, FontId};
+use crate::assets::{Assets, FontAtlas, FontId, GlyphInfo};
 use crate::gpu::GpuContext;
+use crate::render_stats::RenderStats;
 use crate::texture::Sprite;
+use glam::Vec2;
+use std::cell::Cell;
+use std::collections::HashMap;
 
 /// Index into the sprite storage.
 ///
@@ -70,6 +76,14 @@ impl Rect {
             height,
         }
     }
+
+    /// Returns `true` if `point` lies inside the rectangle, edges included.
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.x
+            && point.y >= self.y
+            && point.x <= self.x + self.width
+            && point.y <= self.y + self.height
+    }
 }
 
 /// RGBA color with components in the range `[0.0, 1.0]`.
@@ -82,7 +96,18 @@ impl Rect {
 /// Several commonly-used colors are provided as constants:
 /// - [`Color::WHITE`], [`Color::BLACK`], [`Color::TRANSPARENT`]
 /// - [`Color::DEBUG_BG`], [`Color::DEBUG_BORDER`] for debug UI styling
-#[derive(Clone, Copy, Debug)]
+///
+/// # Other Constructors
+///
+/// ```
+/// use hoplite::Color;
+///
+/// let accent = Color::hex("#1a2b3c").unwrap();
+/// let warning = Color::hex_u32(0xffaa00ff);
+/// let hue = Color::hsv(200.0, 0.8, 0.9);
+/// let faded = Color::lerp(accent, Color::TRANSPARENT, 0.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Color {
     /// Red component (0.0 to 1.0).
     pub r: f32,
@@ -109,6 +134,104 @@ impl Color {
         Self { r, g, b, a: 1.0 }
     }
 
+    /// Creates a color from 8-bit RGBA components (0 to 255).
+    pub const fn from_u8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self {
+            r: r as f32 / 255.0,
+            g: g as f32 / 255.0,
+            b: b as f32 / 255.0,
+            a: a as f32 / 255.0,
+        }
+    }
+
+    /// Creates a color from a packed `0xRRGGBBAA` value.
+    pub const fn hex_u32(rgba: u32) -> Self {
+        let [r, g, b, a] = rgba.to_be_bytes();
+        Self::from_u8(r, g, b, a)
+    }
+
+    /// Parses a CSS-style hex color string.
+    ///
+    /// Accepts `RGB`, `RGBA`, `RRGGBB`, and `RRGGBBAA` digits, with or without
+    /// a leading `#`. Colors without an alpha component are opaque.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseColorError`] if the string has the wrong length or
+    /// contains non-hex characters.
+    pub fn hex(s: &str) -> Result<Self, ParseColorError> {
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        let error = || ParseColorError(s.to_string());
+        if !digits.is_ascii() {
+            return Err(error());
+        }
+
+        let channel = |i: usize, width: usize| {
+            let value =
+                u8::from_str_radix(&digits[i * width..(i + 1) * width], 16).map_err(|_| error())?;
+            // Expand shorthand digits, e.g. "f" -> "ff"
+            Ok(if width == 1 { value * 17 } else { value })
+        };
+
+        let width = match digits.len() {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => return Err(error()),
+        };
+        let a = if digits.len() / width == 4 {
+            channel(3, width)?
+        } else {
+            255
+        };
+        Ok(Self::from_u8(
+            channel(0, width)?,
+            channel(1, width)?,
+            channel(2, width)?,
+            a,
+        ))
+    }
+
+    /// Creates an opaque color from hue, saturation, and value.
+    ///
+    /// `h` is in degrees and wraps around (so `-90.0` and `270.0` match).
+    /// `s` and `v` are clamped to `[0.0, 1.0]`.
+    pub fn hsv(h: f32, s: f32, v: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let m = v - c;
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        Self::rgb(r + m, g + m, b + m)
+    }
+
+    /// Linearly interpolates between two colors, including alpha.
+    ///
+    /// `t` is clamped to `[0.0, 1.0]`, so `0.0` returns `a` and `1.0` returns `b`.
+    pub fn lerp(a: Color, b: Color, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        Self::rgba(
+            a.r + (b.r - a.r) * t,
+            a.g + (b.g - a.g) * t,
+            a.b + (b.b - a.b) * t,
+            a.a + (b.a - a.a) * t,
+        )
+    }
+
+    /// Converts to 8-bit RGBA components, clamping out-of-range values.
+    pub fn to_u8(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
     /// Fully opaque white.
     pub const WHITE: Color = Color::rgba(1.0, 1.0, 1.0, 1.0);
     /// Fully opaque black.
@@ -122,6 +245,181 @@ impl Color {
     pub const DEBUG_BORDER: Color = Color::rgba(0.4, 0.4, 0.4, 1.0);
 }
 
+impl From<Color> for wgpu::Color {
+    /// Converts to a clear color for render pass load operations.
+    fn from(color: Color) -> Self {
+        Self {
+            r: color.r as f64,
+            g: color.g as f64,
+            b: color.b as f64,
+            a: color.a as f64,
+        }
+    }
+}
+
+/// Error returned by [`Color::hex`] for malformed hex strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseColorError(String);
+
+impl std::fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid hex color: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Horizontal alignment for [`Draw2d::text_aligned`].
+///
+/// The alignment decides which part of the text lands on the given x coordinate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    /// The text starts at x.
+    #[default]
+    Left,
+    /// The text is centered on x.
+    Center,
+    /// The text ends at x.
+    Right,
+}
+
+/// Outline and glow drawn around text in signed distance field fonts.
+///
+/// Set with [`Draw2d::set_text_effects`]. Widths are in pixels at the font's
+/// loaded size and scale with the text. The outline and glow together extend
+/// at most the atlas's [spread](crate::FontAtlas::sdf_spread) past the glyph
+/// edge, so larger values are clipped. Regular (non-SDF) fonts ignore these.
+///
+/// # Example
+///
+/// ```ignore
+/// draw2d.set_text_effects(
+///     TextEffects::new()
+///         .outline(2.0, Color::BLACK)
+///         .glow(4.0, Color::rgba(1.0, 0.6, 0.1, 0.8)),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextEffects {
+    /// Outline thickness outside the glyph edge.
+    pub outline_width: f32,
+    /// Outline color.
+    pub outline_color: Color,
+    /// Distance over which the glow fades out beyond the outline.
+    pub glow_radius: f32,
+    /// Glow color at the outline edge.
+    pub glow_color: Color,
+}
+
+impl Default for TextEffects {
+    fn default() -> Self {
+        Self {
+            outline_width: 0.0,
+            outline_color: Color::TRANSPARENT,
+            glow_radius: 0.0,
+            glow_color: Color::TRANSPARENT,
+        }
+    }
+}
+
+impl TextEffects {
+    /// No outline or glow.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an outline of `width` pixels around each glyph.
+    pub fn outline(mut self, width: f32, color: Color) -> Self {
+        self.outline_width = width.max(0.0);
+        self.outline_color = color;
+        self
+    }
+
+    /// Adds a glow that fades out over `radius` pixels beyond the outline.
+    pub fn glow(mut self, radius: f32, color: Color) -> Self {
+        self.glow_radius = radius.max(0.0);
+        self.glow_color = color;
+        self
+    }
+}
+
+/// One copy of a sprite in an instanced draw (see [`Draw2d::sprites_instanced`]).
+///
+/// # Example
+///
+/// ```ignore
+/// let sparks: Vec<SpriteInstance> = positions
+///     .iter()
+///     .map(|p| SpriteInstance::new(p.x, p.y).scale(0.5).color(Color::rgb(1.0, 0.8, 0.2)))
+///     .collect();
+/// draw2d.sprites_instanced(spark, &sparks);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpriteInstance {
+    /// Center of the sprite in screen pixels.
+    pub position: Vec2,
+    /// Size relative to the sprite's native pixel size.
+    pub scale: f32,
+    /// Clockwise rotation around the center, in radians.
+    pub rotation: f32,
+    /// Tint multiplied with the sprite's pixels.
+    pub color: Color,
+}
+
+impl SpriteInstance {
+    /// An untinted, unrotated instance at native size centered on `(x, y)`.
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            position: Vec2::new(x, y),
+            scale: 1.0,
+            rotation: 0.0,
+            color: Color::WHITE,
+        }
+    }
+
+    /// Sets the size relative to the sprite's native size.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the clockwise rotation in radians.
+    pub fn rotation(mut self, radians: f32) -> Self {
+        self.rotation = radians;
+        self
+    }
+
+    /// Sets the tint.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// Per-instance vertex data for `vs_sprite_instanced`.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct SpriteInstanceRaw {
+    center: [f32; 2],
+    /// Width and height in pixels.
+    size: [f32; 2],
+    rotation: f32,
+    color: [f32; 4],
+}
+
+impl SpriteInstanceRaw {
+    const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<SpriteInstanceRaw>() as u64,
+        step_mode: wgpu::VertexStepMode::Instance,
+        attributes: &wgpu::vertex_attr_array![
+            3 => Float32x2,
+            4 => Float32x2,
+            5 => Float32,
+            6 => Float32x4,
+        ],
+    };
+}
+
 /// Vertex format for 2D sprite and text rendering.
 ///
 /// Each vertex contains:
@@ -178,20 +476,65 @@ impl Vertex2d {
 /// Uniform buffer data for 2D rendering.
 ///
 /// Contains the screen resolution for converting pixel coordinates to
-/// normalized device coordinates in the vertex shader.
+/// normalized device coordinates in the vertex shader, and the coverage
+/// gamma applied to bitmap font glyphs in the fragment shader.
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct Draw2dUniforms {
     /// Screen resolution `[width, height]` in pixels.
     resolution: [f32; 2],
+    /// See [`Draw2d::set_text_gamma`].
+    text_gamma: f32,
     /// Padding to align to 16 bytes (required by wgpu uniform buffers).
-    _padding: [f32; 2],
+    _padding: f32,
+}
+
+/// Per-batch uniforms for signed distance field text (`SdfParams` in the shader).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct SdfUniforms {
+    /// Atlas pixels of distance covered by half the texel range.
+    spread: f32,
+    outline_width: f32,
+    glow_radius: f32,
+    _padding: f32,
+    outline_color: [f32; 4],
+    glow_color: [f32; 4],
+}
+
+impl SdfUniforms {
+    /// Uniforms for `effects` on an atlas with the given spread, clipping the
+    /// effects to the distance the atlas stores.
+    fn new(spread: f32, effects: TextEffects) -> Self {
+        let outline_width = effects.outline_width.min(spread);
+        let color = |c: Color| [c.r, c.g, c.b, c.a];
+        Self {
+            spread,
+            outline_width,
+            glow_radius: effects.glow_radius.min(spread - outline_width),
+            _padding: 0.0,
+            outline_color: color(effects.outline_color),
+            glow_color: color(effects.glow_color),
+        }
+    }
 }
 
-/// Maximum number of vertices that can be batched per frame.
+/// Initial vertex buffer capacity.
+///
+/// With 6 vertices per quad, this fits approximately 2,730 quads per frame.
+/// The buffer grows automatically when a frame batches more vertices.
+const INITIAL_VERTEX_CAPACITY: usize = 16384;
+
+/// Initial instance buffer capacity for instanced sprites.
 ///
-/// With 6 vertices per quad, this allows approximately 2,730 quads per frame.
-const MAX_VERTICES: usize = 16384;
+/// Grows like the vertex buffer when a frame draws more instances.
+const INITIAL_INSTANCE_CAPACITY: usize = 1024;
+
+/// Default coverage gamma for bitmap font text; see [`Draw2d::set_text_gamma`].
+pub const DEFAULT_TEXT_GAMMA: f32 = 1.4;
+
+/// Smallest accepted text gamma, keeping glyphs from vanishing entirely.
+const MIN_TEXT_GAMMA: f32 = 0.1;
 
 /// Immediate-mode 2D drawing API for sprites, text, and shapes.
 ///
@@ -222,25 +565,46 @@ const MAX_VERTICES: usize = 16384;
 /// - Sprites are batched per-sprite (each sprite is a separate texture)
 ///
 /// This minimizes GPU state changes while maintaining draw order within each batch type.
-/// Note that colored geometry is always drawn first, followed by text, then sprites.
+/// Within a layer, colored geometry is drawn first, followed by text, then sprites.
+/// Use [`Draw2d::set_layer`] to stack content in a different order.
 pub struct Draw2d {
     // Pipelines for different rendering modes
     /// Pipeline for solid-color rectangles (no texture sampling).
     colored_pipeline: wgpu::RenderPipeline,
     /// Pipeline for font rendering (R8 alpha mask textures).
     textured_pipeline: wgpu::RenderPipeline,
+    /// Pipeline for signed distance field font rendering.
+    sdf_pipeline: wgpu::RenderPipeline,
     /// Pipeline for RGBA sprite rendering.
     sprite_pipeline: wgpu::RenderPipeline,
+    /// Pipeline for instanced RGBA sprites.
+    instanced_sprite_pipeline: wgpu::RenderPipeline,
 
     // Shared GPU resources
     /// Dynamic vertex buffer for all 2D geometry.
     vertex_buffer: wgpu::Buffer,
+    /// Number of vertices `vertex_buffer` can hold.
+    vertex_capacity: usize,
+    /// Dynamic instance buffer for instanced sprites.
+    instance_buffer: wgpu::Buffer,
+    /// Number of instances `instance_buffer` can hold.
+    instance_capacity: usize,
     /// Uniform buffer containing screen resolution.
     uniform_buffer: wgpu::Buffer,
     /// Bind group for uniforms (group 0).
     uniform_bind_group: wgpu::BindGroup,
     /// Layout for texture bind groups (group 1).
     texture_bind_group_layout: wgpu::BindGroupLayout,
+    /// Layout for the SDF parameters bind group (group 2, dynamic offset).
+    sdf_bind_group_layout: wgpu::BindGroupLayout,
+    /// One [`SdfUniforms`] per SDF text batch, `sdf_stride` bytes apart.
+    sdf_buffer: wgpu::Buffer,
+    /// Bind group over `sdf_buffer` (group 2).
+    sdf_bind_group: wgpu::BindGroup,
+    /// Number of SDF batches `sdf_buffer` can hold.
+    sdf_capacity: usize,
+    /// Byte distance between batches in `sdf_buffer`, honoring the device's offset alignment.
+    sdf_stride: u64,
 
     // Per-font bind groups (cached, indexed by FontId)
     font_bind_groups: Vec<Option<wgpu::BindGroup>>,
@@ -250,14 +614,28 @@ pub struct Draw2d {
     pub(crate) sprites: Vec<Sprite>,
     /// Cached bind groups for sprites (indexed by SpriteId).
     sprite_bind_groups: Vec<Option<wgpu::BindGroup>>,
+    /// Full-color sprites drawn for characters no font has a glyph for.
+    color_glyphs: HashMap<char, SpriteId>,
 
     // Current frame vertex batches
-    /// Vertices for solid-color rectangles.
-    colored_vertices: Vec<Vertex2d>,
-    /// Vertices for text, grouped by font.
-    text_batches: Vec<(FontId, Vec<Vertex2d>)>,
-    /// Vertices for sprites, grouped by sprite texture.
-    sprite_batches: Vec<(SpriteId, Vec<Vertex2d>)>,
+    /// Vertices for solid-color shapes, grouped by layer.
+    colored_batches: Vec<(i32, Vec<Vertex2d>)>,
+    /// Vertices for text, grouped by layer, font, and SDF parameters for SDF fonts.
+    text_batches: Vec<(i32, FontId, Option<SdfUniforms>, Vec<Vertex2d>)>,
+    /// Vertices for sprites, grouped by layer and sprite texture.
+    sprite_batches: Vec<(i32, SpriteId, Vec<Vertex2d>)>,
+    /// Instances for instanced sprites, grouped by layer and sprite texture.
+    instanced_batches: Vec<(i32, SpriteId, Vec<SpriteInstanceRaw>)>,
+    /// Layer assigned to subsequent draw calls.
+    layer: i32,
+    /// Effects applied to subsequent SDF text.
+    text_effects: TextEffects,
+    /// Coverage gamma for bitmap font glyphs.
+    text_gamma: f32,
+    /// Work submitted by renders so far this frame.
+    rendering_stats: Cell<RenderStats>,
+    /// Work submitted in the last rendered frame.
+    stats: RenderStats,
 }
 
 impl Draw2d {
@@ -272,8 +650,11 @@ impl Draw2d {
     ///
     /// * `gpu` - The GPU context containing the device and surface configuration
     pub fn new(gpu: &GpuContext) -> Self {
-        let device = &gpu.device;
+        Self::with_format(&gpu.device, gpu.config.format)
+    }
 
+    /// Creates a 2D drawing context that renders into targets of the given format.
+    pub(crate) fn with_format(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
         // Create shaders
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Draw2d Shader"),
@@ -294,7 +675,7 @@ impl Draw2d {
                 label: Some("Draw2d Uniform Layout"),
                 entries: &[wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -337,6 +718,28 @@ impl Draw2d {
                 ],
             });
 
+        // SDF parameters bind group layout (group 2)
+        let sdf_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Draw2d SDF Layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: wgpu::BufferSize::new(
+                            std::mem::size_of::<SdfUniforms>() as u64
+                        ),
+                    },
+                    count: None,
+                }],
+            });
+        let sdf_stride = (std::mem::size_of::<SdfUniforms>() as u64)
+            .next_multiple_of(device.limits().min_uniform_buffer_offset_alignment as u64);
+        let (sdf_buffer, sdf_bind_group) =
+            create_sdf_buffer(device, &sdf_bind_group_layout, sdf_stride, 1);
+
         // Pipeline layouts
         let colored_pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -352,6 +755,16 @@ impl Draw2d {
                 push_constant_ranges: &[],
             });
 
+        let sdf_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Draw2d SDF Pipeline Layout"),
+            bind_group_layouts: &[
+                &uniform_bind_group_layout,
+                &texture_bind_group_layout,
+                &sdf_bind_group_layout,
+            ],
+            push_constant_ranges: &[],
+        });
+
         // Blend state for alpha blending
         let blend_state = wgpu::BlendState {
             color: wgpu::BlendComponent {
@@ -380,7 +793,7 @@ impl Draw2d {
                 module: &shader,
                 entry_point: Some("fs_colored"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
+                    format,
                     blend: Some(blend_state),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -410,7 +823,37 @@ impl Draw2d {
                 module: &shader,
                 entry_point: Some("fs_textured"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
+                    format,
+                    blend: Some(blend_state),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // SDF pipeline (for distance field fonts)
+        let sdf_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Draw2d SDF Pipeline"),
+            layout: Some(&sdf_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs"),
+                buffers: &[Vertex2d::LAYOUT],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_sdf"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
                     blend: Some(blend_state),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -440,7 +883,7 @@ impl Draw2d {
                 module: &shader,
                 entry_point: Some("fs_sprite"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
+                    format,
                     blend: Some(blend_state),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -456,28 +899,71 @@ impl Draw2d {
             cache: None,
         });
 
-        // Vertex buffer
-        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Draw2d Vertex Buffer"),
-            size: (MAX_VERTICES * std::mem::size_of::<Vertex2d>()) as u64,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        // Instanced sprite pipeline (one quad per instance, same fragment path as sprites)
+        let instanced_sprite_pipeline =
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Draw2d Instanced Sprite Pipeline"),
+                layout: Some(&textured_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_sprite_instanced"),
+                    buffers: &[SpriteInstanceRaw::LAYOUT],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_sprite"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(blend_state),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            });
+
+        let vertex_buffer = create_vertex_buffer(device, INITIAL_VERTEX_CAPACITY);
+        let instance_buffer = create_instance_buffer(device, INITIAL_INSTANCE_CAPACITY);
 
         Self {
             colored_pipeline,
             textured_pipeline,
+            sdf_pipeline,
             sprite_pipeline,
+            instanced_sprite_pipeline,
             vertex_buffer,
+            vertex_capacity: INITIAL_VERTEX_CAPACITY,
+            instance_buffer,
+            instance_capacity: INITIAL_INSTANCE_CAPACITY,
             uniform_buffer,
             uniform_bind_group,
             texture_bind_group_layout,
+            sdf_bind_group_layout,
+            sdf_buffer,
+            sdf_bind_group,
+            sdf_capacity: 1,
+            sdf_stride,
             font_bind_groups: Vec::new(),
             sprites: Vec::new(),
             sprite_bind_groups: Vec::new(),
-            colored_vertices: Vec::with_capacity(1024),
+            color_glyphs: HashMap::new(),
+            colored_batches: Vec::new(),
             text_batches: Vec::new(),
             sprite_batches: Vec::new(),
+            instanced_batches: Vec::new(),
+            layer: 0,
+            text_effects: TextEffects::default(),
+            text_gamma: DEFAULT_TEXT_GAMMA,
+            rendering_stats: Cell::new(RenderStats::default()),
+            stats: RenderStats::default(),
         }
     }
 
@@ -504,119 +990,671 @@ impl Draw2d {
         self.sprites.get(id.0)
     }
 
+    /// Draws `sprite` in text wherever `ch` appears and no font has a glyph for it.
+    ///
+    /// Font atlases are single-channel masks, so this is how full-color glyphs
+    /// such as emoji are drawn: register an image per character and text
+    /// rendering draws it with the sprite pipeline, one font size tall, sitting
+    /// on the baseline and as wide as its aspect ratio. The text color's alpha
+    /// fades it, but its colors are kept. The text's font and its
+    /// [fallbacks](Assets::load_fallback_font_from_file) are tried first.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let heart = draw2d.add_sprite(Sprite::from_file(&gpu, "emoji/2764.png")?);
+    /// draw2d.add_color_glyph('❤', heart);
+    /// draw2d.text(&assets, font, 10.0, 10.0, "I ❤ Rust", Color::WHITE);
+    /// ```
+    pub fn add_color_glyph(&mut self, ch: char, sprite: SpriteId) {
+        self.color_glyphs.insert(ch, sprite);
+    }
+
     /// Clears all batched draw calls for the new frame.
     ///
     /// Call this at the end of each frame after [`Draw2d::render`] to prepare
-    /// for the next frame's draw calls.
+    /// for the next frame's draw calls. Also resets the layer to `0` and the
+    /// text effects to none, and makes this frame's
+    /// [`render_stats`](Self::render_stats) readable.
     pub fn clear(&mut self) {
-        self.colored_vertices.clear();
+        self.colored_batches.clear();
         self.text_batches.clear();
         self.sprite_batches.clear();
+        self.instanced_batches.clear();
+        self.layer = 0;
+        self.text_effects = TextEffects::default();
+        self.stats = self.rendering_stats.take();
     }
 
-    /// Draws a solid-color rectangle.
+    /// Returns the draw calls and vertices submitted in the last rendered frame.
     ///
-    /// # Arguments
+    /// Updated by [`clear`](Self::clear), so it describes the frame before the
+    /// one being drawn. Only `draw_calls` and `vertices_2d` are counted; an
+    /// instanced sprite counts as its six vertices.
+    pub fn render_stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    /// Sets the layer for subsequent draw calls.
     ///
-    /// * `x`, `y` - Top-left corner position in pixels
-    /// * `w`, `h` - Width and height in pixels
-    /// * `color` - Fill color
-    pub fn rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
-        let c = [color.r, color.g, color.b, color.a];
-        let uv = [0.0, 0.0]; // Not used for colored quads
+    /// Higher layers are drawn on top of lower ones. Within a layer, colored
+    /// shapes are drawn first, then text, then sprites. Everything starts on
+    /// layer `0`, and the layer resets to `0` each frame.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// draw2d.set_layer(-1);
+    /// draw2d.sprite(background, 0.0, 0.0, Color::WHITE); // Behind everything
+    /// draw2d.set_layer(1);
+    /// draw2d.rect(10.0, 10.0, 50.0, 50.0, Color::BLACK); // In front of text on layer 0
+    /// ```
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
 
-        self.colored_vertices.extend_from_slice(&[
-            Vertex2d {
-                position: [x, y],
-                uv,
-                color: c,
-            },
-            Vertex2d {
-                position: [x + w, y],
-                uv,
-                color: c,
-            },
-            Vertex2d {
-                position: [x, y + h],
-                uv,
-                color: c,
-            },
-            Vertex2d {
-                position: [x + w, y],
-                uv,
-                color: c,
-            },
-            Vertex2d {
-                position: [x + w, y + h],
-                uv,
-                color: c,
-            },
-            Vertex2d {
-                position: [x, y + h],
-                uv,
-                color: c,
-            },
-        ]);
+    /// Returns the layer used by subsequent draw calls.
+    pub fn layer(&self) -> i32 {
+        self.layer
     }
 
-    /// Draws text at the given position.
+    /// Sets the outline and glow for subsequent text in SDF fonts.
     ///
-    /// Text is rendered using the specified font from the asset system.
-    /// The position specifies the top-left corner of the text bounding box.
+    /// Fonts loaded with [`Assets::default_font_sdf`] or
+    /// [`Assets::load_font_sdf_from_file`] use these; regular fonts ignore them.
+    /// Effects reset to none each frame.
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `assets` - Asset manager containing loaded fonts
-    /// * `font_id` - ID of the font to use (from [`Assets::load_font`])
-    /// * `x`, `y` - Top-left corner position in pixels
-    /// * `text` - The string to render
-    /// * `color` - Text color
+    /// ```ignore
+    /// draw2d.set_text_effects(TextEffects::new().outline(3.0, Color::BLACK));
+    /// draw2d.text_sized(&assets, title_font, 40.0, 40.0, 96.0, "HOPLITE", Color::WHITE);
+    /// draw2d.set_text_effects(TextEffects::new());
+    /// ```
+    pub fn set_text_effects(&mut self, effects: TextEffects) {
+        self.text_effects = effects;
+    }
+
+    /// Returns the effects applied to subsequent SDF text.
+    pub fn text_effects(&self) -> TextEffects {
+        self.text_effects
+    }
+
+    /// Sets the coverage gamma for text in regular (bitmap) fonts.
     ///
-    /// # Notes
+    /// Glyph edges are drawn with `pow(coverage, 1.0 / gamma)`. Values above
+    /// `1.0` thicken the antialiased edges, which keeps small text from
+    /// looking thin and patchy; `1.0` draws the raw rasterized coverage, and
+    /// values below `1.0` thin the text. The default is [`DEFAULT_TEXT_GAMMA`].
+    /// Values are clamped to at least `0.1`.
     ///
-    /// - Missing glyphs are skipped with a fallback advance
-    /// - Each font is batched separately for efficient rendering
-    pub fn text(
-        &mut self,
-        assets: &Assets,
-        font_id: FontId,
-        x: f32,
-        y: f32,
-        text: &str,
-        color: Color,
-    ) {
-        let Some(font) = assets.font(font_id) else {
-            return;
-        };
+    /// Unlike the layer and text effects, this persists across frames and
+    /// applies to all bitmap text in the frame. SDF fonts antialias
+    /// analytically and ignore it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Heavier small print on a light background
+    /// ctx.draw.set_text_gamma(1.8);
+    /// ```
+    pub fn set_text_gamma(&mut self, gamma: f32) {
+        self.text_gamma = gamma.max(MIN_TEXT_GAMMA);
+    }
 
-        let c = [color.r, color.g, color.b, color.a];
-        let mut cursor_x = x;
-        let baseline_y = y + font.size(); // Offset to baseline
+    /// Returns the coverage gamma for bitmap font text.
+    pub fn text_gamma(&self) -> f32 {
+        self.text_gamma
+    }
 
-        // Find or create batch for this font
-        let batch_idx = self
-            .text_batches
+    /// Returns the colored vertex batch for the current layer.
+    fn colored_batch(&mut self) -> &mut Vec<Vertex2d> {
+        let layer = self.layer;
+        let idx = match self.colored_batches.iter().position(|(l, _)| *l == layer) {
+            Some(idx) => idx,
+            None => {
+                self.colored_batches.push((layer, Vec::with_capacity(1024)));
+                self.colored_batches.len() - 1
+            }
+        };
+        &mut self.colored_batches[idx].1
+    }
+
+    /// Returns the index of the text batch for this font and SDF parameters on
+    /// the current layer.
+    fn text_batch_index(&mut self, font_id: FontId, sdf: Option<SdfUniforms>) -> usize {
+        let layer = self.layer;
+        self.text_batches
             .iter()
-            .position(|(id, _)| *id == font_id)
+            .position(|(l, id, s, _)| *l == layer && *id == font_id && *s == sdf)
             .unwrap_or_else(|| {
-                self.text_batches.push((font_id, Vec::new()));
+                self.text_batches.push((layer, font_id, sdf, Vec::new()));
                 self.text_batches.len() - 1
-            });
+            })
+    }
 
-        for ch in text.chars() {
-            let Some(glyph) = font.glyph(ch) else {
-                cursor_x += font.size() * 0.5; // Fallback advance for missing glyphs
-                continue;
-            };
+    /// Returns the index of the sprite batch for this sprite on the current layer.
+    fn sprite_batch_index(&mut self, sprite_id: SpriteId) -> usize {
+        let layer = self.layer;
+        self.sprite_batches
+            .iter()
+            .position(|(l, id, _)| *l == layer && *id == sprite_id)
+            .unwrap_or_else(|| {
+                self.sprite_batches.push((layer, sprite_id, Vec::new()));
+                self.sprite_batches.len() - 1
+            })
+    }
 
-            if glyph.width > 0 && glyph.height > 0 {
-                let gx = cursor_x + glyph.offset_x;
-                // Y offset: fontdue's ymin is distance from baseline to top of glyph
-                // We need to go down from baseline, then up by the glyph height
-                let gy = baseline_y - glyph.offset_y - glyph.height as f32;
+    /// Draws a solid-color rectangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`, `y` - Top-left corner position in pixels
+    /// * `w`, `h` - Width and height in pixels
+    /// * `color` - Fill color
+    pub fn rect(&mut self, x: f32, y: f32, w: f32, h: f32, color: Color) {
+        self.colored_quad([x, y], [x + w, y], [x, y + h], [x + w, y + h], color);
+    }
+
+    /// Draws a rectangle with a vertical gradient from `top` to `bottom`.
+    ///
+    /// The colors are interpolated by the GPU across the quad, so a gradient
+    /// costs the same as a solid [`Draw2d::rect`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Sky backdrop
+    /// draw2d.rect_gradient(0.0, 0.0, w, h, Color::hex("#1a2a6c")?, Color::hex("#fdbb2d")?);
+    /// ```
+    pub fn rect_gradient(&mut self, x: f32, y: f32, w: f32, h: f32, top: Color, bottom: Color) {
+        self.rect_gradient_corners(x, y, w, h, [top, top, bottom, bottom]);
+    }
+
+    /// Draws a rectangle with a color at each corner, in the order top-left,
+    /// top-right, bottom-left, bottom-right.
+    ///
+    /// Use `[left, right, left, right]` for a horizontal gradient. The quad is
+    /// drawn as two triangles split along the top-right to bottom-left
+    /// diagonal, so four unrelated colors blend per triangle rather than
+    /// bilinearly.
+    pub fn rect_gradient_corners(&mut self, x: f32, y: f32, w: f32, h: f32, colors: [Color; 4]) {
+        self.gradient_quad([[x, y], [x + w, y], [x, y + h], [x + w, y + h]], colors);
+    }
+
+    /// Draws a solid-color rectangle with rounded corners.
+    ///
+    /// The radius is clamped to half the shorter side, and a radius of zero
+    /// draws a regular [`Draw2d::rect`].
+    ///
+    /// # Arguments
+    ///
+    /// * `x`, `y` - Top-left corner position in pixels
+    /// * `w`, `h` - Width and height in pixels
+    /// * `radius` - Corner radius in pixels
+    /// * `color` - Fill color
+    pub fn rounded_rect(&mut self, x: f32, y: f32, w: f32, h: f32, radius: f32, color: Color) {
+        self.rounded_rect_corners(x, y, w, h, [radius; 4], color);
+    }
+
+    /// Fills a rounded rectangle with per-corner radii.
+    ///
+    /// Radii are ordered top-left, top-right, bottom-right, bottom-left.
+    fn rounded_rect_corners(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        radii: [f32; 4],
+        color: Color,
+    ) {
+        if radii.iter().all(|&r| r <= 0.0) {
+            self.rect(x, y, w, h, color);
+            return;
+        }
+
+        let outline = rounded_outline(x, y, w, h, radii, corner_segments(radii));
+        let c = [color.r, color.g, color.b, color.a];
+        let uv = [0.0, 0.0]; // Not used for colored geometry
+        let vertex = |p: Vec2| Vertex2d {
+            position: p.to_array(),
+            uv,
+            color: c,
+        };
+
+        // The outline is convex, so a fan from the center covers it
+        let center = vertex(Vec2::new(x + w * 0.5, y + h * 0.5));
+        let batch = self.colored_batch();
+        batch.reserve(outline.len() * 3);
+        for (i, &p) in outline.iter().enumerate() {
+            let next = outline[(i + 1) % outline.len()];
+            batch.extend_from_slice(&[center, vertex(p), vertex(next)]);
+        }
+    }
+
+    /// Outlines a rounded rectangle with a border of the given thickness.
+    ///
+    /// The border is drawn inside the rectangle's edges, following the corners.
+    #[allow(clippy::too_many_arguments)]
+    fn rounded_rect_border(
+        &mut self,
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        radius: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        let outer_radii = [radius; 4];
+        let inner_radii = [(radius - thickness).max(0.0); 4];
+        let segments = corner_segments(outer_radii);
+
+        let outer = rounded_outline(x, y, w, h, outer_radii, segments);
+        let inner = rounded_outline(
+            x + thickness,
+            y + thickness,
+            w - thickness * 2.0,
+            h - thickness * 2.0,
+            inner_radii,
+            segments,
+        );
+
+        for i in 0..outer.len() {
+            let next = (i + 1) % outer.len();
+            self.colored_quad(
+                outer[i].to_array(),
+                outer[next].to_array(),
+                inner[i].to_array(),
+                inner[next].to_array(),
+                color,
+            );
+        }
+    }
+
+    /// Draws a straight line segment.
+    ///
+    /// The line is drawn as a quad centered on the segment, so it rotates
+    /// correctly at any angle. Zero-length lines are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `x0`, `y0` - Start point in pixels
+    /// * `x1`, `y1` - End point in pixels
+    /// * `thickness` - Line width in pixels
+    /// * `color` - Line color
+    pub fn line(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, thickness: f32, color: Color) {
+        let start = Vec2::new(x0, y0);
+        let end = Vec2::new(x1, y1);
+        let Some(dir) = (end - start).try_normalize() else {
+            return;
+        };
+
+        // Offset perpendicular to the segment by half the thickness
+        let offset = dir.perp() * (thickness * 0.5);
+        self.colored_quad(
+            (start + offset).to_array(),
+            (end + offset).to_array(),
+            (start - offset).to_array(),
+            (end - offset).to_array(),
+            color,
+        );
+    }
+
+    /// Draws connected line segments through a list of points.
+    ///
+    /// Each segment is drawn with [`Draw2d::line`]. Joints are not mitered,
+    /// so thick polylines show small notches at sharp corners.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Points to connect in order (fewer than 2 draws nothing)
+    /// * `thickness` - Line width in pixels
+    /// * `color` - Line color
+    pub fn polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        for segment in points.windows(2) {
+            let (a, b) = (segment[0], segment[1]);
+            self.line(a.x, a.y, b.x, b.y, thickness, color);
+        }
+    }
+
+    /// Draws a filled circle.
+    ///
+    /// # Arguments
+    ///
+    /// * `cx`, `cy` - Center position in pixels
+    /// * `radius` - Radius in pixels
+    /// * `segments` - Number of edge segments, or `0` to pick one from the radius
+    /// * `color` - Fill color
+    pub fn circle(&mut self, cx: f32, cy: f32, radius: f32, segments: u32, color: Color) {
+        let segments = auto_segments(radius, segments).max(3);
 
-                let gw = glyph.width as f32;
-                let gh = glyph.height as f32;
+        let c = [color.r, color.g, color.b, color.a];
+        let uv = [0.0, 0.0]; // Not used for colored geometry
+        let center = Vertex2d {
+            position: [cx, cy],
+            uv,
+            color: c,
+        };
+        let edge = |i: u32| Vertex2d {
+            position: circle_point(cx, cy, radius, i, segments),
+            uv,
+            color: c,
+        };
+
+        let batch = self.colored_batch();
+        batch.reserve(segments as usize * 3);
+        for i in 0..segments {
+            batch.extend_from_slice(&[center, edge(i), edge(i + 1)]);
+        }
+    }
+
+    /// Draws a ring (a circle with a hole in the middle).
+    ///
+    /// # Arguments
+    ///
+    /// * `cx`, `cy` - Center position in pixels
+    /// * `inner` - Inner radius in pixels
+    /// * `outer` - Outer radius in pixels
+    /// * `segments` - Number of edge segments, or `0` to pick one from the outer radius
+    /// * `color` - Fill color
+    pub fn ring(&mut self, cx: f32, cy: f32, inner: f32, outer: f32, segments: u32, color: Color) {
+        let segments = auto_segments(outer, segments).max(3);
+
+        for i in 0..segments {
+            let inner_a = circle_point(cx, cy, inner, i, segments);
+            let inner_b = circle_point(cx, cy, inner, i + 1, segments);
+            let outer_a = circle_point(cx, cy, outer, i, segments);
+            let outer_b = circle_point(cx, cy, outer, i + 1, segments);
+            self.colored_quad(outer_a, outer_b, inner_a, inner_b, color);
+        }
+    }
+
+    /// Total number of vertices batched so far this frame.
+    fn vertex_count(&self) -> usize {
+        self.colored_batches
+            .iter()
+            .map(|(_, v)| v.len())
+            .sum::<usize>()
+            + self
+                .text_batches
+                .iter()
+                .map(|(_, _, _, v)| v.len())
+                .sum::<usize>()
+            + self
+                .sprite_batches
+                .iter()
+                .map(|(_, _, v)| v.len())
+                .sum::<usize>()
+    }
+
+    /// Pushes a solid-color quad as two triangles.
+    fn colored_quad(
+        &mut self,
+        top_left: [f32; 2],
+        top_right: [f32; 2],
+        bottom_left: [f32; 2],
+        bottom_right: [f32; 2],
+        color: Color,
+    ) {
+        self.gradient_quad([top_left, top_right, bottom_left, bottom_right], [color; 4]);
+    }
+
+    /// Pushes a quad with per-corner colors as two triangles.
+    ///
+    /// Corners are ordered top-left, top-right, bottom-left, bottom-right.
+    fn gradient_quad(&mut self, corners: [[f32; 2]; 4], colors: [Color; 4]) {
+        let uv = [0.0, 0.0]; // Not used for colored quads
+        let vertex = |i: usize| Vertex2d {
+            position: corners[i],
+            uv,
+            color: [colors[i].r, colors[i].g, colors[i].b, colors[i].a],
+        };
+
+        self.colored_batch().extend_from_slice(&[
+            vertex(0),
+            vertex(1),
+            vertex(2),
+            vertex(1),
+            vertex(3),
+            vertex(2),
+        ]);
+    }
+
+    /// Draws text at the given position.
+    ///
+    /// Text is rendered using the specified font from the asset system.
+    /// The position specifies the top-left corner of the text bounding box.
+    ///
+    /// # Arguments
+    ///
+    /// * `assets` - Asset manager containing loaded fonts
+    /// * `font_id` - ID of the font to use (from [`Assets::load_font`])
+    /// * `x`, `y` - Top-left corner position in pixels
+    /// * `text` - The string to render
+    /// * `color` - Text color
+    ///
+    /// # Notes
+    ///
+    /// - Characters the font lacks are drawn from its fallbacks (see
+    ///   [`Assets::load_fallback_font_from_file`]) or [color glyphs](Self::add_color_glyph),
+    ///   and otherwise skipped with a fallback advance
+    /// - `\n` starts a new line, advancing by the font's line height
+    /// - Each font is batched separately for efficient rendering
+    pub fn text(
+        &mut self,
+        assets: &Assets,
+        font_id: FontId,
+        x: f32,
+        y: f32,
+        text: &str,
+        color: Color,
+    ) {
+        let Some(font) = assets.font(font_id) else {
+            return;
+        };
+        self.push_text(
+            assets,
+            font_id,
+            &font,
+            x,
+            y,
+            1.0,
+            text,
+            color,
+            TextFlow::LINE,
+        );
+    }
+
+    /// Draws text with `tracking` extra pixels between characters.
+    ///
+    /// Positive tracking spreads letters apart for airy titles; negative
+    /// tracking tightens them. Otherwise lays out like [`Draw2d::text`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// draw2d.text_spaced(&assets, title_font, 40.0, 40.0, "HOPLITE", Color::WHITE, 12.0);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_spaced(
+        &mut self,
+        assets: &Assets,
+        font_id: FontId,
+        x: f32,
+        y: f32,
+        text: &str,
+        color: Color,
+        tracking: f32,
+    ) {
+        let Some(font) = assets.font(font_id) else {
+            return;
+        };
+        let flow = TextFlow::Horizontal { tracking };
+        self.push_text(assets, font_id, &font, x, y, 1.0, text, color, flow);
+    }
+
+    /// Draws text stacked vertically, one character per line.
+    ///
+    /// Each character is centered in a column one font size wide starting at
+    /// `x`, and the pen moves down by the line height after each one. `\n`
+    /// starts a new column to the right.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Shop sign down the side of the screen
+    /// draw2d.text_vertical(&assets, font, 10.0, 40.0, "OPEN", Color::WHITE);
+    /// ```
+    pub fn text_vertical(
+        &mut self,
+        assets: &Assets,
+        font_id: FontId,
+        x: f32,
+        y: f32,
+        text: &str,
+        color: Color,
+    ) {
+        let Some(font) = assets.font(font_id) else {
+            return;
+        };
+        self.push_text(
+            assets,
+            font_id,
+            &font,
+            x,
+            y,
+            1.0,
+            text,
+            color,
+            TextFlow::Vertical,
+        );
+    }
+
+    /// Draws text scaled to `size` pixels, regardless of the size the font was loaded at.
+    ///
+    /// Signed distance field fonts (see [`Assets::default_font_sdf`]) stay sharp
+    /// at any size and draw the current [`TextEffects`]. Regular fonts are
+    /// scaled as bitmaps and blur when enlarged.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let title = assets.default_font_sdf(&gpu, 48.0);
+    /// draw2d.text_sized(&assets, title, 20.0, 20.0, 120.0, "Big and crisp", Color::WHITE);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_sized(
+        &mut self,
+        assets: &Assets,
+        font_id: FontId,
+        x: f32,
+        y: f32,
+        size: f32,
+        text: &str,
+        color: Color,
+    ) {
+        let Some(font) = assets.font(font_id) else {
+            return;
+        };
+        let scale = size / font.size();
+        self.push_text(
+            assets,
+            font_id,
+            &font,
+            x,
+            y,
+            scale,
+            text,
+            color,
+            TextFlow::LINE,
+        );
+    }
+
+    /// Lays out `text` with glyphs scaled by `scale` into the batch for the font
+    /// drawing each one, moving the pen between glyphs as `flow` says.
+    #[allow(clippy::too_many_arguments)]
+    fn push_text(
+        &mut self,
+        assets: &Assets,
+        font_id: FontId,
+        font: &FontAtlas,
+        x: f32,
+        y: f32,
+        scale: f32,
+        text: &str,
+        color: Color,
+        flow: TextFlow,
+    ) {
+        let c = [color.r, color.g, color.b, color.a];
+        let line_height = font.line_height() * scale;
+        let top_baseline = y + font.size() * scale; // Offset to baseline
+        let mut line_x = x;
+        let mut cursor_x = x;
+        let mut baseline_y = top_baseline;
+        // Batch of each font used so far, since fallbacks batch separately
+        let mut batches: Vec<(FontId, usize)> = Vec::new();
+
+        for ch in text.chars() {
+            if ch == '\n' {
+                match flow {
+                    TextFlow::Horizontal { .. } => baseline_y += line_height,
+                    TextFlow::Vertical => {
+                        line_x += line_height;
+                        baseline_y = top_baseline;
+                    }
+                }
+                cursor_x = line_x;
+                continue;
+            }
+
+            let glyph = self.text_glyph(assets, font_id, ch);
+            let advance = self.advance_of(glyph, font) * scale;
+            if flow == TextFlow::Vertical {
+                cursor_x = line_x + (font.size() * scale - advance) * 0.5;
+            }
+            let step = flow.step(advance, line_height);
+
+            let (glyph_font, atlas, glyph) = match glyph {
+                TextGlyph::Font(id, atlas, glyph) => (id, atlas, glyph),
+                TextGlyph::Color(sprite) => {
+                    let size = font.size() * scale;
+                    let tint = Color::rgba(1.0, 1.0, 1.0, color.a);
+                    self.sprite_rect(sprite, cursor_x, baseline_y - size, advance, size, tint);
+                    cursor_x += step.x;
+                    baseline_y += step.y;
+                    continue;
+                }
+                TextGlyph::Missing => {
+                    cursor_x += step.x;
+                    baseline_y += step.y;
+                    continue;
+                }
+            };
+
+            if glyph.width > 0 && glyph.height > 0 {
+                let batch_idx = match batches.iter().find(|(id, _)| *id == glyph_font) {
+                    Some(&(_, idx)) => idx,
+                    None => {
+                        let sdf = atlas
+                            .sdf_spread()
+                            .map(|spread| SdfUniforms::new(spread, self.text_effects));
+                        let idx = self.text_batch_index(glyph_font, sdf);
+                        batches.push((glyph_font, idx));
+                        idx
+                    }
+                };
+                // Fallback atlases may be rasterized at another size
+                let scale = scale * font.size() / atlas.size();
+
+                let gx = cursor_x + glyph.offset_x * scale;
+                // Y offset: fontdue's ymin is distance from baseline to top of glyph
+                // We need to go down from baseline, then up by the glyph height
+                let gy = baseline_y - (glyph.offset_y + glyph.height as f32) * scale;
+
+                let gw = glyph.width as f32 * scale;
+                let gh = glyph.height as f32 * scale;
 
                 // UV coordinates from atlas
                 let u0 = glyph.uv[0];
@@ -624,7 +1662,7 @@ impl Draw2d {
                 let u1 = u0 + glyph.uv[2];
                 let v1 = v0 + glyph.uv[3];
 
-                self.text_batches[batch_idx].1.extend_from_slice(&[
+                self.text_batches[batch_idx].3.extend_from_slice(&[
                     Vertex2d {
                         position: [gx, gy],
                         uv: [u0, v0],
@@ -658,10 +1696,159 @@ impl Draw2d {
                 ]);
             }
 
-            cursor_x += glyph.advance;
+            cursor_x += step.x;
+            baseline_y += step.y;
         }
     }
 
+    /// Finds what draws `ch` in text using `font_id`: the font, then its
+    /// fallbacks, then a [color glyph](Self::add_color_glyph).
+    fn text_glyph<'a>(&self, assets: &'a Assets, font_id: FontId, ch: char) -> TextGlyph<'a> {
+        if let Some((id, atlas)) = assets.glyph_font(font_id, ch)
+            && let Some(glyph) = atlas.glyph(ch)
+        {
+            return TextGlyph::Font(id, atlas, glyph);
+        }
+        match self.color_glyphs.get(&ch) {
+            Some(&sprite) if self.sprites.get(sprite.0).is_some() => TextGlyph::Color(sprite),
+            _ => TextGlyph::Missing,
+        }
+    }
+
+    /// Returns how far the cursor moves after drawing `glyph`, in pixels at `font`'s size.
+    ///
+    /// Fallback glyphs are scaled to `font`'s size, color glyphs advance by
+    /// their width, and missing characters advance by half the font size.
+    fn advance_of(&self, glyph: TextGlyph, font: &FontAtlas) -> f32 {
+        match glyph {
+            TextGlyph::Font(_, atlas, glyph) => glyph.advance * font.size() / atlas.size(),
+            TextGlyph::Color(sprite) => {
+                let sprite = &self.sprites[sprite.0];
+                font.size() * sprite.width as f32 / sprite.height.max(1) as f32
+            }
+            TextGlyph::Missing => font.size() * 0.5,
+        }
+    }
+
+    /// Returns how far the cursor moves after drawing `ch` in `font`, at its loaded size.
+    fn glyph_advance(&self, assets: &Assets, font_id: FontId, font: &FontAtlas, ch: char) -> f32 {
+        self.advance_of(self.text_glyph(assets, font_id, ch), font)
+    }
+
+    /// Measures the size of a string in pixels without drawing it.
+    ///
+    /// The width sums glyph advances exactly as [`Draw2d::text`] lays them out,
+    /// including the fallback advance for missing glyphs, and is the widest
+    /// line for multiline text. The height is the line height times the number
+    /// of lines. Returns zero if the font isn't loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `assets` - Asset manager containing loaded fonts
+    /// * `font_id` - ID of the font to measure with
+    /// * `text` - The string to measure
+    pub fn measure_text(&self, assets: &Assets, font_id: FontId, text: &str) -> Vec2 {
+        let Some(font) = assets.font(font_id) else {
+            return Vec2::ZERO;
+        };
+
+        let mut width: f32 = 0.0;
+        let mut lines = 0;
+        for line in text.split('\n') {
+            width = width.max(
+                line.chars()
+                    .map(|ch| self.glyph_advance(assets, font_id, &font, ch))
+                    .sum(),
+            );
+            lines += 1;
+        }
+        Vec2::new(width, font.line_height() * lines as f32)
+    }
+
+    /// Draws text aligned horizontally relative to `x`.
+    ///
+    /// With [`TextAlign::Center`] the text is centered on `x`; with
+    /// [`TextAlign::Right`] it ends at `x`. The `y` coordinate is the top of the
+    /// text, as in [`Draw2d::text`]. Each line of multiline text is aligned
+    /// on its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `assets` - Asset manager containing loaded fonts
+    /// * `font_id` - ID of the font to use
+    /// * `x`, `y` - Anchor position in pixels
+    /// * `text` - The string to render
+    /// * `color` - Text color
+    /// * `align` - Which part of the text lands on `x`
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_aligned(
+        &mut self,
+        assets: &Assets,
+        font_id: FontId,
+        x: f32,
+        y: f32,
+        text: &str,
+        color: Color,
+        align: TextAlign,
+    ) {
+        let Some(font) = assets.font(font_id) else {
+            return;
+        };
+
+        for (i, line) in text.split('\n').enumerate() {
+            let width = self.measure_text(assets, font_id, line).x;
+            let line_x = match align {
+                TextAlign::Left => x,
+                TextAlign::Center => x - width * 0.5,
+                TextAlign::Right => x - width,
+            };
+            let line_y = y + i as f32 * font.line_height();
+            self.text(assets, font_id, line_x, line_y, line, color);
+        }
+    }
+
+    /// Draws text word-wrapped to fit within `max_width`.
+    ///
+    /// Lines break on whitespace and at `\n`. Words wider than `max_width` are
+    /// broken mid-word rather than overflowing.
+    ///
+    /// # Arguments
+    ///
+    /// * `assets` - Asset manager containing loaded fonts
+    /// * `font_id` - ID of the font to use
+    /// * `x`, `y` - Top-left corner position in pixels
+    /// * `max_width` - Maximum line width in pixels
+    /// * `text` - The string to render
+    /// * `color` - Text color
+    ///
+    /// # Returns
+    ///
+    /// The total height consumed, for laying out content below the text.
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_wrapped(
+        &mut self,
+        assets: &Assets,
+        font_id: FontId,
+        x: f32,
+        y: f32,
+        max_width: f32,
+        text: &str,
+        color: Color,
+    ) -> f32 {
+        let Some(font) = assets.font(font_id) else {
+            return 0.0;
+        };
+
+        let lines = wrap_text(text, max_width, |ch| {
+            self.glyph_advance(assets, font_id, &font, ch)
+        });
+        for (i, line) in lines.iter().enumerate() {
+            let line_y = y + i as f32 * font.line_height();
+            self.text(assets, font_id, x, line_y, line, color);
+        }
+        lines.len() as f32 * font.line_height()
+    }
+
     /// Draws a sprite at its native size.
     ///
     /// The sprite is drawn at its original pixel dimensions. Use [`Draw2d::sprite_scaled`]
@@ -745,16 +1932,9 @@ impl Draw2d {
         let c = [tint.r, tint.g, tint.b, tint.a];
 
         // Find or create batch for this sprite
-        let batch_idx = self
-            .sprite_batches
-            .iter()
-            .position(|(id, _)| *id == sprite_id)
-            .unwrap_or_else(|| {
-                self.sprite_batches.push((sprite_id, Vec::new()));
-                self.sprite_batches.len() - 1
-            });
+        let batch_idx = self.sprite_batch_index(sprite_id);
 
-        self.sprite_batches[batch_idx].1.extend_from_slice(&[
+        self.sprite_batches[batch_idx].2.extend_from_slice(&[
             Vertex2d {
                 position: [x, y],
                 uv: [u0, v0],
@@ -788,6 +1968,34 @@ impl Draw2d {
         ]);
     }
 
+    /// Draws a sprite as a 9-slice panel stretched over `dest`.
+    ///
+    /// The sprite is cut `border` pixels in from each edge into nine regions.
+    /// Corners are drawn unscaled, edges stretch along their length, and the
+    /// center fills the rest, so the panel resizes without distorting its frame.
+    /// If `dest` is smaller than two borders, the corners shrink to fit.
+    ///
+    /// # Arguments
+    ///
+    /// * `sprite_id` - ID of the sprite (from [`Draw2d::add_sprite`])
+    /// * `dest` - Destination rectangle in pixels
+    /// * `border` - Width of the frame in sprite pixels
+    /// * `tint` - Color multiplier (use [`Color::WHITE`] for no tinting)
+    pub fn sprite_nine_slice(&mut self, sprite_id: SpriteId, dest: Rect, border: f32, tint: Color) {
+        let Some(sprite) = self.sprites.get(sprite_id.0) else {
+            return;
+        };
+        let regions = nine_slice_regions(dest, sprite.width as f32, sprite.height as f32, border);
+        for (dst, src) in regions {
+            if dst.width > 0.0 && dst.height > 0.0 {
+                self.sprite_region(
+                    sprite_id, dst.x, dst.y, dst.width, dst.height, src.x, src.y, src.width,
+                    src.height, tint,
+                );
+            }
+        }
+    }
+
     /// Draws a sprite filling a rectangle with full UV range (0,0 to 1,1).
     ///
     /// Internal helper used by [`Draw2d::sprite`] and [`Draw2d::sprite_scaled`].
@@ -795,16 +2003,9 @@ impl Draw2d {
         let c = [tint.r, tint.g, tint.b, tint.a];
 
         // Find or create batch for this sprite
-        let batch_idx = self
-            .sprite_batches
-            .iter()
-            .position(|(id, _)| *id == sprite_id)
-            .unwrap_or_else(|| {
-                self.sprite_batches.push((sprite_id, Vec::new()));
-                self.sprite_batches.len() - 1
-            });
+        let batch_idx = self.sprite_batch_index(sprite_id);
 
-        self.sprite_batches[batch_idx].1.extend_from_slice(&[
+        self.sprite_batches[batch_idx].2.extend_from_slice(&[
             Vertex2d {
                 position: [x, y],
                 uv: [0.0, 0.0],
@@ -838,6 +2039,53 @@ impl Draw2d {
         ]);
     }
 
+    /// Draws many copies of one sprite with a single instanced draw call.
+    ///
+    /// Each [`SpriteInstance`] is centered on its position and can be scaled,
+    /// rotated, and tinted independently. Only one small record per instance
+    /// is uploaded instead of six vertices, which keeps particle effects with
+    /// tens of thousands of sprites cheap. Instances draw after the regular
+    /// sprites of the same layer.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let stars: Vec<SpriteInstance> = (0..10_000)
+    ///     .map(|i| SpriteInstance::new(xs[i], ys[i]).rotation(time + i as f32))
+    ///     .collect();
+    /// draw2d.sprites_instanced(star, &stars);
+    /// ```
+    pub fn sprites_instanced(&mut self, sprite_id: SpriteId, instances: &[SpriteInstance]) {
+        let Some(sprite) = self.sprites.get(sprite_id.0) else {
+            return;
+        };
+        let native = [sprite.width as f32, sprite.height as f32];
+
+        let layer = self.layer;
+        let batch_idx = self
+            .instanced_batches
+            .iter()
+            .position(|(l, id, _)| *l == layer && *id == sprite_id)
+            .unwrap_or_else(|| {
+                self.instanced_batches.push((layer, sprite_id, Vec::new()));
+                self.instanced_batches.len() - 1
+            });
+
+        self.instanced_batches[batch_idx]
+            .2
+            .extend(instances.iter().map(|instance| SpriteInstanceRaw {
+                center: instance.position.to_array(),
+                size: [native[0] * instance.scale, native[1] * instance.scale],
+                rotation: instance.rotation,
+                color: [
+                    instance.color.r,
+                    instance.color.g,
+                    instance.color.b,
+                    instance.color.a,
+                ],
+            }));
+    }
+
     /// Creates a panel builder for drawing bordered UI panels.
     ///
     /// Returns a [`PanelBuilder`] that allows customizing the panel's appearance
@@ -869,6 +2117,7 @@ impl Draw2d {
             border: Some(Color::DEBUG_BORDER),
             title: None,
             title_font: None,
+            corner_radius: 0.0,
         }
     }
 
@@ -940,102 +2189,461 @@ impl Draw2d {
         }
     }
 
+    /// Grows the vertex buffer if this frame batched more vertices than it holds.
+    ///
+    /// The buffer is reallocated to the next power of two, so growth settles
+    /// quickly and never shrinks. The instanced sprite buffer and per-batch
+    /// SDF text parameters grow the same way. Call this after issuing draw calls and before
+    /// [`Draw2d::render`]; the app loop does this automatically.
+    pub fn ensure_vertex_capacity(&mut self, device: &wgpu::Device) {
+        let sdf_needed = self.sdf_batch_count();
+        if sdf_needed > self.sdf_capacity {
+            self.sdf_capacity = sdf_needed.next_power_of_two();
+            (self.sdf_buffer, self.sdf_bind_group) = create_sdf_buffer(
+                device,
+                &self.sdf_bind_group_layout,
+                self.sdf_stride,
+                self.sdf_capacity,
+            );
+        }
+
+        let instances: usize = self.instanced_batches.iter().map(|(_, _, v)| v.len()).sum();
+        if instances > self.instance_capacity {
+            self.instance_capacity = instances.next_power_of_two();
+            self.instance_buffer = create_instance_buffer(device, self.instance_capacity);
+        }
+
+        let needed = self.vertex_count();
+        if needed <= self.vertex_capacity {
+            return;
+        }
+
+        self.vertex_capacity = needed.next_power_of_two();
+        self.vertex_buffer = create_vertex_buffer(device, self.vertex_capacity);
+    }
+
+    /// Number of text batches drawn with the SDF pipeline this frame.
+    fn sdf_batch_count(&self) -> usize {
+        self.text_batches
+            .iter()
+            .filter(|(_, _, sdf, _)| sdf.is_some())
+            .count()
+    }
+
     /// Renders all batched draw calls to the given render pass.
     ///
-    /// This method flushes all accumulated geometry from the current frame:
-    /// 1. Colored rectangles (using the colored pipeline)
+    /// This method flushes all accumulated geometry from the current frame,
+    /// layer by layer from lowest to highest (see [`Draw2d::set_layer`]). Within
+    /// each layer it draws:
+    /// 1. Colored shapes (using the colored pipeline)
     /// 2. Text batches (using the textured pipeline, one draw per font)
     /// 3. Sprite batches (using the sprite pipeline, one draw per sprite texture)
     ///
     /// Call [`Draw2d::clear`] after this to prepare for the next frame.
     ///
+    /// Geometry beyond the vertex buffer's capacity is not drawn, so call
+    /// [`Draw2d::ensure_vertex_capacity`] first when rendering manually.
+    ///
     /// # Arguments
     ///
     /// * `gpu` - The GPU context for buffer uploads
     /// * `render_pass` - The active render pass to draw into
     /// * `_assets` - Asset manager (currently unused but kept for API consistency)
     pub fn render(&self, gpu: &GpuContext, render_pass: &mut wgpu::RenderPass, _assets: &Assets) {
+        self.render_batches(
+            &gpu.queue,
+            [gpu.width() as f32, gpu.height() as f32],
+            render_pass,
+        );
+    }
+
+    /// Uploads and draws all batches for a target of the given resolution.
+    fn render_batches(
+        &self,
+        queue: &wgpu::Queue,
+        resolution: [f32; 2],
+        render_pass: &mut wgpu::RenderPass,
+    ) {
         // Update uniforms
         let uniforms = Draw2dUniforms {
-            resolution: [gpu.width() as f32, gpu.height() as f32],
-            _padding: [0.0, 0.0],
+            resolution,
+            text_gamma: self.text_gamma,
+            _padding: 0.0,
         };
-        gpu.queue
-            .write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
-
-        // Render colored quads
-        if !self.colored_vertices.is_empty() {
-            gpu.queue.write_buffer(
-                &self.vertex_buffer,
-                0,
-                bytemuck::cast_slice(&self.colored_vertices),
-            );
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
 
-            render_pass.set_pipeline(&self.colored_pipeline);
-            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.draw(0..self.colored_vertices.len() as u32, 0..1);
-        }
+        // Draw layers bottom to top, keeping colored -> text -> sprite within each
+        let mut layers: Vec<i32> = self
+            .colored_batches
+            .iter()
+            .map(|(layer, _)| *layer)
+            .chain(self.text_batches.iter().map(|(layer, ..)| *layer))
+            .chain(self.sprite_batches.iter().map(|(layer, _, _)| *layer))
+            .chain(self.instanced_batches.iter().map(|(layer, _, _)| *layer))
+            .collect();
+        layers.sort_unstable();
+        layers.dedup();
+
+        let mut offset = 0;
+        let mut instance_offset = 0;
+        let mut sdf_index = 0;
+        let mut stats = RenderStats::default();
+        for layer in layers {
+            for (_, vertices) in self.colored_batches.iter().filter(|(l, _)| *l == layer) {
+                self.draw_batch(
+                    queue,
+                    render_pass,
+                    &self.colored_pipeline,
+                    None,
+                    vertices,
+                    &mut offset,
+                    &mut stats,
+                );
+            }
 
-        // Render text batches
-        let mut offset = self.colored_vertices.len();
-        for (font_id, vertices) in &self.text_batches {
-            if vertices.is_empty() {
-                continue;
+            for (_, font_id, sdf, vertices) in
+                self.text_batches.iter().filter(|(l, ..)| *l == layer)
+            {
+                let Some(bind_group) = self
+                    .font_bind_groups
+                    .get(font_id.0)
+                    .and_then(|bg| bg.as_ref())
+                else {
+                    continue;
+                };
+
+                let pipeline = match sdf {
+                    Some(params) if sdf_index < self.sdf_capacity => {
+                        let params_offset = sdf_index as u64 * self.sdf_stride;
+                        queue.write_buffer(
+                            &self.sdf_buffer,
+                            params_offset,
+                            bytemuck::cast_slice(&[*params]),
+                        );
+                        render_pass.set_bind_group(
+                            2,
+                            &self.sdf_bind_group,
+                            &[params_offset as u32],
+                        );
+                        sdf_index += 1;
+                        &self.sdf_pipeline
+                    }
+                    // Parameters beyond the buffer's capacity are dropped, like vertices
+                    Some(_) => continue,
+                    None => &self.textured_pipeline,
+                };
+                self.draw_batch(
+                    queue,
+                    render_pass,
+                    pipeline,
+                    Some(bind_group),
+                    vertices,
+                    &mut offset,
+                    &mut stats,
+                );
             }
 
-            let Some(bind_group) = self
-                .font_bind_groups
-                .get(font_id.0)
-                .and_then(|bg| bg.as_ref())
-            else {
-                continue;
-            };
+            for (_, sprite_id, vertices) in self.sprite_batches.iter().filter(|(l, ..)| *l == layer)
+            {
+                let Some(bind_group) = self
+                    .sprite_bind_groups
+                    .get(sprite_id.0)
+                    .and_then(|bg| bg.as_ref())
+                else {
+                    continue;
+                };
+                self.draw_batch(
+                    queue,
+                    render_pass,
+                    &self.sprite_pipeline,
+                    Some(bind_group),
+                    vertices,
+                    &mut offset,
+                    &mut stats,
+                );
+            }
 
-            gpu.queue.write_buffer(
-                &self.vertex_buffer,
-                (offset * std::mem::size_of::<Vertex2d>()) as u64,
-                bytemuck::cast_slice(vertices),
-            );
+            for (_, sprite_id, instances) in
+                self.instanced_batches.iter().filter(|(l, ..)| *l == layer)
+            {
+                let Some(bind_group) = self
+                    .sprite_bind_groups
+                    .get(sprite_id.0)
+                    .and_then(|bg| bg.as_ref())
+                else {
+                    continue;
+                };
+                self.draw_instanced_batch(
+                    queue,
+                    render_pass,
+                    bind_group,
+                    instances,
+                    &mut instance_offset,
+                    &mut stats,
+                );
+            }
+        }
+        self.rendering_stats.set(self.rendering_stats.get() + stats);
+    }
+
+    /// Uploads one batch of sprite instances at `offset` in the instance buffer and draws
+    /// it, adding the draw to `stats`.
+    ///
+    /// Instances past the buffer's capacity are dropped.
+    fn draw_instanced_batch(
+        &self,
+        queue: &wgpu::Queue,
+        render_pass: &mut wgpu::RenderPass,
+        bind_group: &wgpu::BindGroup,
+        instances: &[SpriteInstanceRaw],
+        offset: &mut usize,
+        stats: &mut RenderStats,
+    ) {
+        let instances = &instances[..instances.len().min(self.instance_capacity - *offset)];
+        if instances.is_empty() {
+            return;
+        }
+
+        queue.write_buffer(
+            &self.instance_buffer,
+            (*offset * std::mem::size_of::<SpriteInstanceRaw>()) as u64,
+            bytemuck::cast_slice(instances),
+        );
+
+        render_pass.set_pipeline(&self.instanced_sprite_pipeline);
+        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        render_pass.set_bind_group(1, bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+        render_pass.draw(0..6, *offset as u32..(*offset + instances.len()) as u32);
+        stats.draw_calls += 1;
+        stats.vertices_2d += 6 * instances.len();
+
+        *offset += instances.len();
+    }
+
+    /// Uploads one batch at `offset` in the vertex buffer and draws it, adding the draw to `stats`.
+    ///
+    /// Vertices past the buffer's capacity are dropped.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_batch(
+        &self,
+        queue: &wgpu::Queue,
+        render_pass: &mut wgpu::RenderPass,
+        pipeline: &wgpu::RenderPipeline,
+        texture_bind_group: Option<&wgpu::BindGroup>,
+        vertices: &[Vertex2d],
+        offset: &mut usize,
+        stats: &mut RenderStats,
+    ) {
+        let vertices = &vertices[..vertices.len().min(self.vertex_capacity - *offset)];
+        if vertices.is_empty() {
+            return;
+        }
 
-            render_pass.set_pipeline(&self.textured_pipeline);
-            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        queue.write_buffer(
+            &self.vertex_buffer,
+            (*offset * std::mem::size_of::<Vertex2d>()) as u64,
+            bytemuck::cast_slice(vertices),
+        );
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        if let Some(bind_group) = texture_bind_group {
             render_pass.set_bind_group(1, bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.draw(offset as u32..(offset + vertices.len()) as u32, 0..1);
+        }
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw(*offset as u32..(*offset + vertices.len()) as u32, 0..1);
+        stats.draw_calls += 1;
+        stats.vertices_2d += vertices.len();
+
+        *offset += vertices.len();
+    }
+}
+
+/// How text layout moves the pen from one glyph to the next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TextFlow {
+    /// Left to right, with `tracking` extra pixels after each glyph.
+    Horizontal { tracking: f32 },
+    /// Top to bottom, one line height per glyph.
+    Vertical,
+}
+
+impl TextFlow {
+    /// Plain left-to-right text.
+    const LINE: Self = Self::Horizontal { tracking: 0.0 };
 
-            offset += vertices.len();
+    /// Pen movement after a glyph that advances `advance` pixels.
+    fn step(self, advance: f32, line_height: f32) -> Vec2 {
+        match self {
+            Self::Horizontal { tracking } => Vec2::new(advance + tracking, 0.0),
+            Self::Vertical => Vec2::new(0.0, line_height),
         }
+    }
+}
+
+/// What draws a character of text.
+#[derive(Clone, Copy)]
+enum TextGlyph<'a> {
+    /// A glyph from the text's font or one of its fallbacks.
+    Font(FontId, &'a FontAtlas, &'a GlyphInfo),
+    /// A sprite registered with [`Draw2d::add_color_glyph`].
+    Color(SpriteId),
+    /// Nothing; the character is skipped.
+    Missing,
+}
 
-        // Render sprite batches
-        for (sprite_id, vertices) in &self.sprite_batches {
-            if vertices.is_empty() {
+/// Breaks `text` into lines no wider than `max_width`.
+///
+/// Words are separated by whitespace and joined with single spaces. Explicit
+/// newlines always break, and words that don't fit on a line of their own are
+/// split between characters.
+fn wrap_text(text: &str, max_width: f32, advance: impl Fn(char) -> f32) -> Vec<String> {
+    let measure = |s: &str| s.chars().map(&advance).sum::<f32>();
+    let space = advance(' ');
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0.0;
+
+        for word in paragraph.split_whitespace() {
+            let word_width = measure(word);
+
+            if !line.is_empty() && line_width + space + word_width <= max_width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += space + word_width;
                 continue;
             }
 
-            let Some(bind_group) = self
-                .sprite_bind_groups
-                .get(sprite_id.0)
-                .and_then(|bg| bg.as_ref())
-            else {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            }
+
+            if word_width <= max_width {
+                line.push_str(word);
+                line_width = word_width;
                 continue;
-            };
+            }
 
-            gpu.queue.write_buffer(
-                &self.vertex_buffer,
-                (offset * std::mem::size_of::<Vertex2d>()) as u64,
-                bytemuck::cast_slice(vertices),
-            );
+            // Hard-break a word that is wider than a whole line
+            for ch in word.chars() {
+                let ch_width = advance(ch);
+                if !line.is_empty() && line_width + ch_width > max_width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0.0;
+                }
+                line.push(ch);
+                line_width += ch_width;
+            }
+        }
 
-            render_pass.set_pipeline(&self.sprite_pipeline);
-            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
-            render_pass.set_bind_group(1, bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-            render_pass.draw(offset as u32..(offset + vertices.len()) as u32, 0..1);
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Creates a vertex buffer holding `capacity` vertices.
+/// Creates the SDF parameters buffer for `capacity` batches and its bind group.
+fn create_sdf_buffer(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    stride: u64,
+    capacity: usize,
+) -> (wgpu::Buffer, wgpu::BindGroup) {
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Draw2d SDF Params"),
+        size: stride * capacity as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Draw2d SDF Bind Group"),
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                buffer: &buffer,
+                offset: 0,
+                size: wgpu::BufferSize::new(std::mem::size_of::<SdfUniforms>() as u64),
+            }),
+        }],
+    });
+    (buffer, bind_group)
+}
+
+fn create_instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Draw2d Instance Buffer"),
+        size: (capacity * std::mem::size_of::<SpriteInstanceRaw>()) as u64,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+fn create_vertex_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Draw2d Vertex Buffer"),
+        size: (capacity * std::mem::size_of::<Vertex2d>()) as u64,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Picks a segment count for a circle, deriving one from the radius if `requested` is 0.
+///
+/// Roughly one segment per pixel of radius keeps edges smooth without
+/// wasting vertices on small circles.
+fn auto_segments(radius: f32, requested: u32) -> u32 {
+    if requested > 0 {
+        requested
+    } else {
+        (radius.ceil() as u32).clamp(12, 128)
+    }
+}
+
+/// Picks a segment count for each rounded corner from the largest radius.
+fn corner_segments(radii: [f32; 4]) -> u32 {
+    let radius = radii.into_iter().fold(0.0, f32::max);
+    ((radius * 0.5).ceil() as u32).clamp(2, 16)
+}
 
-            offset += vertices.len();
+/// Returns the clockwise outline of a rounded rectangle.
+///
+/// Radii are ordered top-left, top-right, bottom-right, bottom-left and are
+/// clamped to half the shorter side. Every corner contributes `segments + 1`
+/// points, so outlines with the same segment count line up point for point.
+fn rounded_outline(x: f32, y: f32, w: f32, h: f32, radii: [f32; 4], segments: u32) -> Vec<Vec2> {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    let max_radius = (w.min(h) * 0.5).max(0.0);
+    let [tl, tr, br, bl] = radii.map(|r| r.clamp(0.0, max_radius));
+
+    // Arc center, radius, and start angle for each corner (y points down)
+    let corners = [
+        (Vec2::new(x + tl, y + tl), tl, PI),
+        (Vec2::new(x + w - tr, y + tr), tr, PI + FRAC_PI_2),
+        (Vec2::new(x + w - br, y + h - br), br, 0.0),
+        (Vec2::new(x + bl, y + h - bl), bl, FRAC_PI_2),
+    ];
+
+    let mut points = Vec::with_capacity(4 * (segments as usize + 1));
+    for (center, radius, start) in corners {
+        for i in 0..=segments {
+            let angle = start + FRAC_PI_2 * i as f32 / segments as f32;
+            points.push(center + Vec2::new(angle.cos(), angle.sin()) * radius);
         }
     }
+    points
+}
+
+/// Returns point `i` of `segments` evenly spaced around a circle.
+fn circle_point(cx: f32, cy: f32, radius: f32, i: u32, segments: u32) -> [f32; 2] {
+    let angle = i as f32 / segments as f32 * std::f32::consts::TAU;
+    [cx + radius * angle.cos(), cy + radius * angle.sin()]
 }
 
 /// Builder for drawing panels with backgrounds, borders, and optional titles.
@@ -1077,6 +2685,8 @@ pub struct PanelBuilder<'a> {
     title: Option<String>,
     /// Font for the title (required if `title` is set).
     title_font: Option<FontId>,
+    /// Corner radius in pixels (0 for sharp corners).
+    corner_radius: f32,
 }
 
 impl<'a> PanelBuilder<'a> {
@@ -1119,6 +2729,14 @@ impl<'a> PanelBuilder<'a> {
         self
     }
 
+    /// Rounds the panel's corners, including the title bar's top corners.
+    ///
+    /// Default: `0.0` (sharp corners)
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = radius;
+        self
+    }
+
     /// Finalizes and draws the panel.
     ///
     /// This consumes the builder and issues draw calls for:
@@ -1133,6 +2751,11 @@ impl<'a> PanelBuilder<'a> {
         let border_width = 1.0;
         let title_height = 22.0;
 
+        if self.corner_radius > 0.0 {
+            self.draw_rounded(assets, border_width, title_height);
+            return;
+        }
+
         // Draw background
         self.draw2d
             .rect(self.x, self.y, self.width, self.height, self.background);
@@ -1178,4 +2801,592 @@ impl<'a> PanelBuilder<'a> {
             );
         }
     }
+
+    /// Draws the panel with rounded corners.
+    fn draw_rounded(self, assets: &Assets, border_width: f32, title_height: f32) {
+        let (x, y, w, h) = (self.x, self.y, self.width, self.height);
+        let radius = self.corner_radius;
+
+        self.draw2d
+            .rounded_rect(x, y, w, h, radius, self.background);
+
+        // Title bar rounds only its top corners to sit flush with the body
+        if let (Some(title_text), Some(font_id)) = (&self.title, self.title_font) {
+            let title_bg = Color::rgba(0.15, 0.15, 0.15, 0.95);
+            let top = radius.min(title_height * 0.5);
+            self.draw2d
+                .rounded_rect_corners(x, y, w, title_height, [top, top, 0.0, 0.0], title_bg);
+            self.draw2d
+                .text(assets, font_id, x + 8.0, y + 4.0, title_text, Color::WHITE);
+        }
+
+        if let Some(border_color) = self.border {
+            self.draw2d
+                .rounded_rect_border(x, y, w, h, radius, border_width, border_color);
+        }
+    }
+}
+
+/// Splits a 9-slice draw into `(destination, source)` rectangle pairs.
+///
+/// Regions are ordered row by row from the top-left corner. The source border
+/// is clamped to half the texture and the destination border to half of `dest`.
+fn nine_slice_regions(dest: Rect, tex_w: f32, tex_h: f32, border: f32) -> [(Rect, Rect); 9] {
+    let src_border = border.max(0.0).min(tex_w / 2.0).min(tex_h / 2.0);
+    let dst_border = src_border.min(dest.width / 2.0).min(dest.height / 2.0);
+
+    // Column and row edges: start, inner start, inner end, end
+    let src_xs = [0.0, src_border, tex_w - src_border, tex_w];
+    let src_ys = [0.0, src_border, tex_h - src_border, tex_h];
+    let dst_xs = [
+        dest.x,
+        dest.x + dst_border,
+        dest.x + dest.width - dst_border,
+        dest.x + dest.width,
+    ];
+    let dst_ys = [
+        dest.y,
+        dest.y + dst_border,
+        dest.y + dest.height - dst_border,
+        dest.y + dest.height,
+    ];
+
+    std::array::from_fn(|i| {
+        let (row, col) = (i / 3, i % 3);
+        let span = |edges: &[f32; 4], k: usize| (edges[k], edges[k + 1] - edges[k]);
+        let ((dx, dw), (dy, dh)) = (span(&dst_xs, col), span(&dst_ys, row));
+        let ((sx, sw), (sy, sh)) = (span(&src_xs, col), span(&src_ys, row));
+        (Rect::new(dx, dy, dw, dh), Rect::new(sx, sy, sw, sh))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rect fields as a tuple, since `Rect` has no `PartialEq`.
+    fn parts(rect: Rect) -> (f32, f32, f32, f32) {
+        (rect.x, rect.y, rect.width, rect.height)
+    }
+
+    #[test]
+    fn nine_slice_keeps_corners_and_stretches_edges() {
+        let regions = nine_slice_regions(Rect::new(10.0, 20.0, 200.0, 100.0), 48.0, 48.0, 16.0);
+
+        // Top-left corner: unscaled
+        assert_eq!(parts(regions[0].0), (10.0, 20.0, 16.0, 16.0));
+        assert_eq!(parts(regions[0].1), (0.0, 0.0, 16.0, 16.0));
+        // Top edge: stretched horizontally only
+        assert_eq!(parts(regions[1].0), (26.0, 20.0, 168.0, 16.0));
+        assert_eq!(parts(regions[1].1), (16.0, 0.0, 16.0, 16.0));
+        // Center fills the rest
+        assert_eq!(parts(regions[4].0), (26.0, 36.0, 168.0, 68.0));
+        assert_eq!(parts(regions[4].1), (16.0, 16.0, 16.0, 16.0));
+        // Bottom-right corner: unscaled
+        assert_eq!(parts(regions[8].0), (194.0, 104.0, 16.0, 16.0));
+        assert_eq!(parts(regions[8].1), (32.0, 32.0, 16.0, 16.0));
+    }
+
+    #[test]
+    fn nine_slice_shrinks_corners_to_fit_small_rects() {
+        let regions = nine_slice_regions(Rect::new(0.0, 0.0, 20.0, 100.0), 48.0, 48.0, 16.0);
+        assert_eq!(parts(regions[0].0), (0.0, 0.0, 10.0, 10.0));
+        assert_eq!(regions[1].0.width, 0.0);
+        assert_eq!(parts(regions[2].0), (10.0, 0.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn hex_parses_all_lengths() {
+        assert_eq!(
+            Color::hex("#1a2b3c").unwrap().to_u8(),
+            [0x1a, 0x2b, 0x3c, 0xff]
+        );
+        assert_eq!(
+            Color::hex("1a2b3c80").unwrap().to_u8(),
+            [0x1a, 0x2b, 0x3c, 0x80]
+        );
+        assert_eq!(
+            Color::hex("#f80").unwrap().to_u8(),
+            [0xff, 0x88, 0x00, 0xff]
+        );
+        assert_eq!(
+            Color::hex("#f808").unwrap().to_u8(),
+            [0xff, 0x88, 0x00, 0x88]
+        );
+        assert_eq!(Color::hex("#1a2b3c80"), Ok(Color::hex_u32(0x1a2b3c80)));
+    }
+
+    #[test]
+    fn hex_rejects_malformed_strings() {
+        for bad in ["", "#", "#12345", "#gg0000", "#1a2b3c4d5e", "#ééé"] {
+            assert!(Color::hex(bad).is_err(), "{bad:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn u8_round_trips() {
+        for rgba in [[0, 0, 0, 0], [255, 255, 255, 255], [12, 34, 56, 78]] {
+            let [r, g, b, a] = rgba;
+            assert_eq!(Color::from_u8(r, g, b, a).to_u8(), rgba);
+        }
+        assert_eq!(Color::rgba(2.0, -1.0, 0.5, 1.0).to_u8(), [255, 0, 128, 255]);
+    }
+
+    #[test]
+    fn hsv_matches_primaries_and_clamps() {
+        assert_eq!(Color::hsv(0.0, 1.0, 1.0).to_u8(), [255, 0, 0, 255]);
+        assert_eq!(Color::hsv(120.0, 1.0, 1.0).to_u8(), [0, 255, 0, 255]);
+        assert_eq!(Color::hsv(-120.0, 1.0, 1.0).to_u8(), [0, 0, 255, 255]);
+        assert_eq!(Color::hsv(60.0, 2.0, 1.0).to_u8(), [255, 255, 0, 255]);
+        assert_eq!(Color::hsv(300.0, 0.0, 0.5).to_u8(), [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn lerp_interpolates_and_clamps() {
+        let mid = Color::lerp(Color::BLACK, Color::WHITE, 0.5);
+        assert_eq!(mid, Color::rgba(0.5, 0.5, 0.5, 1.0));
+        assert_eq!(Color::lerp(Color::BLACK, Color::WHITE, -1.0), Color::BLACK);
+        assert_eq!(Color::lerp(Color::BLACK, Color::WHITE, 3.0), Color::WHITE);
+    }
+
+    /// Monospace advance of 10 pixels per character.
+    fn mono(_: char) -> f32 {
+        10.0
+    }
+
+    #[test]
+    fn text_flow_steps_across_or_down() {
+        assert_eq!(TextFlow::LINE.step(10.0, 24.0), Vec2::new(10.0, 0.0));
+        assert_eq!(
+            TextFlow::Horizontal { tracking: -2.0 }.step(10.0, 24.0),
+            Vec2::new(8.0, 0.0)
+        );
+        assert_eq!(TextFlow::Vertical.step(10.0, 24.0), Vec2::new(0.0, 24.0));
+    }
+
+    #[test]
+    fn wrap_text_breaks_on_whitespace() {
+        let lines = wrap_text("the quick brown fox", 100.0, mono);
+        assert_eq!(lines, ["the quick", "brown fox"]);
+    }
+
+    #[test]
+    fn wrap_text_keeps_explicit_newlines() {
+        let lines = wrap_text("one\n\ntwo", 100.0, mono);
+        assert_eq!(lines, ["one", "", "two"]);
+    }
+
+    #[test]
+    fn wrap_text_hard_breaks_long_words() {
+        let lines = wrap_text("a abcdefghijkl b", 50.0, mono);
+        assert_eq!(lines, ["a", "abcde", "fghij", "kl b"]);
+    }
+
+    #[test]
+    fn rounded_outline_stays_inside_bounds() {
+        let outline = rounded_outline(10.0, 20.0, 100.0, 40.0, [8.0, 8.0, 0.0, 50.0], 4);
+        assert_eq!(outline.len(), 4 * 5);
+
+        for p in &outline {
+            assert!(p.x >= 10.0 - 1e-4 && p.x <= 110.0 + 1e-4, "{p}");
+            assert!(p.y >= 20.0 - 1e-4 && p.y <= 60.0 + 1e-4, "{p}");
+        }
+
+        // Sharp bottom-right corner collapses to the rect corner
+        assert!(
+            outline[10..15]
+                .iter()
+                .all(|p| p.distance(Vec2::new(110.0, 60.0)) < 1e-4)
+        );
+        // Oversized bottom-left radius is clamped to half the height
+        assert!(outline[15].distance(Vec2::new(30.0, 60.0)) < 1e-4);
+    }
+
+    #[test]
+    fn auto_segments_scale_with_radius() {
+        assert_eq!(auto_segments(50.0, 7), 7);
+        assert_eq!(auto_segments(2.0, 0), 12);
+        assert_eq!(auto_segments(40.0, 0), 40);
+        assert_eq!(auto_segments(5000.0, 0), 128);
+    }
+
+    /// Renders `draw` into an RGBA8 target cleared to black and reads back its pixels.
+    fn render_pixels(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        draw: &Draw2d,
+        width: u32,
+        height: u32,
+    ) -> Vec<u8> {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let target = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            draw.render_batches(queue, [width as f32, height as f32], &mut pass);
+        }
+        queue.submit([encoder.finish()]);
+
+        crate::screenshot::read_texture_rgba(device, queue, &target)
+            .expect("Rgba8Unorm targets can be read back")
+            .into_raw()
+    }
+
+    #[test]
+    fn vertex_buffer_grows_to_fit_50k_vertices() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        // One 1x1 rect per pixel: 128 * 66 * 6 = 50,688 vertices
+        const WIDTH: u32 = 128;
+        const HEIGHT: u32 = 66;
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+
+        let mut draw = Draw2d::with_format(&gpu.device, format);
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                draw.rect(x as f32, y as f32, 1.0, 1.0, Color::WHITE);
+            }
+        }
+        assert!(draw.vertex_count() > 50_000);
+
+        draw.ensure_vertex_capacity(&gpu.device);
+        assert!(draw.vertex_capacity >= draw.vertex_count());
+        assert!(draw.vertex_capacity.is_power_of_two());
+
+        let pixels = render_pixels(&gpu.device, &gpu.queue, &draw, WIDTH, HEIGHT);
+
+        // Every pixel, including those covered by the last vertices, is white
+        let unlit = pixels.chunks(4).filter(|p| p[0] != 255).count();
+        assert_eq!(unlit, 0, "{unlit} pixels were not drawn");
+    }
+
+    #[test]
+    fn text_falls_back_to_other_fonts_then_color_glyphs() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+        let mut assets = Assets::new();
+        let font = assets.default_font(&gpu, 16.0);
+        let accents = assets
+            .load_fallback_font_from_file(&gpu, font, "src/fonts/JetBrainsMono-Regular.ttf", ['é'])
+            .unwrap();
+        let mut draw = Draw2d::with_format(&gpu.device, wgpu::TextureFormat::Rgba8Unorm);
+        let wide = draw.add_sprite(Sprite::from_rgba(&gpu, &[255; 8], 2, 1, "star"));
+        draw.add_color_glyph('★', wide);
+
+        // Monospace, so fallback glyphs advance like the font's own
+        let advance = draw.measure_text(&assets, font, "a").x;
+        assert_eq!(draw.measure_text(&assets, font, "aé").x, 2.0 * advance);
+        // Color glyphs are one font size tall and as wide as their aspect ratio
+        assert_eq!(draw.measure_text(&assets, font, "★").x, 32.0);
+
+        draw.text(&assets, font, 0.0, 0.0, "aé★", Color::WHITE);
+        let fonts: Vec<FontId> = draw.text_batches.iter().map(|(_, id, ..)| *id).collect();
+        assert_eq!(fonts, [font, accents]);
+        assert_eq!(draw.sprite_batches.len(), 1);
+        assert_eq!(draw.sprite_batches[0].2.len(), 6);
+    }
+
+    #[test]
+    fn higher_layers_draw_on_top() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        let mut draw = Draw2d::with_format(&gpu.device, wgpu::TextureFormat::Rgba8Unorm);
+        draw.set_layer(1);
+        draw.rect(0.0, 0.0, 4.0, 4.0, Color::rgb(1.0, 0.0, 0.0));
+        draw.set_layer(0);
+        draw.rect(0.0, 0.0, 4.0, 4.0, Color::rgb(0.0, 0.0, 1.0));
+        assert_eq!(draw.layer(), 0);
+
+        let pixels = render_pixels(&gpu.device, &gpu.queue, &draw, 4, 4);
+        assert_eq!(&pixels[..4], &[255, 0, 0, 255]);
+
+        draw.clear();
+        assert_eq!(draw.layer(), 0);
+    }
+
+    #[test]
+    fn instanced_sprites_are_centered_scaled_and_rotated() {
+        use wgpu::util::DeviceExt;
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &[255; 64],
+        );
+        let sprite = Sprite {
+            view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            texture,
+            sampler: gpu
+                .device
+                .create_sampler(&wgpu::SamplerDescriptor::default()),
+            width: 4,
+            height: 4,
+        };
+
+        let mut draw = Draw2d::with_format(&gpu.device, wgpu::TextureFormat::Rgba8Unorm);
+        let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &draw.texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&sprite.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sprite.sampler),
+                },
+            ],
+        });
+        let id = draw.add_sprite(sprite);
+        draw.sprite_bind_groups[id.0] = Some(bind_group);
+
+        // An 8x8 red square centered at (8, 8), then the same turned 45 degrees
+        let square = SpriteInstance::new(8.0, 8.0)
+            .scale(2.0)
+            .color(Color::rgb(1.0, 0.0, 0.0));
+        draw.sprites_instanced(id, &[square]);
+        draw.ensure_vertex_capacity(&gpu.device);
+        let pixels = render_pixels(&gpu.device, &gpu.queue, &draw, 16, 16);
+        let red = |pixels: &[u8], x: usize, y: usize| pixels[(y * 16 + x) * 4] > 128;
+        assert!(red(&pixels, 8, 8) && red(&pixels, 4, 4));
+        assert!(!red(&pixels, 8, 3) && !red(&pixels, 1, 1));
+
+        draw.clear();
+        draw.sprites_instanced(id, &[square.rotation(std::f32::consts::FRAC_PI_4)]);
+        let pixels = render_pixels(&gpu.device, &gpu.queue, &draw, 16, 16);
+        assert!(red(&pixels, 8, 8) && red(&pixels, 8, 3));
+        assert!(!red(&pixels, 4, 4));
+    }
+
+    #[test]
+    fn gradient_rect_interpolates_between_edges() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        let mut draw = Draw2d::with_format(&gpu.device, wgpu::TextureFormat::Rgba8Unorm);
+        draw.rect_gradient(
+            0.0,
+            0.0,
+            4.0,
+            16.0,
+            Color::rgb(1.0, 0.0, 0.0),
+            Color::rgb(0.0, 0.0, 1.0),
+        );
+
+        let pixels = render_pixels(&gpu.device, &gpu.queue, &draw, 4, 16);
+        let at = |y: usize| &pixels[y * 16..][..4];
+        assert!(at(0)[0] > 230 && at(0)[2] < 25, "{:?}", at(0));
+        assert!(at(15)[2] > 230 && at(15)[0] < 25, "{:?}", at(15));
+        assert!(at(8)[0].abs_diff(at(8)[2]) < 40, "{:?}", at(8));
+    }
+
+    #[test]
+    fn sdf_text_draws_fill_and_outline_when_scaled_up() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        // An 8x8 square "glyph" with 4 texels of spread, drawn 4x larger
+        use wgpu::util::DeviceExt;
+        let field = crate::assets::signed_distance_field(&[255; 64], 8, 8, 4);
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 16,
+                    height: 16,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &field,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let mut draw = Draw2d::with_format(&gpu.device, wgpu::TextureFormat::Rgba8Unorm);
+        draw.font_bind_groups
+            .push(Some(gpu.device.create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &draw.texture_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(&sampler),
+                        },
+                    ],
+                },
+            )));
+
+        let effects = TextEffects::new().outline(2.0, Color::rgb(1.0, 0.0, 0.0));
+        let sdf = SdfUniforms::new(4.0, effects);
+        let batch = draw.text_batch_index(FontId(0), Some(sdf));
+        let corner = |x: f32, y: f32| Vertex2d {
+            position: [x * 64.0, y * 64.0],
+            uv: [x, y],
+            color: [1.0; 4],
+        };
+        draw.text_batches[batch].3.extend_from_slice(&[
+            corner(0.0, 0.0),
+            corner(1.0, 0.0),
+            corner(0.0, 1.0),
+            corner(1.0, 0.0),
+            corner(1.0, 1.0),
+            corner(0.0, 1.0),
+        ]);
+        draw.ensure_vertex_capacity(&gpu.device);
+
+        let pixels = render_pixels(&gpu.device, &gpu.queue, &draw, 64, 64);
+        let at = |x: usize, y: usize| &pixels[(y * 64 + x) * 4..][..3];
+        assert_eq!(at(32, 32), [255, 255, 255], "fill");
+        assert_eq!(at(12, 32), [255, 0, 0], "outline");
+        assert_eq!(at(2, 32), [0, 0, 0], "outside");
+    }
+
+    #[test]
+    fn text_gamma_lifts_partial_glyph_coverage() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        // A single texel of 25% coverage stretched over the whole target
+        use wgpu::util::DeviceExt;
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &[64],
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = gpu
+            .device
+            .create_sampler(&wgpu::SamplerDescriptor::default());
+
+        let mut draw = Draw2d::with_format(&gpu.device, wgpu::TextureFormat::Rgba8Unorm);
+        draw.font_bind_groups
+            .push(Some(gpu.device.create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &draw.texture_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(&sampler),
+                        },
+                    ],
+                },
+            )));
+        let batch = draw.text_batch_index(FontId(0), None);
+        let corner = |x: f32, y: f32| Vertex2d {
+            position: [x * 4.0, y * 4.0],
+            uv: [x, y],
+            color: [1.0; 4],
+        };
+        draw.text_batches[batch].3.extend_from_slice(&[
+            corner(0.0, 0.0),
+            corner(1.0, 0.0),
+            corner(0.0, 1.0),
+            corner(1.0, 0.0),
+            corner(1.0, 1.0),
+            corner(0.0, 1.0),
+        ]);
+        draw.ensure_vertex_capacity(&gpu.device);
+
+        // White over black shows the final alpha directly
+        let mut red = |gamma: f32| {
+            draw.set_text_gamma(gamma);
+            i32::from(render_pixels(&gpu.device, &gpu.queue, &draw, 4, 4)[0])
+        };
+        assert!((red(1.0) - 64).abs() <= 1, "raw coverage");
+        assert!((red(2.0) - 128).abs() <= 1, "sqrt of coverage");
+
+        draw.set_text_gamma(-3.0);
+        assert_eq!(draw.text_gamma(), MIN_TEXT_GAMMA);
+    }
 }
@@ -55,6 +55,19 @@ pub struct MeshId(pub(crate) usize);
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct TextureId(pub(crate) usize);
 
+/// Type-safe handle to a custom mesh material (WGSL shader) stored in the MeshQueue.
+///
+/// Obtained from [`SetupContext::add_material`](crate::SetupContext::add_material).
+///
+/// # Example
+///
+/// ```ignore
+/// let water: MaterialId = ctx.add_material(include_str!("water.wgsl"));
+/// frame.mesh(plane).material(water).draw();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MaterialId(pub(crate) usize);
+
 // Keep MeshHandle and TextureHandle as aliases for backwards compatibility in ECS contexts
 /// Alias for [`MeshId`] - used in ECS components.
 pub type MeshHandle = MeshId;
@@ -91,6 +104,8 @@ pub struct RenderMesh {
     pub color: Color,
     /// Optional texture. If `None`, uses vertex colors only.
     pub texture: Option<TextureHandle>,
+    /// Optional custom material. If `None`, uses the built-in mesh shader.
+    pub material: Option<MaterialId>,
 }
 
 impl RenderMesh {
@@ -100,6 +115,7 @@ impl RenderMesh {
             mesh,
             color,
             texture: None,
+            material: None,
         }
     }
 
@@ -109,6 +125,13 @@ impl RenderMesh {
             mesh,
             color,
             texture: Some(texture),
+            material: None,
         }
     }
+
+    /// Render this mesh with a custom material instead of the built-in shader.
+    pub fn with_material(mut self, material: MaterialId) -> Self {
+        self.material = Some(material);
+        self
+    }
 }
@@ -50,12 +50,15 @@
 //!     }
 //! "#;
 //!
-//! let effect = EffectPass::new(&gpu, shader);
+//! let effect = EffectPass::new(&gpu, shader)?;
 //! effect.render(&gpu, &mut render_pass, time);
 //! ```
 
-use crate::camera::Camera;
+use crate::camera::{Camera, ProjectionMode};
+use crate::error::{Error, capture_shader_errors};
 use crate::gpu::GpuContext;
+use crate::shader_include;
+use crate::uniforms::{UniformHandle, UserUniforms};
 
 /// Standard uniforms available to all screen-space effect passes.
 ///
@@ -96,7 +99,7 @@ pub struct ScreenUniforms {
 ///     time: f32,
 ///     fov: f32,
 ///     camera_pos: vec3f,
-///     // Note: padding fields are implicit in WGSL
+///     ortho_height: f32, // 0.0 for perspective cameras
 ///     camera_forward: vec3f,
 ///     camera_right: vec3f,
 ///     camera_up: vec3f,
@@ -128,8 +131,9 @@ pub struct WorldUniforms {
     pub fov: f32,
     /// Camera position in world space.
     pub camera_pos: [f32; 3],
-    /// Padding for 16-byte alignment.
-    pub _pad1: f32,
+    /// Visible view height in world units for orthographic cameras,
+    /// or `0.0` for perspective cameras.
+    pub ortho_height: f32,
     /// Camera forward direction (normalized).
     pub camera_forward: [f32; 3],
     /// Padding for 16-byte alignment.
@@ -155,17 +159,20 @@ pub struct WorldUniforms {
 /// - **Screen-space** (via [`new`](Self::new)): Uses `ScreenUniforms` with resolution and time.
 /// - **World-space** (via [`new_world`](Self::new_world)): Uses `WorldUniforms` with full camera data.
 ///
+/// Screen-space passes created with [`with_uniforms`](Self::with_uniforms) also
+/// receive a user uniform struct at `@group(1) @binding(0)`.
+///
 /// # Example
 ///
 /// ```no_run
 /// use hoplite::{GpuContext, EffectPass};
 ///
 /// // Screen-space effect
-/// let vignette = EffectPass::new(&gpu, include_str!("shaders/vignette.wgsl"));
+/// let vignette = EffectPass::new(&gpu, include_str!("shaders/vignette.wgsl"))?;
 /// vignette.render(&gpu, &mut render_pass, time);
 ///
 /// // World-space effect (raymarching)
-/// let raymarch = EffectPass::new_world(&gpu, include_str!("shaders/raymarch.wgsl"));
+/// let raymarch = EffectPass::new_world(&gpu, include_str!("shaders/raymarch.wgsl"))?;
 /// raymarch.render_with_camera(&gpu, &mut render_pass, time, &camera);
 /// ```
 pub struct EffectPass {
@@ -173,6 +180,9 @@ pub struct EffectPass {
     uniform_buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
     uses_camera: bool,
+    format: wgpu::TextureFormat,
+    /// Extra uniforms at `@group(1) @binding(0)` (see [`with_uniforms`](Self::with_uniforms)).
+    user: Option<UserUniforms>,
 }
 
 impl EffectPass {
@@ -182,8 +192,29 @@ impl EffectPass {
     /// define `vs` and `fs` entry points. Use [`render`](Self::render) to draw.
     ///
     /// See the module-level documentation for shader requirements.
-    pub fn new(gpu: &GpuContext, shader_source: &str) -> Self {
-        Self::create(gpu, shader_source, false)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new(gpu: &GpuContext, shader_source: &str) -> Result<Self, Error> {
+        Self::create(gpu, shader_source, false, gpu.config.format, None)
+    }
+
+    /// Create a screen-space effect pass that renders into targets of `format`.
+    ///
+    /// [`new`](Self::new) targets the surface format; use this for passes that
+    /// write into intermediate targets of another format, such as
+    /// [`HDR_FORMAT`](crate::HDR_FORMAT).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new_with_format(
+        gpu: &GpuContext,
+        shader_source: &str,
+        format: wgpu::TextureFormat,
+    ) -> Result<Self, Error> {
+        Self::create(gpu, shader_source, false, format, None)
     }
 
     /// Create a new world-space effect pass from WGSL shader source.
@@ -192,13 +223,88 @@ impl EffectPass {
     /// define `vs` and `fs` entry points. Use [`render_with_camera`](Self::render_with_camera) to draw.
     ///
     /// See `WorldUniforms` for the uniform layout and ray construction example.
-    pub fn new_world(gpu: &GpuContext, shader_source: &str) -> Self {
-        Self::create(gpu, shader_source, true)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new_world(gpu: &GpuContext, shader_source: &str) -> Result<Self, Error> {
+        Self::create(gpu, shader_source, true, gpu.config.format, None)
+    }
+
+    /// Create a world-space effect pass that renders into targets of `format`.
+    ///
+    /// See [`new_with_format`](Self::new_with_format).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new_world_with_format(
+        gpu: &GpuContext,
+        shader_source: &str,
+        format: wgpu::TextureFormat,
+    ) -> Result<Self, Error> {
+        Self::create(gpu, shader_source, true, format, None)
+    }
+
+    /// Create a screen-space effect pass with a user uniform buffer of type `T`.
+    ///
+    /// Besides `ScreenUniforms` at binding 0, the shader declares a struct
+    /// matching `T` at `@group(1) @binding(0)`. The buffer starts zeroed; upload
+    /// values with [`set_uniforms`](Self::set_uniforms), or through
+    /// [`uniform_handle`](Self::uniform_handle) once the pass is in a render graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    /// struct Waves { speed: f32, scale: f32, _pad: [f32; 2] }
+    ///
+    /// // WGSL: @group(1) @binding(0) var<uniform> waves: Waves;
+    /// let pass = EffectPass::with_uniforms::<Waves>(&gpu, WAVES_SHADER)?;
+    /// pass.set_uniforms(&gpu, &Waves { speed: 2.0, scale: 8.0, _pad: [0.0; 2] });
+    /// ```
+    pub fn with_uniforms<T: bytemuck::Pod>(
+        gpu: &GpuContext,
+        shader_source: &str,
+    ) -> Result<Self, Error> {
+        Self::create(
+            gpu,
+            shader_source,
+            false,
+            gpu.config.format,
+            Some(std::mem::size_of::<T>()),
+        )
     }
 
     /// Internal constructor that creates the pipeline and resources.
-    fn create(gpu: &GpuContext, shader_source: &str, uses_camera: bool) -> Self {
+    fn create(
+        gpu: &GpuContext,
+        shader_source: &str,
+        uses_camera: bool,
+        format: wgpu::TextureFormat,
+        user_size: Option<usize>,
+    ) -> Result<Self, Error> {
+        let shader_source = shader_include::expand_source(shader_source)?;
+        capture_shader_errors(&gpu.device, || {
+            Self::build(gpu, &shader_source, uses_camera, format, user_size)
+        })
+    }
+
+    /// Creates the shader module, uniform buffer, and pipeline.
+    fn build(
+        gpu: &GpuContext,
+        shader_source: &str,
+        uses_camera: bool,
+        format: wgpu::TextureFormat,
+        user_size: Option<usize>,
+    ) -> Self {
         let device = &gpu.device;
+        let user = user_size.map(|size| UserUniforms::new(device, size));
 
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Effect Shader"),
@@ -241,9 +347,12 @@ impl EffectPass {
             }],
         });
 
+        let bind_group_layouts: Vec<&wgpu::BindGroupLayout> = std::iter::once(&bind_group_layout)
+            .chain(user.as_ref().map(|user| &user.bind_group_layout))
+            .collect();
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Effect Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
+            bind_group_layouts: &bind_group_layouts,
             push_constant_ranges: &[],
         });
 
@@ -260,7 +369,7 @@ impl EffectPass {
                 module: &shader,
                 entry_point: Some("fs"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
+                    format,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -281,6 +390,34 @@ impl EffectPass {
             uniform_buffer,
             bind_group,
             uses_camera,
+            format,
+            user,
+        }
+    }
+
+    /// Upload this pass's user uniforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pass wasn't created with [`with_uniforms`](Self::with_uniforms),
+    /// or if `T` isn't the size of the type it was created with.
+    pub fn set_uniforms<T: bytemuck::Pod>(&self, gpu: &GpuContext, value: &T) {
+        self.uniform_handle()
+            .expect("This effect has no user uniforms. Create it with with_uniforms().")
+            .set(gpu, value);
+    }
+
+    /// Handle for updating user uniforms after the pass has moved into a render graph.
+    ///
+    /// Returns `None` unless the pass was created with [`with_uniforms`](Self::with_uniforms).
+    pub fn uniform_handle(&self) -> Option<UniformHandle> {
+        self.user.as_ref().map(|user| user.handle.clone())
+    }
+
+    /// Binds the user uniforms, if any, at group 1.
+    fn bind_user_uniforms(&self, render_pass: &mut wgpu::RenderPass) {
+        if let Some(user) = &self.user {
+            render_pass.set_bind_group(1, &user.bind_group, &[]);
         }
     }
 
@@ -299,7 +436,7 @@ impl EffectPass {
         );
 
         let uniforms = ScreenUniforms {
-            resolution: [gpu.width() as f32, gpu.height() as f32],
+            resolution: [gpu.render_width() as f32, gpu.render_height() as f32],
             time,
             _padding: 0.0,
         };
@@ -308,6 +445,7 @@ impl EffectPass {
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
+        self.bind_user_uniforms(render_pass);
         render_pass.draw(0..3, 0..1);
     }
 
@@ -333,11 +471,14 @@ impl EffectPass {
         );
 
         let uniforms = WorldUniforms {
-            resolution: [gpu.width() as f32, gpu.height() as f32],
+            resolution: [gpu.render_width() as f32, gpu.render_height() as f32],
             time,
             fov: camera.fov,
             camera_pos: camera.position.to_array(),
-            _pad1: 0.0,
+            ortho_height: match camera.projection {
+                ProjectionMode::Perspective => 0.0,
+                ProjectionMode::Orthographic { height } => height,
+            },
             camera_forward: camera.forward.to_array(),
             _pad2: 0.0,
             camera_right: camera.right().to_array(),
@@ -350,6 +491,7 @@ impl EffectPass {
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
+        self.bind_user_uniforms(render_pass);
         render_pass.draw(0..3, 0..1);
     }
 
@@ -360,4 +502,9 @@ impl EffectPass {
     pub fn uses_camera(&self) -> bool {
         self.uses_camera
     }
+
+    /// The texture format this pass renders into.
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
 }
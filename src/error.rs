@@ -0,0 +1,109 @@
+//! Unified error type for fallible Hoplite operations.
+//!
+//! Every loader and shader constructor in the crate returns either [`Error`] or an
+//! error type that converts into it, so application code can bubble failures up
+//! with `?` regardless of where they originated.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hoplite::*;
+//!
+//! fn load_assets(ctx: &mut SetupContext) -> Result<(TextureId, SpriteId), hoplite::Error> {
+//!     let brick = ctx.texture_from_file("assets/brick.png")?;
+//!     let icon = ctx.sprite_from_file("assets/icon.png")?;
+//!     Ok((brick, icon))
+//! }
+//! ```
+
+use crate::geometry::GeometryError;
+use crate::gpu::GpuError;
+
+/// Errors that can occur anywhere in Hoplite.
+#[derive(Debug)]
+pub enum Error {
+    /// A file could not be read.
+    Io(std::io::Error),
+    /// An image could not be decoded.
+    Image(image::ImageError),
+    /// A WGSL shader failed to compile or its pipeline failed validation.
+    Shader(String),
+    /// 3D geometry could not be loaded or parsed.
+    Geometry(GeometryError),
+    /// A screenshot could not be requested, or a texture could not be read back.
+    Screenshot(String),
+    /// The GPU could not be initialized.
+    Gpu(GpuError),
+    /// The event loop or window could not be created.
+    Window(String),
+    /// A font could not be parsed or rasterized into an atlas.
+    Font(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "IO error: {}", e),
+            Error::Image(e) => write!(f, "Image error: {}", e),
+            Error::Shader(msg) => write!(f, "Shader error: {}", msg),
+            Error::Geometry(e) => write!(f, "Geometry error: {}", e),
+            Error::Screenshot(msg) => write!(f, "Screenshot error: {}", msg),
+            Error::Gpu(e) => write!(f, "GPU error: {}", e),
+            Error::Window(msg) => write!(f, "Window error: {}", msg),
+            Error::Font(msg) => write!(f, "Font error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Image(e) => Some(e),
+            Error::Geometry(e) => Some(e),
+            Error::Gpu(e) => Some(e),
+            Error::Shader(_) | Error::Screenshot(_) | Error::Window(_) | Error::Font(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(e: image::ImageError) -> Self {
+        Error::Image(e)
+    }
+}
+
+impl From<GeometryError> for Error {
+    fn from(e: GeometryError) -> Self {
+        Error::Geometry(e)
+    }
+}
+
+impl From<GpuError> for Error {
+    fn from(e: GpuError) -> Self {
+        Error::Gpu(e)
+    }
+}
+
+/// Runs `f` inside a wgpu validation error scope.
+///
+/// Shader compilation and pipeline creation report failures through wgpu's
+/// uncaptured error handler, which panics by default. Wrapping them in an error
+/// scope turns those failures into an [`Error::Shader`] instead.
+pub(crate) fn capture_shader_errors<T>(
+    device: &wgpu::Device,
+    f: impl FnOnce() -> T,
+) -> Result<T, Error> {
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let value = f();
+    match pollster::block_on(device.pop_error_scope()) {
+        Some(e) => Err(Error::Shader(e.to_string())),
+        None => Ok(value),
+    }
+}
@@ -0,0 +1,152 @@
+//! A damped follow camera for third-person views.
+//!
+//! Unlike [`OrbitCamera`](crate::OrbitCamera) and
+//! [`FreelookCamera`](crate::FreelookCamera), which are driven by input,
+//! [`CameraFollow`] is driven by a target position. It trails the target at a
+//! fixed offset and eases toward it, so a moving player doesn't jerk the view.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use hoplite::{CameraFollow, Vec3};
+//!
+//! let mut follow = CameraFollow::new()
+//!     .offset([0.0, 3.0, 8.0])
+//!     .smoothing(0.2);
+//!
+//! // In frame loop:
+//! follow.update(player_position, frame.dt);
+//! frame.set_camera(follow.camera());
+//! ```
+
+use glam::Vec3;
+
+use crate::camera::{Camera, ProjectionMode};
+
+/// A camera controller that trails a moving target with exponential damping.
+///
+/// The camera sits at `target + offset` and looks at `target`. Each
+/// [`update`](Self::update) moves `target` toward the followed position,
+/// covering about 63% of the remaining distance every `smoothing` seconds.
+/// The first update snaps straight to the followed position.
+#[derive(Clone, Debug)]
+pub struct CameraFollow {
+    /// Smoothed point the camera looks at.
+    pub target: Vec3,
+    /// Camera position relative to the target.
+    pub offset: Vec3,
+    /// Damping time constant in seconds. `0.0` follows rigidly.
+    pub smoothing: f32,
+    /// Field of view in radians.
+    pub fov: f32,
+    /// Whether `target` has been set by an update yet.
+    tracking: bool,
+}
+
+impl Default for CameraFollow {
+    fn default() -> Self {
+        Self {
+            target: Vec3::ZERO,
+            offset: Vec3::new(0.0, 2.0, 6.0),
+            smoothing: 0.15,
+            fov: std::f32::consts::FRAC_PI_2,
+            tracking: false,
+        }
+    }
+}
+
+impl CameraFollow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the camera position relative to the target.
+    pub fn offset(mut self, offset: impl Into<Vec3>) -> Self {
+        self.offset = offset.into();
+        self
+    }
+
+    /// Set the damping time constant in seconds.
+    pub fn smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing.max(0.0);
+        self
+    }
+
+    /// Set the field of view in degrees.
+    pub fn fov(mut self, fov_degrees: f32) -> Self {
+        self.fov = fov_degrees.to_radians();
+        self
+    }
+
+    /// Jump straight to `target_pos`, skipping the damping.
+    ///
+    /// Use this after teleporting the followed object.
+    pub fn snap_to(&mut self, target_pos: impl Into<Vec3>) {
+        self.target = target_pos.into();
+        self.tracking = true;
+    }
+
+    /// Ease toward `target_pos` by `dt` seconds.
+    pub fn update(&mut self, target_pos: impl Into<Vec3>, dt: f32) {
+        let target_pos = target_pos.into();
+        if !self.tracking {
+            self.snap_to(target_pos);
+            return;
+        }
+        self.target = self.target.lerp(target_pos, damping(self.smoothing, dt));
+    }
+
+    /// Get the current camera state.
+    pub fn camera(&self) -> Camera {
+        let position = self.target + self.offset;
+
+        Camera {
+            position,
+            forward: (self.target - position).normalize_or(Vec3::NEG_Z),
+            up: Vec3::Y,
+            fov: self.fov,
+            near: 0.1,
+            far: 1000.0,
+            projection: ProjectionMode::Perspective,
+        }
+    }
+}
+
+/// Fraction of the remaining distance to cover in `dt` seconds.
+///
+/// Exponential so the result doesn't depend on frame rate.
+pub(crate) fn damping(smoothing: f32, dt: f32) -> f32 {
+    if smoothing <= 0.0 {
+        1.0
+    } else {
+        1.0 - (-dt.max(0.0) / smoothing).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_update_snaps_then_damps() {
+        let mut follow = CameraFollow::new().offset([0.0, 0.0, 5.0]).smoothing(1.0);
+        follow.update([10.0, 0.0, 0.0], 0.016);
+        assert_eq!(follow.target, Vec3::new(10.0, 0.0, 0.0));
+
+        follow.update([20.0, 0.0, 0.0], 1.0);
+        let expected = 20.0 - 10.0 * (-1.0f32).exp();
+        assert!((follow.target.x - expected).abs() < 1e-4);
+
+        let camera = follow.camera();
+        assert_eq!(camera.position, follow.target + Vec3::new(0.0, 0.0, 5.0));
+        assert!((camera.forward - Vec3::NEG_Z).length() < 1e-5);
+    }
+
+    #[test]
+    fn damping_is_frame_rate_independent() {
+        let one_step = damping(0.2, 0.1);
+        let two_steps = 1.0 - (1.0 - damping(0.2, 0.05)).powi(2);
+        assert!((one_step - two_steps).abs() < 1e-6);
+        assert_eq!(damping(0.0, 0.1), 1.0);
+    }
+}
@@ -31,7 +31,10 @@
 use glam::Vec3;
 use winit::keyboard::KeyCode;
 
-use crate::camera::Camera;
+/// Pitch limit short of straight up or down, to avoid gimbal lock.
+const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+use crate::camera::{Camera, ProjectionMode};
 use crate::input::Input;
 
 /// Configuration for seated mode with view constraints.
@@ -176,6 +179,7 @@ impl FreelookMode {
 /// - **A/D**: Strafe left/right
 /// - **Space**: Move up
 /// - **Left Shift**: Move down
+/// - **Left Control**: Sprint, multiplying speed by the sprint multiplier
 /// - **Mouse**: Look around
 ///
 /// # Example
@@ -183,7 +187,10 @@ impl FreelookMode {
 /// ```ignore
 /// let mut camera = FreelookCamera::new()
 ///     .position([0.0, 1.8, 0.0])
-///     .speed(5.0);
+///     .move_speed(5.0)
+///     .sprint_multiplier(3.0)
+///     .mouse_sensitivity(0.002)
+///     .invert_y(true);
 ///
 /// // In frame loop:
 /// camera.update(frame.input, frame.dt);
@@ -213,6 +220,10 @@ pub struct FreelookCamera {
     pub sensitivity: f32,
     /// Movement speed in units per second (Unseated mode only).
     pub speed: f32,
+    /// Factor applied to `speed` while sprinting.
+    pub sprint_multiplier: f32,
+    /// Whether moving the mouse up looks down.
+    pub invert_y: bool,
     /// Near clipping plane.
     pub near: f32,
     /// Far clipping plane.
@@ -229,6 +240,8 @@ impl Default for FreelookCamera {
             mode: FreelookMode::Unseated,
             sensitivity: 0.003,
             speed: 5.0,
+            sprint_multiplier: 2.0,
+            invert_y: false,
             near: 0.1,
             far: 1000.0,
         }
@@ -270,10 +283,7 @@ impl FreelookCamera {
 
     /// Set the initial pitch (vertical angle) in radians.
     pub fn pitch(mut self, pitch: f32) -> Self {
-        self.pitch = pitch.clamp(
-            -std::f32::consts::FRAC_PI_2 + 0.01,
-            std::f32::consts::FRAC_PI_2 - 0.01,
-        );
+        self.pitch = pitch.clamp(-MAX_PITCH, MAX_PITCH);
         self
     }
 
@@ -281,25 +291,58 @@ impl FreelookCamera {
     pub fn looking_toward(mut self, direction: impl Into<Vec3>) -> Self {
         let dir = direction.into().normalize_or_zero();
         self.yaw = dir.x.atan2(-dir.z);
-        self.pitch = dir.y.asin().clamp(
-            -std::f32::consts::FRAC_PI_2 + 0.01,
-            std::f32::consts::FRAC_PI_2 - 0.01,
-        );
+        self.pitch = dir.y.asin().clamp(-MAX_PITCH, MAX_PITCH);
         self
     }
 
-    /// Set mouse sensitivity.
-    pub fn sensitivity(mut self, sensitivity: f32) -> Self {
+    /// Set mouse sensitivity in radians per pixel.
+    pub fn mouse_sensitivity(mut self, sensitivity: f32) -> Self {
         self.sensitivity = sensitivity;
         self
     }
 
-    /// Set movement speed (Unseated mode only).
-    pub fn speed(mut self, speed: f32) -> Self {
+    /// Set mouse sensitivity. Same as [`mouse_sensitivity`](Self::mouse_sensitivity).
+    pub fn sensitivity(self, sensitivity: f32) -> Self {
+        self.mouse_sensitivity(sensitivity)
+    }
+
+    /// Set movement speed in units per second (Unseated mode only).
+    pub fn move_speed(mut self, speed: f32) -> Self {
         self.speed = speed;
         self
     }
 
+    /// Set movement speed. Same as [`move_speed`](Self::move_speed).
+    pub fn speed(self, speed: f32) -> Self {
+        self.move_speed(speed)
+    }
+
+    /// Set how much faster the camera moves while Left Control is held.
+    pub fn sprint_multiplier(mut self, multiplier: f32) -> Self {
+        self.sprint_multiplier = multiplier;
+        self
+    }
+
+    /// Invert vertical mouse look, so moving the mouse up looks down.
+    pub fn invert_y(mut self, invert: bool) -> Self {
+        self.invert_y = invert;
+        self
+    }
+
+    /// Current `(yaw, pitch)` in radians, for saving the view.
+    pub fn angles(&self) -> (f32, f32) {
+        (self.yaw, self.pitch)
+    }
+
+    /// Restore a view saved with [`angles`](Self::angles).
+    ///
+    /// Pitch is clamped short of straight up or down. Seated constraints
+    /// apply on the next [`update`](Self::update).
+    pub fn set_angles(&mut self, yaw: f32, pitch: f32) {
+        self.yaw = yaw;
+        self.pitch = pitch.clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
     /// Set near and far clipping planes.
     pub fn clip_planes(mut self, near: f32, far: f32) -> Self {
         self.near = near;
@@ -373,17 +416,15 @@ impl FreelookCamera {
     pub fn update(&mut self, input: &Input, dt: f32) {
         // Mouse look (works in both modes)
         let delta = input.mouse_delta();
+        let vertical = if self.invert_y { -delta.y } else { delta.y };
         self.yaw += delta.x * self.sensitivity;
-        self.pitch -= delta.y * self.sensitivity;
+        self.pitch -= vertical * self.sensitivity;
 
         // Apply constraints based on mode
         match &self.mode {
             FreelookMode::Unseated => {
                 // Clamp pitch to avoid gimbal lock (no yaw constraint)
-                self.pitch = self.pitch.clamp(
-                    -std::f32::consts::FRAC_PI_2 + 0.01,
-                    std::f32::consts::FRAC_PI_2 - 0.01,
-                );
+                self.pitch = self.pitch.clamp(-MAX_PITCH, MAX_PITCH);
 
                 // WASD movement
                 let forward = self.forward_direction();
@@ -411,7 +452,12 @@ impl FreelookCamera {
                 }
 
                 if velocity.length_squared() > 0.0 {
-                    self.position += velocity.normalize() * self.speed * dt;
+                    let speed = if input.key_down(KeyCode::ControlLeft) {
+                        self.speed * self.sprint_multiplier
+                    } else {
+                        self.speed
+                    };
+                    self.position += velocity.normalize() * speed * dt;
                 }
             }
             FreelookMode::Seated(config) => {
@@ -442,6 +488,61 @@ impl FreelookCamera {
             fov: self.fov,
             near: self.near,
             far: self.far,
+            projection: ProjectionMode::Perspective,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::{ElementState, WindowEvent};
+
+    fn cursor_to(input: &mut Input, x: f64, y: f64) {
+        input.handle_event(&WindowEvent::CursorMoved {
+            device_id: winit::event::DeviceId::dummy(),
+            position: winit::dpi::PhysicalPosition::new(x, y),
+        });
+    }
+
+    #[test]
+    fn sprint_multiplies_move_speed() {
+        let mut camera = FreelookCamera::new().move_speed(4.0).sprint_multiplier(3.0);
+        let mut input = Input::new();
+        input.handle_key(KeyCode::KeyW, ElementState::Pressed);
+        camera.update(&input, 0.5);
+        assert!((camera.position - Vec3::new(0.0, 0.0, -2.0)).length() < 1e-5);
+
+        input.handle_key(KeyCode::ControlLeft, ElementState::Pressed);
+        camera.update(&input, 0.5);
+        assert!((camera.position - Vec3::new(0.0, 0.0, -8.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn invert_y_flips_vertical_look() {
+        let mut input = Input::new();
+        cursor_to(&mut input, 0.0, -10.0);
+
+        let mut normal = FreelookCamera::new().mouse_sensitivity(0.01);
+        normal.update(&input, 0.016);
+        assert!((normal.pitch - 0.1).abs() < 1e-6);
+
+        let mut inverted = FreelookCamera::new().mouse_sensitivity(0.01).invert_y(true);
+        inverted.update(&input, 0.016);
+        assert!((inverted.pitch + 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn angles_round_trip_and_clamp_pitch() {
+        let mut camera = FreelookCamera::new().yaw(1.25).pitch(-0.5);
+        let (yaw, pitch) = camera.angles();
+
+        let mut restored = FreelookCamera::new();
+        restored.set_angles(yaw, pitch);
+        assert_eq!(restored.angles(), (1.25, -0.5));
+        assert_eq!(restored.camera().forward, camera.camera().forward);
+
+        camera.set_angles(0.0, 10.0);
+        assert_eq!(camera.pitch, MAX_PITCH);
+    }
+}
@@ -0,0 +1,99 @@
+//! View frustum extraction and visibility tests.
+//!
+//! A [`Frustum`] is the six clip planes of a view-projection matrix. The mesh
+//! renderer uses it to skip meshes whose bounding spheres are entirely off
+//! screen (see [`SetupContext::set_frustum_culling`](crate::SetupContext::set_frustum_culling)).
+//!
+//! # Example
+//!
+//! ```
+//! use hoplite::{Camera, Frustum, Vec3};
+//!
+//! let camera = Camera::new().at([0.0, 0.0, 5.0]).looking_at([0.0, 0.0, 0.0]);
+//! let view_proj = camera.projection_matrix(16.0 / 9.0, 0.1, 100.0) * camera.view_matrix();
+//! let frustum = Frustum::from_view_projection(&view_proj);
+//!
+//! assert!(frustum.intersects_sphere(Vec3::ZERO, 1.0));
+//! assert!(!frustum.intersects_sphere(Vec3::new(0.0, 0.0, 10.0), 1.0)); // Behind the camera
+//! ```
+
+use glam::{Mat4, Vec3, Vec4};
+
+/// The six planes bounding a camera's visible volume.
+///
+/// Each plane is stored as `(normal, distance)` packed in a [`Vec4`], with the
+/// normal pointing into the frustum and normalized to unit length.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    /// Left, right, bottom, top, near, and far planes.
+    pub planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extracts the frustum planes from a view-projection matrix.
+    ///
+    /// Expects wgpu clip space conventions (depth from 0 to 1), as produced by
+    /// [`Camera::projection_matrix`](crate::Camera::projection_matrix). Works for
+    /// both perspective and orthographic projections.
+    pub fn from_view_projection(view_proj: &Mat4) -> Self {
+        let [r0, r1, r2, r3] = [0, 1, 2, 3].map(|i| view_proj.row(i));
+        let planes = [r3 + r0, r3 - r0, r3 + r1, r3 - r1, r2, r3 - r2].map(|plane| {
+            let length = plane.truncate().length();
+            if length > 0.0 { plane / length } else { plane }
+        });
+        Self { planes }
+    }
+
+    /// Returns `true` if any part of the sphere may be inside the frustum.
+    ///
+    /// Conservative: spheres near a frustum corner can be reported visible
+    /// even when slightly outside, but visible spheres are never rejected.
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(center) + plane.w >= -radius)
+    }
+
+    /// Returns `true` if the point is inside the frustum.
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.intersects_sphere(point, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::Camera;
+
+    fn frustum(camera: &Camera) -> Frustum {
+        let view_proj = camera.projection_matrix(1.0, 0.1, 100.0) * camera.view_matrix();
+        Frustum::from_view_projection(&view_proj)
+    }
+
+    #[test]
+    fn spheres_outside_any_plane_are_rejected() {
+        // Looking down -Z from the origin with a 90 degree vertical FOV
+        let camera = Camera::new().at([0.0, 0.0, 0.0]).with_fov(90.0);
+        let f = frustum(&camera);
+
+        assert!(f.contains_point(Vec3::new(0.0, 0.0, -10.0)));
+        assert!(!f.contains_point(Vec3::new(0.0, 0.0, 10.0)));
+        assert!(!f.contains_point(Vec3::new(0.0, 0.0, -0.05)));
+        assert!(!f.contains_point(Vec3::new(0.0, 0.0, -200.0)));
+
+        // At distance 10 the view spans x in [-10, 10]
+        assert!(!f.intersects_sphere(Vec3::new(-13.0, 0.0, -10.0), 2.0));
+        assert!(f.intersects_sphere(Vec3::new(-11.0, 0.0, -10.0), 2.0));
+        assert!(!f.intersects_sphere(Vec3::new(0.0, 13.0, -10.0), 2.0));
+    }
+
+    #[test]
+    fn orthographic_frustum_has_parallel_sides() {
+        let camera = Camera::new().at([0.0, 0.0, 0.0]).orthographic(10.0);
+        let f = frustum(&camera);
+
+        assert!(f.contains_point(Vec3::new(4.9, 4.9, -50.0)));
+        assert!(!f.contains_point(Vec3::new(5.1, 0.0, -1.0)));
+        assert!(!f.contains_point(Vec3::new(5.1, 0.0, -90.0)));
+    }
+}
@@ -0,0 +1,100 @@
+//! Gamepad support via gilrs.
+//!
+//! This module translates gilrs events into [`Input`] updates. It is only
+//! compiled with the `gamepad` feature; [`Input::poll_gamepads`] calls into it
+//! once per frame.
+
+use winit::event::ElementState;
+
+use crate::input::{GamepadAxis, GamepadButton, GamepadId, Input};
+
+/// Creates the gilrs context, or `None` if no gamepad backend is available.
+pub(crate) fn init() -> Option<gilrs::Gilrs> {
+    match gilrs::Gilrs::new() {
+        Ok(gilrs) => Some(gilrs),
+        Err(e) => {
+            eprintln!("[input] Gamepad support unavailable: {}", e);
+            None
+        }
+    }
+}
+
+/// Drains pending gilrs events into `input`.
+pub(crate) fn poll(gilrs: &mut gilrs::Gilrs, input: &mut Input) {
+    // Gamepads connected before startup don't send a Connected event
+    for (id, _) in gilrs.gamepads() {
+        input.handle_gamepad_connected(gamepad_id(id));
+    }
+
+    while let Some(gilrs::Event { id, event, .. }) = gilrs.next_event() {
+        let id = gamepad_id(id);
+        match event {
+            gilrs::EventType::Connected => input.handle_gamepad_connected(id),
+            gilrs::EventType::Disconnected => input.handle_gamepad_disconnected(id),
+            gilrs::EventType::ButtonPressed(button, _) => {
+                if let Some(button) = map_button(button) {
+                    input.handle_gamepad_button(id, button, ElementState::Pressed);
+                }
+            }
+            gilrs::EventType::ButtonReleased(button, _) => {
+                if let Some(button) = map_button(button) {
+                    input.handle_gamepad_button(id, button, ElementState::Released);
+                }
+            }
+            // Analog triggers report their pressure as button values
+            gilrs::EventType::ButtonChanged(gilrs::Button::LeftTrigger2, value, _) => {
+                input.handle_gamepad_axis(id, GamepadAxis::LeftTrigger, value);
+            }
+            gilrs::EventType::ButtonChanged(gilrs::Button::RightTrigger2, value, _) => {
+                input.handle_gamepad_axis(id, GamepadAxis::RightTrigger, value);
+            }
+            gilrs::EventType::AxisChanged(axis, value, _) => {
+                if let Some(axis) = map_axis(axis) {
+                    input.handle_gamepad_axis(id, axis, value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn gamepad_id(id: gilrs::GamepadId) -> GamepadId {
+    GamepadId(usize::from(id))
+}
+
+fn map_button(button: gilrs::Button) -> Option<GamepadButton> {
+    use gilrs::Button;
+
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::North => GamepadButton::North,
+        Button::West => GamepadButton::West,
+        Button::LeftTrigger => GamepadButton::LeftBumper,
+        Button::RightTrigger => GamepadButton::RightBumper,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::Mode => GamepadButton::Mode,
+        Button::LeftThumb => GamepadButton::LeftStick,
+        Button::RightThumb => GamepadButton::RightStick,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+fn map_axis(axis: gilrs::Axis) -> Option<GamepadAxis> {
+    use gilrs::Axis;
+
+    Some(match axis {
+        Axis::LeftStickX => GamepadAxis::LeftStickX,
+        Axis::LeftStickY => GamepadAxis::LeftStickY,
+        Axis::RightStickX => GamepadAxis::RightStickX,
+        Axis::RightStickY => GamepadAxis::RightStickY,
+        _ => return None,
+    })
+}
@@ -1,7 +1,8 @@
 //! Fluent geometry loading for 3D models.
 //!
 //! This module provides a fun, ergonomic way to load 3D geometry from various file formats.
-//! Currently supports STL files, with an extensible architecture for adding more formats.
+//! Currently supports STL and Wavefront OBJ files, with an extensible architecture for
+//! adding more formats.
 //!
 //! # Quick Start
 //!
@@ -31,6 +32,7 @@
 //! | Format | Extensions | Notes |
 //! |--------|------------|-------|
 //! | STL    | `.stl`     | Binary and ASCII, no UV coordinates |
+//! | OBJ    | `.obj`     | Positions, normals, and UVs; polygons are triangulated |
 //!
 //! # The GeometryLoader
 //!
@@ -54,8 +56,9 @@
 //! ```
 
 use crate::gpu::GpuContext;
-use crate::mesh::{Mesh, Vertex3d};
+use crate::mesh::{Mesh, Vertex3d, generate_tangents};
 use glam::{Quat, Vec3};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Errors that can occur when loading geometry.
@@ -67,6 +70,13 @@ pub enum GeometryError {
     UnknownFormat(String),
     /// The geometry data was invalid or corrupt.
     ParseError(String),
+    /// A text-based file was malformed at a specific line.
+    Parse {
+        /// 1-based line number where the problem was found.
+        line: usize,
+        /// Description of the problem.
+        message: String,
+    },
 }
 
 impl std::fmt::Display for GeometryError {
@@ -77,6 +87,9 @@ impl std::fmt::Display for GeometryError {
                 write!(f, "Unknown geometry format: '{}'", ext)
             }
             GeometryError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            GeometryError::Parse { line, message } => {
+                write!(f, "Parse error on line {}: {}", line, message)
+            }
         }
     }
 }
@@ -242,9 +255,12 @@ impl RawGeometry {
         }
     }
 
-    /// Uploads this geometry to the GPU as a [`Mesh`].
+    /// Uploads this geometry to the GPU as a [`Mesh`], generating tangents
+    /// for normal mapping.
     pub fn upload(&self, gpu: &GpuContext) -> Mesh {
-        Mesh::new(gpu, &self.vertices, &self.indices)
+        let mut vertices = self.vertices.clone();
+        generate_tangents(&mut vertices, &self.indices);
+        Mesh::new(gpu, &vertices, &self.indices)
     }
 }
 
@@ -324,6 +340,36 @@ impl PendingGeometry {
         }
     }
 
+    /// Load OBJ geometry from a file path.
+    pub fn from_obj(path: impl AsRef<Path>) -> Self {
+        let result = Self::load_obj_file(path.as_ref()).map_err(|e| e.to_string());
+
+        Self {
+            result,
+            center: false,
+            normalize: false,
+            smooth_normals: false,
+            scale_factor: None,
+            translation: None,
+            rotation: None,
+        }
+    }
+
+    /// Load OBJ geometry from bytes.
+    pub fn from_obj_bytes(bytes: &[u8]) -> Self {
+        let result = Self::parse_obj_bytes(bytes).map_err(|e| e.to_string());
+
+        Self {
+            result,
+            center: false,
+            normalize: false,
+            smooth_normals: false,
+            scale_factor: None,
+            translation: None,
+            rotation: None,
+        }
+    }
+
     /// Load STL geometry from bytes.
     pub fn from_stl_bytes(bytes: &[u8]) -> Self {
         let result = Self::parse_stl_bytes(bytes).map_err(|e| e.to_string());
@@ -441,6 +487,7 @@ impl PendingGeometry {
 
         match ext.as_str() {
             "stl" => Self::load_stl_file(path),
+            "obj" => Self::load_obj_file(path),
             _ => Err(GeometryError::UnknownFormat(ext)),
         }
     }
@@ -489,6 +536,178 @@ impl PendingGeometry {
         let mut cursor = std::io::Cursor::new(bytes);
         Self::parse_stl(&mut cursor)
     }
+
+    // Internal: Load OBJ file
+    pub(crate) fn load_obj_file(path: &Path) -> Result<RawGeometry, GeometryError> {
+        let source = std::fs::read_to_string(path)?;
+        Self::parse_obj(&source)
+    }
+
+    // Internal: Parse OBJ from bytes
+    fn parse_obj_bytes(bytes: &[u8]) -> Result<RawGeometry, GeometryError> {
+        let source = std::str::from_utf8(bytes)
+            .map_err(|e| GeometryError::ParseError(format!("OBJ is not valid UTF-8: {}", e)))?;
+        Self::parse_obj(source)
+    }
+
+    // Internal: Parse OBJ source text
+    //
+    // Polygons are fan-triangulated. Corners that share the same position/UV/normal
+    // triple are deduplicated; faces without normals get flat normals from their
+    // winding and don't share vertices. V coordinates are flipped so that (0, 0)
+    // is the top-left of the texture, matching wgpu's convention.
+    fn parse_obj(source: &str) -> Result<RawGeometry, GeometryError> {
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut uvs: Vec<[f32; 2]> = Vec::new();
+        let mut normals: Vec<[f32; 3]> = Vec::new();
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut shared: HashMap<(usize, Option<usize>, usize), u32> = HashMap::new();
+
+        for (line_idx, raw_line) in source.lines().enumerate() {
+            let line_num = line_idx + 1;
+            let line = raw_line.split('#').next().unwrap_or("").trim();
+            let mut tokens = line.split_whitespace();
+            let Some(keyword) = tokens.next() else {
+                continue;
+            };
+
+            match keyword {
+                "v" => positions.push(parse_obj_floats::<3>(tokens, line_num, "v")?),
+                "vn" => normals.push(parse_obj_floats::<3>(tokens, line_num, "vn")?),
+                "vt" => {
+                    let [u, v] = parse_obj_floats::<2>(tokens, line_num, "vt")?;
+                    uvs.push([u, 1.0 - v]);
+                }
+                "f" => {
+                    let corners = tokens
+                        .map(|t| {
+                            parse_obj_corner(t, positions.len(), uvs.len(), normals.len(), line_num)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    if corners.len() < 3 {
+                        return Err(GeometryError::Parse {
+                            line: line_num,
+                            message: format!(
+                                "face needs at least 3 vertices, got {}",
+                                corners.len()
+                            ),
+                        });
+                    }
+
+                    for i in 1..corners.len() - 1 {
+                        let tri = [corners[0], corners[i], corners[i + 1]];
+
+                        if tri.iter().all(|c| c.2.is_some()) {
+                            for (v, t, n) in tri {
+                                let n = n.unwrap_or_default();
+                                let index = *shared.entry((v, t, n)).or_insert_with(|| {
+                                    vertices.push(Vertex3d::new(
+                                        positions[v],
+                                        normals[n],
+                                        t.map_or([0.0, 0.0], |t| uvs[t]),
+                                    ));
+                                    (vertices.len() - 1) as u32
+                                });
+                                indices.push(index);
+                            }
+                        } else {
+                            let p0 = Vec3::from(positions[tri[0].0]);
+                            let p1 = Vec3::from(positions[tri[1].0]);
+                            let p2 = Vec3::from(positions[tri[2].0]);
+                            let normal = (p1 - p0).cross(p2 - p0).normalize_or_zero();
+
+                            for (v, t, _) in tri {
+                                indices.push(vertices.len() as u32);
+                                vertices.push(Vertex3d::new(
+                                    positions[v],
+                                    normal.into(),
+                                    t.map_or([0.0, 0.0], |t| uvs[t]),
+                                ));
+                            }
+                        }
+                    }
+                }
+                // Groups, objects, materials, and smoothing groups don't affect geometry
+                _ => {}
+            }
+        }
+
+        if indices.is_empty() {
+            return Err(GeometryError::ParseError(
+                "OBJ contains no faces".to_string(),
+            ));
+        }
+
+        Ok(RawGeometry::new(vertices, indices))
+    }
+}
+
+/// Parses exactly `N` floats following an OBJ keyword, ignoring any extras
+/// (such as the optional `w` component).
+fn parse_obj_floats<'s, const N: usize>(
+    mut tokens: impl Iterator<Item = &'s str>,
+    line: usize,
+    keyword: &str,
+) -> Result<[f32; N], GeometryError> {
+    let mut out = [0.0; N];
+    for value in &mut out {
+        let token = tokens.next().ok_or_else(|| GeometryError::Parse {
+            line,
+            message: format!("'{}' needs {} components", keyword, N),
+        })?;
+        *value = token.parse().map_err(|_| GeometryError::Parse {
+            line,
+            message: format!("invalid number '{}'", token),
+        })?;
+    }
+    Ok(out)
+}
+
+/// Parses one `v`, `v/vt`, `v//vn`, or `v/vt/vn` face corner into zero-based
+/// indices, resolving negative (relative) indices against the counts so far.
+fn parse_obj_corner(
+    token: &str,
+    position_count: usize,
+    uv_count: usize,
+    normal_count: usize,
+    line: usize,
+) -> Result<(usize, Option<usize>, Option<usize>), GeometryError> {
+    let resolve =
+        |part: Option<&str>, count: usize, what: &str| -> Result<Option<usize>, GeometryError> {
+            let Some(part) = part.filter(|p| !p.is_empty()) else {
+                return Ok(None);
+            };
+            let index: i64 = part.parse().map_err(|_| GeometryError::Parse {
+                line,
+                message: format!("invalid {} index '{}'", what, part),
+            })?;
+            let resolved = if index > 0 {
+                index - 1
+            } else {
+                count as i64 + index
+            };
+            if index == 0 || resolved < 0 || resolved >= count as i64 {
+                return Err(GeometryError::Parse {
+                    line,
+                    message: format!("{} index {} out of range ({} defined)", what, index, count),
+                });
+            }
+            Ok(Some(resolved as usize))
+        };
+
+    let mut parts = token.split('/');
+    let position =
+        resolve(parts.next(), position_count, "vertex")?.ok_or_else(|| GeometryError::Parse {
+            line,
+            message: format!("face corner '{}' has no vertex index", token),
+        })?;
+    let uv = resolve(parts.next(), uv_count, "texture coordinate")?;
+    let normal = resolve(parts.next(), normal_count, "normal")?;
+
+    Ok((position, uv, normal))
 }
 
 impl<'a> GeometryLoader<'a> {
@@ -496,6 +715,7 @@ impl<'a> GeometryLoader<'a> {
     ///
     /// Currently supports:
     /// - `.stl` - STL files (binary and ASCII)
+    /// - `.obj` - Wavefront OBJ files
     ///
     /// # Example
     ///
@@ -527,6 +747,28 @@ impl<'a> GeometryLoader<'a> {
         }
     }
 
+    /// Loads a Wavefront OBJ file specifically.
+    ///
+    /// Reads positions, normals, and UVs, triangulating polygonal faces.
+    /// Faces without normals get flat normals computed from their winding,
+    /// and missing UVs default to `[0.0, 0.0]`.
+    pub fn from_obj(gpu: &'a GpuContext, path: impl AsRef<Path>) -> Self {
+        Self {
+            gpu,
+            pending: PendingGeometry::from_obj(path),
+        }
+    }
+
+    /// Loads OBJ geometry from raw bytes.
+    ///
+    /// Perfect for embedded assets using `include_bytes!`.
+    pub fn from_obj_bytes(gpu: &'a GpuContext, bytes: &[u8]) -> Self {
+        Self {
+            gpu,
+            pending: PendingGeometry::from_obj_bytes(bytes),
+        }
+    }
+
     /// Loads STL geometry from raw bytes.
     ///
     /// Perfect for embedded assets using `include_bytes!`.
@@ -710,4 +952,73 @@ mod tests {
         assert!((center.y).abs() < 0.001);
         assert!((center.z).abs() < 0.001);
     }
+
+    #[test]
+    fn obj_full_corners_share_vertices() {
+        let obj = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vt 0 0
+vt 1 0
+vt 1 1
+vt 0 1
+vn 0 0 1
+f 1/1/1 2/2/1 3/3/1 4/4/1
+";
+        let geom = PendingGeometry::parse_obj(obj).unwrap();
+
+        // Quad is fan-triangulated into two triangles sharing two corners
+        assert_eq!(geom.vertices.len(), 4);
+        assert_eq!(geom.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!(geom.vertices[0].normal, [0.0, 0.0, 1.0]);
+        // V is flipped to top-left origin
+        assert_eq!(geom.vertices[0].uv, [0.0, 1.0]);
+        assert_eq!(geom.vertices[2].uv, [1.0, 0.0]);
+    }
+
+    #[test]
+    fn obj_without_normals_gets_flat_normals() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+        let geom = PendingGeometry::parse_obj(obj).unwrap();
+
+        assert_eq!(geom.vertices.len(), 3);
+        for v in &geom.vertices {
+            assert_eq!(v.normal, [0.0, 0.0, 1.0]);
+            assert_eq!(v.uv, [0.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn obj_negative_indices_and_skipped_uvs() {
+        let obj = "\
+v 0 0 0
+v 1 0 0
+v 0 1 0
+vn 0 0 -1
+f -3//-1 -2//-1 -1//-1
+";
+        let geom = PendingGeometry::parse_obj(obj).unwrap();
+
+        assert_eq!(geom.vertices.len(), 3);
+        assert_eq!(geom.vertices[1].position, [1.0, 0.0, 0.0]);
+        assert_eq!(geom.vertices[1].normal, [0.0, 0.0, -1.0]);
+        assert_eq!(geom.vertices[1].uv, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn obj_errors_report_line_numbers() {
+        let obj = "v 0 0 0\nv 1 0 0\n# comment\nf 1 2 7\n";
+        match PendingGeometry::parse_obj(obj) {
+            Err(GeometryError::Parse { line, .. }) => assert_eq!(line, 4),
+            other => panic!("expected a parse error, got {:?}", other.map(|g| g.indices)),
+        }
+
+        let obj = "v 0 0 zero\n";
+        match PendingGeometry::parse_obj(obj) {
+            Err(GeometryError::Parse { line, .. }) => assert_eq!(line, 1),
+            other => panic!("expected a parse error, got {:?}", other.map(|g| g.indices)),
+        }
+    }
 }
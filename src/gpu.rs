@@ -9,6 +9,10 @@
 //! A `GpuContext` is created from a winit [`Window`] and handles all the wgpu boilerplate:
 //! instance creation, adapter selection, device/queue creation, and surface configuration.
 //!
+//! [`GpuContext::new_headless`] creates one without a window instead. Frames then
+//! render into an offscreen texture that can be read back, which is how
+//! [`run_headless`](crate::run_headless) produces images in CI or on a server.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -33,14 +37,119 @@
 //!
 //! [`Window`]: winit::window::Window
 
+use std::cell::{Cell, RefCell};
 use std::sync::Arc;
 use winit::window::Window;
 
+use crate::screenshot::ScreenshotQueue;
+
+/// Device features needed to write timestamps between render passes.
+const TIMESTAMP_FEATURES: wgpu::Features =
+    wgpu::Features::TIMESTAMP_QUERY.union(wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS);
+
+/// Why a [`GpuContext`] could not be created.
+#[derive(Debug)]
+pub enum GpuError {
+    /// No adapter matched the requested backends and could present to the window.
+    NoAdapter(wgpu::RequestAdapterError),
+    /// The window's surface could not be created.
+    Surface(wgpu::CreateSurfaceError),
+    /// The adapter refused to create a device.
+    Device(wgpu::RequestDeviceError),
+}
+
+impl std::fmt::Display for GpuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GpuError::NoAdapter(e) => write!(f, "Failed to find a suitable GPU adapter: {}", e),
+            GpuError::Surface(e) => write!(f, "Failed to create surface: {}", e),
+            GpuError::Device(e) => write!(f, "Failed to create device: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GpuError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GpuError::NoAdapter(e) => Some(e),
+            GpuError::Surface(e) => Some(e),
+            GpuError::Device(e) => Some(e),
+        }
+    }
+}
+
+/// Options for creating a [`GpuContext`].
+///
+/// Most apps set these through [`AppConfig`](crate::AppConfig) instead.
+///
+/// # Example
+///
+/// ```ignore
+/// use hoplite::{GpuContext, GpuOptions};
+///
+/// let gpu = GpuContext::new_with_options(window, GpuOptions {
+///     power_preference: wgpu::PowerPreference::HighPerformance,
+///     ..Default::default()
+/// });
+/// println!("Rendering on {}", gpu.adapter_info().name);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GpuOptions {
+    /// Whether presenting waits for the display refresh (see [`GpuContext::set_vsync`]).
+    pub vsync: bool,
+    /// Which adapter to prefer on machines with more than one GPU.
+    pub power_preference: wgpu::PowerPreference,
+    /// Graphics APIs wgpu may use, such as `Backends::VULKAN` to rule out GL.
+    pub backends: wgpu::Backends,
+}
+
+impl Default for GpuOptions {
+    /// Vsync on, wgpu's default power preference, and the primary backends
+    /// (Vulkan, Metal, DX12, and WebGPU).
+    fn default() -> Self {
+        Self {
+            vsync: true,
+            power_preference: wgpu::PowerPreference::default(),
+            backends: wgpu::Backends::PRIMARY,
+        }
+    }
+}
+
+/// Format of the offscreen target headless contexts render into.
+const OFFSCREEN_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// The texture a frame renders into, from [`GpuContext::acquire_output`].
+///
+/// Pass it back to [`GpuContext::present_output`] once the frame's work is submitted.
+pub enum OutputTexture {
+    /// The window surface's next texture.
+    Surface(wgpu::SurfaceTexture),
+    /// The offscreen target of a headless context.
+    Offscreen(wgpu::Texture),
+}
+
+impl OutputTexture {
+    /// The texture to render into.
+    pub fn texture(&self) -> &wgpu::Texture {
+        match self {
+            OutputTexture::Surface(output) => &output.texture,
+            OutputTexture::Offscreen(texture) => texture,
+        }
+    }
+
+    /// Creates a default view of the texture for use as a color attachment.
+    pub fn view(&self) -> wgpu::TextureView {
+        self.texture()
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    }
+}
+
 /// Core GPU context holding wgpu resources.
 ///
 /// This struct owns all the fundamental wgpu objects needed for rendering:
 /// the surface for presenting to the window, the device for creating GPU resources,
-/// the queue for submitting commands, and the surface configuration.
+/// the queue for submitting commands, and the surface configuration. Headless
+/// contexts replace the surface with an offscreen texture.
 ///
 /// All fields are public to allow direct access to wgpu APIs when needed.
 /// The context is typically created once at startup and passed by reference
@@ -61,14 +170,31 @@ use winit::window::Window;
 /// println!("{}x{} (aspect: {})", gpu.width(), gpu.height(), gpu.aspect());
 /// ```
 pub struct GpuContext {
-    /// The surface for presenting rendered frames to the window.
-    pub surface: wgpu::Surface<'static>,
+    /// The surface for presenting rendered frames to the window, or `None`
+    /// for a headless context (see [`surface`](Self::surface)).
+    surface: Option<wgpu::Surface<'static>>,
     /// The logical GPU device for creating resources and pipelines.
     pub device: wgpu::Device,
     /// The command queue for submitting work to the GPU.
     pub queue: wgpu::Queue,
     /// Current surface configuration (format, size, present mode).
+    ///
+    /// Headless contexts keep one too, describing their offscreen target.
     pub config: wgpu::SurfaceConfiguration,
+    /// Render target standing in for the surface in a headless context.
+    offscreen: Option<wgpu::Texture>,
+    /// Screenshot requests serviced by [`present`](Self::present).
+    pub(crate) screenshots: RefCell<ScreenshotQueue>,
+    /// Present modes the surface supports, used by [`set_vsync`](Self::set_vsync).
+    pub(crate) present_modes: Vec<wgpu::PresentMode>,
+    /// Whether render graphs record GPU timestamps (see [`set_profiling`](Self::set_profiling)).
+    profiling: Cell<bool>,
+    /// Per-node timings from the most recently profiled render graph.
+    pub(crate) timings: RefCell<Vec<(String, f32)>>,
+    /// Fraction of the surface size used for 3D rendering (see [`set_render_scale`](Self::set_render_scale)).
+    render_scale: f32,
+    /// The adapter the device was created on.
+    adapter_info: wgpu::AdapterInfo,
 }
 
 impl GpuContext {
@@ -79,37 +205,86 @@ impl GpuContext {
     /// 2. Creates a surface for the window
     /// 3. Requests a suitable GPU adapter
     /// 4. Creates the logical device and command queue
-    /// 5. Configures the surface with an sRGB format and Fifo present mode
+    /// 5. Configures the surface with an sRGB format and Fifo (vsync) present mode
     ///
     /// # Panics
     ///
     /// Panics if no suitable GPU adapter is found or device creation fails.
+    /// Use [`try_new`](Self::try_new) to handle these cases instead.
     pub fn new(window: Arc<Window>) -> Self {
+        Self::new_with_vsync(window, true)
+    }
+
+    /// Create a new GPU context, returning an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GpuError`] if the surface, adapter, or device can't be created.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// match GpuContext::try_new(window) {
+    ///     Ok(gpu) => { /* render */ }
+    ///     Err(e) => eprintln!("This machine can't run the game: {}", e),
+    /// }
+    /// ```
+    pub fn try_new(window: Arc<Window>) -> Result<Self, GpuError> {
+        Self::try_new_with_options(window, GpuOptions::default())
+    }
+
+    /// Create a new GPU context, choosing the present mode from `vsync`.
+    ///
+    /// Identical to [`new`](Self::new), except that with `vsync` disabled the
+    /// surface presents without waiting for the display (see [`set_vsync`](Self::set_vsync)).
+    pub fn new_with_vsync(window: Arc<Window>, vsync: bool) -> Self {
+        Self::new_with_options(
+            window,
+            GpuOptions {
+                vsync,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a new GPU context with explicit adapter and present options.
+    ///
+    /// Identical to [`new`](Self::new), but lets `options` pick the backends
+    /// and power preference used to choose the adapter. Check
+    /// [`adapter_info`](Self::adapter_info) to see which one was chosen.
+    ///
+    /// # Panics
+    ///
+    /// Same conditions as [`new`](Self::new).
+    pub fn new_with_options(window: Arc<Window>, options: GpuOptions) -> Self {
+        Self::try_new_with_options(window, options).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a new GPU context with explicit options, returning an error
+    /// instead of panicking.
+    ///
+    /// See [`new_with_options`](Self::new_with_options) and [`try_new`](Self::try_new).
+    pub fn try_new_with_options(
+        window: Arc<Window>,
+        options: GpuOptions,
+    ) -> Result<Self, GpuError> {
         let size = window.inner_size();
 
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::PRIMARY,
+            backends: options.backends,
             ..Default::default()
         });
 
-        let surface = instance.create_surface(window).unwrap();
+        let surface = instance.create_surface(window).map_err(GpuError::Surface)?;
 
         let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
+            power_preference: options.power_preference,
             compatible_surface: Some(&surface),
             force_fallback_adapter: false,
         }))
-        .expect("Failed to find a suitable GPU adapter");
-
-        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
-            label: Some("Hoplite Device"),
-            required_features: wgpu::Features::empty(),
-            required_limits: wgpu::Limits::default(),
-            memory_hints: Default::default(),
-            trace: Default::default(),
-            experimental_features: Default::default(),
-        }))
-        .expect("Failed to create device");
+        .map_err(GpuError::NoAdapter)?;
+
+        let (device, queue) = request_device(&adapter)?;
 
         let surface_caps = surface.get_capabilities(&adapter);
         let surface_format = surface_caps
@@ -119,26 +294,271 @@ impl GpuContext {
             .copied()
             .unwrap_or(surface_caps.formats[0]);
 
+        // COPY_SRC lets screenshots read back the frame before it is presented
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | (surface_caps.usages & wgpu::TextureUsages::COPY_SRC);
+
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format: surface_format,
             width: size.width,
             height: size.height,
-            present_mode: wgpu::PresentMode::Fifo,
+            present_mode: choose_present_mode(&surface_caps.present_modes, options.vsync),
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
         };
         surface.configure(&device, &config);
 
-        Self {
-            surface,
+        Ok(Self {
+            surface: Some(surface),
+            device,
+            queue,
+            config,
+            offscreen: None,
+            screenshots: RefCell::new(ScreenshotQueue::default()),
+            present_modes: surface_caps.present_modes,
+            profiling: Cell::new(false),
+            timings: RefCell::new(Vec::new()),
+            render_scale: 1.0,
+            adapter_info: adapter.get_info(),
+        })
+    }
+
+    /// Create a GPU context without a window, rendering to an offscreen texture.
+    ///
+    /// Frames acquired with [`acquire_output`](Self::acquire_output) all render
+    /// into a `width` x `height` sRGB RGBA8 texture, which
+    /// [`read_offscreen`](Self::read_offscreen) copies back to the CPU.
+    /// Everything else, including render graphs and screenshots, works as it
+    /// does with a window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no adapter is found or device creation fails. Use
+    /// [`try_new_headless`](Self::try_new_headless) to handle these cases instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let gpu = GpuContext::new_headless(256, 256);
+    /// // ... render a frame ...
+    /// let image = gpu.read_offscreen().unwrap();
+    /// image.save("frame.png")?;
+    /// ```
+    pub fn new_headless(width: u32, height: u32) -> Self {
+        Self::try_new_headless(width, height).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a headless GPU context, returning an error instead of panicking.
+    ///
+    /// See [`new_headless`](Self::new_headless). Zero dimensions are raised to one pixel.
+    ///
+    /// The primary backends are tried first. Without a window there's no
+    /// surface to be compatible with, so if none of them has an adapter any
+    /// backend is accepted, such as GL on CI machines with only a software
+    /// rasterizer. Use [`try_new_headless_with_options`](Self::try_new_headless_with_options)
+    /// to pick the backends exactly.
+    pub fn try_new_headless(width: u32, height: u32) -> Result<Self, GpuError> {
+        let options = GpuOptions::default();
+        match Self::try_new_headless_with_options(width, height, options.clone()) {
+            Err(GpuError::NoAdapter(_)) => Self::try_new_headless_with_options(
+                width,
+                height,
+                GpuOptions {
+                    backends: wgpu::Backends::all(),
+                    ..options
+                },
+            ),
+            result => result,
+        }
+    }
+
+    /// Create a headless GPU context on an adapter chosen by `options`.
+    ///
+    /// Like [`try_new_headless`](Self::try_new_headless), but only
+    /// `options.backends` are considered. `options.vsync` has no effect
+    /// without a window.
+    pub fn try_new_headless_with_options(
+        width: u32,
+        height: u32,
+        options: GpuOptions,
+    ) -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: options.backends,
+            ..Default::default()
+        });
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: options.power_preference,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .map_err(GpuError::NoAdapter)?;
+
+        let (device, queue) = request_device(&adapter)?;
+
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            format: OFFSCREEN_FORMAT,
+            width: width.max(1),
+            height: height.max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        let offscreen = create_offscreen(&device, &config);
+
+        Ok(Self {
+            surface: None,
             device,
             queue,
             config,
+            offscreen: Some(offscreen),
+            screenshots: RefCell::new(ScreenshotQueue::default()),
+            present_modes: vec![wgpu::PresentMode::Fifo],
+            profiling: Cell::new(false),
+            timings: RefCell::new(Vec::new()),
+            render_scale: 1.0,
+            adapter_info: adapter.get_info(),
+        })
+    }
+
+    /// Returns `true` if this context renders offscreen instead of to a window.
+    pub fn is_headless(&self) -> bool {
+        self.surface.is_none()
+    }
+
+    /// Returns the surface presenting frames to the window, or `None` for a
+    /// headless context.
+    ///
+    /// This replaces the former public `surface` field: code that called
+    /// `gpu.surface.configure(..)` or `gpu.surface.get_current_texture()` now
+    /// unwraps this first, or uses [`acquire_output`](Self::acquire_output),
+    /// which works for both kinds of context.
+    pub fn surface(&self) -> Option<&wgpu::Surface<'static>> {
+        self.surface.as_ref()
+    }
+
+    /// Returns information about the GPU adapter in use.
+    ///
+    /// Useful for logging which device and backend were picked, for example
+    /// `gpu.adapter_info().name` and `gpu.adapter_info().backend`.
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.adapter_info
+    }
+
+    /// Returns `true` if the device can record GPU timestamps for profiling.
+    pub fn supports_timestamps(&self) -> bool {
+        self.device.features().contains(TIMESTAMP_FEATURES)
+    }
+
+    /// Enable or disable GPU profiling of render graph nodes.
+    ///
+    /// While enabled, every render graph records a timestamp before and after
+    /// each node and reads the durations back once the frame finishes (see
+    /// [`RenderGraph::last_timings`](crate::RenderGraph::last_timings) and
+    /// [`last_timings`](Self::last_timings)). Waiting for the readback stalls
+    /// the CPU, so leave profiling off when not measuring.
+    ///
+    /// Has no effect if [`supports_timestamps`](Self::supports_timestamps) is false.
+    pub fn set_profiling(&self, enabled: bool) {
+        if enabled && !self.supports_timestamps() {
+            eprintln!("[profiler] GPU timestamp queries are not supported on this device");
+        }
+        self.profiling.set(enabled);
+        if !enabled {
+            self.timings.borrow_mut().clear();
+        }
+    }
+
+    /// Returns `true` if render graphs should record GPU timings this frame.
+    pub fn profiling(&self) -> bool {
+        self.profiling.get() && self.supports_timestamps()
+    }
+
+    /// Per-node GPU timings, in milliseconds, of the most recently profiled render graph.
+    ///
+    /// Empty unless [`set_profiling`](Self::set_profiling) is enabled and supported.
+    pub fn last_timings(&self) -> Vec<(String, f32)> {
+        self.timings.borrow().clone()
+    }
+
+    /// Enable or disable vsync, reconfiguring the surface.
+    ///
+    /// With vsync, frames are presented in step with the display refresh
+    /// (`Fifo`), which also caps the frame rate. Without it, `Mailbox` is used
+    /// if supported, then `Immediate`; if neither is available the surface
+    /// stays on `Fifo`.
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.config.present_mode = choose_present_mode(&self.present_modes, vsync);
+        if let Some(surface) = &self.surface {
+            surface.configure(&self.device, &self.config);
+        }
+    }
+
+    /// Returns `true` if the surface waits for the display refresh when presenting.
+    pub fn vsync(&self) -> bool {
+        matches!(
+            self.config.present_mode,
+            wgpu::PresentMode::Fifo | wgpu::PresentMode::FifoRelaxed
+        )
+    }
+
+    /// Present a surface texture, capturing any requested screenshots first.
+    ///
+    /// Custom render code that acquires the surface texture itself should call
+    /// this instead of `SurfaceTexture::present` so screenshots keep working.
+    pub fn present(&self, output: wgpu::SurfaceTexture) {
+        self.present_output(OutputTexture::Surface(output));
+    }
+
+    /// Acquire the texture to render this frame into.
+    ///
+    /// This is the surface's next texture, or the offscreen target of a
+    /// headless context. Render code should use this rather than the surface
+    /// directly so it also runs headless.
+    ///
+    /// # Errors
+    ///
+    /// Returns the surface's error if its next texture can't be acquired.
+    pub fn acquire_output(&self) -> Result<OutputTexture, wgpu::SurfaceError> {
+        match (&self.surface, &self.offscreen) {
+            (Some(surface), _) => surface.get_current_texture().map(OutputTexture::Surface),
+            (None, Some(texture)) => Ok(OutputTexture::Offscreen(texture.clone())),
+            (None, None) => {
+                unreachable!("GpuContext has neither a surface nor an offscreen target")
+            }
         }
     }
 
+    /// Finish a frame from [`acquire_output`](Self::acquire_output), capturing
+    /// any requested screenshots and presenting surface textures.
+    pub fn present_output(&self, output: OutputTexture) {
+        self.screenshots
+            .borrow_mut()
+            .capture(&self.device, &self.queue, output.texture());
+        if let OutputTexture::Surface(output) = output {
+            output.present();
+        }
+    }
+
+    /// Copy the offscreen target of a headless context back to the CPU.
+    ///
+    /// Returns `None` for windowed contexts. Blocks until the GPU has finished
+    /// all submitted work.
+    pub fn read_offscreen(&self) -> Option<image::RgbaImage> {
+        let texture = self.offscreen.as_ref()?;
+        crate::screenshot::read_texture_rgba(&self.device, &self.queue, texture)
+    }
+
+    /// Returns `true` if the surface can be read back for screenshots.
+    pub fn supports_screenshots(&self) -> bool {
+        self.config.usage.contains(wgpu::TextureUsages::COPY_SRC)
+            && crate::screenshot::is_readable_format(self.config.format)
+    }
+
     /// Resize the surface to new dimensions.
     ///
     /// Call this when the window is resized. Ignores zero-sized dimensions
@@ -147,7 +567,12 @@ impl GpuContext {
         if width > 0 && height > 0 {
             self.config.width = width;
             self.config.height = height;
-            self.surface.configure(&self.device, &self.config);
+            if let Some(surface) = &self.surface {
+                surface.configure(&self.device, &self.config);
+            }
+            if self.offscreen.is_some() {
+                self.offscreen = Some(create_offscreen(&self.device, &self.config));
+            }
         }
     }
 
@@ -165,4 +590,127 @@ impl GpuContext {
     pub fn aspect(&self) -> f32 {
         self.config.width as f32 / self.config.height as f32
     }
+
+    /// Render 3D content at a fraction of the surface resolution.
+    ///
+    /// Render targets and the mesh depth buffer are allocated at
+    /// [`render_width`](Self::render_width) x [`render_height`](Self::render_height),
+    /// and render graphs upscale their result to the surface. 2D drawing stays
+    /// at native resolution. Values are clamped to `0.1..=1.0`.
+    pub fn set_render_scale(&mut self, scale: f32) {
+        self.render_scale = scale.clamp(0.1, 1.0);
+    }
+
+    /// Returns the render scale (1.0 unless set with [`set_render_scale`](Self::set_render_scale)).
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Returns the width in pixels that render targets are allocated at.
+    pub fn render_width(&self) -> u32 {
+        scaled_size(self.config.width, self.render_scale)
+    }
+
+    /// Returns the height in pixels that render targets are allocated at.
+    pub fn render_height(&self) -> u32 {
+        scaled_size(self.config.height, self.render_scale)
+    }
+}
+
+/// Creates the logical device and queue, enabling the optional features the engine uses.
+fn request_device(adapter: &wgpu::Adapter) -> Result<(wgpu::Device, wgpu::Queue), GpuError> {
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        label: Some("Hoplite Device"),
+        // Timestamp queries are optional; they are only used when profiling.
+        // Filtering 32-bit float textures is used when available
+        required_features: adapter.features()
+            & (TIMESTAMP_FEATURES | wgpu::Features::FLOAT32_FILTERABLE),
+        required_limits: wgpu::Limits::default(),
+        memory_hints: Default::default(),
+        trace: Default::default(),
+        experimental_features: Default::default(),
+    }))
+    .map_err(GpuError::Device)
+}
+
+/// Creates the render target a headless context uses in place of a surface.
+fn create_offscreen(device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Headless Output"),
+        size: wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: config.format,
+        usage: config.usage,
+        view_formats: &[],
+    })
+}
+
+/// Scales a surface dimension, rounding and never going below one pixel.
+fn scaled_size(size: u32, scale: f32) -> u32 {
+    ((size as f32 * scale).round() as u32).max(1)
+}
+
+/// Picks a present mode from those the surface supports.
+///
+/// `Fifo` is guaranteed to be supported, so it is both the vsync choice and
+/// the fallback when no low-latency mode is available.
+fn choose_present_mode(supported: &[wgpu::PresentMode], vsync: bool) -> wgpu::PresentMode {
+    if vsync {
+        return wgpu::PresentMode::Fifo;
+    }
+    [wgpu::PresentMode::Mailbox, wgpu::PresentMode::Immediate]
+        .into_iter()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(wgpu::PresentMode::Fifo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn present_mode_prefers_mailbox_without_vsync() {
+        use wgpu::PresentMode::*;
+
+        assert_eq!(choose_present_mode(&[Fifo, Mailbox, Immediate], true), Fifo);
+        assert_eq!(
+            choose_present_mode(&[Fifo, Immediate, Mailbox], false),
+            Mailbox
+        );
+        assert_eq!(choose_present_mode(&[Fifo, Immediate], false), Immediate);
+        assert_eq!(choose_present_mode(&[Fifo], false), Fifo);
+    }
+
+    #[test]
+    fn headless_runs_render_to_readable_pixels() {
+        let red = crate::Color::rgb(1.0, 0.0, 0.0);
+        let result = crate::run_headless_fallible(4, 2, 2, move |_| {
+            move |frame: &mut crate::Frame| frame.rect(0.0, 0.0, 4.0, 2.0, red)
+        });
+        let pixels = match result {
+            Ok(pixels) => pixels,
+            Err(crate::Error::Gpu(e)) => {
+                crate::test_support::skip_without_gpu(e);
+                return;
+            }
+            Err(e) => panic!("{}", e),
+        };
+
+        assert_eq!(pixels.len(), 4 * 2 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn scaled_size_rounds_and_stays_positive() {
+        assert_eq!(scaled_size(3840, 1.0), 3840);
+        assert_eq!(scaled_size(3840, 0.5), 1920);
+        assert_eq!(scaled_size(1081, 0.5), 541);
+        assert_eq!(scaled_size(1, 0.1), 1);
+    }
 }
@@ -41,11 +41,25 @@
 //! # Ok::<(), std::io::Error>(())
 //! ```
 //!
+//! # Includes
+//!
+//! Shaders may pull in shared code with `#include "path.wgsl"`, resolved
+//! relative to the including file. Included files are watched too, so editing
+//! a shared helper recompiles every shader that includes it.
+//!
 //! # Error Handling
 //!
 //! Hot-reload compilation errors are logged to stderr with the `[hot-reload]` prefix.
-//! The passes use `catch_unwind` to handle potential panics from invalid shaders,
-//! ensuring the application remains stable during development.
+//! Shader validation errors are captured rather than panicking, so the previous
+//! working shader stays active and the application remains stable during development.
+//! The most recent error is also kept until the next successful compile and is
+//! available from each pass's `last_error()`, e.g. to show it on screen.
+//!
+//! # Reload Callbacks
+//!
+//! Resources created against a pass, such as custom bind groups or uploaded
+//! uniforms, can go stale when it recompiles. Register `on_reload` callbacks to
+//! rebuild them; they run inside `check_reload`, after a successful recompile.
 //!
 //! [`EffectPass`]: crate::effect_pass::EffectPass
 //! [`PostProcessPass`]: crate::post_process::PostProcessPass
@@ -59,6 +73,10 @@ use crate::camera::Camera;
 use crate::effect_pass::EffectPass;
 use crate::gpu::GpuContext;
 use crate::post_process::{PostProcessPass, WorldPostProcessPass};
+use crate::shader_include;
+
+/// Callback run after a hot pass recompiles successfully.
+type ReloadFn = Box<dyn FnMut(&GpuContext)>;
 
 /// A shader source that can be hot-reloaded from disk.
 ///
@@ -66,6 +84,9 @@ use crate::post_process::{PostProcessPass, WorldPostProcessPass};
 /// and reloads the source when changes are detected. Higher-level types like
 /// [`HotEffectPass`] build on this to provide automatic recompilation.
 ///
+/// `#include` directives are expanded on every load, and the included files'
+/// modification times are tracked alongside the shader's own.
+///
 /// # Example
 ///
 /// ```no_run
@@ -82,8 +103,11 @@ use crate::post_process::{PostProcessPass, WorldPostProcessPass};
 /// ```
 pub struct HotShader {
     path: PathBuf,
-    last_modified: SystemTime,
+    /// The shader and every file it includes, with their last seen modification times.
+    watched: Vec<(PathBuf, SystemTime)>,
     source: String,
+    /// Why the last reload or compile failed, cleared on the next success.
+    error: Option<String>,
 }
 
 impl HotShader {
@@ -91,56 +115,104 @@ impl HotShader {
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read or its metadata cannot be accessed.
+    /// Returns an error if the file or one of its includes cannot be read, its
+    /// metadata cannot be accessed, or an `#include` is malformed or cyclic.
     pub fn new(path: impl AsRef<Path>) -> std::io::Result<Self> {
         let path = path.as_ref().to_path_buf();
-        let source = fs::read_to_string(&path)?;
-        let last_modified = fs::metadata(&path)?.modified()?;
+        let expanded = shader_include::expand_file(&path)?;
+        let watched = expanded
+            .files
+            .into_iter()
+            .map(|file| {
+                let modified = fs::metadata(&file)?.modified()?;
+                Ok((file, modified))
+            })
+            .collect::<std::io::Result<_>>()?;
 
         Ok(Self {
             path,
-            last_modified,
-            source,
+            watched,
+            source: expanded.source,
+            error: None,
         })
     }
 
-    /// Check if the shader file has been modified and reload if so.
+    /// Check if the shader or any file it includes has been modified, and reload if so.
     ///
-    /// Compares the file's current modification time against the cached timestamp.
-    /// If newer, reads the file contents and updates the internal source.
+    /// Compares each file's current modification time against the cached timestamp.
+    /// If any is newer, re-reads the shader, expands its includes again, and
+    /// updates the internal source.
     ///
     /// # Returns
     ///
     /// `true` if the shader was reloaded, `false` otherwise (including on errors).
+    /// A failed reload keeps the previous source and is reported by
+    /// [`last_error`](Self::last_error).
     pub fn check_reload(&mut self) -> bool {
-        let Ok(metadata) = fs::metadata(&self.path) else {
-            return false;
-        };
-
-        let Ok(modified) = metadata.modified() else {
+        let mut changed = false;
+        for (file, last_modified) in &mut self.watched {
+            let Ok(modified) = fs::metadata(&*file).and_then(|m| m.modified()) else {
+                continue;
+            };
+            if modified > *last_modified {
+                *last_modified = modified;
+                changed = true;
+            }
+        }
+        if !changed {
             return false;
-        };
+        }
 
-        if modified > self.last_modified {
-            if let Ok(source) = fs::read_to_string(&self.path) {
-                self.source = source;
-                self.last_modified = modified;
-                return true;
+        match Self::new(&self.path) {
+            Ok(reloaded) => {
+                *self = reloaded;
+                true
+            }
+            Err(e) => {
+                eprintln!("[hot-reload] {:?}: {}", self.path, e);
+                self.error = Some(format!("{}: {}", self.path.display(), e));
+                false
             }
         }
-
-        false
     }
 
-    /// Get the current shader source.
+    /// Get the current shader source, with includes expanded.
     pub fn source(&self) -> &str {
         &self.source
     }
 
+    /// Get the files this shader was built from: the shader itself, then its includes.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.watched.iter().map(|(file, _)| file.as_path())
+    }
+
     /// Get the shader file path.
     pub fn path(&self) -> &Path {
         &self.path
     }
+
+    /// Get the error from the most recent failed reload, if it hasn't been
+    /// followed by a successful one.
+    ///
+    /// Passes built on this also record shader compilation errors here.
+    pub fn last_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Records the outcome of compiling the current source.
+    fn set_compile_result<T>(&mut self, result: Result<T, String>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.error = None;
+                Some(value)
+            }
+            Err(e) => {
+                eprintln!("[hot-reload] {}", e);
+                self.error = Some(format!("{}: {}", self.path.display(), e));
+                None
+            }
+        }
+    }
 }
 
 /// A hot-reloadable fullscreen effect pass.
@@ -174,6 +246,7 @@ pub struct HotEffectPass {
     shader: HotShader,
     pass: Option<EffectPass>,
     uses_camera: bool,
+    on_reload: Vec<ReloadFn>,
 }
 
 impl HotEffectPass {
@@ -186,13 +259,14 @@ impl HotEffectPass {
     ///
     /// Returns an error if the shader file cannot be read.
     pub fn new(gpu: &GpuContext, path: impl AsRef<Path>) -> std::io::Result<Self> {
-        let shader = HotShader::new(path)?;
-        let pass = Self::try_compile(gpu, shader.source(), false);
+        let mut shader = HotShader::new(path)?;
+        let pass = shader.set_compile_result(Self::try_compile(gpu, shader.source(), false));
 
         Ok(Self {
             shader,
             pass,
             uses_camera: false,
+            on_reload: Vec::new(),
         })
     }
 
@@ -206,52 +280,48 @@ impl HotEffectPass {
     ///
     /// Returns an error if the shader file cannot be read.
     pub fn new_world(gpu: &GpuContext, path: impl AsRef<Path>) -> std::io::Result<Self> {
-        let shader = HotShader::new(path)?;
-        let pass = Self::try_compile(gpu, shader.source(), true);
+        let mut shader = HotShader::new(path)?;
+        let pass = shader.set_compile_result(Self::try_compile(gpu, shader.source(), true));
 
         Ok(Self {
             shader,
             pass,
             uses_camera: true,
+            on_reload: Vec::new(),
         })
     }
 
-    /// Attempt to compile the shader, catching panics for safety.
-    ///
-    /// Returns `None` if compilation fails or panics.
-    fn try_compile(gpu: &GpuContext, source: &str, uses_camera: bool) -> Option<EffectPass> {
-        // wgpu shader compilation can panic on invalid shaders in some cases,
-        // but typically returns errors through validation. We use catch_unwind
-        // for extra safety during hot-reload.
-        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            if uses_camera {
-                EffectPass::new_world(gpu, source)
-            } else {
-                EffectPass::new(gpu, source)
-            }
-        }));
-
-        match result {
-            Ok(pass) => Some(pass),
-            Err(_) => {
-                eprintln!("[hot-reload] Shader compilation panicked, keeping previous version");
-                None
-            }
-        }
+    /// Attempt to compile the shader, returning the compilation error as text.
+    fn try_compile(
+        gpu: &GpuContext,
+        source: &str,
+        uses_camera: bool,
+    ) -> Result<EffectPass, String> {
+        let result = if uses_camera {
+            EffectPass::new_world(gpu, source)
+        } else {
+            EffectPass::new(gpu, source)
+        };
+        result.map_err(|e| e.to_string())
     }
 
     /// Check for shader changes and recompile if needed.
     ///
     /// Call this once per frame, typically at the start of your render loop.
-    /// If the shader file has been modified, attempts to recompile. On failure,
-    /// retains the previous working shader and logs an error to stderr.
+    /// If the shader file has been modified, attempts to recompile. On success,
+    /// runs the [`on_reload`](Self::on_reload) callbacks. On failure, retains
+    /// the previous working shader and logs an error to stderr.
     pub fn check_reload(&mut self, gpu: &GpuContext) {
         if self.shader.check_reload() {
             eprintln!("[hot-reload] Reloading shader: {:?}", self.shader.path());
 
-            if let Some(new_pass) = Self::try_compile(gpu, self.shader.source(), self.uses_camera) {
+            let result = Self::try_compile(gpu, self.shader.source(), self.uses_camera);
+            if let Some(new_pass) = self.shader.set_compile_result(result) {
                 self.pass = Some(new_pass);
                 eprintln!("[hot-reload] Shader compiled successfully");
+                for callback in &mut self.on_reload {
+                    callback(gpu);
+                }
             } else {
                 eprintln!("[hot-reload] Shader compilation failed, keeping previous version");
             }
@@ -293,6 +363,33 @@ impl HotEffectPass {
     pub fn is_valid(&self) -> bool {
         self.pass.is_some()
     }
+
+    /// Returns the most recent reload or compilation error, if the shader
+    /// hasn't compiled successfully since.
+    pub fn last_error(&self) -> Option<&str> {
+        self.shader.last_error()
+    }
+
+    /// Register a callback that runs after each successful recompile.
+    ///
+    /// Use it to recreate bind groups or re-upload uniforms that depend on the
+    /// old pipeline. The callback runs on the render thread from within
+    /// [`check_reload`](Self::check_reload), with the [`GpuContext`] available.
+    /// It does not run for the initial compile or for failed recompiles.
+    /// Multiple callbacks run in registration order.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut effect = HotEffectPass::new(&gpu, "shaders/terrain.wgsl")?;
+    /// effect.on_reload(|gpu| {
+    ///     println!("terrain shader reloaded at {}x{}", gpu.width(), gpu.height());
+    /// });
+    /// ```
+    pub fn on_reload(&mut self, callback: impl FnMut(&GpuContext) + 'static) -> &mut Self {
+        self.on_reload.push(Box::new(callback));
+        self
+    }
 }
 
 /// A hot-reloadable post-processing pass.
@@ -320,6 +417,7 @@ impl HotEffectPass {
 pub struct HotPostProcessPass {
     shader: HotShader,
     pass: Option<PostProcessPass>,
+    on_reload: Vec<ReloadFn>,
 }
 
 impl HotPostProcessPass {
@@ -329,25 +427,19 @@ impl HotPostProcessPass {
     ///
     /// Returns an error if the shader file cannot be read.
     pub fn new(gpu: &GpuContext, path: impl AsRef<Path>) -> std::io::Result<Self> {
-        let shader = HotShader::new(path)?;
-        let pass = Self::try_compile(gpu, shader.source());
+        let mut shader = HotShader::new(path)?;
+        let pass = shader.set_compile_result(Self::try_compile(gpu, shader.source()));
 
-        Ok(Self { shader, pass })
+        Ok(Self {
+            shader,
+            pass,
+            on_reload: Vec::new(),
+        })
     }
 
-    /// Attempt to compile the shader, catching panics for safety.
-    fn try_compile(gpu: &GpuContext, source: &str) -> Option<PostProcessPass> {
-        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            PostProcessPass::new(gpu, source)
-        }));
-
-        match result {
-            Ok(pass) => Some(pass),
-            Err(_) => {
-                eprintln!("[hot-reload] Shader compilation panicked, keeping previous version");
-                None
-            }
-        }
+    /// Attempt to compile the shader, returning the compilation error as text.
+    fn try_compile(gpu: &GpuContext, source: &str) -> Result<PostProcessPass, String> {
+        PostProcessPass::new(gpu, source).map_err(|e| e.to_string())
     }
 
     /// Check for shader changes and recompile if needed.
@@ -357,9 +449,13 @@ impl HotPostProcessPass {
         if self.shader.check_reload() {
             eprintln!("[hot-reload] Reloading shader: {:?}", self.shader.path());
 
-            if let Some(new_pass) = Self::try_compile(gpu, self.shader.source()) {
+            let result = Self::try_compile(gpu, self.shader.source());
+            if let Some(new_pass) = self.shader.set_compile_result(result) {
                 self.pass = Some(new_pass);
                 eprintln!("[hot-reload] Shader compiled successfully");
+                for callback in &mut self.on_reload {
+                    callback(gpu);
+                }
             } else {
                 eprintln!("[hot-reload] Shader compilation failed, keeping previous version");
             }
@@ -386,6 +482,21 @@ impl HotPostProcessPass {
     pub fn is_valid(&self) -> bool {
         self.pass.is_some()
     }
+
+    /// Returns the most recent reload or compilation error, if the shader
+    /// hasn't compiled successfully since.
+    pub fn last_error(&self) -> Option<&str> {
+        self.shader.last_error()
+    }
+
+    /// Register a callback that runs after each successful recompile.
+    ///
+    /// The callback runs from within [`check_reload`](Self::check_reload) on the
+    /// render thread. See [`HotEffectPass::on_reload`] for details.
+    pub fn on_reload(&mut self, callback: impl FnMut(&GpuContext) + 'static) -> &mut Self {
+        self.on_reload.push(Box::new(callback));
+        self
+    }
 }
 
 /// A hot-reloadable world-space post-processing pass.
@@ -414,6 +525,7 @@ impl HotPostProcessPass {
 pub struct HotWorldPostProcessPass {
     shader: HotShader,
     pass: Option<WorldPostProcessPass>,
+    on_reload: Vec<ReloadFn>,
 }
 
 impl HotWorldPostProcessPass {
@@ -423,25 +535,19 @@ impl HotWorldPostProcessPass {
     ///
     /// Returns an error if the shader file cannot be read.
     pub fn new(gpu: &GpuContext, path: impl AsRef<Path>) -> std::io::Result<Self> {
-        let shader = HotShader::new(path)?;
-        let pass = Self::try_compile(gpu, shader.source());
+        let mut shader = HotShader::new(path)?;
+        let pass = shader.set_compile_result(Self::try_compile(gpu, shader.source()));
 
-        Ok(Self { shader, pass })
+        Ok(Self {
+            shader,
+            pass,
+            on_reload: Vec::new(),
+        })
     }
 
-    /// Attempt to compile the shader, catching panics for safety.
-    fn try_compile(gpu: &GpuContext, source: &str) -> Option<WorldPostProcessPass> {
-        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            WorldPostProcessPass::new(gpu, source)
-        }));
-
-        match result {
-            Ok(pass) => Some(pass),
-            Err(_) => {
-                eprintln!("[hot-reload] Shader compilation panicked, keeping previous version");
-                None
-            }
-        }
+    /// Attempt to compile the shader, returning the compilation error as text.
+    fn try_compile(gpu: &GpuContext, source: &str) -> Result<WorldPostProcessPass, String> {
+        WorldPostProcessPass::new(gpu, source).map_err(|e| e.to_string())
     }
 
     /// Check for shader changes and recompile if needed.
@@ -451,9 +557,13 @@ impl HotWorldPostProcessPass {
         if self.shader.check_reload() {
             eprintln!("[hot-reload] Reloading shader: {:?}", self.shader.path());
 
-            if let Some(new_pass) = Self::try_compile(gpu, self.shader.source()) {
+            let result = Self::try_compile(gpu, self.shader.source());
+            if let Some(new_pass) = self.shader.set_compile_result(result) {
                 self.pass = Some(new_pass);
                 eprintln!("[hot-reload] Shader compiled successfully");
+                for callback in &mut self.on_reload {
+                    callback(gpu);
+                }
             } else {
                 eprintln!("[hot-reload] Shader compilation failed, keeping previous version");
             }
@@ -472,9 +582,25 @@ impl HotWorldPostProcessPass {
         time: f32,
         camera: &Camera,
         input_view: &wgpu::TextureView,
+    ) {
+        self.render_with_depth(gpu, render_pass, time, camera, input_view, None);
+    }
+
+    /// Render the post-process effect with camera data and scene depth.
+    ///
+    /// See [`WorldPostProcessPass::render_with_depth`].
+    /// Does nothing if no valid shader is loaded.
+    pub fn render_with_depth(
+        &self,
+        gpu: &GpuContext,
+        render_pass: &mut wgpu::RenderPass,
+        time: f32,
+        camera: &Camera,
+        input_view: &wgpu::TextureView,
+        depth_view: Option<&wgpu::TextureView>,
     ) {
         if let Some(ref pass) = self.pass {
-            pass.render(gpu, render_pass, time, camera, input_view);
+            pass.render_with_depth(gpu, render_pass, time, camera, input_view, depth_view);
         }
     }
 
@@ -482,4 +608,59 @@ impl HotWorldPostProcessPass {
     pub fn is_valid(&self) -> bool {
         self.pass.is_some()
     }
+
+    /// Returns the most recent reload or compilation error, if the shader
+    /// hasn't compiled successfully since.
+    pub fn last_error(&self) -> Option<&str> {
+        self.shader.last_error()
+    }
+
+    /// Register a callback that runs after each successful recompile.
+    ///
+    /// The callback runs from within [`check_reload`](Self::check_reload) on the
+    /// render thread. See [`HotEffectPass::on_reload`] for details.
+    pub fn on_reload(&mut self, callback: impl FnMut(&GpuContext) + 'static) -> &mut Self {
+        self.on_reload.push(Box::new(callback));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Bumps the modification time of `path` past any earlier write.
+    fn touch(path: &Path, seconds_ahead: u64) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(seconds_ahead))
+            .unwrap();
+    }
+
+    #[test]
+    fn failed_reload_keeps_source_and_reports_error_until_fixed() {
+        let dir = std::env::temp_dir().join(format!("hoplite-hot-shader-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let main = dir.join("main.wgsl");
+        fs::write(&main, "fn main() {}").unwrap();
+
+        let mut shader = HotShader::new(&main).unwrap();
+        assert_eq!(shader.last_error(), None);
+
+        fs::write(&main, "#include \"missing.wgsl\"\nfn main() {}").unwrap();
+        touch(&main, 60);
+        assert!(!shader.check_reload());
+        assert_eq!(shader.source(), "fn main() {}\n");
+        let error = shader.last_error().expect("reload error is recorded");
+        assert!(error.contains("missing.wgsl"), "{}", error);
+
+        fs::write(&main, "fn fixed() {}").unwrap();
+        touch(&main, 120);
+        assert!(shader.check_reload());
+        assert_eq!(shader.last_error(), None);
+    }
 }
@@ -1,7 +1,7 @@
-//! Input handling for keyboard and mouse events.
+//! Input handling for keyboard, mouse, and gamepad events.
 //!
-//! This module provides the [`Input`] struct, which tracks the state of keyboard keys
-//! and mouse buttons across frames. It distinguishes between three states for each input:
+//! This module provides the [`Input`] struct, which tracks the state of keyboard keys,
+//! mouse buttons, and gamepad buttons across frames. It distinguishes between three states for each input:
 //!
 //! - **Down**: The input is currently held (persists across frames)
 //! - **Pressed**: The input was just pressed this frame (single-frame event)
@@ -34,13 +34,128 @@
 //!     // Move forward (triggers every frame while held)
 //! }
 //! ```
+//!
+//! # Gamepads
+//!
+//! Gamepad support is provided by [gilrs](https://docs.rs/gilrs) behind the
+//! `gamepad` cargo feature (on Linux this needs libudev). Without the feature,
+//! the gamepad methods are still available but no gamepads ever connect.
+//!
+//! ```ignore
+//! for pad in input.gamepads() {
+//!     if input.gamepad_button_pressed(pad, GamepadButton::South) {
+//!         // Jump
+//!     }
+//!     let steer = input.gamepad_axis(pad, GamepadAxis::LeftStickX, 0.15);
+//! }
+//! ```
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 use glam::Vec2;
-use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::event::{DeviceEvent, ElementState, Ime, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
+/// Identifies a connected gamepad.
+///
+/// IDs are stable while the gamepad stays connected and may be reused after
+/// it disconnects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GamepadId(pub usize);
+
+/// A gamepad button, named by position on an Xbox-style layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadButton {
+    /// Bottom face button (A on Xbox, Cross on PlayStation).
+    South,
+    /// Right face button (B on Xbox, Circle on PlayStation).
+    East,
+    /// Top face button (Y on Xbox, Triangle on PlayStation).
+    North,
+    /// Left face button (X on Xbox, Square on PlayStation).
+    West,
+    /// Left shoulder bumper.
+    LeftBumper,
+    /// Right shoulder bumper.
+    RightBumper,
+    /// Left trigger, as a digital button.
+    LeftTrigger,
+    /// Right trigger, as a digital button.
+    RightTrigger,
+    /// Select / Back / Share.
+    Select,
+    /// Start / Menu / Options.
+    Start,
+    /// Guide / Home button.
+    Mode,
+    /// Left stick click.
+    LeftStick,
+    /// Right stick click.
+    RightStick,
+    /// D-pad up.
+    DPadUp,
+    /// D-pad down.
+    DPadDown,
+    /// D-pad left.
+    DPadLeft,
+    /// D-pad right.
+    DPadRight,
+}
+
+/// An analog gamepad axis.
+///
+/// Stick axes range from `-1.0` to `1.0` with positive Y pointing up.
+/// Trigger axes range from `0.0` (released) to `1.0` (fully pressed).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    /// Left stick horizontal.
+    LeftStickX,
+    /// Left stick vertical.
+    LeftStickY,
+    /// Right stick horizontal.
+    RightStickX,
+    /// Right stick vertical.
+    RightStickY,
+    /// Left trigger pressure.
+    LeftTrigger,
+    /// Right trigger pressure.
+    RightTrigger,
+}
+
+/// Button and axis state for one connected gamepad.
+#[derive(Default)]
+struct GamepadState {
+    /// Buttons currently held down.
+    buttons_down: HashSet<GamepadButton>,
+    /// Buttons pressed this frame (cleared at the start of each frame).
+    buttons_pressed: HashSet<GamepadButton>,
+    /// Buttons released this frame (cleared at the start of each frame).
+    buttons_released: HashSet<GamepadButton>,
+    /// Latest value of each axis that has reported.
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+/// Default longest gap between the presses of a double click.
+const DEFAULT_DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// How far, in pixels, the cursor may move before a press becomes a drag.
+///
+/// Also the furthest apart the two presses of a double click may be.
+const CLICK_SLOP: f32 = 4.0;
+
+/// Press history of one mouse button, for double clicks and drags.
+#[derive(Clone, Copy, Debug, Default)]
+struct ButtonGesture {
+    /// Time and position of a press that a second press could make a double click.
+    last_press: Option<(Instant, Vec2)>,
+    /// Where the button went down, while it's held and on the frame it's released.
+    press_position: Option<Vec2>,
+    /// Whether the cursor has left the press position far enough to drag.
+    dragging: bool,
+}
+
 /// Tracks input state for keyboard and mouse across frames.
 ///
 /// This struct maintains three categories of state for both keyboard keys and mouse buttons:
@@ -67,16 +182,60 @@ pub struct Input {
     mouse_buttons_pressed: HashSet<MouseButton>,
     /// Mouse buttons released this frame (cleared at the start of each frame).
     mouse_buttons_released: HashSet<MouseButton>,
+    /// Mouse buttons double-clicked this frame (cleared at the start of each frame).
+    mouse_double_clicked: HashSet<MouseButton>,
+    /// Click and drag state of each mouse button that has been pressed.
+    gestures: HashMap<MouseButton, ButtonGesture>,
+    /// Longest gap between the presses of a double click.
+    double_click_interval: Duration,
     /// Current mouse position in window coordinates (pixels from top-left).
     mouse_position: Vec2,
-    /// Mouse movement delta accumulated this frame.
+    /// Raw mouse motion accumulated this frame from device events.
     mouse_delta: Vec2,
+    /// Cursor position change accumulated this frame.
+    cursor_delta: Vec2,
+    /// Whether any raw mouse motion has been received, so the platform reports it.
+    raw_motion: bool,
     /// Scroll wheel delta accumulated this frame, normalized to "lines".
     scroll_delta: Vec2,
+    /// Characters typed this frame, after layout and modifiers are applied.
+    typed_chars: Vec<char>,
+    /// Whether backspace was pressed (or auto-repeated) this frame.
+    backspace_pressed: bool,
+    /// State of each connected gamepad.
+    gamepads: HashMap<GamepadId, GamepadState>,
+    /// Gamepad event source, or `None` if gilrs failed to initialize.
+    #[cfg(feature = "gamepad")]
+    gilrs: Option<gilrs::Gilrs>,
+}
+
+#[cfg(feature = "gamepad")]
+impl Default for Input {
+    fn default() -> Self {
+        Self {
+            gilrs: crate::gamepad::init(),
+            ..Self::idle()
+        }
+    }
 }
 
+#[cfg(not(feature = "gamepad"))]
 impl Default for Input {
     fn default() -> Self {
+        Self::idle()
+    }
+}
+
+impl Input {
+    /// Creates a new input tracker with all state cleared.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Input with nothing held or pressed and no gamepad event source.
+    ///
+    /// Given to scenes paused beneath a pushed scene so they don't react to input.
+    pub(crate) fn idle() -> Self {
         Self {
             keys_down: HashSet::new(),
             keys_pressed: HashSet::new(),
@@ -84,18 +243,21 @@ impl Default for Input {
             mouse_buttons_down: HashSet::new(),
             mouse_buttons_pressed: HashSet::new(),
             mouse_buttons_released: HashSet::new(),
+            mouse_double_clicked: HashSet::new(),
+            gestures: HashMap::new(),
+            double_click_interval: DEFAULT_DOUBLE_CLICK_INTERVAL,
             mouse_position: Vec2::ZERO,
             mouse_delta: Vec2::ZERO,
+            cursor_delta: Vec2::ZERO,
+            raw_motion: false,
             scroll_delta: Vec2::ZERO,
+            typed_chars: Vec::new(),
+            backspace_pressed: false,
+            gamepads: HashMap::new(),
+            #[cfg(feature = "gamepad")]
+            gilrs: None,
         }
     }
-}
-
-impl Input {
-    /// Creates a new input tracker with all state cleared.
-    pub fn new() -> Self {
-        Self::default()
-    }
 
     /// Resets per-frame input state.
     ///
@@ -104,13 +266,28 @@ impl Input {
     /// as well as the mouse movement and scroll deltas.
     ///
     /// The "down" states are preserved, as they represent inputs that are still held.
+    /// Drags of buttons released last frame end here.
     pub fn begin_frame(&mut self) {
         self.keys_pressed.clear();
         self.keys_released.clear();
+        for button in &self.mouse_buttons_released {
+            if let Some(gesture) = self.gestures.get_mut(button) {
+                gesture.press_position = None;
+                gesture.dragging = false;
+            }
+        }
         self.mouse_buttons_pressed.clear();
         self.mouse_buttons_released.clear();
+        self.mouse_double_clicked.clear();
         self.mouse_delta = Vec2::ZERO;
+        self.cursor_delta = Vec2::ZERO;
         self.scroll_delta = Vec2::ZERO;
+        self.typed_chars.clear();
+        self.backspace_pressed = false;
+        for pad in self.gamepads.values_mut() {
+            pad.buttons_pressed.clear();
+            pad.buttons_released.clear();
+        }
     }
 
     /// Processes a window event and updates input state accordingly.
@@ -118,8 +295,9 @@ impl Input {
     /// This method handles the following event types:
     /// - [`WindowEvent::KeyboardInput`]: Updates key down/pressed/released state
     /// - [`WindowEvent::MouseInput`]: Updates mouse button down/pressed/released state
-    /// - [`WindowEvent::CursorMoved`]: Updates mouse position and accumulates movement delta
+    /// - [`WindowEvent::CursorMoved`]: Updates mouse position and accumulates cursor delta
     /// - [`WindowEvent::MouseWheel`]: Accumulates scroll delta (normalized to lines)
+    /// - [`WindowEvent::Ime`]: Appends committed IME text to the typed characters
     ///
     /// Other event types are ignored.
     ///
@@ -132,50 +310,136 @@ impl Input {
         match event {
             WindowEvent::KeyboardInput { event, .. } => {
                 if let PhysicalKey::Code(key) = event.physical_key {
-                    match event.state {
-                        ElementState::Pressed => {
-                            if !self.keys_down.contains(&key) {
-                                self.keys_pressed.insert(key);
-                            }
-                            self.keys_down.insert(key);
-                        }
-                        ElementState::Released => {
-                            self.keys_down.remove(&key);
-                            self.keys_released.insert(key);
-                        }
-                    }
-                }
-            }
-            WindowEvent::MouseInput { state, button, .. } => match state {
-                ElementState::Pressed => {
-                    if !self.mouse_buttons_down.contains(button) {
-                        self.mouse_buttons_pressed.insert(*button);
-                    }
-                    self.mouse_buttons_down.insert(*button);
+                    self.handle_key(key, event.state);
                 }
-                ElementState::Released => {
-                    self.mouse_buttons_down.remove(button);
-                    self.mouse_buttons_released.insert(*button);
+                if let (ElementState::Pressed, Some(text)) = (event.state, &event.text) {
+                    self.handle_text(text);
                 }
-            },
+            }
+            WindowEvent::Ime(Ime::Commit(text)) => {
+                self.handle_text(text);
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.handle_mouse_button(*button, *state);
+            }
             WindowEvent::CursorMoved { position, .. } => {
                 let new_pos = Vec2::new(position.x as f32, position.y as f32);
-                self.mouse_delta += new_pos - self.mouse_position;
+                self.cursor_delta += new_pos - self.mouse_position;
                 self.mouse_position = new_pos;
+                for gesture in self.gestures.values_mut() {
+                    if let Some(start) = gesture.press_position {
+                        gesture.dragging |= start.distance(new_pos) > CLICK_SLOP;
+                    }
+                }
             }
             WindowEvent::MouseWheel { delta, .. } => {
-                let d = match delta {
-                    winit::event::MouseScrollDelta::LineDelta(x, y) => Vec2::new(*x, *y),
-                    winit::event::MouseScrollDelta::PixelDelta(pos) => {
-                        Vec2::new(pos.x as f32, pos.y as f32) / 120.0
-                    }
-                };
-                self.scroll_delta += d;
+                self.scroll_delta += scroll_lines(delta);
             }
             _ => {}
         }
     }
 
+    /// Updates a key's down/pressed/released state.
+    ///
+    /// Called by [`handle_event`](Self::handle_event) for keyboard events.
+    /// Repeated presses of a held key don't mark it as pressed again.
+    pub fn handle_key(&mut self, key: KeyCode, state: ElementState) {
+        match state {
+            ElementState::Pressed => {
+                // Unlike `keys_pressed`, backspace honors OS key repeat so
+                // holding it deletes continuously in text fields.
+                if key == KeyCode::Backspace {
+                    self.backspace_pressed = true;
+                }
+                if !self.keys_down.contains(&key) {
+                    self.keys_pressed.insert(key);
+                }
+                self.keys_down.insert(key);
+            }
+            ElementState::Released => {
+                self.keys_down.remove(&key);
+                self.keys_released.insert(key);
+            }
+        }
+    }
+
+    /// Appends typed text to this frame's [`typed_chars`](Self::typed_chars).
+    ///
+    /// Called by [`handle_event`](Self::handle_event) with the layout-aware text
+    /// of each key press (including repeats) and with committed IME text.
+    /// Control characters such as backspace, enter, and tab are dropped; query
+    /// those as keys instead.
+    pub fn handle_text(&mut self, text: &str) {
+        self.typed_chars
+            .extend(text.chars().filter(|c| !c.is_control()));
+    }
+
+    /// Updates a mouse button's down/pressed/released state.
+    ///
+    /// Called by [`handle_event`](Self::handle_event) for mouse button events.
+    pub fn handle_mouse_button(&mut self, button: MouseButton, state: ElementState) {
+        self.handle_mouse_button_at(button, state, Instant::now());
+    }
+
+    /// [`handle_mouse_button`](Self::handle_mouse_button) with the time of the event.
+    fn handle_mouse_button_at(&mut self, button: MouseButton, state: ElementState, now: Instant) {
+        match state {
+            ElementState::Pressed => {
+                if !self.mouse_buttons_down.contains(&button) {
+                    self.mouse_buttons_pressed.insert(button);
+                    self.track_press(button, now);
+                }
+                self.mouse_buttons_down.insert(button);
+            }
+            ElementState::Released => {
+                self.mouse_buttons_down.remove(&button);
+                self.mouse_buttons_released.insert(button);
+            }
+        }
+    }
+
+    /// Starts a possible drag and detects the second press of a double click.
+    fn track_press(&mut self, button: MouseButton, now: Instant) {
+        let position = self.mouse_position;
+        let gesture = self.gestures.entry(button).or_default();
+        let double = gesture.last_press.is_some_and(|(time, at)| {
+            now.duration_since(time) <= self.double_click_interval
+                && at.distance(position) <= CLICK_SLOP
+        });
+        if double {
+            self.mouse_double_clicked.insert(button);
+            // A third quick press starts a new pair instead of double-clicking again
+            gesture.last_press = None;
+        } else {
+            gesture.last_press = Some((now, position));
+        }
+        gesture.press_position = Some(position);
+        gesture.dragging = false;
+    }
+
+    /// Returns the characters typed this frame, in order.
+    ///
+    /// Unlike key queries, these are logical characters: shift, caps lock,
+    /// keyboard layout, key repeat, and IME composition are already applied.
+    /// Use this to feed text fields and consoles.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// name.extend(frame.input.typed_chars());
+    /// if frame.input.backspace_pressed() {
+    ///     name.pop();
+    /// }
+    /// ```
+    pub fn typed_chars(&self) -> &[char] {
+        &self.typed_chars
+    }
+
+    /// Returns `true` if backspace was pressed this frame, including OS key repeat.
+    pub fn backspace_pressed(&self) -> bool {
+        self.backspace_pressed
+    }
+
     /// Returns `true` if the key is currently held down.
     ///
     /// This returns `true` for every frame that the key remains pressed,
@@ -229,23 +493,111 @@ impl Input {
         self.mouse_buttons_released.contains(&button)
     }
 
+    /// Returns `true` if the mouse button was double-clicked this frame.
+    ///
+    /// True on the frame of the second press, when it comes within the
+    /// [double-click interval](Self::set_double_click_interval) of the first
+    /// and close to where the first one landed. The first press still reports
+    /// [`mouse_pressed`](Self::mouse_pressed) as usual.
+    #[inline]
+    pub fn mouse_double_clicked(&self, button: MouseButton) -> bool {
+        self.mouse_double_clicked.contains(&button)
+    }
+
+    /// Sets the longest gap between the presses of a double click.
+    ///
+    /// Defaults to 400 milliseconds.
+    pub fn set_double_click_interval(&mut self, interval: Duration) {
+        self.double_click_interval = interval;
+    }
+
+    /// Returns where the mouse button went down, while it's held.
+    ///
+    /// Also reported on the frame the button is released. Unlike
+    /// [`mouse_drag_start`](Self::mouse_drag_start), this doesn't wait for
+    /// the cursor to move.
+    pub fn mouse_press_position(&self, button: MouseButton) -> Option<Vec2> {
+        self.gestures.get(&button)?.press_position
+    }
+
+    /// Returns where a drag with the mouse button started, if one is in progress.
+    ///
+    /// A press turns into a drag once the cursor moves a few pixels away from
+    /// where the button went down, and stays one until the button is released.
+    /// The drag is still reported on the frame of the release, so a release
+    /// with no drag start is a plain click.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if let Some(start) = frame.input.mouse_drag_start(MouseButton::Left) {
+    ///     let end = start + frame.input.mouse_drag_delta(MouseButton::Left);
+    ///     let (min, size) = (start.min(end), (end - start).abs());
+    ///     frame.rect(min.x, min.y, size.x, size.y, Color::rgba(1.0, 1.0, 1.0, 0.2));
+    /// } else if frame.input.mouse_released(MouseButton::Left) {
+    ///     // Clicked without dragging
+    /// }
+    /// ```
+    pub fn mouse_drag_start(&self, button: MouseButton) -> Option<Vec2> {
+        let gesture = self.gestures.get(&button)?;
+        if gesture.dragging {
+            gesture.press_position
+        } else {
+            None
+        }
+    }
+
+    /// Returns how far the cursor has moved since a drag with the button started.
+    ///
+    /// Zero when the button isn't being dragged. See
+    /// [`mouse_drag_start`](Self::mouse_drag_start).
+    pub fn mouse_drag_delta(&self, button: MouseButton) -> Vec2 {
+        self.mouse_drag_start(button)
+            .map_or(Vec2::ZERO, |start| self.mouse_position - start)
+    }
+
     /// Returns the current mouse position in window coordinates.
     ///
     /// The position is measured in pixels from the top-left corner of the window's
     /// client area. The value is updated whenever a [`WindowEvent::CursorMoved`] event
-    /// is processed.
+    /// is processed, so it stops changing while the cursor is captured or pinned
+    /// against the window edge. Use [`mouse_delta`](Self::mouse_delta) for
+    /// camera look and other relative motion.
     #[inline]
     pub fn mouse_position(&self) -> Vec2 {
         self.mouse_position
     }
 
-    /// Returns the accumulated mouse movement delta for this frame.
+    /// Returns the raw mouse movement accumulated this frame.
+    ///
+    /// The delta is the total motion reported by the mouse itself since the
+    /// last call to [`begin_frame`](Self::begin_frame), in the device's
+    /// (unaccelerated, platform-dependent) units. Unlike changes in
+    /// [`mouse_position`](Self::mouse_position), it keeps reporting motion
+    /// while the cursor is hidden and grabbed by
+    /// [`capture_cursor`](crate::Frame::capture_cursor) or stuck at the
+    /// window edge, which makes it the right input for FPS-style look controls.
     ///
-    /// The delta represents the total mouse movement since the last call to
-    /// [`begin_frame`](Self::begin_frame). Positive X is rightward, positive Y is downward.
+    /// On platforms that don't report raw motion, this falls back to
+    /// [`cursor_delta`](Self::cursor_delta). Positive X is rightward,
+    /// positive Y is downward.
     #[inline]
     pub fn mouse_delta(&self) -> Vec2 {
-        self.mouse_delta
+        if self.raw_motion {
+            self.mouse_delta
+        } else {
+            self.cursor_delta
+        }
+    }
+
+    /// Returns how far the cursor moved across the window this frame, in pixels.
+    ///
+    /// This follows [`mouse_position`](Self::mouse_position), so it is zero
+    /// while the cursor is captured. Use it to drag things under the cursor;
+    /// use [`mouse_delta`](Self::mouse_delta) for relative look controls.
+    #[inline]
+    pub fn cursor_delta(&self) -> Vec2 {
+        self.cursor_delta
     }
 
     /// Returns the accumulated scroll wheel delta for this frame.
@@ -275,5 +627,319 @@ impl Input {
     /// * `dy` - Vertical movement (positive = down)
     pub fn handle_raw_mouse_motion(&mut self, dx: f32, dy: f32) {
         self.mouse_delta += Vec2::new(dx, dy);
+        self.raw_motion = true;
+    }
+
+    /// Processes a device event and updates input state accordingly.
+    ///
+    /// Only [`DeviceEvent::MouseMotion`] is handled, through
+    /// [`handle_raw_mouse_motion`](Self::handle_raw_mouse_motion). Other event
+    /// types are ignored.
+    pub fn handle_device_event(&mut self, event: &DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta } = event {
+            self.handle_raw_mouse_motion(delta.0 as f32, delta.1 as f32);
+        }
+    }
+
+    /// Returns the IDs of all connected gamepads, in ascending order.
+    pub fn gamepads(&self) -> Vec<GamepadId> {
+        let mut ids: Vec<GamepadId> = self.gamepads.keys().copied().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Returns `true` if the gamepad button is currently held down.
+    ///
+    /// Returns `false` if the gamepad isn't connected.
+    #[inline]
+    pub fn gamepad_button_down(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.gamepads
+            .get(&id)
+            .is_some_and(|pad| pad.buttons_down.contains(&button))
+    }
+
+    /// Returns `true` if the gamepad button was pressed this frame.
+    #[inline]
+    pub fn gamepad_button_pressed(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.gamepads
+            .get(&id)
+            .is_some_and(|pad| pad.buttons_pressed.contains(&button))
+    }
+
+    /// Returns `true` if the gamepad button was released this frame.
+    #[inline]
+    pub fn gamepad_button_released(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.gamepads
+            .get(&id)
+            .is_some_and(|pad| pad.buttons_released.contains(&button))
+    }
+
+    /// Returns the value of a gamepad axis with a deadzone applied.
+    ///
+    /// Values with a magnitude below `deadzone` read as `0.0`. Values outside it
+    /// are rescaled so the output still ramps smoothly from `0.0` to `1.0`.
+    /// Returns `0.0` if the gamepad isn't connected.
+    pub fn gamepad_axis(&self, id: GamepadId, axis: GamepadAxis, deadzone: f32) -> f32 {
+        let value = self
+            .gamepads
+            .get(&id)
+            .and_then(|pad| pad.axes.get(&axis))
+            .copied()
+            .unwrap_or(0.0);
+
+        let deadzone = deadzone.clamp(0.0, 0.99);
+        if value.abs() < deadzone {
+            return 0.0;
+        }
+        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+    }
+
+    /// Polls connected gamepads and applies their events.
+    ///
+    /// Call once per frame before querying gamepad state. This also picks up
+    /// gamepads connected or disconnected mid-session. Does nothing without the
+    /// `gamepad` feature.
+    pub fn poll_gamepads(&mut self) {
+        #[cfg(feature = "gamepad")]
+        if let Some(mut gilrs) = self.gilrs.take() {
+            crate::gamepad::poll(&mut gilrs, self);
+            self.gilrs = Some(gilrs);
+        }
+    }
+
+    /// Registers a newly connected gamepad.
+    pub fn handle_gamepad_connected(&mut self, id: GamepadId) {
+        self.gamepads.entry(id).or_default();
+    }
+
+    /// Forgets a disconnected gamepad and all of its state.
+    pub fn handle_gamepad_disconnected(&mut self, id: GamepadId) {
+        self.gamepads.remove(&id);
+    }
+
+    /// Updates a gamepad button's down/pressed/released state.
+    ///
+    /// Unknown gamepads are registered as connected.
+    pub fn handle_gamepad_button(
+        &mut self,
+        id: GamepadId,
+        button: GamepadButton,
+        state: ElementState,
+    ) {
+        let pad = self.gamepads.entry(id).or_default();
+        match state {
+            ElementState::Pressed => {
+                if pad.buttons_down.insert(button) {
+                    pad.buttons_pressed.insert(button);
+                }
+            }
+            ElementState::Released => {
+                if pad.buttons_down.remove(&button) {
+                    pad.buttons_released.insert(button);
+                }
+            }
+        }
+    }
+
+    /// Records the latest value of a gamepad axis.
+    ///
+    /// Unknown gamepads are registered as connected.
+    pub fn handle_gamepad_axis(&mut self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.gamepads
+            .entry(id)
+            .or_default()
+            .axes
+            .insert(axis, value);
+    }
+}
+
+/// Pixels per scroll "line" when normalizing touchpad-style scroll events.
+const PIXELS_PER_SCROLL_LINE: f32 = 120.0;
+
+/// Normalizes a scroll event to lines, whether it reports lines or pixels.
+fn scroll_lines(delta: &MouseScrollDelta) -> Vec2 {
+    match delta {
+        MouseScrollDelta::LineDelta(x, y) => Vec2::new(*x, *y),
+        MouseScrollDelta::PixelDelta(pos) => {
+            Vec2::new(pos.x as f32, pos.y as f32) / PIXELS_PER_SCROLL_LINE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAD: GamepadId = GamepadId(0);
+
+    #[test]
+    fn scroll_deltas_normalize_to_lines() {
+        let lines = MouseScrollDelta::LineDelta(0.0, 2.0);
+        assert_eq!(scroll_lines(&lines), Vec2::new(0.0, 2.0));
+
+        let pixels = MouseScrollDelta::PixelDelta(winit::dpi::PhysicalPosition::new(-60.0, 240.0));
+        assert_eq!(scroll_lines(&pixels), Vec2::new(-0.5, 2.0));
+    }
+
+    #[test]
+    fn mouse_delta_prefers_raw_motion_over_cursor_movement() {
+        let mut input = Input::new();
+        let moved = |x: f64, y: f64| WindowEvent::CursorMoved {
+            device_id: winit::event::DeviceId::dummy(),
+            position: winit::dpi::PhysicalPosition::new(x, y),
+        };
+
+        // Without raw motion, the cursor movement stands in
+        input.handle_event(&moved(10.0, 20.0));
+        assert_eq!(input.mouse_delta(), Vec2::new(10.0, 20.0));
+
+        // Once raw motion arrives it isn't added on top of the cursor movement
+        input.begin_frame();
+        input.handle_event(&moved(15.0, 20.0));
+        input.handle_device_event(&DeviceEvent::MouseMotion { delta: (3.0, -1.0) });
+        assert_eq!(input.mouse_delta(), Vec2::new(3.0, -1.0));
+        assert_eq!(input.cursor_delta(), Vec2::new(5.0, 0.0));
+
+        // Captured cursors stop moving but raw motion keeps coming
+        input.begin_frame();
+        input.handle_device_event(&DeviceEvent::MouseMotion { delta: (-4.0, 2.0) });
+        assert_eq!(input.mouse_delta(), Vec2::new(-4.0, 2.0));
+        assert_eq!(input.cursor_delta(), Vec2::ZERO);
+        assert_eq!(input.mouse_position(), Vec2::new(15.0, 20.0));
+
+        input.begin_frame();
+        assert_eq!(input.mouse_delta(), Vec2::ZERO);
+    }
+
+    fn cursor_to(input: &mut Input, x: f64, y: f64) {
+        input.handle_event(&WindowEvent::CursorMoved {
+            device_id: winit::event::DeviceId::dummy(),
+            position: winit::dpi::PhysicalPosition::new(x, y),
+        });
+    }
+
+    #[test]
+    fn double_clicks_need_two_quick_nearby_presses() {
+        let mut input = Input::new();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let click = |input: &mut Input, ms| {
+            input.begin_frame();
+            input.handle_mouse_button_at(MouseButton::Left, ElementState::Pressed, at(ms));
+            input.handle_mouse_button_at(MouseButton::Left, ElementState::Released, at(ms));
+            input.mouse_double_clicked(MouseButton::Left)
+        };
+
+        assert!(!click(&mut input, 0));
+        assert!(click(&mut input, 200));
+        // A third press starts over rather than double-clicking again
+        assert!(!click(&mut input, 300));
+        assert!(!click(&mut input, 1000));
+
+        // Too far from the first press
+        cursor_to(&mut input, 50.0, 0.0);
+        assert!(!click(&mut input, 1100));
+
+        input.set_double_click_interval(Duration::from_millis(50));
+        assert!(!click(&mut input, 1200));
+        assert!(click(&mut input, 1240));
+    }
+
+    #[test]
+    fn presses_become_drags_after_moving_past_the_slop() {
+        let mut input = Input::new();
+        cursor_to(&mut input, 10.0, 10.0);
+        input.handle_mouse_button(MouseButton::Left, ElementState::Pressed);
+
+        cursor_to(&mut input, 12.0, 11.0);
+        assert_eq!(input.mouse_drag_start(MouseButton::Left), None);
+        assert_eq!(input.mouse_drag_delta(MouseButton::Left), Vec2::ZERO);
+
+        input.begin_frame();
+        cursor_to(&mut input, 30.0, 5.0);
+        assert_eq!(
+            input.mouse_drag_start(MouseButton::Left),
+            Some(Vec2::new(10.0, 10.0))
+        );
+        assert_eq!(
+            input.mouse_drag_delta(MouseButton::Left),
+            Vec2::new(20.0, -5.0)
+        );
+
+        // Moving back near the start doesn't cancel the drag
+        cursor_to(&mut input, 11.0, 10.0);
+        assert!(input.mouse_drag_start(MouseButton::Left).is_some());
+
+        // The drag is still visible on the release frame, then ends
+        input.handle_mouse_button(MouseButton::Left, ElementState::Released);
+        assert!(input.mouse_drag_start(MouseButton::Left).is_some());
+        input.begin_frame();
+        assert_eq!(input.mouse_drag_start(MouseButton::Left), None);
+        assert_eq!(input.mouse_drag_start(MouseButton::Right), None);
+    }
+
+    #[test]
+    fn typed_text_skips_control_chars_and_clears_each_frame() {
+        let mut input = Input::new();
+        input.handle_text("H");
+        input.handle_text("é\r\t");
+        input.handle_text("\u{8}");
+        input.handle_key(KeyCode::Backspace, ElementState::Pressed);
+
+        assert_eq!(input.typed_chars(), ['H', 'é']);
+        assert!(input.backspace_pressed());
+
+        input.begin_frame();
+        assert!(input.typed_chars().is_empty());
+        assert!(!input.backspace_pressed());
+
+        // OS key repeat re-sends the press while the key is held
+        input.handle_key(KeyCode::Backspace, ElementState::Pressed);
+        assert!(input.backspace_pressed());
+        assert!(!input.key_pressed(KeyCode::Backspace));
+    }
+
+    #[test]
+    fn gamepad_buttons_track_pressed_and_released_per_frame() {
+        let mut input = Input::new();
+        input.handle_gamepad_connected(PAD);
+        input.handle_gamepad_button(PAD, GamepadButton::South, ElementState::Pressed);
+
+        assert!(input.gamepad_button_pressed(PAD, GamepadButton::South));
+        assert!(input.gamepad_button_down(PAD, GamepadButton::South));
+
+        input.begin_frame();
+        assert!(!input.gamepad_button_pressed(PAD, GamepadButton::South));
+        assert!(input.gamepad_button_down(PAD, GamepadButton::South));
+
+        input.handle_gamepad_button(PAD, GamepadButton::South, ElementState::Released);
+        assert!(input.gamepad_button_released(PAD, GamepadButton::South));
+        assert!(!input.gamepad_button_down(PAD, GamepadButton::South));
+    }
+
+    #[test]
+    fn gamepad_axis_applies_deadzone() {
+        let mut input = Input::new();
+        input.handle_gamepad_axis(PAD, GamepadAxis::LeftStickX, 0.1);
+        assert_eq!(input.gamepad_axis(PAD, GamepadAxis::LeftStickX, 0.2), 0.0);
+
+        input.handle_gamepad_axis(PAD, GamepadAxis::LeftStickX, -0.6);
+        assert!((input.gamepad_axis(PAD, GamepadAxis::LeftStickX, 0.2) + 0.5).abs() < 1e-6);
+
+        input.handle_gamepad_axis(PAD, GamepadAxis::LeftStickX, 1.0);
+        assert_eq!(input.gamepad_axis(PAD, GamepadAxis::LeftStickX, 0.2), 1.0);
+    }
+
+    #[test]
+    fn disconnected_gamepads_read_as_idle() {
+        let mut input = Input::new();
+        input.handle_gamepad_button(PAD, GamepadButton::Start, ElementState::Pressed);
+        input.handle_gamepad_axis(PAD, GamepadAxis::RightTrigger, 1.0);
+        assert_eq!(input.gamepads(), [PAD]);
+
+        input.handle_gamepad_disconnected(PAD);
+        assert!(input.gamepads().is_empty());
+        assert!(!input.gamepad_button_down(PAD, GamepadButton::Start));
+        assert_eq!(input.gamepad_axis(PAD, GamepadAxis::RightTrigger, 0.0), 0.0);
     }
 }
@@ -0,0 +1,239 @@
+//! Action-based input mapping.
+//!
+//! An [`InputMap`] binds named actions like `"jump"` to one or more physical
+//! inputs, so game code asks "was jump pressed?" instead of hardcoding keys.
+//! Rebinding is then just a matter of changing the map.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut controls = InputMap::new();
+//! controls
+//!     .bind("jump", KeyCode::Space)
+//!     .bind("jump", GamepadButton::South)
+//!     .bind("fire", MouseButton::Left)
+//!     .bind("move_forward", KeyCode::KeyW)
+//!     .bind("move_forward", KeyCode::ArrowUp);
+//!
+//! move |frame| {
+//!     if controls.pressed(frame.input, "jump") {
+//!         // Jump once per press, from keyboard or gamepad
+//!     }
+//!     if controls.held(frame.input, "move_forward") {
+//!         // Move every frame while any bound input is held
+//!     }
+//! }
+//! ```
+//!
+//! # Saving Bindings
+//!
+//! With the `serde` feature, [`InputMap`] and [`Binding`] implement
+//! `Serialize` and `Deserialize`, so a map can be stored in any serde format.
+
+use std::collections::HashMap;
+
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+use crate::input::{GamepadButton, Input};
+
+/// A physical input that can trigger an action.
+///
+/// Gamepad bindings match the button on any connected gamepad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Binding {
+    /// A keyboard key.
+    Key(KeyCode),
+    /// A mouse button.
+    Mouse(MouseButton),
+    /// A gamepad button.
+    Gamepad(GamepadButton),
+}
+
+impl From<KeyCode> for Binding {
+    fn from(key: KeyCode) -> Self {
+        Binding::Key(key)
+    }
+}
+
+impl From<MouseButton> for Binding {
+    fn from(button: MouseButton) -> Self {
+        Binding::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for Binding {
+    fn from(button: GamepadButton) -> Self {
+        Binding::Gamepad(button)
+    }
+}
+
+impl Binding {
+    /// Returns `true` if this input is currently held down.
+    pub fn down(&self, input: &Input) -> bool {
+        match *self {
+            Binding::Key(key) => input.key_down(key),
+            Binding::Mouse(button) => input.mouse_down(button),
+            Binding::Gamepad(button) => input
+                .gamepads()
+                .into_iter()
+                .any(|pad| input.gamepad_button_down(pad, button)),
+        }
+    }
+
+    /// Returns `true` if this input was pressed this frame.
+    pub fn pressed(&self, input: &Input) -> bool {
+        match *self {
+            Binding::Key(key) => input.key_pressed(key),
+            Binding::Mouse(button) => input.mouse_pressed(button),
+            Binding::Gamepad(button) => input
+                .gamepads()
+                .into_iter()
+                .any(|pad| input.gamepad_button_pressed(pad, button)),
+        }
+    }
+
+    /// Returns `true` if this input was released this frame.
+    pub fn released(&self, input: &Input) -> bool {
+        match *self {
+            Binding::Key(key) => input.key_released(key),
+            Binding::Mouse(button) => input.mouse_released(button),
+            Binding::Gamepad(button) => input
+                .gamepads()
+                .into_iter()
+                .any(|pad| input.gamepad_button_released(pad, button)),
+        }
+    }
+}
+
+/// Maps named actions to physical input bindings.
+///
+/// Queries return `false` for actions with no bindings, so unknown action
+/// names never panic.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap {
+    /// Bindings for each action, in the order they were added.
+    actions: HashMap<String, Vec<Binding>>,
+}
+
+impl InputMap {
+    /// Creates an empty input map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a binding to an action, creating the action if needed.
+    ///
+    /// Binding the same input to an action twice has no effect.
+    pub fn bind(&mut self, action: impl Into<String>, binding: impl Into<Binding>) -> &mut Self {
+        let binding = binding.into();
+        let bindings = self.actions.entry(action.into()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// Removes a single binding from an action.
+    pub fn unbind(&mut self, action: &str, binding: impl Into<Binding>) -> &mut Self {
+        let binding = binding.into();
+        if let Some(bindings) = self.actions.get_mut(action) {
+            bindings.retain(|b| *b != binding);
+        }
+        self
+    }
+
+    /// Removes an action and all of its bindings.
+    pub fn clear_action(&mut self, action: &str) -> &mut Self {
+        self.actions.remove(action);
+        self
+    }
+
+    /// Returns the bindings for an action, or an empty slice if it has none.
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns `true` if any binding for the action is held down.
+    pub fn held(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|b| b.down(input))
+    }
+
+    /// Returns `true` if any binding for the action was pressed this frame.
+    pub fn pressed(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|b| b.pressed(input))
+    }
+
+    /// Returns `true` if any binding for the action was released this frame.
+    pub fn released(&self, input: &Input, action: &str) -> bool {
+        self.bindings(action).iter().any(|b| b.released(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::GamepadId;
+    use winit::event::ElementState;
+
+    fn controls() -> InputMap {
+        let mut map = InputMap::new();
+        map.bind("jump", KeyCode::Space)
+            .bind("jump", GamepadButton::South)
+            .bind("fire", MouseButton::Left);
+        map
+    }
+
+    #[test]
+    fn any_bound_input_triggers_the_action() {
+        let map = controls();
+        let mut input = Input::new();
+
+        input.handle_gamepad_button(GamepadId(3), GamepadButton::South, ElementState::Pressed);
+        assert!(map.pressed(&input, "jump"));
+        assert!(map.held(&input, "jump"));
+        assert!(!map.pressed(&input, "fire"));
+
+        input.begin_frame();
+        assert!(!map.pressed(&input, "jump"));
+        assert!(map.held(&input, "jump"));
+
+        input.handle_mouse_button(MouseButton::Left, ElementState::Pressed);
+        input.handle_mouse_button(MouseButton::Left, ElementState::Released);
+        assert!(map.pressed(&input, "fire"));
+        assert!(map.released(&input, "fire"));
+        assert!(!map.held(&input, "fire"));
+    }
+
+    #[test]
+    fn rebinding_replaces_the_physical_input() {
+        let mut map = controls();
+        map.unbind("jump", KeyCode::Space)
+            .bind("jump", KeyCode::KeyJ);
+        assert_eq!(
+            map.bindings("jump"),
+            [
+                Binding::Gamepad(GamepadButton::South),
+                Binding::Key(KeyCode::KeyJ)
+            ]
+        );
+
+        let mut input = Input::new();
+        input.handle_key(KeyCode::Space, ElementState::Pressed);
+        assert!(!map.pressed(&input, "jump"));
+        input.handle_key(KeyCode::KeyJ, ElementState::Pressed);
+        assert!(map.pressed(&input, "jump"));
+    }
+
+    #[test]
+    fn unknown_actions_are_never_active() {
+        let map = controls();
+        let mut input = Input::new();
+        input.handle_key(KeyCode::Space, ElementState::Pressed);
+
+        assert!(map.bindings("crouch").is_empty());
+        assert!(!map.held(&input, "crouch"));
+    }
+}
@@ -42,49 +42,94 @@
 
 mod app;
 mod assets;
+mod billboard;
 mod camera;
+mod clock;
+mod debug_lines;
 mod draw2d;
 mod ecs;
 mod effect_pass;
+mod error;
+mod follow_camera;
 mod freelook_camera;
+mod frustum;
+#[cfg(feature = "gamepad")]
+mod gamepad;
 mod geometry;
 mod gpu;
 mod hot_shader;
 mod input;
+mod input_map;
+mod lighting;
 mod mesh;
 mod mesh_pass;
+mod model;
+pub mod noise;
 mod orbit_camera;
+mod particles;
 mod picking;
 mod post_process;
 mod render_graph;
+mod render_stats;
+mod rng;
 pub mod scene;
 mod scene_projection;
+mod screenshot;
+mod shader_include;
+mod sprite_animation;
+#[cfg(test)]
+mod test_support;
 mod texture;
+mod timestep;
+mod ui;
+mod uniforms;
 
 pub use app::{
-    AppConfig, Frame, MeshBuilder, MeshLoader, SceneSetupContext, SetupContext, run,
-    run_with_config, run_with_scenes, run_with_scenes_config,
+    AppConfig, Frame, MeshBuilder, MeshLoader, SceneSetupContext, SetupContext, run, run_fallible,
+    run_fallible_with_config, run_headless, run_headless_fallible, run_with_config,
+    run_with_scenes, run_with_scenes_config,
 };
 pub use assets::{Assets, FontAtlas, FontId};
-pub use camera::Camera;
-pub use draw2d::{Color, Draw2d, PanelBuilder, Rect, SpriteId};
+pub use billboard::{Billboard, BillboardMode};
+pub use camera::{Camera, ProjectionMode};
+pub use debug_lines::DebugLines;
+pub use draw2d::{
+    Color, DEFAULT_TEXT_GAMMA, Draw2d, PanelBuilder, ParseColorError, Rect, SpriteId,
+    SpriteInstance, TextAlign, TextEffects,
+};
 pub use effect_pass::EffectPass;
+pub use error::Error;
+pub use follow_camera::CameraFollow;
 pub use freelook_camera::{FreelookCamera, FreelookMode, SeatedConfig};
+pub use frustum::Frustum;
 pub use geometry::{GeometryError, GeometryLoader, PendingGeometry, RawGeometry};
-pub use gpu::GpuContext;
+pub use gpu::{GpuContext, GpuError, GpuOptions, OutputTexture};
 pub use hot_shader::{HotEffectPass, HotPostProcessPass, HotShader, HotWorldPostProcessPass};
-pub use input::Input;
-pub use mesh::{Mesh, Transform, Vertex3d};
-pub use mesh_pass::MeshPass;
+pub use input::{GamepadAxis, GamepadButton, GamepadId, Input};
+pub use input_map::{Binding, InputMap};
+pub use lighting::{DirectionalLight, Lighting, MAX_POINT_LIGHTS, PointLight};
+pub use mesh::{
+    Aabb, Mesh, Transform, UvTransform, Vertex3d, Vertex3dColored, compute_flat_normals,
+    compute_smooth_normals, generate_tangents,
+};
+pub use mesh_pass::{CullMode, MeshPass};
+pub use model::ModelPart;
 pub use orbit_camera::{OrbitCamera, OrbitMode};
+pub use particles::ParticleSystem;
 pub use post_process::{PostProcessPass, WorldPostProcessPass};
 pub use render_graph::{
-    EffectNode, HotEffectNode, HotPostProcessNode, HotWorldPostProcessNode, MeshNode, MeshQueue,
-    PostProcessNode, RenderContext, RenderGraph, RenderGraphBuilder, RenderNode, RenderTarget,
+    BloomHandle, BloomNode, BloomSettings, EffectNode, HDR_FORMAT, HotEffectNode,
+    HotPostProcessNode, HotWorldPostProcessNode, MeshNode, MeshQueue, PostProcessNode,
+    RenderContext, RenderGraph, RenderGraphBuilder, RenderNode, RenderTarget, SkyboxNode,
     WorldPostProcessNode,
 };
+pub use render_stats::RenderStats;
+pub use rng::Rng;
 pub use scene_projection::SceneProjection;
+pub use sprite_animation::SpriteAnimation;
 pub use texture::{Sprite, Texture};
+pub use timestep::{FixedTimestep, MAX_FIXED_STEPS_PER_FRAME};
+pub use uniforms::UniformHandle;
 
 // Re-export glam math types for convenience
 pub use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
@@ -94,8 +139,8 @@ pub use winit::event::MouseButton;
 pub use winit::keyboard::KeyCode;
 
 // ECS support and type-safe handles
-pub use ecs::{MeshHandle, MeshId, RenderMesh, TextureHandle, TextureId};
+pub use ecs::{MaterialId, MeshHandle, MeshId, RenderMesh, TextureHandle, TextureId};
 pub use hecs::{Entity, World};
 
 // 3D picking and collision
-pub use picking::{Collider, PickResult, Ray, RayHit};
+pub use picking::{Collider, ColliderShape, PickResult, Ray, RayHit, TriangleHit};
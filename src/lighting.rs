@@ -0,0 +1,96 @@
+//! Scene lighting for the built-in mesh shader.
+//!
+//! [`Lighting`] holds one optional directional "sun" light, an ambient term, and
+//! up to [`MAX_POINT_LIGHTS`] point lights. It lives in the shared
+//! [`MeshQueue`](crate::MeshQueue) and is uploaded by the mesh pass every frame,
+//! so changes made during setup or in the frame closure take effect immediately.
+//!
+//! When no lights are configured, meshes keep the default look: a fixed
+//! half-Lambert key light from above. Adding a sun or any point light switches
+//! to the configured lights.
+//!
+//! # Example
+//!
+//! ```ignore
+//! run(|ctx| {
+//!     ctx.enable_mesh_rendering();
+//!     ctx.set_sun(Vec3::new(-0.4, -1.0, -0.2), Color::rgb(1.0, 0.95, 0.85));
+//!     ctx.add_point_light(Vec3::new(0.0, 2.0, 0.0), Color::rgb(1.0, 0.5, 0.2), 8.0);
+//!
+//!     move |frame| { /* ... */ }
+//! });
+//! ```
+
+use glam::Vec3;
+
+use crate::draw2d::Color;
+
+/// Maximum number of point lights the mesh shader evaluates.
+///
+/// Point lights added beyond this limit are ignored.
+pub const MAX_POINT_LIGHTS: usize = 8;
+
+/// Ambient intensity used until one is set explicitly.
+const DEFAULT_AMBIENT: f32 = 0.15;
+
+/// A directional light, like the sun, that lights everything from one direction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectionalLight {
+    /// Direction the light travels (e.g. `-Y` shines straight down). Need not be normalized.
+    pub direction: Vec3,
+    /// Light color; components above 1.0 make it brighter.
+    pub color: Color,
+}
+
+/// A point light that fades out to nothing at `range`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointLight {
+    /// World-space position.
+    pub position: Vec3,
+    /// Light color; components above 1.0 make it brighter.
+    pub color: Color,
+    /// Distance at which the light's contribution reaches zero.
+    pub range: f32,
+}
+
+/// Lights applied to meshes drawn with the built-in mesh shader.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lighting {
+    /// Optional directional light.
+    pub sun: Option<DirectionalLight>,
+    /// Ambient intensity added to every surface when lights are configured.
+    pub ambient: f32,
+    /// Point lights; only the first [`MAX_POINT_LIGHTS`] are used.
+    pub point_lights: Vec<PointLight>,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Self {
+            sun: None,
+            ambient: DEFAULT_AMBIENT,
+            point_lights: Vec::new(),
+        }
+    }
+}
+
+impl Lighting {
+    /// Returns `true` if any light is set, replacing the default look.
+    pub fn is_configured(&self) -> bool {
+        self.sun.is_some() || !self.point_lights.is_empty()
+    }
+
+    /// Set the directional light.
+    pub fn set_sun(&mut self, direction: Vec3, color: Color) {
+        self.sun = Some(DirectionalLight { direction, color });
+    }
+
+    /// Add a point light.
+    pub fn add_point_light(&mut self, position: Vec3, color: Color, range: f32) {
+        self.point_lights.push(PointLight {
+            position,
+            color,
+            range,
+        });
+    }
+}
@@ -55,19 +55,24 @@
 //!
 //! # Vertex Layout
 //!
-//! The [`Vertex3d`] struct uses the following GPU layout (32 bytes per vertex):
+//! The [`Vertex3d`] struct uses the following GPU layout (48 bytes per vertex):
 //!
 //! | Attribute | Format    | Offset | Shader Location |
 //! |-----------|-----------|--------|-----------------|
 //! | position  | Float32x3 | 0      | 0               |
 //! | normal    | Float32x3 | 12     | 1               |
 //! | uv        | Float32x2 | 24     | 2               |
+//! | tangent   | Float32x4 | 32     | 3               |
 //!
 //! This layout is exposed via [`Vertex3d::LAYOUT`] for custom pipeline creation.
+//!
+//! [`Vertex3dColored`] adds a per-vertex `color` (Float32x4 at offset 48,
+//! shader location 4) for vertex-painted meshes made with [`Mesh::new_colored`].
 
 use crate::geometry::{GeometryError, GeometryLoader};
 use crate::gpu::GpuContext;
-use glam::{Mat4, Vec3};
+use crate::picking::{Ray, TriangleHit};
+use glam::{Mat4, Vec2, Vec3};
 use std::path::Path;
 
 /// A vertex for 3D mesh rendering with position, normal, and texture coordinates.
@@ -79,10 +84,11 @@ use std::path::Path;
 ///
 /// # Memory Layout
 ///
-/// Each vertex occupies 32 bytes:
+/// Each vertex occupies 48 bytes:
 /// - `position`: 12 bytes (3 × f32) at offset 0
 /// - `normal`: 12 bytes (3 × f32) at offset 12
 /// - `uv`: 8 bytes (2 × f32) at offset 24
+/// - `tangent`: 16 bytes (4 × f32) at offset 32
 ///
 /// # Example
 ///
@@ -105,6 +111,12 @@ pub struct Vertex3d {
     pub normal: [f32; 3],
     /// Texture coordinates, typically in the range [0, 1].
     pub uv: [f32; 2],
+    /// Tangent for normal mapping: `xyz` points along increasing `u`, and `w`
+    /// (±1) gives the bitangent as `cross(normal, tangent.xyz) * w`.
+    ///
+    /// Zero until filled in by [`generate_tangents`]; normal maps are ignored
+    /// on vertices without a tangent.
+    pub tangent: [f32; 4],
 }
 
 impl Vertex3d {
@@ -112,9 +124,9 @@ impl Vertex3d {
     ///
     /// Use this when creating custom render pipelines that need to read
     /// [`Vertex3d`] data. The layout defines:
-    /// - **Array stride**: 32 bytes per vertex
+    /// - **Array stride**: 48 bytes per vertex
     /// - **Step mode**: Per-vertex (not per-instance)
-    /// - **Attributes**: position (loc 0), normal (loc 1), uv (loc 2)
+    /// - **Attributes**: position (loc 0), normal (loc 1), uv (loc 2), tangent (loc 3)
     ///
     /// # Example
     ///
@@ -151,11 +163,20 @@ impl Vertex3d {
                 shader_location: 2,
                 format: wgpu::VertexFormat::Float32x2,
             },
+            // tangent
+            wgpu::VertexAttribute {
+                offset: 32,
+                shader_location: 3,
+                format: wgpu::VertexFormat::Float32x4,
+            },
         ],
     };
 
     /// Creates a new vertex with the given position, normal, and UV coordinates.
     ///
+    /// The tangent starts at zero; call [`generate_tangents`] once the mesh's
+    /// triangles are known to enable normal mapping.
+    ///
     /// # Arguments
     ///
     /// * `position` - The 3D position in model space
@@ -178,10 +199,275 @@ impl Vertex3d {
             position,
             normal,
             uv,
+            tangent: [0.0; 4],
         }
     }
 }
 
+/// A [`Vertex3d`] with an extra per-vertex RGBA color.
+///
+/// Meshes built from these with [`Mesh::new_colored`] multiply the vertex
+/// color into the surface color, on top of the texture and draw tint. Use it
+/// for vertex-painted or procedurally colored geometry.
+///
+/// # Memory Layout
+///
+/// Each vertex occupies 64 bytes: the 48-byte [`Vertex3d`] layout followed by
+/// `color` (4 × f32) at offset 48.
+///
+/// # Example
+///
+/// ```
+/// use hoplite::Vertex3dColored;
+///
+/// let vertex = Vertex3dColored::new(
+///     [0.0, 1.0, 0.0],       // position
+///     [0.0, 0.0, 1.0],       // normal
+///     [0.5, 0.0],            // uv
+///     [1.0, 0.0, 0.0, 1.0],  // red
+/// );
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Vertex3dColored {
+    /// The 3D position of this vertex in model space.
+    pub position: [f32; 3],
+    /// The surface normal vector (should be normalized for correct lighting).
+    pub normal: [f32; 3],
+    /// Texture coordinates, typically in the range [0, 1].
+    pub uv: [f32; 2],
+    /// Tangent for normal mapping, as in [`Vertex3d::tangent`].
+    pub tangent: [f32; 4],
+    /// Linear RGBA color multiplied into the surface color.
+    pub color: [f32; 4],
+}
+
+impl Vertex3dColored {
+    /// The wgpu vertex buffer layout descriptor for this vertex type.
+    ///
+    /// Matches [`Vertex3d::LAYOUT`] for locations 0–3, with a 64-byte stride
+    /// and `color` at location 4.
+    pub const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<Vertex3dColored>() as u64,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &[
+            // position
+            wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x3,
+            },
+            // normal
+            wgpu::VertexAttribute {
+                offset: 12,
+                shader_location: 1,
+                format: wgpu::VertexFormat::Float32x3,
+            },
+            // uv
+            wgpu::VertexAttribute {
+                offset: 24,
+                shader_location: 2,
+                format: wgpu::VertexFormat::Float32x2,
+            },
+            // tangent
+            wgpu::VertexAttribute {
+                offset: 32,
+                shader_location: 3,
+                format: wgpu::VertexFormat::Float32x4,
+            },
+            // color
+            wgpu::VertexAttribute {
+                offset: 48,
+                shader_location: 4,
+                format: wgpu::VertexFormat::Float32x4,
+            },
+        ],
+    };
+
+    /// Creates a colored vertex with a zero tangent.
+    pub fn new(position: [f32; 3], normal: [f32; 3], uv: [f32; 2], color: [f32; 4]) -> Self {
+        Self::from_vertex(Vertex3d::new(position, normal, uv), color)
+    }
+
+    /// Adds a color to an existing vertex, keeping its tangent.
+    pub fn from_vertex(vertex: Vertex3d, color: [f32; 4]) -> Self {
+        Self {
+            position: vertex.position,
+            normal: vertex.normal,
+            uv: vertex.uv,
+            tangent: vertex.tangent,
+            color,
+        }
+    }
+
+    /// Returns the vertex without its color.
+    pub fn vertex(&self) -> Vertex3d {
+        Vertex3d {
+            position: self.position,
+            normal: self.normal,
+            uv: self.uv,
+            tangent: self.tangent,
+        }
+    }
+}
+
+/// Fills in [`Vertex3d::tangent`] for every vertex from the triangles' UV layout.
+///
+/// Tangents are accumulated per triangle, then orthogonalized against each
+/// vertex normal. The bitangent follows decreasing `v`, so normal maps are
+/// read OpenGL-style (green pointing up in the image), matching glTF.
+/// Vertices that only touch triangles with degenerate UVs keep a zero tangent,
+/// which makes the mesh shader skip normal mapping for them.
+///
+/// The built-in primitives and loaded models already have tangents; call this
+/// for custom meshes before [`Mesh::new`].
+///
+/// # Example
+///
+/// ```
+/// use hoplite::{Vertex3d, generate_tangents};
+///
+/// let mut vertices = vec![
+///     Vertex3d::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0]),
+///     Vertex3d::new([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 1.0]),
+///     Vertex3d::new([0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0]),
+/// ];
+/// generate_tangents(&mut vertices, &[0, 1, 2]);
+/// assert_eq!(vertices[0].tangent, [1.0, 0.0, 0.0, 1.0]);
+/// ```
+pub fn generate_tangents(vertices: &mut [Vertex3d], indices: &[u32]) {
+    let mut tangents = vec![Vec3::ZERO; vertices.len()];
+    let mut bitangents = vec![Vec3::ZERO; vertices.len()];
+
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+        if a.max(b).max(c) >= vertices.len() {
+            continue;
+        }
+        let position = |i: usize| Vec3::from(vertices[i].position);
+        // Flip v so the bitangent points up the image rather than down
+        let uv = |i: usize| Vec2::new(vertices[i].uv[0], -vertices[i].uv[1]);
+
+        let (e1, e2) = (position(b) - position(a), position(c) - position(a));
+        let (d1, d2) = (uv(b) - uv(a), uv(c) - uv(a));
+        let det = d1.x * d2.y - d2.x * d1.y;
+        if det.abs() < 1e-12 {
+            continue;
+        }
+
+        let tangent = (e1 * d2.y - e2 * d1.y) / det;
+        let bitangent = (e2 * d1.x - e1 * d2.x) / det;
+        for i in [a, b, c] {
+            tangents[i] += tangent;
+            bitangents[i] += bitangent;
+        }
+    }
+
+    for ((vertex, tangent), bitangent) in vertices.iter_mut().zip(tangents).zip(bitangents) {
+        let normal = Vec3::from(vertex.normal);
+        let tangent = (tangent - normal * normal.dot(tangent)).normalize_or_zero();
+        let handedness = if normal.cross(tangent).dot(bitangent) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        vertex.tangent = if tangent == Vec3::ZERO {
+            [0.0; 4]
+        } else {
+            tangent.extend(handedness).to_array()
+        };
+    }
+}
+
+/// Recomputes [`Vertex3d::normal`] by averaging the faces around each vertex.
+///
+/// Each triangle adds its unnormalized face normal to its three vertices, so
+/// larger faces count for more, then every vertex normal is normalized. Only
+/// vertices shared by index are smoothed together; split vertices along an
+/// edge to keep it sharp. Triangles must wind counter-clockwise when seen from
+/// the front. Vertices not used by any triangle get a zero normal.
+///
+/// # Example
+///
+/// ```
+/// use hoplite::{Vertex3d, compute_smooth_normals};
+///
+/// let mut vertices = vec![
+///     Vertex3d::new([0.0, 0.0, 0.0], [0.0; 3], [0.0, 0.0]),
+///     Vertex3d::new([1.0, 0.0, 0.0], [0.0; 3], [1.0, 0.0]),
+///     Vertex3d::new([0.0, 1.0, 0.0], [0.0; 3], [0.0, 1.0]),
+/// ];
+/// compute_smooth_normals(&mut vertices, &[0, 1, 2]);
+/// assert_eq!(vertices[0].normal, [0.0, 0.0, 1.0]);
+/// ```
+pub fn compute_smooth_normals(vertices: &mut [Vertex3d], indices: &[u32]) {
+    let mut normals = vec![Vec3::ZERO; vertices.len()];
+
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+        if a.max(b).max(c) >= vertices.len() {
+            continue;
+        }
+        // Cross product length is twice the area, which weights the average
+        let face = face_normal(vertices, a, b, c);
+        for i in [a, b, c] {
+            normals[i] += face;
+        }
+    }
+
+    for (vertex, normal) in vertices.iter_mut().zip(normals) {
+        vertex.normal = normal.normalize_or_zero().to_array();
+    }
+}
+
+/// Splits every triangle into its own three vertices, each with the face normal.
+///
+/// Gives a faceted look where every triangle is lit evenly. The returned
+/// indices are simply `0..n` in the original triangle order. Degenerate
+/// triangles get a zero normal.
+///
+/// # Example
+///
+/// ```
+/// use hoplite::{Vertex3d, compute_flat_normals};
+///
+/// let vertices = vec![
+///     Vertex3d::new([0.0, 0.0, 0.0], [0.0; 3], [0.0, 0.0]),
+///     Vertex3d::new([1.0, 0.0, 0.0], [0.0; 3], [1.0, 0.0]),
+///     Vertex3d::new([0.0, 1.0, 0.0], [0.0; 3], [0.0, 1.0]),
+///     Vertex3d::new([0.0, 0.0, 1.0], [0.0; 3], [0.0, 0.0]),
+/// ];
+/// let (flat, indices) = compute_flat_normals(&vertices, &[0, 1, 2, 0, 3, 1]);
+/// assert_eq!(flat.len(), 6);
+/// assert_eq!(indices, vec![0, 1, 2, 3, 4, 5]);
+/// ```
+pub fn compute_flat_normals(vertices: &[Vertex3d], indices: &[u32]) -> (Vec<Vertex3d>, Vec<u32>) {
+    let mut flat = Vec::with_capacity(indices.len());
+
+    for tri in indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+        if a.max(b).max(c) >= vertices.len() {
+            continue;
+        }
+        let normal = face_normal(vertices, a, b, c)
+            .normalize_or_zero()
+            .to_array();
+        flat.extend([a, b, c].map(|i| Vertex3d {
+            normal,
+            ..vertices[i]
+        }));
+    }
+
+    let indices = (0..flat.len() as u32).collect();
+    (flat, indices)
+}
+
+/// Unnormalized counter-clockwise normal of triangle `a, b, c`.
+fn face_normal(vertices: &[Vertex3d], a: usize, b: usize, c: usize) -> Vec3 {
+    let [pa, pb, pc] = [a, b, c].map(|i| Vec3::from(vertices[i].position));
+    (pb - pa).cross(pc - pa)
+}
+
 /// GPU-resident 3D mesh geometry with vertex and index buffers.
 ///
 /// A `Mesh` holds the GPU buffers required to render 3D geometry. Once created,
@@ -251,10 +537,89 @@ impl Vertex3d {
 pub struct Mesh {
     /// The GPU buffer containing vertex data.
     pub(crate) vertex_buffer: wgpu::Buffer,
-    /// The GPU buffer containing index data (u32 indices).
+    /// The GPU buffer containing index data.
     pub(crate) index_buffer: wgpu::Buffer,
     /// The number of indices in the mesh (determines draw call size).
     pub(crate) index_count: u32,
+    /// Width of each index in `index_buffer` (16 or 32 bits).
+    pub(crate) index_format: wgpu::IndexFormat,
+    /// Model-space axis-aligned bounds of the vertices.
+    pub(crate) bounds: Aabb,
+    /// Distance from the bounds' center to the farthest vertex.
+    pub(crate) bounding_radius: f32,
+    /// CPU copy of the vertices, kept for triangle picking.
+    pub(crate) vertices: Vec<Vertex3d>,
+    /// CPU copy of the indices (widened to `u32`), kept for triangle picking.
+    pub(crate) indices: Vec<u32>,
+    /// Whether `vertex_buffer` holds [`Vertex3dColored`] rather than [`Vertex3d`].
+    pub(crate) vertex_colors: bool,
+}
+
+/// An axis-aligned bounding box in model or world space.
+///
+/// # Example
+///
+/// ```
+/// use hoplite::{Aabb, Vec3};
+///
+/// let bounds = Aabb::from_points([Vec3::new(-1.0, 0.0, 2.0), Vec3::new(1.0, 4.0, 3.0)]);
+/// assert_eq!(bounds.center(), Vec3::new(0.0, 2.0, 2.5));
+/// assert_eq!(bounds.size(), Vec3::new(2.0, 4.0, 1.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    /// Minimum corner.
+    pub min: Vec3,
+    /// Maximum corner.
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Creates a box from its minimum and maximum corners.
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Computes the smallest box containing all `points`.
+    ///
+    /// Returns a zero-sized box at the origin if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Self {
+        let mut points = points.into_iter();
+        let Some(first) = points.next() else {
+            return Self::new(Vec3::ZERO, Vec3::ZERO);
+        };
+        points.fold(Self::new(first, first), |bounds, p| {
+            Self::new(bounds.min.min(p), bounds.max.max(p))
+        })
+    }
+
+    /// Returns the center of the box.
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns the full size of the box on each axis.
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    /// Returns half the size of the box on each axis.
+    pub fn half_extents(&self) -> Vec3 {
+        self.size() * 0.5
+    }
+
+    /// Returns the box enclosing this one after applying `matrix`.
+    pub fn transformed(&self, matrix: &Mat4) -> Self {
+        let corners = (0..8).map(|i| {
+            let pick = |bit: u32, lo: f32, hi: f32| if i & bit == 0 { lo } else { hi };
+            matrix.transform_point3(Vec3::new(
+                pick(1, self.min.x, self.max.x),
+                pick(2, self.min.y, self.max.y),
+                pick(4, self.min.z, self.max.z),
+            ))
+        });
+        Self::from_points(corners)
+    }
 }
 
 impl Mesh {
@@ -292,29 +657,230 @@ impl Mesh {
     /// });
     /// ```
     pub fn new(gpu: &GpuContext, vertices: &[Vertex3d], indices: &[u32]) -> Self {
+        Self::from_index_bytes(
+            &gpu.device,
+            vertices,
+            bytemuck::cast_slice(indices),
+            indices.to_vec(),
+            wgpu::IndexFormat::Uint32,
+        )
+    }
+
+    /// Creates a mesh from raw vertex data and 16-bit indices.
+    ///
+    /// Identical to [`Mesh::new`] but stores indices as `u16`, halving the size
+    /// of the index buffer. Use this for small meshes with at most 65,536 vertices,
+    /// especially when spawning many of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `gpu` - The GPU context for buffer allocation
+    /// * `vertices` - Slice of vertices defining the mesh geometry
+    /// * `indices` - Slice of u16 indices defining triangles (3 indices per triangle)
+    pub fn new_u16(gpu: &GpuContext, vertices: &[Vertex3d], indices: &[u16]) -> Self {
+        Self::from_index_bytes(
+            &gpu.device,
+            vertices,
+            bytemuck::cast_slice(indices),
+            indices.iter().map(|&i| i as u32).collect(),
+            wgpu::IndexFormat::Uint16,
+        )
+    }
+
+    /// Creates a mesh whose vertices carry their own colors.
+    ///
+    /// The mesh renders with a pipeline variant that multiplies each vertex's
+    /// color into the texture and tint. Custom materials still apply, but
+    /// their shaders don't receive the vertex color.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hoplite::*;
+    ///
+    /// run(|ctx| {
+    ///     let vertices = [
+    ///         Vertex3dColored::new([0.0, 0.5, 0.0], [0.0, 0.0, 1.0], [0.5, 0.0], [1.0, 0.0, 0.0, 1.0]),
+    ///         Vertex3dColored::new([-0.5, -0.5, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0], [0.0, 1.0, 0.0, 1.0]),
+    ///         Vertex3dColored::new([0.5, -0.5, 0.0], [0.0, 0.0, 1.0], [1.0, 1.0], [0.0, 0.0, 1.0, 1.0]),
+    ///     ];
+    ///     let triangle = Mesh::new_colored(&ctx.gpu, &vertices, &[0, 1, 2]);
+    ///     move |_| {}
+    /// });
+    /// ```
+    pub fn new_colored(gpu: &GpuContext, vertices: &[Vertex3dColored], indices: &[u32]) -> Self {
+        Self::upload(
+            &gpu.device,
+            bytemuck::cast_slice(vertices),
+            vertices.iter().map(Vertex3dColored::vertex).collect(),
+            true,
+            bytemuck::cast_slice(indices),
+            indices.to_vec(),
+            wgpu::IndexFormat::Uint32,
+        )
+    }
+
+    /// Creates a mesh with smooth normals computed from its triangles.
+    ///
+    /// Any normals already in `vertices` are replaced, see
+    /// [`compute_smooth_normals`]. Tangents are generated too, so the mesh
+    /// works with normal maps.
+    pub fn with_computed_normals(
+        gpu: &GpuContext,
+        mut vertices: Vec<Vertex3d>,
+        indices: &[u32],
+    ) -> Self {
+        compute_smooth_normals(&mut vertices, indices);
+        Self::primitive(gpu, vertices, indices)
+    }
+
+    /// Creates a faceted mesh with one normal per triangle.
+    ///
+    /// Vertices are duplicated per face, see [`compute_flat_normals`].
+    /// Tangents are generated too, so the mesh works with normal maps.
+    pub fn with_flat_normals(gpu: &GpuContext, vertices: &[Vertex3d], indices: &[u32]) -> Self {
+        let (vertices, indices) = compute_flat_normals(vertices, indices);
+        Self::primitive(gpu, vertices, &indices)
+    }
+
+    /// Generates tangents for a built-in primitive, then uploads it.
+    fn primitive(gpu: &GpuContext, mut vertices: Vec<Vertex3d>, indices: &[u32]) -> Self {
+        generate_tangents(&mut vertices, indices);
+        Self::new(gpu, &vertices, indices)
+    }
+
+    /// Uploads vertices and pre-encoded index data in the given format.
+    pub(crate) fn from_index_bytes(
+        device: &wgpu::Device,
+        vertices: &[Vertex3d],
+        index_bytes: &[u8],
+        indices: Vec<u32>,
+        index_format: wgpu::IndexFormat,
+    ) -> Self {
+        Self::upload(
+            device,
+            bytemuck::cast_slice(vertices),
+            vertices.to_vec(),
+            false,
+            index_bytes,
+            indices,
+            index_format,
+        )
+    }
+
+    /// Uploads pre-encoded vertex and index data.
+    ///
+    /// `vertices` is the CPU copy used for bounds and picking, whatever the
+    /// GPU vertex format.
+    fn upload(
+        device: &wgpu::Device,
+        vertex_bytes: &[u8],
+        vertices: Vec<Vertex3d>,
+        vertex_colors: bool,
+        index_bytes: &[u8],
+        indices: Vec<u32>,
+        index_format: wgpu::IndexFormat,
+    ) -> Self {
         use wgpu::util::DeviceExt;
 
-        let vertex_buffer = gpu
-            .device
-            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Mesh Vertex Buffer"),
-                contents: bytemuck::cast_slice(vertices),
-                usage: wgpu::BufferUsages::VERTEX,
-            });
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Vertex Buffer"),
+            contents: vertex_bytes,
+            usage: wgpu::BufferUsages::VERTEX,
+        });
 
-        let index_buffer = gpu
-            .device
-            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Mesh Index Buffer"),
-                contents: bytemuck::cast_slice(indices),
-                usage: wgpu::BufferUsages::INDEX,
-            });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Index Buffer"),
+            contents: index_bytes,
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        let bounds = Aabb::from_points(vertices.iter().map(|v| Vec3::from(v.position)));
+        let center = bounds.center();
+        let bounding_radius = vertices
+            .iter()
+            .map(|v| Vec3::from(v.position).distance(center))
+            .fold(0.0, f32::max);
 
         Self {
             vertex_buffer,
             index_buffer,
             index_count: indices.len() as u32,
+            index_format,
+            bounds,
+            bounding_radius,
+            vertices,
+            indices,
+            vertex_colors,
+        }
+    }
+
+    /// Returns the model-space axis-aligned bounding box of the mesh's vertices.
+    ///
+    /// Computed once when the mesh is created.
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+
+    /// Returns a model-space bounding sphere as `(center, radius)`.
+    ///
+    /// The sphere is centered on the bounding box and just reaches the
+    /// farthest vertex, which is usually tighter than the box's corner.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Frame a model: back the camera off far enough to see the whole sphere
+    /// let (center, radius) = mesh.bounding_sphere();
+    /// let distance = radius / (camera.fov.to_radians() * 0.5).sin();
+    /// ```
+    pub fn bounding_sphere(&self) -> (Vec3, f32) {
+        (self.bounds.center(), self.bounding_radius)
+    }
+
+    /// Finds the closest triangle hit by a model-space ray.
+    ///
+    /// Tests every triangle with [`Ray::intersect_triangle`], so both faces
+    /// are hit regardless of winding. The ray direction need not be normalized;
+    /// the returned distance is in units of its length. Use
+    /// [`Frame::pick_mesh`](crate::Frame::pick_mesh) to pick meshes placed in
+    /// the world.
+    ///
+    /// # Returns
+    ///
+    /// The distance along the ray and the hit triangle, or `None` if no
+    /// triangle is hit in front of the ray origin.
+    pub fn intersect_ray(&self, ray: &Ray) -> Option<(f32, TriangleHit)> {
+        let mut closest: Option<(f32, TriangleHit)> = None;
+        for (index, tri) in self.indices.chunks_exact(3).enumerate() {
+            let [a, b, c] = [0, 1, 2].map(|k| &self.vertices[tri[k] as usize]);
+            let Some((t, u, v)) =
+                ray.intersect_triangle(a.position.into(), b.position.into(), c.position.into())
+            else {
+                continue;
+            };
+            if closest.as_ref().is_some_and(|(best, _)| *best <= t) {
+                continue;
+            }
+            let barycentric = Vec3::new(1.0 - u - v, u, v);
+            let uv = Vec2::from(a.uv) * barycentric.x
+                + Vec2::from(b.uv) * barycentric.y
+                + Vec2::from(c.uv) * barycentric.z;
+            closest = Some((
+                t,
+                TriangleHit {
+                    index,
+                    barycentric,
+                    uv,
+                },
+            ));
         }
+        closest
+    }
+
+    /// Returns the format of this mesh's index buffer.
+    pub fn index_format(&self) -> wgpu::IndexFormat {
+        self.index_format
     }
 
     /// Creates a unit cube centered at the origin.
@@ -395,7 +961,7 @@ impl Mesh {
             20, 21, 22, 22, 23, 20, // left
         ];
 
-        Self::new(gpu, &vertices, &indices)
+        Self::primitive(gpu, vertices, &indices)
     }
 
     /// Creates a UV sphere centered at the origin with configurable tessellation.
@@ -478,7 +1044,7 @@ impl Mesh {
             }
         }
 
-        Self::new(gpu, &vertices, &indices)
+        Self::primitive(gpu, vertices, &indices)
     }
 
     /// Creates a flat rectangular plane on the XZ axis (horizontal ground plane).
@@ -528,7 +1094,100 @@ impl Mesh {
 
         let indices = vec![0, 1, 2, 2, 3, 0];
 
-        Self::new(gpu, &vertices, &indices)
+        Self::primitive(gpu, vertices, &indices)
+    }
+
+    /// Creates a cylinder along the Y axis, centered at the origin, with capped ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `gpu` - The GPU context for buffer allocation
+    /// * `radius` - Radius of the cylinder
+    /// * `height` - Total height (extends ±height/2 from center)
+    /// * `segments` - Number of divisions around the circumference (minimum 3)
+    ///
+    /// # Geometry Details
+    ///
+    /// - **Vertices**: `4 × segments + 6` (smooth side plus two flat-shaded caps)
+    /// - **Triangles**: `4 × segments`
+    /// - **UV mapping**: Side wraps u around the circumference, v top to bottom;
+    ///   caps use a circular projection of the unit square
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let pillar = Mesh::cylinder(&ctx.gpu, 0.5, 3.0, 24);
+    /// ```
+    pub fn cylinder(gpu: &GpuContext, radius: f32, height: f32, segments: u32) -> Self {
+        let (vertices, indices) = cylinder_geometry(radius, height, segments);
+        Self::primitive(gpu, vertices, &indices)
+    }
+
+    /// Creates a cone along the Y axis with its base at `-height/2` and tip at `+height/2`.
+    ///
+    /// # Arguments
+    ///
+    /// * `gpu` - The GPU context for buffer allocation
+    /// * `radius` - Radius of the base
+    /// * `height` - Distance from base to tip
+    /// * `segments` - Number of divisions around the base (minimum 3)
+    ///
+    /// # Geometry Details
+    ///
+    /// - **Vertices**: `3 × segments + 3` (one tip vertex per segment for smooth side normals)
+    /// - **Triangles**: `2 × segments`
+    /// - **UV mapping**: Side wraps u around the base, v tip to base; base cap
+    ///   uses a circular projection of the unit square
+    pub fn cone(gpu: &GpuContext, radius: f32, height: f32, segments: u32) -> Self {
+        let (vertices, indices) = cone_geometry(radius, height, segments);
+        Self::primitive(gpu, vertices, &indices)
+    }
+
+    /// Creates a torus (donut) lying flat on the XZ plane, centered at the origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `gpu` - The GPU context for buffer allocation
+    /// * `major_radius` - Distance from the center to the middle of the tube
+    /// * `minor_radius` - Radius of the tube
+    /// * `segments` - Number of divisions around the ring (minimum 3)
+    /// * `rings` - Number of divisions around the tube (minimum 3)
+    ///
+    /// # Geometry Details
+    ///
+    /// - **Vertices**: `(segments + 1) × (rings + 1)`
+    /// - **Triangles**: `segments × rings × 2`
+    /// - **UV mapping**: u around the ring, v around the tube
+    pub fn torus(
+        gpu: &GpuContext,
+        major_radius: f32,
+        minor_radius: f32,
+        segments: u32,
+        rings: u32,
+    ) -> Self {
+        let (vertices, indices) = torus_geometry(major_radius, minor_radius, segments, rings);
+        Self::primitive(gpu, vertices, &indices)
+    }
+
+    /// Creates a capsule (a cylinder with hemispherical ends) along the Y axis,
+    /// centered at the origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `gpu` - The GPU context for buffer allocation
+    /// * `radius` - Radius of the body and end caps
+    /// * `height` - Total height including both caps (clamped to at least `2 × radius`)
+    /// * `segments` - Number of divisions around the circumference (minimum 3)
+    /// * `rings` - Number of latitude divisions per hemisphere (minimum 1)
+    ///
+    /// # Geometry Details
+    ///
+    /// - **Vertices**: `(segments + 1) × (2 × rings + 2)`
+    /// - **Triangles**: `segments × (2 × rings + 1) × 2`
+    /// - **UV mapping**: u around the circumference, v top to bottom by height
+    pub fn capsule(gpu: &GpuContext, radius: f32, height: f32, segments: u32, rings: u32) -> Self {
+        let (vertices, indices) = capsule_geometry(radius, height, segments, rings);
+        Self::primitive(gpu, vertices, &indices)
     }
 
     /// Loads a mesh from an STL file.
@@ -709,6 +1368,22 @@ impl Transform {
         }
     }
 
+    /// Creates a transform positioned at `(x, y, z)`.
+    ///
+    /// Shorthand for [`Transform::from_position`] with separate components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hoplite::{Transform, Vec3};
+    ///
+    /// let transform = Transform::translation(0.0, 0.0, -5.0);
+    /// assert_eq!(transform.position, Vec3::new(0.0, 0.0, -5.0));
+    /// ```
+    pub fn translation(x: f32, y: f32, z: f32) -> Self {
+        Self::from_position(Vec3::new(x, y, z))
+    }
+
     /// Sets the position (translation) component.
     ///
     /// # Example
@@ -746,6 +1421,93 @@ impl Transform {
         self
     }
 
+    /// Rotates by `angle` radians around the world X axis, on top of the
+    /// current rotation.
+    ///
+    /// The position is unchanged; use [`Transform::rotate_around`] to orbit a point.
+    pub fn rotate_x(self, angle: f32) -> Self {
+        self.rotate(glam::Quat::from_rotation_x(angle))
+    }
+
+    /// Rotates by `angle` radians around the world Y axis, on top of the
+    /// current rotation.
+    ///
+    /// The position is unchanged; use [`Transform::rotate_around`] to orbit a point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hoplite::Transform;
+    ///
+    /// // Spinning object 5 units in front of the camera
+    /// let time = 1.5;
+    /// let transform = Transform::translation(0.0, 0.0, -5.0).rotate_y(time);
+    /// ```
+    pub fn rotate_y(self, angle: f32) -> Self {
+        self.rotate(glam::Quat::from_rotation_y(angle))
+    }
+
+    /// Rotates by `angle` radians around the world Z axis, on top of the
+    /// current rotation.
+    ///
+    /// The position is unchanged; use [`Transform::rotate_around`] to orbit a point.
+    pub fn rotate_z(self, angle: f32) -> Self {
+        self.rotate(glam::Quat::from_rotation_z(angle))
+    }
+
+    /// Applies `rotation` after the current rotation.
+    fn rotate(mut self, rotation: glam::Quat) -> Self {
+        self.rotation = (rotation * self.rotation).normalize();
+        self
+    }
+
+    /// Rotates so the local -Z axis points at `target`, with local +Y as close
+    /// to `up` as possible.
+    ///
+    /// This matches the camera convention, so a model facing -Z ends up facing
+    /// `target`. The rotation is left unchanged if `target` is at the current
+    /// position or directly along `up`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hoplite::{Transform, Vec3};
+    ///
+    /// let transform = Transform::translation(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y);
+    /// let forward = transform.rotation * Vec3::NEG_Z;
+    /// assert!((forward - Vec3::NEG_Z).length() < 1e-5);
+    /// ```
+    pub fn looking_at(mut self, target: Vec3, up: Vec3) -> Self {
+        let back = (self.position - target).normalize_or_zero();
+        let right = up.cross(back).normalize_or_zero();
+        if back == Vec3::ZERO || right == Vec3::ZERO {
+            return self;
+        }
+        let up = back.cross(right);
+        self.rotation = glam::Quat::from_mat3(&glam::Mat3::from_cols(right, up, back));
+        self
+    }
+
+    /// Rotates by `angle` radians around `axis` through `pivot`.
+    ///
+    /// Both the position and the orientation turn, as if the object were
+    /// attached to the pivot by a rigid arm.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hoplite::{Transform, Vec3};
+    ///
+    /// let transform = Transform::translation(2.0, 0.0, 0.0)
+    ///     .rotate_around(Vec3::ZERO, Vec3::Y, std::f32::consts::PI);
+    /// assert!((transform.position - Vec3::new(-2.0, 0.0, 0.0)).length() < 1e-5);
+    /// ```
+    pub fn rotate_around(mut self, pivot: Vec3, axis: Vec3, angle: f32) -> Self {
+        let rotation = glam::Quat::from_axis_angle(axis.normalize_or(Vec3::Y), angle);
+        self.position = pivot + rotation * (self.position - pivot);
+        self.rotate(rotation)
+    }
+
     /// Sets non-uniform scale factors for each axis.
     ///
     /// Use this when you need different scale values on different axes.
@@ -806,3 +1568,582 @@ impl Transform {
         Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.position)
     }
 }
+
+/// A 2D transform applied to a mesh's texture coordinates when it is drawn.
+///
+/// Each UV is scaled, then rotated counter-clockwise around the UV origin by
+/// `rotation` radians, then shifted by `offset`. Animating the offset scrolls
+/// a texture across a surface without touching the mesh, which suits conveyor
+/// belts, water, and skies; pair it with a repeating texture so the edges tile.
+///
+/// # Example
+///
+/// ```ignore
+/// frame.mesh(belt)
+///     .texture(belt_tex)
+///     .uv_offset(Vec2::new(frame.time * 0.5, 0.0))
+///     .uv_scale(Vec2::new(4.0, 1.0))
+///     .draw();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvTransform {
+    /// Added to the UVs last.
+    pub offset: Vec2,
+    /// Multiplies the UVs first; `2.0` repeats a tiling texture twice.
+    pub scale: Vec2,
+    /// Counter-clockwise rotation in radians, applied after scaling.
+    pub rotation: f32,
+}
+
+impl UvTransform {
+    /// Leaves UVs unchanged.
+    pub const IDENTITY: Self = Self {
+        offset: Vec2::ZERO,
+        scale: Vec2::ONE,
+        rotation: 0.0,
+    };
+
+    /// Transforms a single UV coordinate, as the mesh shader does.
+    pub fn apply(&self, uv: Vec2) -> Vec2 {
+        Vec2::from_angle(self.rotation).rotate(uv * self.scale) + self.offset
+    }
+}
+
+impl Default for UvTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Unit direction in the XZ plane for step `i` of `segments` around the Y axis.
+fn around_y(i: u32, segments: u32) -> (f32, f32) {
+    let theta = std::f32::consts::TAU * i as f32 / segments as f32;
+    (theta.cos(), theta.sin())
+}
+
+/// Appends a flat disc cap at height `y`, facing +Y if `up` or -Y otherwise.
+fn push_cap(
+    vertices: &mut Vec<Vertex3d>,
+    indices: &mut Vec<u32>,
+    radius: f32,
+    y: f32,
+    segments: u32,
+    up: bool,
+) {
+    let normal = if up {
+        [0.0, 1.0, 0.0]
+    } else {
+        [0.0, -1.0, 0.0]
+    };
+    let center = vertices.len() as u32;
+    vertices.push(Vertex3d::new([0.0, y, 0.0], normal, [0.5, 0.5]));
+    for i in 0..=segments {
+        let (c, s) = around_y(i, segments);
+        vertices.push(Vertex3d::new(
+            [c * radius, y, s * radius],
+            normal,
+            [0.5 + c * 0.5, 0.5 + s * 0.5],
+        ));
+    }
+    for i in 0..segments {
+        let rim = center + 1 + i;
+        if up {
+            indices.extend_from_slice(&[center, rim + 1, rim]);
+        } else {
+            indices.extend_from_slice(&[center, rim, rim + 1]);
+        }
+    }
+}
+
+/// Vertex and index data for [`Mesh::cylinder`].
+fn cylinder_geometry(radius: f32, height: f32, segments: u32) -> (Vec<Vertex3d>, Vec<u32>) {
+    let segments = segments.max(3);
+    let half = height * 0.5;
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    // Side: a bottom and top vertex per column
+    for i in 0..=segments {
+        let (c, s) = around_y(i, segments);
+        let u = i as f32 / segments as f32;
+        let normal = [c, 0.0, s];
+        vertices.push(Vertex3d::new(
+            [c * radius, -half, s * radius],
+            normal,
+            [u, 1.0],
+        ));
+        vertices.push(Vertex3d::new(
+            [c * radius, half, s * radius],
+            normal,
+            [u, 0.0],
+        ));
+    }
+    for i in 0..segments {
+        let bottom = i * 2;
+        let top = bottom + 1;
+        indices.extend_from_slice(&[bottom, top, bottom + 2, bottom + 2, top, top + 2]);
+    }
+
+    push_cap(&mut vertices, &mut indices, radius, half, segments, true);
+    push_cap(&mut vertices, &mut indices, radius, -half, segments, false);
+
+    (vertices, indices)
+}
+
+/// Vertex and index data for [`Mesh::cone`].
+fn cone_geometry(radius: f32, height: f32, segments: u32) -> (Vec<Vertex3d>, Vec<u32>) {
+    let segments = segments.max(3);
+    let half = height * 0.5;
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    // Side normals tilt up by the slope of the cone
+    let slope_normal =
+        |c: f32, s: f32| Vec3::new(c * height, radius, s * height).normalize_or_zero();
+
+    for i in 0..=segments {
+        let (c, s) = around_y(i, segments);
+        let u = i as f32 / segments as f32;
+        vertices.push(Vertex3d::new(
+            [c * radius, -half, s * radius],
+            slope_normal(c, s).to_array(),
+            [u, 1.0],
+        ));
+    }
+    // One tip vertex per segment, with the normal halfway between its base vertices
+    let tips = vertices.len() as u32;
+    for i in 0..segments {
+        let theta = std::f32::consts::TAU * (i as f32 + 0.5) / segments as f32;
+        let u = (i as f32 + 0.5) / segments as f32;
+        vertices.push(Vertex3d::new(
+            [0.0, half, 0.0],
+            slope_normal(theta.cos(), theta.sin()).to_array(),
+            [u, 0.0],
+        ));
+    }
+    for i in 0..segments {
+        indices.extend_from_slice(&[i, tips + i, i + 1]);
+    }
+
+    push_cap(&mut vertices, &mut indices, radius, -half, segments, false);
+
+    (vertices, indices)
+}
+
+/// Vertex and index data for [`Mesh::torus`].
+fn torus_geometry(
+    major_radius: f32,
+    minor_radius: f32,
+    segments: u32,
+    rings: u32,
+) -> (Vec<Vertex3d>, Vec<u32>) {
+    let segments = segments.max(3);
+    let rings = rings.max(3);
+    let mut vertices = Vec::with_capacity(((segments + 1) * (rings + 1)) as usize);
+    let mut indices = Vec::with_capacity((segments * rings * 6) as usize);
+
+    for i in 0..=segments {
+        let (c, s) = around_y(i, segments);
+        for j in 0..=rings {
+            let phi = std::f32::consts::TAU * j as f32 / rings as f32;
+            let normal = Vec3::new(phi.cos() * c, phi.sin(), phi.cos() * s);
+            let position =
+                Vec3::new(c * major_radius, 0.0, s * major_radius) + normal * minor_radius;
+            let uv = [i as f32 / segments as f32, j as f32 / rings as f32];
+            vertices.push(Vertex3d::new(position.to_array(), normal.to_array(), uv));
+        }
+    }
+
+    for i in 0..segments {
+        for j in 0..rings {
+            let current = i * (rings + 1) + j;
+            let next = current + rings + 1;
+            indices.extend_from_slice(&[current, current + 1, next, next, current + 1, next + 1]);
+        }
+    }
+
+    (vertices, indices)
+}
+
+/// Vertex and index data for [`Mesh::capsule`].
+fn capsule_geometry(
+    radius: f32,
+    height: f32,
+    segments: u32,
+    rings: u32,
+) -> (Vec<Vertex3d>, Vec<u32>) {
+    let segments = segments.max(3);
+    let rings = rings.max(1);
+    let height = height.max(radius * 2.0);
+    let half_body = height * 0.5 - radius;
+    let rows = 2 * rings + 2;
+    let mut vertices = Vec::with_capacity(((segments + 1) * rows) as usize);
+    let mut indices = Vec::with_capacity((segments * (rows - 1) * 6) as usize);
+
+    // Top hemisphere rows run pole to equator, then the bottom hemisphere
+    // repeats the equator lowered by the body height; the band between the
+    // two equator rows forms the cylindrical body.
+    for row in 0..rows {
+        let (phi, offset) = if row <= rings {
+            (
+                std::f32::consts::FRAC_PI_2 * row as f32 / rings as f32,
+                half_body,
+            )
+        } else {
+            let step = (row - rings - 1) as f32 / rings as f32;
+            (std::f32::consts::FRAC_PI_2 * (1.0 + step), -half_body)
+        };
+        let ring_radius = phi.sin();
+        let y = phi.cos();
+
+        for seg in 0..=segments {
+            let (c, s) = around_y(seg, segments);
+            let normal = Vec3::new(c * ring_radius, y, s * ring_radius);
+            let position = normal * radius + Vec3::new(0.0, offset, 0.0);
+            let v = (height * 0.5 - position.y) / height;
+            let uv = [seg as f32 / segments as f32, v];
+            vertices.push(Vertex3d::new(position.to_array(), normal.to_array(), uv));
+        }
+    }
+
+    for row in 0..rows - 1 {
+        for seg in 0..segments {
+            let current = row * (segments + 1) + seg;
+            let next = current + segments + 1;
+            indices.extend_from_slice(&[current, current + 1, next, current + 1, next + 1, next]);
+        }
+    }
+
+    (vertices, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts every non-degenerate triangle winds counter-clockwise when
+    /// viewed from the side its vertex normals point to.
+    fn assert_outward_ccw(vertices: &[Vertex3d], indices: &[u32]) {
+        for tri in indices.chunks(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| &vertices[tri[k] as usize]);
+            let [pa, pb, pc] = [a, b, c].map(|v| Vec3::from(v.position));
+            let face = (pb - pa).cross(pc - pa);
+            if face.length_squared() < 1e-10 {
+                continue;
+            }
+            let normal = Vec3::from(a.normal) + Vec3::from(b.normal) + Vec3::from(c.normal);
+            assert!(face.dot(normal) > 0.0, "triangle {:?} winds inward", tri);
+        }
+    }
+
+    fn assert_indices_in_range(vertices: &[Vertex3d], indices: &[u32]) {
+        assert_eq!(indices.len() % 3, 0);
+        assert!(indices.iter().all(|&i| (i as usize) < vertices.len()));
+    }
+
+    #[test]
+    fn colored_vertex_extends_vertex3d_layout() {
+        assert_eq!(std::mem::size_of::<Vertex3dColored>(), 64);
+        let plain = Vertex3d::LAYOUT.attributes;
+        let colored = Vertex3dColored::LAYOUT.attributes;
+        assert_eq!(&colored[..plain.len()], plain);
+        assert_eq!((colored[4].offset, colored[4].shader_location), (48, 4));
+
+        let vertex = Vertex3d::new([1.0, 2.0, 3.0], [0.0, 1.0, 0.0], [0.5, 0.25]);
+        let colored = Vertex3dColored::from_vertex(vertex, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(
+            bytemuck::bytes_of(&colored.vertex()),
+            bytemuck::bytes_of(&vertex)
+        );
+    }
+
+    #[test]
+    fn smooth_normals_are_area_weighted() {
+        // A small triangle facing +Z and a large one facing +X share vertex 0
+        let mut vertices = vec![
+            Vertex3d::new([0.0, 0.0, 0.0], [0.0; 3], [0.0; 2]),
+            Vertex3d::new([1.0, 0.0, 0.0], [0.0; 3], [0.0; 2]),
+            Vertex3d::new([0.0, 1.0, 0.0], [0.0; 3], [0.0; 2]),
+            Vertex3d::new([0.0, 0.0, -3.0], [0.0; 3], [0.0; 2]),
+            Vertex3d::new([0.0, 3.0, 0.0], [0.0; 3], [0.0; 2]),
+            Vertex3d::new([5.0, 5.0, 5.0], [1.0, 0.0, 0.0], [0.0; 2]),
+        ];
+        compute_smooth_normals(&mut vertices, &[0, 1, 2, 0, 3, 4]);
+
+        let shared = Vec3::from(vertices[0].normal);
+        assert!(shared.abs_diff_eq(Vec3::new(9.0, 0.0, 1.0).normalize(), 1e-5));
+        assert_eq!(vertices[1].normal, [0.0, 0.0, 1.0]);
+        assert_eq!(vertices[3].normal, [1.0, 0.0, 0.0]);
+        assert_eq!(vertices[5].normal, [0.0; 3]);
+    }
+
+    #[test]
+    fn flat_normals_split_faces_and_agree_with_authored_normals() {
+        let (vertices, indices) = cylinder_geometry(1.0, 2.0, 12);
+        let (flat, flat_indices) = compute_flat_normals(&vertices, &indices);
+        assert_eq!(flat.len(), indices.len());
+        assert_indices_in_range(&flat, &flat_indices);
+
+        for (tri, original) in flat.chunks(3).zip(indices.chunks(3)) {
+            assert!(tri.iter().all(|v| v.normal == tri[0].normal));
+            // Faces should lean the same way as the cylinder's smooth normals
+            for &i in original {
+                let authored = Vec3::from(vertices[i as usize].normal);
+                assert!(Vec3::from(tri[0].normal).dot(authored) > 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn transform_builders_compose_in_srt_order() {
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let transform = Transform::translation(1.0, 2.0, 3.0)
+            .rotate_x(quarter)
+            .rotate_y(quarter)
+            .uniform_scale(2.0);
+
+        // Later rotations apply after earlier ones, around world axes
+        let expected = glam::Quat::from_rotation_y(quarter) * glam::Quat::from_rotation_x(quarter);
+        assert!(transform.rotation.abs_diff_eq(expected, 1e-5));
+
+        // Scale, then rotate, then translate: +X -> (2,0,0) -> (0,0,-2) -> (1,2,1)
+        let point = transform.matrix().transform_point3(Vec3::X);
+        assert!(point.abs_diff_eq(Vec3::new(1.0, 2.0, 1.0), 1e-5));
+    }
+
+    #[test]
+    fn transform_looking_at_and_rotate_around() {
+        let transform = Transform::translation(3.0, 0.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y);
+        assert!((transform.rotation * Vec3::NEG_Z).abs_diff_eq(Vec3::NEG_X, 1e-5));
+        assert!((transform.rotation * Vec3::Y).abs_diff_eq(Vec3::Y, 1e-5));
+
+        // Degenerate targets leave the rotation alone
+        let same = Transform::new().rotate_z(1.0);
+        assert_eq!(same.looking_at(Vec3::ZERO, Vec3::Y).rotation, same.rotation);
+        assert_eq!(same.looking_at(Vec3::Y, Vec3::Y).rotation, same.rotation);
+
+        // Orbiting keeps the object facing the pivot
+        let orbited = transform.rotate_around(Vec3::ZERO, Vec3::Y, std::f32::consts::FRAC_PI_2);
+        assert!(
+            orbited
+                .position
+                .abs_diff_eq(Vec3::new(0.0, 0.0, -3.0), 1e-5)
+        );
+        assert!((orbited.rotation * Vec3::NEG_Z).abs_diff_eq(Vec3::Z, 1e-5));
+    }
+
+    #[test]
+    fn uv_transform_scales_then_rotates_then_offsets() {
+        assert_eq!(
+            UvTransform::default().apply(Vec2::new(0.3, 0.7)),
+            Vec2::new(0.3, 0.7)
+        );
+
+        let uv = UvTransform {
+            offset: Vec2::new(0.5, 0.0),
+            scale: Vec2::new(2.0, 1.0),
+            rotation: std::f32::consts::FRAC_PI_2,
+        };
+        // (1, 0) -> (2, 0) -> (0, 2) -> (0.5, 2)
+        assert!(
+            uv.apply(Vec2::new(1.0, 0.0))
+                .abs_diff_eq(Vec2::new(0.5, 2.0), 1e-5)
+        );
+    }
+
+    #[test]
+    fn aabb_from_points_and_transform() {
+        let (vertices, _) = capsule_geometry(0.5, 3.0, 16, 6);
+        let bounds = Aabb::from_points(vertices.iter().map(|v| Vec3::from(v.position)));
+        assert!(bounds.min.abs_diff_eq(Vec3::new(-0.5, -1.5, -0.5), 1e-5));
+        assert!(bounds.max.abs_diff_eq(Vec3::new(0.5, 1.5, 0.5), 1e-5));
+
+        let moved = bounds.transformed(&Mat4::from_scale_rotation_translation(
+            Vec3::splat(2.0),
+            glam::Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            Vec3::new(10.0, 0.0, 0.0),
+        ));
+        assert!(moved.min.abs_diff_eq(Vec3::new(7.0, -1.0, -1.0), 1e-4));
+        assert!(moved.max.abs_diff_eq(Vec3::new(13.0, 1.0, 1.0), 1e-4));
+
+        assert_eq!(Aabb::from_points([]).size(), Vec3::ZERO);
+    }
+
+    #[test]
+    fn cylinder_has_expected_counts_and_winding() {
+        let (vertices, indices) = cylinder_geometry(0.5, 2.0, 16);
+        assert_eq!(vertices.len(), 4 * 16 + 6);
+        assert_eq!(indices.len(), 4 * 16 * 3);
+        assert_indices_in_range(&vertices, &indices);
+        assert_outward_ccw(&vertices, &indices);
+    }
+
+    #[test]
+    fn cone_has_expected_counts_and_winding() {
+        let (vertices, indices) = cone_geometry(0.5, 1.0, 12);
+        assert_eq!(vertices.len(), 3 * 12 + 3);
+        assert_eq!(indices.len(), 2 * 12 * 3);
+        assert_indices_in_range(&vertices, &indices);
+        assert_outward_ccw(&vertices, &indices);
+    }
+
+    #[test]
+    fn torus_has_expected_counts_and_winding() {
+        let (vertices, indices) = torus_geometry(1.0, 0.25, 24, 12);
+        assert_eq!(vertices.len(), 25 * 13);
+        assert_eq!(indices.len(), 24 * 12 * 6);
+        assert_indices_in_range(&vertices, &indices);
+        assert_outward_ccw(&vertices, &indices);
+    }
+
+    #[test]
+    fn capsule_has_expected_counts_winding_and_height() {
+        let (vertices, indices) = capsule_geometry(0.5, 3.0, 16, 6);
+        assert_eq!(vertices.len(), 17 * (2 * 6 + 2));
+        assert_eq!(indices.len(), 16 * (2 * 6 + 1) * 6);
+        assert_indices_in_range(&vertices, &indices);
+        assert_outward_ccw(&vertices, &indices);
+
+        let (min_y, max_y) = vertices.iter().fold((f32::MAX, f32::MIN), |(lo, hi), v| {
+            (lo.min(v.position[1]), hi.max(v.position[1]))
+        });
+        assert!((min_y + 1.5).abs() < 1e-5 && (max_y - 1.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn generated_tangents_are_unit_and_perpendicular_to_normals() {
+        let (mut vertices, indices) = torus_geometry(1.0, 0.25, 24, 12);
+        generate_tangents(&mut vertices, &indices);
+
+        for vertex in &vertices {
+            let tangent = Vec3::from_slice(&vertex.tangent[..3]);
+            assert!((tangent.length() - 1.0).abs() < 1e-4);
+            assert!(tangent.dot(Vec3::from(vertex.normal)).abs() < 1e-4);
+            assert_eq!(vertex.tangent[3].abs(), 1.0);
+        }
+    }
+
+    #[test]
+    fn mirrored_uvs_flip_tangent_handedness() {
+        let triangle = |u: [f32; 3]| {
+            let mut vertices = [
+                Vertex3d::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [u[0], 1.0]),
+                Vertex3d::new([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [u[1], 1.0]),
+                Vertex3d::new([0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [u[2], 0.0]),
+            ];
+            generate_tangents(&mut vertices, &[0, 1, 2]);
+            vertices[0].tangent
+        };
+
+        assert_eq!(triangle([0.0, 1.0, 0.0]), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(triangle([1.0, 0.0, 1.0]), [-1.0, 0.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn degenerate_uvs_leave_tangents_zero() {
+        let mut vertices = [
+            Vertex3d::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0]),
+            Vertex3d::new([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0]),
+            Vertex3d::new([0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0]),
+        ];
+        generate_tangents(&mut vertices, &[0, 1, 2]);
+        assert!(vertices.iter().all(|v| v.tangent == [0.0; 4]));
+    }
+
+    #[test]
+    fn u16_indexed_triangle_renders_without_validation_errors() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        gpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let vertices = [
+            Vertex3d::new([0.0, 0.5, 0.0], [0.0, 0.0, 1.0], [0.5, 0.0]),
+            Vertex3d::new([-0.5, -0.5, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0]),
+            Vertex3d::new([0.5, -0.5, 0.0], [0.0, 0.0, 1.0], [1.0, 1.0]),
+        ];
+        let indices: [u16; 3] = [0, 1, 2];
+        let mesh = Mesh::new_u16(&gpu, &vertices, &indices);
+        assert_eq!(mesh.index_format(), wgpu::IndexFormat::Uint16);
+
+        let shader = gpu.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(
+                "@vertex fn vs(@location(0) p: vec3f) -> @builtin(position) vec4f { return vec4f(p, 1.0); }
+                 @fragment fn fs() -> @location(0) vec4f { return vec4f(1.0); }"
+                    .into(),
+            ),
+        });
+        let pipeline = gpu
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: None,
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs"),
+                    buffers: &[Vertex3d::LAYOUT],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs"),
+                    targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            });
+
+        let target = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = gpu
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+            pass.set_index_buffer(mesh.index_buffer.slice(..), mesh.index_format);
+            pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+        }
+        gpu.queue.submit([encoder.finish()]);
+
+        let error = pollster::block_on(gpu.device.pop_error_scope());
+        assert!(error.is_none(), "validation error: {:?}", error);
+
+        assert_eq!(mesh.bounds().size(), Vec3::new(1.0, 1.0, 0.0));
+        let (center, radius) = mesh.bounding_sphere();
+        assert_eq!(center, Vec3::ZERO);
+        assert!((radius - 0.5f32.hypot(0.5)).abs() < 1e-6);
+    }
+}
@@ -8,13 +8,18 @@
 //!
 //! The mesh pass uses three bind groups:
 //! - **Group 0**: Camera uniforms (view/projection matrices, camera position, time)
-//! - **Group 1**: Model uniforms (model matrix, normal matrix, color)
-//! - **Group 2**: Texture and sampler for the mesh surface
+//!   and light uniforms (see [`Lighting`])
+//! - **Group 1**: Per-instance [`InstanceData`] (model matrix, normal matrix, color)
+//!   in a storage buffer written once per frame, plus each instance's texture
+//!   array layer (binding 1), [`UvTransform`] (binding 2), and alpha cutoff (binding 3)
+//! - **Group 2**: Texture and sampler for the mesh surface (bindings 0-1), a
+//!   tangent-space normal map's sampler and texture (bindings 2-3), and a
+//!   texture array (binding 4)
 //!
 //! # Example
 //!
 //! ```ignore
-//! use hoplite::{MeshPass, DrawCall, Camera, Mesh, Transform};
+//! use hoplite::{MeshPass, DrawCall, Camera, CullMode, Mesh, Transform, UvTransform};
 //!
 //! // Create the mesh pass
 //! let mesh_pass = MeshPass::new(&gpu);
@@ -26,6 +31,12 @@
 //!         transform: Transform::from_position([0.0, 0.0, 0.0]),
 //!         color: Color::WHITE,
 //!         texture: Some(&my_texture),
+//!         layer: 0,
+//!         uv_transform: UvTransform::IDENTITY,
+//!         normal_map: None,
+//!         material: None,
+//!         cull_mode: CullMode::Back,
+//!         alpha_cutoff: 0.0,
 //!     },
 //! ];
 //!
@@ -45,10 +56,17 @@
 //! before rendering meshes. This is useful for layering the 3D scene over 2D content
 //! from previous render passes.
 
+use glam::Mat4;
+use std::ops::Range;
+
 use crate::camera::Camera;
 use crate::draw2d::Color;
+use crate::ecs::MaterialId;
+use crate::error::capture_shader_errors;
 use crate::gpu::GpuContext;
-use crate::mesh::{Mesh, Transform, Vertex3d};
+use crate::lighting::{Lighting, MAX_POINT_LIGHTS};
+use crate::mesh::{Mesh, Transform, UvTransform, Vertex3d, Vertex3dColored};
+use crate::render_stats::RenderStats;
 use crate::texture::Texture;
 
 /// Camera uniforms for 3D rendering.
@@ -85,10 +103,32 @@ pub struct InstanceData {
     pub color: [f32; 4],
 }
 
+/// Per-instance [`UvTransform`] as laid out in the UV storage buffer.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct InstanceUv {
+    offset: [f32; 2],
+    scale: [f32; 2],
+    rotation: f32,
+    _pad: f32,
+}
+
+impl From<UvTransform> for InstanceUv {
+    fn from(uv: UvTransform) -> Self {
+        Self {
+            offset: uv.offset.to_array(),
+            scale: uv.scale.to_array(),
+            rotation: uv.rotation,
+            _pad: 0.0,
+        }
+    }
+}
+
 /// Legacy per-instance model uniforms (kept for compatibility).
 ///
-/// This structure is uploaded to the GPU for each draw call and provides
-/// the shader with model-specific transformation and color data.
+/// [`MeshPass`] no longer uses this: every draw's [`InstanceData`] is packed
+/// into one storage buffer, written once per frame, and read by index from
+/// the shader.
 #[repr(C)]
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct ModelUniforms {
@@ -100,6 +140,76 @@ pub struct ModelUniforms {
     pub color: [f32; 4],
 }
 
+/// A single point light as laid out in the light uniform buffer.
+#[repr(C)]
+#[derive(Copy, Clone, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PointLightUniform {
+    /// World-space position.
+    pub position: [f32; 3],
+    /// Distance at which the light fades to zero.
+    pub range: f32,
+    /// Linear RGB color.
+    pub color: [f32; 3],
+    pub _pad: f32,
+}
+
+/// Light uniforms uploaded to the mesh shader (group 0, binding 1).
+///
+/// When both `sun_enabled` and `point_count` are zero the shader falls back to
+/// its default key light.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LightUniforms {
+    /// Normalized direction from surfaces toward the sun.
+    pub sun_direction: [f32; 3],
+    /// 1 if the sun is enabled, 0 otherwise.
+    pub sun_enabled: u32,
+    /// Linear RGB sun color.
+    pub sun_color: [f32; 3],
+    /// Ambient intensity.
+    pub ambient: f32,
+    /// Number of valid entries in `point_lights`.
+    pub point_count: u32,
+    pub _pad: [u32; 3],
+    /// Point lights; entries past `point_count` are ignored.
+    pub point_lights: [PointLightUniform; MAX_POINT_LIGHTS],
+}
+
+impl From<&Lighting> for LightUniforms {
+    fn from(lighting: &Lighting) -> Self {
+        let mut point_lights = [PointLightUniform::default(); MAX_POINT_LIGHTS];
+        for (slot, light) in point_lights.iter_mut().zip(&lighting.point_lights) {
+            *slot = PointLightUniform {
+                position: light.position.to_array(),
+                range: light.range.max(f32::EPSILON),
+                color: [light.color.r, light.color.g, light.color.b],
+                _pad: 0.0,
+            };
+        }
+
+        let (sun_direction, sun_color) = match lighting.sun {
+            Some(sun) => (
+                (-sun.direction).normalize_or(glam::Vec3::Y).to_array(),
+                [sun.color.r, sun.color.g, sun.color.b],
+            ),
+            None => ([0.0; 3], [0.0; 3]),
+        };
+
+        Self {
+            sun_direction,
+            sun_enabled: lighting.sun.is_some() as u32,
+            sun_color,
+            ambient: lighting.ambient,
+            point_count: lighting.point_lights.len().min(MAX_POINT_LIGHTS) as u32,
+            _pad: [0; 3],
+            point_lights,
+        }
+    }
+}
+
+/// Number of instances the instance buffer can hold before it first has to grow.
+const INITIAL_INSTANCE_CAPACITY: usize = 4096;
+
 /// A draw call queued for rendering.
 ///
 /// Represents a single mesh to be rendered with its associated transform,
@@ -114,7 +224,13 @@ pub struct ModelUniforms {
 ///     transform: Transform::from_position([0.0, 1.0, 0.0])
 ///         .with_scale([2.0, 2.0, 2.0]),
 ///     color: Color::RED,
-///     texture: None, // Uses default white texture
+///     texture: None,    // Uses default white texture
+///     layer: 0,         // Only used by texture arrays
+///     uv_transform: UvTransform::IDENTITY,
+///     normal_map: None, // Leaves the surface normals unchanged
+///     material: None,   // Uses the built-in mesh shader
+///     cull_mode: CullMode::Back,
+///     alpha_cutoff: 0.0, // Blends by alpha instead of cutting out
 /// };
 /// ```
 pub struct DrawCall<'a> {
@@ -126,6 +242,73 @@ pub struct DrawCall<'a> {
     pub color: Color,
     /// Optional texture to apply. If `None`, a default white texture is used.
     pub texture: Option<&'a Texture>,
+    /// Layer to sample when `texture` is a [texture array](Texture::array_from_files).
+    ///
+    /// Draws of the same mesh and array batch together whatever their layers.
+    /// Ignored for other textures.
+    pub layer: u32,
+    /// Offset, scale, and rotation applied to the mesh's UVs before sampling.
+    pub uv_transform: UvTransform,
+    /// Optional tangent-space normal map. If `None`, a flat default is used.
+    ///
+    /// Load normal maps with [`Texture::normal_map_from_file`] so they aren't
+    /// treated as sRGB colors.
+    pub normal_map: Option<&'a Texture>,
+    /// Optional custom material. If `None`, the built-in mesh shader is used.
+    pub material: Option<MaterialId>,
+    /// Which faces to discard; [`CullMode::Back`] for ordinary closed meshes.
+    pub cull_mode: CullMode,
+    /// Fragments with alpha below this are discarded and the rest drawn opaque
+    /// with depth writes, so cutouts like foliage need no sorting. `0.0`
+    /// disables the cutout and blends by alpha instead.
+    pub alpha_cutoff: f32,
+}
+
+/// Which triangle faces a mesh draw discards.
+///
+/// Front faces wind counter-clockwise. [`Front`](Self::Front) fixes meshes
+/// authored with the opposite winding, and [`None`](Self::None) draws both
+/// sides of open surfaces like foliage or cloth.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CullMode {
+    /// Discard back faces.
+    #[default]
+    Back,
+    /// Discard front faces, drawing the inside of counter-clockwise meshes or
+    /// the outside of clockwise ones.
+    Front,
+    /// Draw both faces.
+    None,
+}
+
+impl CullMode {
+    /// Every mode, in the order [`MeshPipelines`] stores their variants.
+    const ALL: [Self; 3] = [Self::Back, Self::Front, Self::None];
+
+    fn face(self) -> Option<wgpu::Face> {
+        match self {
+            Self::Back => Some(wgpu::Face::Back),
+            Self::Front => Some(wgpu::Face::Front),
+            Self::None => None,
+        }
+    }
+}
+
+/// How a mesh pipeline tests and writes the depth buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DepthMode {
+    /// Less-than test with depth writes, shading every fragment that passes.
+    Standard,
+    /// Less-than test with depth writes and no color output, laying down the
+    /// nearest depth before shading.
+    Prepass,
+    /// Shades only fragments at the depth the prepass left, without writing depth.
+    Equal,
+}
+
+impl DepthMode {
+    /// Every mode, in the order [`MeshPipelines`] stores their variants.
+    const ALL: [Self; 3] = [Self::Standard, Self::Prepass, Self::Equal];
 }
 
 /// Handles 3D mesh rendering with depth testing.
@@ -144,9 +327,12 @@ pub struct DrawCall<'a> {
 ///
 /// # Pipeline Configuration
 ///
-/// - Back-face culling enabled (counter-clockwise front faces)
+/// - Back-face culling by default (counter-clockwise front faces), with front-face
+///   and no-culling variants selected per draw by [`CullMode`]
 /// - Alpha blending for transparent meshes
 /// - Depth write and Less-than comparison
+/// - With [`set_depth_prepass`](Self::set_depth_prepass), opaque meshes first
+///   write depth only, then shade with an Equal comparison and no depth writes
 ///
 /// # Usage
 ///
@@ -154,17 +340,26 @@ pub struct DrawCall<'a> {
 /// 1. Call [`ensure_depth_size`](Self::ensure_depth_size) if the window may have resized
 /// 2. Optionally call [`blit`](Self::blit) to composite a background texture
 /// 3. Call [`render`](Self::render) with your camera and draw calls
-/// Maximum number of instances that can be rendered in a single batch.
-const MAX_INSTANCES: usize = 4096;
-
 pub struct MeshPass {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: MeshPipelines,
+    /// The built-in mesh shader, which also supplies the prepass fragment stage.
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    /// Compiled material pipelines indexed by [`MaterialId`]; `None` if compilation failed.
+    materials: Vec<Option<MeshPipelines>>,
     camera_buffer: wgpu::Buffer,
+    light_buffer: wgpu::Buffer,
     camera_bind_group: wgpu::BindGroup,
     instance_buffer: wgpu::Buffer,
-    #[allow(dead_code)]
+    /// Texture array layer of each instance, parallel to `instance_buffer`.
+    layer_buffer: wgpu::Buffer,
+    /// [`InstanceUv`] of each instance, parallel to `instance_buffer`.
+    uv_buffer: wgpu::Buffer,
+    /// Alpha cutoff of each instance, parallel to `instance_buffer`.
+    cutoff_buffer: wgpu::Buffer,
     instance_bind_group_layout: wgpu::BindGroupLayout,
     instance_bind_group: wgpu::BindGroup,
+    instance_capacity: usize,
     /// The depth texture used for depth testing.
     pub(crate) depth_texture: wgpu::Texture,
     /// View into the depth texture for render pass attachment.
@@ -175,6 +370,13 @@ pub struct MeshPass {
     blit_sampler: wgpu::Sampler,
     texture_bind_group_layout: wgpu::BindGroupLayout,
     default_texture: Texture,
+    /// Single white layer bound at binding 4 when the surface isn't a texture array.
+    default_texture_array: Texture,
+    default_normal_map: Texture,
+    /// Color target format of every pipeline.
+    format: wgpu::TextureFormat,
+    /// Whether opaque draws are preceded by a depth-only pass.
+    depth_prepass: bool,
 }
 
 impl MeshPass {
@@ -191,6 +393,14 @@ impl MeshPass {
     ///
     /// * `gpu` - The GPU context containing the device, queue, and surface configuration
     pub fn new(gpu: &GpuContext) -> Self {
+        Self::new_with_format(gpu, gpu.config.format)
+    }
+
+    /// Creates a mesh pass that renders into color targets of `format`.
+    ///
+    /// Use this when the pass writes into an intermediate target of another
+    /// format, such as [`HDR_FORMAT`](crate::HDR_FORMAT).
+    pub fn new_with_format(gpu: &GpuContext, format: wgpu::TextureFormat) -> Self {
         let device = &gpu.device;
 
         // Create shader
@@ -207,61 +417,111 @@ impl MeshPass {
             mapped_at_creation: false,
         });
 
+        use wgpu::util::DeviceExt;
+
+        // Starts with no lights configured, i.e. the shader's default lighting
+        let light_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Light Uniforms"),
+            contents: bytemuck::cast_slice(&[LightUniforms::from(&Lighting::default())]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
         let camera_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Camera Bind Group Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
                     },
-                    count: None,
-                }],
+                ],
             });
 
         let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Camera Bind Group"),
             layout: &camera_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: camera_buffer.as_entire_binding(),
-            }],
-        });
-
-        // Instance storage buffer (group 1) - holds all instance data for batched rendering
-        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Storage Buffer"),
-            size: (std::mem::size_of::<InstanceData>() * MAX_INSTANCES) as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: camera_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: light_buffer.as_entire_binding(),
+                },
+            ],
         });
 
         let instance_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Instance Bind Group Layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: true },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
                     },
-                    count: None,
-                }],
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
             });
 
-        let instance_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Instance Bind Group"),
-            layout: &instance_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: instance_buffer.as_entire_binding(),
-            }],
-        });
+        // Instance storage buffers (group 1) - hold all instance data for batched rendering
+        let (instance_buffer, layer_buffer, uv_buffer, cutoff_buffer, instance_bind_group) =
+            Self::create_instance_buffer(
+                gpu,
+                &instance_bind_group_layout,
+                INITIAL_INSTANCE_CAPACITY,
+            );
 
         // Texture bind group layout (group 2)
         let texture_bind_group_layout =
@@ -284,6 +544,32 @@ impl MeshPass {
                         ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                         count: None,
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 4,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2Array,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
                 ],
             });
 
@@ -291,6 +577,15 @@ impl MeshPass {
         let default_texture =
             Texture::from_rgba(gpu, &[255, 255, 255, 255], 1, 1, "Default White Texture");
 
+        // The shader multiplies both surface textures, so white leaves the other
+        // unchanged. Two layers, since GL can't bind a one-layer array view
+        let default_texture_array =
+            Texture::array_from_rgba(gpu, &[255; 8], 1, 1, 2, "Default White Texture Array");
+
+        // A flat normal (0, 0, 1) for meshes without a normal map
+        let default_normal_map =
+            Texture::normal_map_from_rgba(gpu, &[128, 128, 255, 255], 1, 1, "Default Normal Map");
+
         // Pipeline layout
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Mesh Pipeline Layout"),
@@ -364,7 +659,7 @@ impl MeshPass {
                 module: &blit_shader,
                 entry_point: Some("fs"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
+                    format,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -380,62 +675,261 @@ impl MeshPass {
             cache: None,
         });
 
-        // Render pipeline
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Mesh Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs"),
-                buffers: &[Vertex3d::LAYOUT],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                cull_mode: Some(wgpu::Face::Back),
-                front_face: wgpu::FrontFace::Ccw,
-                ..Default::default()
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Depth32Float,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        let pipeline =
+            Self::create_default_pipelines(gpu, &pipeline_layout, &shader, format, false);
 
         Self {
             pipeline,
+            shader,
+            pipeline_layout,
+            materials: Vec::new(),
             camera_buffer,
+            light_buffer,
             camera_bind_group,
             instance_buffer,
+            layer_buffer,
+            uv_buffer,
+            cutoff_buffer,
             instance_bind_group_layout,
             instance_bind_group,
+            instance_capacity: INITIAL_INSTANCE_CAPACITY,
             depth_texture,
             depth_view,
-            depth_size: (gpu.width(), gpu.height()),
+            depth_size: (gpu.render_width(), gpu.render_height()),
             blit_pipeline,
             blit_bind_group_layout,
             blit_sampler,
             texture_bind_group_layout,
             default_texture,
+            default_texture_array,
+            default_normal_map,
+            format,
+            depth_prepass: false,
         }
     }
 
+    /// Creates the built-in shader's pipelines, with variants that read per-vertex colors.
+    fn create_default_pipelines(
+        gpu: &GpuContext,
+        layout: &wgpu::PipelineLayout,
+        shader: &wgpu::ShaderModule,
+        format: wgpu::TextureFormat,
+        depth_prepass: bool,
+    ) -> MeshPipelines {
+        MeshPipelines::new(depth_prepass, |vertex_colors, cull_mode, depth_mode| {
+            if vertex_colors {
+                Self::create_pipeline(
+                    gpu,
+                    layout,
+                    (shader, shader),
+                    format,
+                    "Mesh Pipeline (Vertex Colors)",
+                    "vs_colored",
+                    Vertex3dColored::LAYOUT,
+                    cull_mode,
+                    depth_mode,
+                )
+            } else {
+                Self::create_pipeline(
+                    gpu,
+                    layout,
+                    (shader, shader),
+                    format,
+                    "Mesh Pipeline",
+                    "vs",
+                    Vertex3d::LAYOUT,
+                    cull_mode,
+                    depth_mode,
+                )
+            }
+        })
+    }
+
+    /// Creates a mesh pipeline for `shader` using the shared bind group layouts.
+    ///
+    /// `shaders` is the pipeline's own shader and the built-in mesh shader, whose
+    /// empty `fs_depth_only` entry point is the fragment stage of
+    /// [`DepthMode::Prepass`] pipelines.
+    #[allow(clippy::too_many_arguments)]
+    fn create_pipeline(
+        gpu: &GpuContext,
+        layout: &wgpu::PipelineLayout,
+        shaders: (&wgpu::ShaderModule, &wgpu::ShaderModule),
+        format: wgpu::TextureFormat,
+        label: &str,
+        vertex_entry: &str,
+        vertex_layout: wgpu::VertexBufferLayout<'static>,
+        cull_mode: CullMode,
+        depth_mode: DepthMode,
+    ) -> wgpu::RenderPipeline {
+        let (shader, mesh_shader) = shaders;
+        let fragment = if depth_mode == DepthMode::Prepass {
+            // The target stays attached so the prepass shares the color pass,
+            // but nothing is written to it
+            wgpu::FragmentState {
+                module: mesh_shader,
+                entry_point: Some("fs_depth_only"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::empty(),
+                })],
+                compilation_options: Default::default(),
+            }
+        } else {
+            wgpu::FragmentState {
+                module: shader,
+                entry_point: Some("fs"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }
+        };
+        gpu.device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(layout),
+                vertex: wgpu::VertexState {
+                    module: shader,
+                    entry_point: Some(vertex_entry),
+                    buffers: &[vertex_layout],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(fragment),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    cull_mode: cull_mode.face(),
+                    front_face: wgpu::FrontFace::Ccw,
+                    ..Default::default()
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: wgpu::TextureFormat::Depth32Float,
+                    depth_write_enabled: depth_mode != DepthMode::Equal,
+                    depth_compare: if depth_mode == DepthMode::Equal {
+                        wgpu::CompareFunction::Equal
+                    } else {
+                        wgpu::CompareFunction::Less
+                    },
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+    }
+
+    /// Compiles pipelines for any materials added since the last call.
+    ///
+    /// `sources` holds the WGSL of every registered material, indexed by
+    /// [`MaterialId`]. Materials that fail to compile are reported with a
+    /// `[shader]` prefix and render with the default mesh shader instead.
+    ///
+    /// Each material also gets pipelines for meshes with vertex colors. They
+    /// use the material's own `vs` entry point, so the color is ignored.
+    pub fn ensure_materials(&mut self, gpu: &GpuContext, sources: &[String]) {
+        for (index, source) in sources.iter().enumerate().skip(self.materials.len()) {
+            let label = format!("Mesh Material {}", index);
+            let pipeline = capture_shader_errors(&gpu.device, || {
+                let shader = gpu
+                    .device
+                    .create_shader_module(wgpu::ShaderModuleDescriptor {
+                        label: Some(&label),
+                        source: wgpu::ShaderSource::Wgsl(source.as_str().into()),
+                    });
+                MeshPipelines::new(
+                    self.depth_prepass,
+                    |vertex_colors, cull_mode, depth_mode| {
+                        let vertex_layout = if vertex_colors {
+                            Vertex3dColored::LAYOUT
+                        } else {
+                            Vertex3d::LAYOUT
+                        };
+                        Self::create_pipeline(
+                            gpu,
+                            &self.pipeline_layout,
+                            (&shader, &self.shader),
+                            self.format,
+                            &label,
+                            "vs",
+                            vertex_layout,
+                            cull_mode,
+                            depth_mode,
+                        )
+                    },
+                )
+            });
+            match pipeline {
+                Ok(pipeline) => self.materials.push(Some(pipeline)),
+                Err(e) => {
+                    eprintln!("[shader] Failed to create material {}: {}", index, e);
+                    self.materials.push(None);
+                }
+            }
+        }
+    }
+
+    /// Returns the pipeline for a material, or the default mesh pipeline.
+    fn material_pipeline(
+        &self,
+        material: Option<MaterialId>,
+        vertex_colors: bool,
+        cull_mode: CullMode,
+        depth_mode: DepthMode,
+    ) -> &wgpu::RenderPipeline {
+        material
+            .and_then(|id| self.materials.get(id.0))
+            .and_then(Option::as_ref)
+            .unwrap_or(&self.pipeline)
+            .get(vertex_colors, cull_mode, depth_mode)
+    }
+
+    /// Enables or disables the depth prepass used by subsequent [`render`](Self::render) calls.
+    ///
+    /// With the prepass, opaque draws are first drawn writing depth only, then
+    /// shaded with an Equal depth test and no depth writes, so each pixel runs
+    /// the full fragment shader once however much geometry overlaps it. Draws
+    /// with a translucent color (alpha below 1) or an alpha cutoff skip the
+    /// prepass and are shaded after the opaque ones, as usual.
+    ///
+    /// Enabling builds the extra pipeline variants, and materials are compiled
+    /// again by the next [`ensure_materials`](Self::ensure_materials) call.
+    /// Does nothing if the setting is unchanged.
+    pub fn set_depth_prepass(&mut self, gpu: &GpuContext, enabled: bool) {
+        if self.depth_prepass == enabled {
+            return;
+        }
+        self.depth_prepass = enabled;
+        self.pipeline = Self::create_default_pipelines(
+            gpu,
+            &self.pipeline_layout,
+            &self.shader,
+            self.format,
+            enabled,
+        );
+        self.materials.clear();
+    }
+
+    /// Returns whether the depth prepass is enabled.
+    pub fn depth_prepass(&self) -> bool {
+        self.depth_prepass
+    }
+
+    /// Uploads the lights used by subsequent [`render`](Self::render) calls.
+    ///
+    /// Only the first [`MAX_POINT_LIGHTS`] point lights are uploaded. An empty
+    /// [`Lighting`] restores the shader's default lighting.
+    pub fn set_lighting(&self, gpu: &GpuContext, lighting: &Lighting) {
+        gpu.queue.write_buffer(
+            &self.light_buffer,
+            0,
+            bytemuck::cast_slice(&[LightUniforms::from(lighting)]),
+        );
+    }
+
     /// Creates a bind group for a texture.
     ///
     /// This creates a GPU bind group that binds a texture and its sampler
@@ -455,28 +949,152 @@ impl MeshPass {
         gpu: &GpuContext,
         texture: &Texture,
     ) -> wgpu::BindGroup {
+        self.create_surface_bind_group(gpu, texture, &self.default_normal_map)
+    }
+
+    /// Creates a group 2 bind group for a texture and a tangent-space normal map.
+    ///
+    /// Like [`create_texture_bind_group`](Self::create_texture_bind_group), but
+    /// binds `normal_map` at bindings 2-3 instead of the flat default.
+    ///
+    /// A [texture array](Texture::array_from_files) is bound at binding 4 with
+    /// a white texture at binding 0; any other texture is bound at binding 0
+    /// with a single white layer at binding 4.
+    pub fn create_surface_bind_group(
+        &self,
+        gpu: &GpuContext,
+        texture: &Texture,
+        normal_map: &Texture,
+    ) -> wgpu::BindGroup {
+        let (single, array) = if texture.array {
+            (&self.default_texture, texture)
+        } else {
+            (texture, &self.default_texture_array)
+        };
         gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Mesh Texture Bind Group"),
             layout: &self.texture_bind_group_layout,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&texture.view),
+                    resource: wgpu::BindingResource::TextureView(&single.view),
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
                     resource: wgpu::BindingResource::Sampler(&texture.sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&normal_map.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&normal_map.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(&array.view),
+                },
             ],
         })
     }
 
+    /// Creates the instance data, layer, and UV buffers for `capacity` instances, and their bind group.
+    fn create_instance_buffer(
+        gpu: &GpuContext,
+        layout: &wgpu::BindGroupLayout,
+        capacity: usize,
+    ) -> (
+        wgpu::Buffer,
+        wgpu::Buffer,
+        wgpu::Buffer,
+        wgpu::Buffer,
+        wgpu::BindGroup,
+    ) {
+        let buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance Storage Buffer"),
+            size: (std::mem::size_of::<InstanceData>() * capacity) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let layers = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance Layer Buffer"),
+            size: (std::mem::size_of::<u32>() * capacity) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let uvs = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance UV Buffer"),
+            size: (std::mem::size_of::<InstanceUv>() * capacity) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let cutoffs = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance Alpha Cutoff Buffer"),
+            size: (std::mem::size_of::<f32>() * capacity) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Instance Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: layers.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: uvs.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: cutoffs.as_entire_binding(),
+                },
+            ],
+        });
+
+        (buffer, layers, uvs, cutoffs, bind_group)
+    }
+
+    /// Ensures the instance buffer can hold at least `count` instances.
+    ///
+    /// Call this before rendering whenever the number of draw calls may have
+    /// grown. The buffer grows to the next power of two and never shrinks.
+    /// Draw calls beyond the current capacity are dropped by [`render`](Self::render).
+    ///
+    /// # Arguments
+    ///
+    /// * `gpu` - The GPU context
+    /// * `count` - Number of draw calls that will be rendered
+    pub fn ensure_instance_capacity(&mut self, gpu: &GpuContext, count: usize) {
+        if count > self.instance_capacity {
+            let capacity = count.next_power_of_two();
+            let (buffer, layers, uvs, cutoffs, bind_group) =
+                Self::create_instance_buffer(gpu, &self.instance_bind_group_layout, capacity);
+            self.instance_buffer = buffer;
+            self.layer_buffer = layers;
+            self.uv_buffer = uvs;
+            self.cutoff_buffer = cutoffs;
+            self.instance_bind_group = bind_group;
+            self.instance_capacity = capacity;
+        }
+    }
+
     fn create_depth_texture(gpu: &GpuContext) -> (wgpu::Texture, wgpu::TextureView) {
         let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Depth Texture"),
             size: wgpu::Extent3d {
-                width: gpu.width(),
-                height: gpu.height(),
+                width: gpu.render_width(),
+                height: gpu.render_height(),
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
@@ -490,21 +1108,22 @@ impl MeshPass {
         (texture, view)
     }
 
-    /// Ensures the depth buffer matches the current screen size.
+    /// Ensures the depth buffer matches the current render resolution.
     ///
     /// Call this method at the start of each frame if the window may have been
     /// resized. If the depth buffer dimensions don't match the GPU context's
-    /// current dimensions, a new depth texture is created.
+    /// [`render_width`](GpuContext::render_width) and
+    /// [`render_height`](GpuContext::render_height), a new depth texture is created.
     ///
     /// # Arguments
     ///
     /// * `gpu` - The GPU context with the current screen dimensions
     pub fn ensure_depth_size(&mut self, gpu: &GpuContext) {
-        if self.depth_size != (gpu.width(), gpu.height()) {
+        if self.depth_size != (gpu.render_width(), gpu.render_height()) {
             let (texture, view) = Self::create_depth_texture(gpu);
             self.depth_texture = texture;
             self.depth_view = view;
-            self.depth_size = (gpu.width(), gpu.height());
+            self.depth_size = (gpu.render_width(), gpu.render_height());
         }
     }
 
@@ -558,6 +1177,22 @@ impl MeshPass {
         render_pass.draw(0..3, 0..1);
     }
 
+    /// Projection matrix used for rendering with this camera.
+    ///
+    /// Uses the camera's own clip planes, so rendering agrees with
+    /// [`Frame::mouse_ray`](crate::Frame::mouse_ray) and other picking.
+    fn projection(gpu: &GpuContext, camera: &Camera) -> Mat4 {
+        camera.projection_matrix(gpu.aspect(), camera.near, camera.far)
+    }
+
+    /// Combined view-projection matrix used for rendering with this camera.
+    ///
+    /// Matches the matrix uploaded by [`render`](Self::render), so it can be used
+    /// to build a [`Frustum`](crate::Frustum) for culling.
+    pub fn view_projection(gpu: &GpuContext, camera: &Camera) -> Mat4 {
+        Self::projection(gpu, camera) * camera.view_matrix()
+    }
+
     /// Renders a list of draw calls.
     ///
     /// This is the main rendering method for the mesh pass. It updates camera
@@ -568,7 +1203,8 @@ impl MeshPass {
     ///
     /// * `gpu` - The GPU context
     /// * `render_pass` - The active render pass to draw into (must have depth attachment)
-    /// * `camera` - The camera providing view and projection matrices
+    /// * `camera` - The camera providing view and projection matrices, clipped
+    ///   to its `near` and `far` planes
     /// * `time` - Elapsed time in seconds (passed to shaders for animation)
     /// * `draw_calls` - Slice of draw calls to render
     ///
@@ -576,16 +1212,29 @@ impl MeshPass {
     ///
     /// - Returns early if `draw_calls` is empty
     /// - Camera uniforms are updated once at the start
-    /// - For each draw call:
-    ///   - Model and normal matrices are computed from the transform
-    ///   - A texture bind group is created (using default white if no texture specified)
-    ///   - The mesh is drawn with indexed rendering
+    /// - Draw calls are grouped by (material, cull mode, mesh, texture, normal map) in order
+    ///   of first appearance, then ordered by pipeline so each one is bound once
+    /// - With the [depth prepass](Self::set_depth_prepass), opaque groups are first
+    ///   drawn depth-only, and translucent or cutout ones are drawn after all opaque ones
+    /// - For each group:
+    ///   - Model and normal matrices are computed from each transform
+    ///   - A texture bind group is created (using default white if no texture
+    ///     specified, and a flat normal map if no normal map is specified)
+    ///   - All instances are drawn with a single instanced `draw_indexed`
     ///
     /// # Performance
     ///
     /// Uses instanced rendering with a storage buffer for per-instance data.
-    /// All instance transforms and colors are uploaded once before the render pass,
-    /// then batched draw calls render all instances of each mesh type efficiently.
+    /// Instances of each group are packed contiguously and uploaded in one write,
+    /// so thousands of copies of the same mesh cost one draw call. A mesh with a
+    /// unique texture simply becomes a group with a single instance.
+    ///
+    /// At most as many draw calls as the instance buffer holds are rendered; call
+    /// [`ensure_instance_capacity`](Self::ensure_instance_capacity) first to grow it.
+    ///
+    /// # Returns
+    ///
+    /// The draw calls, triangles, and mesh instances submitted.
     pub fn render(
         &self,
         gpu: &GpuContext,
@@ -593,14 +1242,14 @@ impl MeshPass {
         camera: &Camera,
         time: f32,
         draw_calls: &[DrawCall],
-    ) {
+    ) -> RenderStats {
         if draw_calls.is_empty() {
-            return;
+            return RenderStats::default();
         }
 
         // Update camera uniforms
         let view = camera.view_matrix();
-        let proj = camera.projection_matrix(gpu.aspect(), 0.1, 1000.0);
+        let proj = Self::projection(gpu, camera);
         let view_proj = proj * view;
 
         let camera_uniforms = CameraUniforms {
@@ -617,65 +1266,582 @@ impl MeshPass {
             bytemuck::cast_slice(&[camera_uniforms]),
         );
 
-        // Build instance data for all draw calls and group by (mesh, texture)
-        // We use raw pointers as keys since we need to identify unique mesh/texture combinations
+        // Group draw calls by (material, cull mode, mesh, texture, normal map), keeping the
+        // order each group first appears. We use raw pointers as keys since we need to identify unique
+        // mesh/texture combinations
         use std::collections::HashMap;
 
-        // Key: (mesh pointer, texture pointer)
-        // Value: (mesh reference, texture reference, list of instance indices)
-        type BatchKey = (*const Mesh, *const Texture);
-        let mut batches: HashMap<BatchKey, (&Mesh, &Texture, Vec<u32>)> = HashMap::new();
-
-        let mut instance_data: Vec<InstanceData> =
-            Vec::with_capacity(draw_calls.len().min(MAX_INSTANCES));
+        type BatchKey = (
+            bool,
+            Option<MaterialId>,
+            CullMode,
+            *const Mesh,
+            *const Texture,
+            *const Texture,
+        );
+        let mut batch_lookup: HashMap<BatchKey, usize> = HashMap::new();
+        let mut batches: Vec<Batch> = Vec::new();
 
-        for call in draw_calls.iter().take(MAX_INSTANCES) {
-            let model_matrix = call.transform.matrix();
-            let normal_matrix = model_matrix.inverse().transpose();
+        for call in draw_calls.iter().take(self.instance_capacity) {
+            let texture = call.texture.unwrap_or(&self.default_texture);
+            let normal_map = call.normal_map.unwrap_or(&self.default_normal_map);
+            // The prepass can't discard, so cutouts are shaded without one
+            let skips_prepass =
+                self.depth_prepass && (call.color.a < 1.0 || call.alpha_cutoff > 0.0);
+            let key: BatchKey = (
+                skips_prepass,
+                call.material,
+                call.cull_mode,
+                call.mesh as *const Mesh,
+                texture as *const Texture,
+                normal_map as *const Texture,
+            );
 
-            let instance_idx = instance_data.len() as u32;
-            instance_data.push(InstanceData {
-                model: model_matrix.to_cols_array_2d(),
-                normal_matrix: normal_matrix.to_cols_array_2d(),
-                color: [call.color.r, call.color.g, call.color.b, call.color.a],
+            let batch_idx = *batch_lookup.entry(key).or_insert_with(|| {
+                batches.push(Batch {
+                    skips_prepass,
+                    material: call.material,
+                    cull_mode: call.cull_mode,
+                    mesh: call.mesh,
+                    texture,
+                    normal_map,
+                    calls: Vec::new(),
+                });
+                batches.len() - 1
             });
+            batches[batch_idx].calls.push(call);
+        }
 
-            let texture = call.texture.unwrap_or(&self.default_texture);
-            let key: BatchKey = (call.mesh as *const Mesh, texture as *const Texture);
+        // Keep batches sharing a pipeline adjacent so each one is bound once.
+        // The sort is stable, so draw order within a pipeline is preserved.
+        // Draws skipping the prepass come last so they can't hide opaque ones
+        // behind them from the Equal depth test.
+        batches.sort_by_key(|batch| {
+            (
+                batch.skips_prepass,
+                batch.material,
+                batch.mesh.vertex_colors,
+                batch.cull_mode,
+            )
+        });
+
+        // Pack each batch's instances contiguously so it can be drawn with one instance range
+        let mut instance_data: Vec<InstanceData> =
+            Vec::with_capacity(draw_calls.len().min(self.instance_capacity));
+        let mut layers: Vec<u32> = Vec::with_capacity(instance_data.capacity());
+        let mut uvs: Vec<InstanceUv> = Vec::with_capacity(instance_data.capacity());
+        let mut cutoffs: Vec<f32> = Vec::with_capacity(instance_data.capacity());
+        let mut ranges = Vec::with_capacity(batches.len());
+
+        for batch in &batches {
+            let start = instance_data.len() as u32;
+            for call in &batch.calls {
+                let model_matrix = call.transform.matrix();
+                let normal_matrix = model_matrix.inverse().transpose();
 
-            batches
-                .entry(key)
-                .or_insert_with(|| (call.mesh, texture, Vec::new()))
-                .2
-                .push(instance_idx);
+                instance_data.push(InstanceData {
+                    model: model_matrix.to_cols_array_2d(),
+                    normal_matrix: normal_matrix.to_cols_array_2d(),
+                    color: [call.color.r, call.color.g, call.color.b, call.color.a],
+                });
+                layers.push(call.layer);
+                uvs.push(call.uv_transform.into());
+                cutoffs.push(call.alpha_cutoff);
+            }
+            ranges.push(start..instance_data.len() as u32);
         }
 
         // Upload all instance data in one write before the render pass
-        if !instance_data.is_empty() {
-            gpu.queue.write_buffer(
-                &self.instance_buffer,
-                0,
-                bytemuck::cast_slice(&instance_data),
-            );
-        }
+        gpu.queue.write_buffer(
+            &self.instance_buffer,
+            0,
+            bytemuck::cast_slice(&instance_data),
+        );
+        gpu.queue
+            .write_buffer(&self.layer_buffer, 0, bytemuck::cast_slice(&layers));
+        gpu.queue
+            .write_buffer(&self.uv_buffer, 0, bytemuck::cast_slice(&uvs));
+        gpu.queue
+            .write_buffer(&self.cutoff_buffer, 0, bytemuck::cast_slice(&cutoffs));
 
-        render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
         render_pass.set_bind_group(1, &self.instance_bind_group, &[]);
 
-        // Render each batch with instanced drawing
-        for (_key, (mesh, texture, indices)) in batches {
-            let texture_bind_group = self.create_texture_bind_group(gpu, texture);
-            render_pass.set_bind_group(2, &texture_bind_group, &[]);
+        let texture_bind_groups: Vec<wgpu::BindGroup> = batches
+            .iter()
+            .map(|batch| self.create_surface_bind_group(gpu, batch.texture, batch.normal_map))
+            .collect();
+
+        let mut stats = RenderStats {
+            mesh_count: instance_data.len(),
+            ..Default::default()
+        };
+
+        // Render each batch with a single instanced draw, switching pipelines only
+        // when the material, vertex format, cull mode, or depth mode changes
+        let mut bound_pipeline = None;
+        let mut draw = |batch: &Batch, bind_group, instances: Range<u32>, depth_mode| {
+            let pipeline_key = (
+                batch.material,
+                batch.mesh.vertex_colors,
+                batch.cull_mode,
+                depth_mode,
+            );
+            if bound_pipeline != Some(pipeline_key) {
+                render_pass.set_pipeline(self.material_pipeline(
+                    batch.material,
+                    batch.mesh.vertex_colors,
+                    batch.cull_mode,
+                    depth_mode,
+                ));
+                bound_pipeline = Some(pipeline_key);
+            }
+
+            render_pass.set_bind_group(2, bind_group, &[]);
 
+            let mesh = batch.mesh;
             render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.set_index_buffer(mesh.index_buffer.slice(..), mesh.index_format);
+
+            stats.draw_calls += 1;
+            stats.triangles += (mesh.index_count / 3) as usize * instances.len();
+
+            // Each instance reads its data from the storage buffer via instance_index
+            render_pass.draw_indexed(0..mesh.index_count, 0, instances);
+        };
 
-            // Draw all instances of this mesh/texture combination
-            // Each instance uses its index into the storage buffer
-            for &instance_idx in &indices {
-                render_pass.draw_indexed(0..mesh.index_count, 0, instance_idx..instance_idx + 1);
+        let batches = batches.iter().zip(&texture_bind_groups).zip(ranges);
+        if self.depth_prepass {
+            for ((batch, bind_group), instances) in batches.clone() {
+                if !batch.skips_prepass {
+                    draw(batch, bind_group, instances, DepthMode::Prepass);
+                }
+            }
+        }
+        for ((batch, bind_group), instances) in batches {
+            let depth_mode = if self.depth_prepass && !batch.skips_prepass {
+                DepthMode::Equal
+            } else {
+                DepthMode::Standard
+            };
+            draw(batch, bind_group, instances, depth_mode);
+        }
+        stats
+    }
+}
+
+/// Pipelines for one mesh shader, by [`DepthMode`], vertex format, and [`CullMode`].
+struct MeshPipelines {
+    /// Flattened variants in [`index`](Self::index) order. Only
+    /// [`DepthMode::Standard`] variants exist unless built for a depth prepass.
+    variants: Vec<wgpu::RenderPipeline>,
+}
+
+impl MeshPipelines {
+    /// Builds every variant with `create(vertex_colors, cull_mode, depth_mode)`.
+    fn new(
+        depth_prepass: bool,
+        create: impl Fn(bool, CullMode, DepthMode) -> wgpu::RenderPipeline,
+    ) -> Self {
+        let depth_modes = if depth_prepass {
+            &DepthMode::ALL[..]
+        } else {
+            &DepthMode::ALL[..1]
+        };
+        let mut variants = Vec::new();
+        for &depth_mode in depth_modes {
+            for vertex_colors in [false, true] {
+                for cull_mode in CullMode::ALL {
+                    variants.push(create(vertex_colors, cull_mode, depth_mode));
+                }
             }
         }
+        Self { variants }
+    }
+
+    /// Position of a variant in `variants`.
+    fn index(vertex_colors: bool, cull_mode: CullMode, depth_mode: DepthMode) -> usize {
+        (depth_mode as usize * 2 + vertex_colors as usize) * CullMode::ALL.len()
+            + cull_mode as usize
+    }
+
+    fn get(
+        &self,
+        vertex_colors: bool,
+        cull_mode: CullMode,
+        depth_mode: DepthMode,
+    ) -> &wgpu::RenderPipeline {
+        &self.variants[Self::index(vertex_colors, cull_mode, depth_mode)]
+    }
+}
+
+/// Draw calls sharing a pipeline, mesh, and surface textures, drawn as one instanced call.
+struct Batch<'a, 'c> {
+    /// Translucent or alpha-cutout draws, shaded after the prepassed ones
+    /// without a prepass. Always `false` while it's disabled.
+    skips_prepass: bool,
+    material: Option<MaterialId>,
+    cull_mode: CullMode,
+    mesh: &'a Mesh,
+    texture: &'a Texture,
+    normal_map: &'a Texture,
+    calls: Vec<&'c DrawCall<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn light_uniforms_match_wgsl_layout() {
+        // 3 x 16-byte header rows followed by 32-byte point lights
+        assert_eq!(std::mem::size_of::<PointLightUniform>(), 32);
+        assert_eq!(
+            std::mem::size_of::<LightUniforms>(),
+            48 + 32 * MAX_POINT_LIGHTS
+        );
+    }
+
+    #[test]
+    fn light_uniforms_point_toward_sun_and_cap_point_lights() {
+        let mut lighting = Lighting::default();
+        let default = LightUniforms::from(&lighting);
+        assert_eq!((default.sun_enabled, default.point_count), (0, 0));
+
+        lighting.set_sun(Vec3::new(0.0, -2.0, 0.0), Color::WHITE);
+        for i in 0..MAX_POINT_LIGHTS + 3 {
+            lighting.add_point_light(Vec3::splat(i as f32), Color::WHITE, 5.0);
+        }
+
+        let uniforms = LightUniforms::from(&lighting);
+        assert_eq!(uniforms.sun_enabled, 1);
+        assert_eq!(uniforms.sun_direction, [0.0, 1.0, 0.0]);
+        assert_eq!(uniforms.point_count, MAX_POINT_LIGHTS as u32);
+        assert_eq!(uniforms.point_lights[2].position, [2.0; 3]);
+    }
+
+    #[test]
+    fn instance_uv_matches_wgsl_layout() {
+        // vec2f offset, vec2f scale, f32 rotation, f32 padding
+        assert_eq!(std::mem::size_of::<InstanceUv>(), 24);
+        let uv = InstanceUv::from(UvTransform {
+            rotation: 1.5,
+            ..UvTransform::IDENTITY
+        });
+        assert_eq!(
+            (uv.offset, uv.scale, uv.rotation),
+            ([0.0; 2], [1.0; 2], 1.5)
+        );
+    }
+
+    #[test]
+    fn cull_modes_index_their_pipeline_variants() {
+        for (index, mode) in CullMode::ALL.into_iter().enumerate() {
+            assert_eq!(mode as usize, index);
+        }
+        assert_eq!(CullMode::default().face(), Some(wgpu::Face::Back));
+        assert_eq!(CullMode::Front.face(), Some(wgpu::Face::Front));
+        assert_eq!(CullMode::None.face(), None);
+    }
+
+    #[test]
+    fn pipeline_variants_have_distinct_indices_in_build_order() {
+        let mut expected = 0;
+        for depth_mode in DepthMode::ALL {
+            for vertex_colors in [false, true] {
+                for cull_mode in CullMode::ALL {
+                    assert_eq!(
+                        MeshPipelines::index(vertex_colors, cull_mode, depth_mode),
+                        expected
+                    );
+                    expected += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn alpha_cutout_discards_or_draws_opaque() {
+        // Center and corner pixels of an 8x8 frame with a half transparent cube
+        let render = |cutoff: f32| {
+            let result = crate::run_headless_fallible(8, 8, 1, move |ctx| {
+                ctx.enable_mesh_rendering();
+                let cube = ctx.mesh_cube();
+                move |frame: &mut crate::Frame| {
+                    frame.camera.position = Vec3::new(0.0, 0.0, 3.0);
+                    frame.camera.forward = Vec3::NEG_Z;
+                    frame
+                        .mesh(cube)
+                        .color(Color::rgba(1.0, 1.0, 1.0, 0.4))
+                        .alpha_cutout(cutoff)
+                        .draw();
+                }
+            });
+            result.map(|pixels| {
+                (
+                    pixels[(4 * 8 + 4) * 4..][..4].to_vec(),
+                    pixels[..4].to_vec(),
+                )
+            })
+        };
+        let (blended, kept, discarded) = match (render(0.0), render(0.3), render(0.5)) {
+            (Ok(blended), Ok(kept), Ok(discarded)) => (blended, kept, discarded),
+            (Err(crate::Error::Gpu(e)), _, _)
+            | (_, Err(crate::Error::Gpu(e)), _)
+            | (_, _, Err(crate::Error::Gpu(e))) => {
+                crate::test_support::skip_without_gpu(e);
+                return;
+            }
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => panic!("{}", e),
+        };
+
+        // Above the cutoff the cube is drawn opaque, brighter than when blended
+        assert!(kept.0[0] > blended.0[0], "{:?} vs {:?}", kept.0, blended.0);
+        let (center, background) = discarded;
+        assert_eq!(center, background, "cube below the cutoff wasn't discarded");
+    }
+
+    #[test]
+    fn depth_prepass_renders_the_same_image() {
+        // Overlapping opaque cubes behind a translucent one
+        let render = |prepass: bool| {
+            crate::run_headless_fallible(16, 16, 1, move |ctx| {
+                ctx.enable_mesh_rendering();
+                if prepass {
+                    ctx.enable_depth_prepass();
+                }
+                let cube = ctx.mesh_cube();
+                move |frame: &mut crate::Frame| {
+                    frame.camera.position = Vec3::new(0.0, 0.0, 3.0);
+                    frame.camera.forward = Vec3::NEG_Z;
+                    for (x, z, color) in [
+                        (-0.4, -1.0, Color::rgb(1.0, 0.2, 0.2)),
+                        (0.4, 0.0, Color::rgb(0.2, 1.0, 0.2)),
+                        (0.0, 1.5, Color::rgba(0.2, 0.2, 1.0, 0.5)),
+                    ] {
+                        frame
+                            .mesh(cube)
+                            .transform(Transform::from_position(Vec3::new(x, 0.0, z)))
+                            .color(color)
+                            .draw();
+                    }
+                }
+            })
+        };
+        let (without, with) = match (render(false), render(true)) {
+            (Ok(without), Ok(with)) => (without, with),
+            (Err(crate::Error::Gpu(e)), _) | (_, Err(crate::Error::Gpu(e))) => {
+                crate::test_support::skip_without_gpu(e);
+                return;
+            }
+            (Err(e), _) | (_, Err(e)) => panic!("{}", e),
+        };
+        let (center, background) = (&without[(8 * 16 + 8) * 4..][..4], &without[..4]);
+        assert_ne!(center, background, "the cubes weren't drawn");
+        assert_eq!(without, with, "the prepass changed the rendered image");
+    }
+
+    #[test]
+    fn depth_prepass_keeps_occluded_meshes_hidden() {
+        // A green cube in front of a red one drawn after it
+        let result = crate::run_headless_fallible(8, 8, 1, |ctx| {
+            ctx.enable_mesh_rendering();
+            ctx.enable_depth_prepass();
+            let cube = ctx.mesh_cube();
+            move |frame: &mut crate::Frame| {
+                frame.camera.position = Vec3::new(0.0, 0.0, 2.0);
+                frame.camera.forward = Vec3::NEG_Z;
+                for (z, color) in [
+                    (0.0, Color::rgb(0.0, 1.0, 0.0)),
+                    (-2.0, Color::rgb(1.0, 0.0, 0.0)),
+                ] {
+                    frame
+                        .mesh(cube)
+                        .transform(Transform::from_position(Vec3::new(0.0, 0.0, z)))
+                        .color(color)
+                        .draw();
+                }
+            }
+        });
+        let pixels = match result {
+            Ok(pixels) => pixels,
+            Err(crate::Error::Gpu(e)) => {
+                crate::test_support::skip_without_gpu(e);
+                return;
+            }
+            Err(e) => panic!("{}", e),
+        };
+        let center = &pixels[(4 * 8 + 4) * 4..][..3];
+        assert!(
+            center[1] > 0 && center[0] == 0,
+            "occluded cube showed through: {center:?}"
+        );
+    }
+
+    #[test]
+    fn stats_report_the_previous_frames_draws() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let recorded = seen.clone();
+        let result = crate::run_headless_fallible(8, 8, 2, move |ctx| {
+            ctx.enable_mesh_rendering();
+            let cube = ctx.mesh_cube();
+            move |frame: &mut crate::Frame| {
+                recorded.borrow_mut().push(frame.stats());
+                // Three instances of one mesh share a single draw
+                for x in [-1.0, 0.0, 1.0] {
+                    frame
+                        .mesh(cube)
+                        .transform(Transform::from_position(Vec3::new(x, 0.0, -4.0)))
+                        .draw();
+                }
+                frame.rect(0.0, 0.0, 2.0, 2.0, Color::WHITE);
+            }
+        });
+        match result {
+            Ok(_) => {}
+            Err(crate::Error::Gpu(e)) => {
+                crate::test_support::skip_without_gpu(e);
+                return;
+            }
+            Err(e) => panic!("{}", e),
+        }
+
+        let seen = seen.borrow();
+        assert_eq!(seen[0], RenderStats::default());
+        assert_eq!(
+            seen[1],
+            RenderStats {
+                draw_calls: 2,
+                triangles: 3 * 12,
+                vertices_2d: 6,
+                mesh_count: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn untextured_meshes_are_not_black() {
+        let result = crate::run_headless_fallible(8, 8, 1, |ctx| {
+            ctx.enable_mesh_rendering();
+            let cube = ctx.mesh_cube();
+            move |frame: &mut crate::Frame| {
+                // Close enough that the cube covers the center pixel
+                frame.camera.position = Vec3::new(0.0, 0.0, 2.0);
+                frame.mesh(cube).draw();
+            }
+        });
+        let pixels = match result {
+            Ok(pixels) => pixels,
+            Err(crate::Error::Gpu(e)) => {
+                crate::test_support::skip_without_gpu(e);
+                return;
+            }
+            Err(e) => panic!("{}", e),
+        };
+        let center = &pixels[(4 * 8 + 4) * 4..][..3];
+        assert_ne!(center, [0, 0, 0], "untextured cube rendered black");
+    }
+
+    #[test]
+    fn meshes_render_out_to_the_camera_far_plane() {
+        // Center and corner pixels of an 8x8 frame with a cube whose near face
+        // is 1005 units away
+        let render = |far: f32| {
+            let result = crate::run_headless_fallible(8, 8, 1, move |ctx| {
+                ctx.enable_mesh_rendering();
+                let cube = ctx.mesh_cube();
+                move |frame: &mut crate::Frame| {
+                    frame.camera.far = far;
+                    frame
+                        .mesh(cube)
+                        .transform(
+                            Transform::from_position(Vec3::new(0.0, 0.0, -1500.0))
+                                .scale(Vec3::splat(1000.0)),
+                        )
+                        .draw();
+                }
+            });
+            result.map(|pixels| {
+                (
+                    pixels[(4 * 8 + 4) * 4..][..4].to_vec(),
+                    pixels[..4].to_vec(),
+                )
+            })
+        };
+        let (visible, clipped) = match (render(2000.0), render(1000.0)) {
+            (Ok(visible), Ok(clipped)) => (visible, clipped),
+            (Err(crate::Error::Gpu(e)), _) | (_, Err(crate::Error::Gpu(e))) => {
+                crate::test_support::skip_without_gpu(e);
+                return;
+            }
+            (Err(e), _) | (_, Err(e)) => panic!("{}", e),
+        };
+
+        let (center, background) = visible;
+        assert_ne!(center, background, "cube inside the far plane wasn't drawn");
+        let (center, background) = clipped;
+        assert_eq!(
+            center, background,
+            "cube beyond the far plane wasn't clipped"
+        );
+    }
+
+    #[test]
+    fn mesh_shader_compiles() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        crate::error::capture_shader_errors(&gpu.device, || {
+            gpu.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(include_str!("shaders/mesh.wgsl").into()),
+                })
+        })
+        .expect("mesh.wgsl failed to compile");
+    }
+
+    #[test]
+    fn vertex_color_pipeline_matches_layout() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        crate::error::capture_shader_errors(&gpu.device, || {
+            let shader = gpu
+                .device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(include_str!("shaders/mesh.wgsl").into()),
+                });
+            gpu.device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: None,
+                    layout: None,
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: Some("vs_colored"),
+                        buffers: &[Vertex3dColored::LAYOUT],
+                        compilation_options: Default::default(),
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point: Some("fs"),
+                        targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                        compilation_options: Default::default(),
+                    }),
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    multiview: None,
+                    cache: None,
+                })
+        })
+        .expect("vs_colored doesn't match Vertex3dColored::LAYOUT");
     }
 }
@@ -0,0 +1,351 @@
+//! glTF model loading.
+//!
+//! A glTF file can hold many meshes arranged in a node hierarchy, each with
+//! its own material. [`SetupContext::model_from_gltf`](crate::SetupContext::model_from_gltf)
+//! flattens all of that into a list of [`ModelPart`]s: one registered mesh per
+//! glTF primitive, placed by its world-space node transform and carrying its
+//! base color and base-color texture.
+//!
+//! Only base color is read from materials; animations, skins, and the rest of
+//! the PBR parameters are ignored.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let helmet = ctx.model_from_gltf("assets/helmet.glb")?;
+//!
+//! move |frame| {
+//!     let spin = Transform::new().rotation(Quat::from_rotation_y(frame.time));
+//!     frame.draw_model(&helmet, spin);
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use glam::Mat4;
+
+use crate::draw2d::Color;
+use crate::ecs::{MeshId, TextureId};
+use crate::geometry::{GeometryError, RawGeometry};
+use crate::gpu::GpuContext;
+use crate::mesh::{Transform, Vertex3d};
+use crate::render_graph::MeshQueue;
+use crate::texture::Texture;
+
+/// One mesh of a loaded model, ready to draw.
+///
+/// Parts that share a glTF mesh or image share the same [`MeshId`] and
+/// [`TextureId`], so drawing a model with repeated parts still instances well.
+#[derive(Clone, Copy, Debug)]
+pub struct ModelPart {
+    /// The uploaded geometry.
+    pub mesh: MeshId,
+    /// World-space placement from the flattened node hierarchy.
+    pub transform: Transform,
+    /// Base color factor of the part's material.
+    pub color: Color,
+    /// Base-color texture of the part's material, if it has one.
+    pub texture: Option<TextureId>,
+}
+
+/// CPU-side contents of a glTF file, before anything is uploaded.
+pub(crate) struct GltfModel {
+    /// One entry per glTF primitive.
+    pub primitives: Vec<GltfPrimitive>,
+    /// `(primitive index, world transform)` for every placed primitive.
+    pub instances: Vec<(usize, Transform)>,
+    /// Decoded images as `(rgba8 pixels, width, height)`.
+    pub images: Vec<(Vec<u8>, u32, u32)>,
+}
+
+/// A single glTF primitive with its material's base color.
+pub(crate) struct GltfPrimitive {
+    pub geometry: RawGeometry,
+    pub color: Color,
+    /// Index into [`GltfModel::images`].
+    pub image: Option<usize>,
+}
+
+impl GltfModel {
+    /// Loads a `.gltf` or `.glb` file along with its buffers and images.
+    pub fn load(path: &Path) -> Result<Self, GeometryError> {
+        let (document, buffers, images) = gltf::import(path).map_err(gltf_error)?;
+        Self::from_import(&document, &buffers, &images)
+    }
+
+    /// Loads a model from `.glb` bytes or self-contained `.gltf` JSON.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, GeometryError> {
+        let (document, buffers, images) = gltf::import_slice(bytes).map_err(gltf_error)?;
+        Self::from_import(&document, &buffers, &images)
+    }
+
+    fn from_import(
+        document: &gltf::Document,
+        buffers: &[gltf::buffer::Data],
+        images: &[gltf::image::Data],
+    ) -> Result<Self, GeometryError> {
+        let mut model = GltfModel {
+            primitives: Vec::new(),
+            instances: Vec::new(),
+            images: images.iter().map(image_to_rgba).collect::<Result<_, _>>()?,
+        };
+
+        // Primitive indices for each glTF mesh, so meshes reused by several
+        // nodes are only converted once
+        let mut mesh_primitives: Vec<Vec<usize>> = Vec::new();
+        for mesh in document.meshes() {
+            let mut indices = Vec::new();
+            for primitive in mesh.primitives() {
+                if primitive.mode() != gltf::mesh::Mode::Triangles {
+                    continue;
+                }
+                indices.push(model.primitives.len());
+                model.primitives.push(read_primitive(&primitive, buffers)?);
+            }
+            mesh_primitives.push(indices);
+        }
+
+        let scene = document
+            .default_scene()
+            .or_else(|| document.scenes().next())
+            .ok_or_else(|| GeometryError::ParseError("glTF contains no scenes".to_string()))?;
+
+        let mut stack: Vec<(gltf::Node, Mat4)> =
+            scene.nodes().map(|node| (node, Mat4::IDENTITY)).collect();
+        while let Some((node, parent)) = stack.pop() {
+            let world = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+            if let Some(mesh) = node.mesh() {
+                let (scale, rotation, position) = world.to_scale_rotation_translation();
+                let transform = Transform {
+                    position,
+                    rotation,
+                    scale,
+                };
+                for &primitive in &mesh_primitives[mesh.index()] {
+                    model.instances.push((primitive, transform));
+                }
+            }
+            stack.extend(node.children().map(|child| (child, world)));
+        }
+
+        if model.instances.is_empty() {
+            return Err(GeometryError::ParseError(
+                "glTF scene contains no triangle meshes".to_string(),
+            ));
+        }
+
+        Ok(model)
+    }
+
+    /// Uploads every primitive and referenced image, registering them with `queue`.
+    ///
+    /// Images are uploaded once no matter how many primitives use them.
+    pub fn upload(self, gpu: &GpuContext, queue: &mut MeshQueue) -> Vec<ModelPart> {
+        let mut textures: HashMap<usize, TextureId> = HashMap::new();
+        let primitives: Vec<(MeshId, Color, Option<TextureId>)> = self
+            .primitives
+            .into_iter()
+            .map(|primitive| {
+                let texture = primitive.image.map(|image| {
+                    *textures.entry(image).or_insert_with(|| {
+                        let (pixels, width, height) = &self.images[image];
+                        let label = format!("glTF Image {}", image);
+                        queue.add_texture(Texture::from_rgba(gpu, pixels, *width, *height, &label))
+                    })
+                });
+                let mesh = queue.add_mesh(primitive.geometry.upload(gpu));
+                (mesh, primitive.color, texture)
+            })
+            .collect();
+
+        self.instances
+            .into_iter()
+            .map(|(primitive, transform)| {
+                let (mesh, color, texture) = primitives[primitive];
+                ModelPart {
+                    mesh,
+                    transform,
+                    color,
+                    texture,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Reads a triangle primitive's vertices, indices, and base color.
+///
+/// Missing normals are recomputed, missing UVs default to `[0.0, 0.0]`, and
+/// non-indexed primitives get sequential indices.
+fn read_primitive(
+    primitive: &gltf::Primitive,
+    buffers: &[gltf::buffer::Data],
+) -> Result<GltfPrimitive, GeometryError> {
+    let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|data| &data.0[..]));
+
+    let positions: Vec<[f32; 3]> = reader
+        .read_positions()
+        .ok_or_else(|| GeometryError::ParseError("glTF primitive has no positions".to_string()))?
+        .collect();
+    let normals: Option<Vec<[f32; 3]>> = reader.read_normals().map(Iterator::collect);
+    let uvs: Option<Vec<[f32; 2]>> = reader
+        .read_tex_coords(0)
+        .map(|coords| coords.into_f32().collect());
+    let indices: Vec<u32> = match reader.read_indices() {
+        Some(indices) => indices.into_u32().collect(),
+        None => (0..positions.len() as u32).collect(),
+    };
+
+    let vertices = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &position)| {
+            let normal = normals.as_ref().map_or([0.0; 3], |n| n[i]);
+            let uv = uvs.as_ref().map_or([0.0; 2], |uv| uv[i]);
+            Vertex3d::new(position, normal, uv)
+        })
+        .collect();
+
+    let mut geometry = RawGeometry::new(vertices, indices);
+    if normals.is_none() {
+        geometry.recalculate_normals();
+    }
+
+    let pbr = primitive.material().pbr_metallic_roughness();
+    let [r, g, b, a] = pbr.base_color_factor();
+
+    Ok(GltfPrimitive {
+        geometry,
+        color: Color::rgba(r, g, b, a),
+        image: pbr
+            .base_color_texture()
+            .map(|info| info.texture().source().index()),
+    })
+}
+
+/// Expands an 8-bit glTF image to RGBA8.
+fn image_to_rgba(image: &gltf::image::Data) -> Result<(Vec<u8>, u32, u32), GeometryError> {
+    use gltf::image::Format;
+
+    let pixels = match image.format {
+        Format::R8G8B8A8 => image.pixels.clone(),
+        Format::R8G8B8 => image
+            .pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        Format::R8G8 => image
+            .pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        Format::R8 => image.pixels.iter().flat_map(|&l| [l, l, l, 255]).collect(),
+        other => {
+            return Err(GeometryError::ParseError(format!(
+                "unsupported glTF image format {:?}",
+                other
+            )));
+        }
+    };
+
+    Ok((pixels, image.width, image.height))
+}
+
+fn gltf_error(error: gltf::Error) -> GeometryError {
+    match error {
+        gltf::Error::Io(e) => GeometryError::Io(e),
+        other => GeometryError::ParseError(format!("glTF error: {}", other)),
+    }
+}
+
+/// Combines a parent transform with a part's own transform.
+pub(crate) fn compose(parent: Transform, part: Transform) -> Transform {
+    let (scale, rotation, position) =
+        (parent.matrix() * part.matrix()).to_scale_rotation_translation();
+    Transform {
+        position,
+        rotation,
+        scale,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{Quat, Vec3};
+
+    /// One red triangle without normals, placed by a nested node and a root node.
+    const TRIANGLE_GLTF: &str = r#"{
+        "asset": { "version": "2.0" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0, 2] }],
+        "nodes": [
+            { "translation": [1, 0, 0], "children": [1] },
+            { "scale": [2, 2, 2], "mesh": 0 },
+            { "translation": [0, 5, 0], "mesh": 0 }
+        ],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 }, "material": 0 }] }],
+        "materials": [{ "pbrMetallicRoughness": { "baseColorFactor": [1, 0, 0, 1] } }],
+        "accessors": [{
+            "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [0, 0, 0], "max": [1, 1, 0]
+        }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "buffers": [{
+            "byteLength": 36,
+            "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAAAACAPwAAAAAAAAAAAAAAAAAAgD8AAAAA"
+        }]
+    }"#;
+
+    #[test]
+    fn gltf_hierarchy_is_flattened_into_world_transforms() {
+        let model = GltfModel::from_slice(TRIANGLE_GLTF.as_bytes()).unwrap();
+
+        // The mesh is converted once and placed by both nodes
+        assert_eq!(model.primitives.len(), 1);
+        let mut placements: Vec<_> = model
+            .instances
+            .iter()
+            .map(|(primitive, t)| (*primitive, t.position, t.scale))
+            .collect();
+        placements.sort_by(|a, b| a.1.y.total_cmp(&b.1.y));
+        assert_eq!(
+            placements,
+            [
+                (0, Vec3::new(1.0, 0.0, 0.0), Vec3::splat(2.0)),
+                (0, Vec3::new(0.0, 5.0, 0.0), Vec3::ONE),
+            ]
+        );
+
+        let primitive = &model.primitives[0];
+        assert_eq!(primitive.color, Color::rgba(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(primitive.image, None);
+        assert_eq!(primitive.geometry.indices, [0, 1, 2]);
+        // Missing normals are recomputed from the winding
+        for vertex in &primitive.geometry.vertices {
+            assert!((Vec3::from(vertex.normal) - Vec3::Z).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn invalid_gltf_is_a_parse_error() {
+        assert!(matches!(
+            GltfModel::from_slice(b"not a model"),
+            Err(GeometryError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn compose_applies_parent_after_part() {
+        let parent = Transform {
+            position: Vec3::new(0.0, 0.0, -5.0),
+            rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::ONE,
+        };
+        let part = Transform::from_position(Vec3::new(1.0, 0.0, 0.0));
+
+        let placed = compose(parent, part);
+        assert!((placed.position - Vec3::new(0.0, 0.0, -6.0)).length() < 1e-5);
+    }
+}
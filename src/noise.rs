@@ -0,0 +1,246 @@
+//! Deterministic CPU noise for terrain, procedural textures, and animation.
+//!
+//! Every function takes a `seed`; the same inputs always give the same output
+//! on every platform, and different seeds give unrelated fields. All noise is
+//! continuous, repeating only over the full `i32` lattice.
+//!
+//! | Function | Range | Character |
+//! |----------|-------|-----------|
+//! | [`value_noise_2d`], [`value_noise_3d`] | `[0, 1]` | Blobby, grid-aligned |
+//! | [`perlin_2d`], [`perlin_3d`] | `[-1, 1]`, zero at integer points | Smooth gradient noise |
+//! | [`fbm_2d`], [`fbm_3d`] | `[-1, 1]` | Layered Perlin with finer detail per octave |
+//!
+//! # Example
+//!
+//! ```ignore
+//! use hoplite::noise;
+//!
+//! // Heightmap for a 64x64 terrain grid
+//! let heights: Vec<f32> = (0..64 * 64)
+//!     .map(|i| {
+//!         let (x, z) = ((i % 64) as f32, (i / 64) as f32);
+//!         noise::fbm_2d(x * 0.05, z * 0.05, 1234, 5) * 8.0
+//!     })
+//!     .collect();
+//!
+//! // Wobble a light over time
+//! let flicker = noise::perlin_2d(frame.time * 3.0, 0.0, 7) * 0.2 + 1.0;
+//! ```
+
+/// The 12 cube-edge gradients of improved Perlin noise.
+const GRADIENTS_3D: [[f32; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
+
+/// Value noise in `[0, 1]` on a 2D lattice.
+pub fn value_noise_2d(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, fx) = split(x);
+    let (y0, fy) = split(y);
+    let corner = |dx: i32, dy: i32| unit(hash(x0 + dx, y0 + dy, 0, seed));
+    let (u, v) = (fade(fx), fade(fy));
+    lerp(
+        lerp(corner(0, 0), corner(1, 0), u),
+        lerp(corner(0, 1), corner(1, 1), u),
+        v,
+    )
+}
+
+/// Value noise in `[0, 1]` on a 3D lattice.
+pub fn value_noise_3d(x: f32, y: f32, z: f32, seed: u32) -> f32 {
+    let (x0, fx) = split(x);
+    let (y0, fy) = split(y);
+    let (z0, fz) = split(z);
+    let corner = |dx: i32, dy: i32, dz: i32| unit(hash(x0 + dx, y0 + dy, z0 + dz, seed));
+    let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+    let layer = |dz: i32| {
+        lerp(
+            lerp(corner(0, 0, dz), corner(1, 0, dz), u),
+            lerp(corner(0, 1, dz), corner(1, 1, dz), u),
+            v,
+        )
+    };
+    lerp(layer(0), layer(1), w)
+}
+
+/// Perlin gradient noise in `[-1, 1]`, zero at integer coordinates.
+pub fn perlin_2d(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, fx) = split(x);
+    let (y0, fy) = split(y);
+    let corner = |dx: i32, dy: i32| {
+        // One of 8 evenly spaced unit gradients
+        let angle = (hash(x0 + dx, y0 + dy, 0, seed) & 7) as f32 * std::f32::consts::FRAC_PI_4;
+        let (sin, cos) = angle.sin_cos();
+        cos * (fx - dx as f32) + sin * (fy - dy as f32)
+    };
+    let (u, v) = (fade(fx), fade(fy));
+    let n = lerp(
+        lerp(corner(0, 0), corner(1, 0), u),
+        lerp(corner(0, 1), corner(1, 1), u),
+        v,
+    );
+    // Unit gradients peak at sqrt(1/2); stretch that to the full range
+    (n * std::f32::consts::SQRT_2).clamp(-1.0, 1.0)
+}
+
+/// Perlin gradient noise in `[-1, 1]`, zero at integer coordinates.
+pub fn perlin_3d(x: f32, y: f32, z: f32, seed: u32) -> f32 {
+    let (x0, fx) = split(x);
+    let (y0, fy) = split(y);
+    let (z0, fz) = split(z);
+    let corner = |dx: i32, dy: i32, dz: i32| {
+        let g = GRADIENTS_3D[(hash(x0 + dx, y0 + dy, z0 + dz, seed) % 12) as usize];
+        g[0] * (fx - dx as f32) + g[1] * (fy - dy as f32) + g[2] * (fz - dz as f32)
+    };
+    let (u, v, w) = (fade(fx), fade(fy), fade(fz));
+    let layer = |dz: i32| {
+        lerp(
+            lerp(corner(0, 0, dz), corner(1, 0, dz), u),
+            lerp(corner(0, 1, dz), corner(1, 1, dz), u),
+            v,
+        )
+    };
+    lerp(layer(0), layer(1), w).clamp(-1.0, 1.0)
+}
+
+/// Fractal Brownian motion: `octaves` layers of [`perlin_2d`], in `[-1, 1]`.
+///
+/// Each octave doubles the frequency and halves the amplitude, and the sum is
+/// normalized so adding octaves adds detail without changing the range.
+/// Zero octaves is treated as one.
+pub fn fbm_2d(x: f32, y: f32, seed: u32, octaves: u32) -> f32 {
+    fbm(octaves, seed, |frequency, seed| {
+        perlin_2d(x * frequency, y * frequency, seed)
+    })
+}
+
+/// Fractal Brownian motion: `octaves` layers of [`perlin_3d`], in `[-1, 1]`.
+///
+/// See [`fbm_2d`].
+pub fn fbm_3d(x: f32, y: f32, z: f32, seed: u32, octaves: u32) -> f32 {
+    fbm(octaves, seed, |frequency, seed| {
+        perlin_3d(x * frequency, y * frequency, z * frequency, seed)
+    })
+}
+
+/// Sums octaves of `sample(frequency, seed)`, each with its own seed, normalized by total amplitude.
+fn fbm(octaves: u32, seed: u32, sample: impl Fn(f32, u32) -> f32) -> f32 {
+    let mut sum = 0.0;
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    for octave in 0..octaves.max(1) {
+        sum += amplitude * sample(frequency, seed.wrapping_add(octave));
+        total += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    sum / total
+}
+
+/// Splits a coordinate into its lattice cell and the offset within it.
+fn split(t: f32) -> (i32, f32) {
+    let cell = t.floor();
+    (cell as i32, t - cell)
+}
+
+/// Quintic fade curve, with zero first and second derivatives at 0 and 1.
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Maps a hash to `[0, 1]`.
+fn unit(h: u32) -> f32 {
+    (h >> 8) as f32 / ((1 << 24) - 1) as f32
+}
+
+/// Mixes lattice coordinates and a seed into a well-scrambled `u32`.
+fn hash(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    let mut h = seed.wrapping_mul(0x27D4_EB2D)
+        ^ (x as u32).wrapping_mul(0x8DA6_B343)
+        ^ (y as u32).wrapping_mul(0xD816_3841)
+        ^ (z as u32).wrapping_mul(0xCB1A_B31F);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2C1B_3C6D);
+    h ^= h >> 12;
+    h = h.wrapping_mul(0x297A_2D39);
+    h ^= h >> 15;
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sample points spread over several lattice cells, including negatives.
+    fn samples() -> impl Iterator<Item = (f32, f32, f32)> {
+        (0..2000).map(|i| {
+            let i = i as f32;
+            (i * 0.137 - 40.0, i * 0.291 - 90.0, i * 0.053 - 17.0)
+        })
+    }
+
+    #[test]
+    fn noise_stays_in_documented_ranges() {
+        for (x, y, z) in samples() {
+            assert!((0.0..=1.0).contains(&value_noise_2d(x, y, 3)));
+            assert!((0.0..=1.0).contains(&value_noise_3d(x, y, z, 3)));
+            assert!((-1.0..=1.0).contains(&perlin_2d(x, y, 3)));
+            assert!((-1.0..=1.0).contains(&perlin_3d(x, y, z, 3)));
+            assert!((-1.0..=1.0).contains(&fbm_2d(x, y, 3, 6)));
+            assert!((-1.0..=1.0).contains(&fbm_3d(x, y, z, 3, 6)));
+        }
+    }
+
+    #[test]
+    fn noise_is_continuous() {
+        const STEP: f32 = 1e-3;
+        // The fade curve's slope is at most 1.875, so a small step moves each
+        // function by a small multiple of it, even across cell boundaries
+        const MAX_CHANGE: f32 = 0.02;
+        for (x, y, z) in samples() {
+            let pairs = [
+                (value_noise_2d(x, y, 9), value_noise_2d(x + STEP, y, 9)),
+                (
+                    value_noise_3d(x, y, z, 9),
+                    value_noise_3d(x, y, z + STEP, 9),
+                ),
+                (perlin_2d(x, y, 9), perlin_2d(x, y + STEP, 9)),
+                (perlin_3d(x, y, z, 9), perlin_3d(x + STEP, y, z, 9)),
+                (fbm_2d(x, y, 9, 4), fbm_2d(x + STEP, y + STEP, 9, 4)),
+            ];
+            for (a, b) in pairs {
+                assert!((a - b).abs() < MAX_CHANGE, "jump from {} to {}", a, b);
+            }
+        }
+        // Crossing an integer boundary is as smooth as anywhere else
+        let below = perlin_2d(-STEP, 0.5, 1);
+        let above = perlin_2d(STEP, 0.5, 1);
+        assert!((below - above).abs() < MAX_CHANGE);
+    }
+
+    #[test]
+    fn noise_is_deterministic_per_seed() {
+        assert_eq!(perlin_3d(1.3, 2.7, -0.4, 5), perlin_3d(1.3, 2.7, -0.4, 5));
+        assert_ne!(perlin_2d(1.3, 2.7, 5), perlin_2d(1.3, 2.7, 6));
+        assert_ne!(value_noise_2d(0.5, 0.5, 5), value_noise_2d(0.5, 0.5, 6));
+
+        // Perlin noise passes through zero on the lattice
+        assert_eq!(perlin_2d(4.0, -2.0, 5), 0.0);
+        assert_eq!(perlin_3d(4.0, -2.0, 1.0, 5), 0.0);
+    }
+}
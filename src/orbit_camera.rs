@@ -1,9 +1,13 @@
-use glam::Vec3;
+use glam::{Vec2, Vec3};
 use winit::event::MouseButton;
 
-use crate::camera::Camera;
+use crate::camera::{Camera, ProjectionMode};
+use crate::follow_camera::damping;
 use crate::input::Input;
 
+/// Elevation limit short of straight up or down, to avoid gimbal lock.
+const MAX_ELEVATION: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
 /// Controls how the orbit camera moves.
 #[derive(Clone, Copy, Debug)]
 pub enum OrbitMode {
@@ -22,13 +26,34 @@ impl Default for OrbitMode {
     }
 }
 
+/// Input not yet applied to the camera, eased out over later updates.
+#[derive(Clone, Copy, Debug, Default)]
+struct PendingMotion {
+    /// Azimuth and elevation change in radians.
+    rotation: Vec2,
+    /// Distance change.
+    zoom: f32,
+    /// Target offset in world space.
+    pan: Vec3,
+}
+
 /// A camera controller that orbits around a target point.
 ///
+/// In interactive mode, dragging with the left mouse button rotates around
+/// the target, the scroll wheel zooms between the zoom limits, and, with
+/// [`enable_pan`](Self::enable_pan), dragging with the middle button slides
+/// the target across the view. With [`damping`](Self::damping) set, the
+/// camera eases into each movement and keeps gliding briefly after the mouse
+/// stops instead of following it rigidly.
+///
 /// # Example
 /// ```ignore
 /// let mut orbit = OrbitCamera::new()
 ///     .target(Vec3::ZERO)
 ///     .distance(5.0)
+///     .zoom_limits(1.0, 20.0)
+///     .enable_pan(true)
+///     .damping(0.1)
 ///     .mode(OrbitMode::Interactive);
 ///
 /// // In frame loop:
@@ -57,6 +82,11 @@ pub struct OrbitCamera {
     pub min_distance: f32,
     /// Maximum distance from target.
     pub max_distance: f32,
+    /// Whether middle-mouse dragging pans the target.
+    pub pan_enabled: bool,
+    /// Damping time constant in seconds; `0` applies input immediately.
+    pub damping: f32,
+    pending: PendingMotion,
 }
 
 impl Default for OrbitCamera {
@@ -72,6 +102,9 @@ impl Default for OrbitCamera {
             zoom_sensitivity: 0.5,
             min_distance: 0.5,
             max_distance: 100.0,
+            pan_enabled: false,
+            damping: 0.0,
+            pending: PendingMotion::default(),
         }
     }
 }
@@ -113,10 +146,7 @@ impl OrbitCamera {
 
     /// Set the initial elevation (vertical angle) in radians.
     pub fn elevation(mut self, elevation: f32) -> Self {
-        self.elevation = elevation.clamp(
-            -std::f32::consts::FRAC_PI_2 + 0.01,
-            std::f32::consts::FRAC_PI_2 - 0.01,
-        );
+        self.elevation = elevation.clamp(-MAX_ELEVATION, MAX_ELEVATION);
         self
     }
 
@@ -132,14 +162,35 @@ impl OrbitCamera {
         self
     }
 
-    /// Set distance limits.
-    pub fn distance_limits(mut self, min: f32, max: f32) -> Self {
+    /// Set how close and how far the scroll wheel can zoom.
+    pub fn zoom_limits(mut self, min: f32, max: f32) -> Self {
         self.min_distance = min;
         self.max_distance = max;
         self.distance = self.distance.clamp(min, max);
         self
     }
 
+    /// Set distance limits. Same as [`zoom_limits`](Self::zoom_limits).
+    pub fn distance_limits(self, min: f32, max: f32) -> Self {
+        self.zoom_limits(min, max)
+    }
+
+    /// Enable or disable panning the target with the middle mouse button.
+    pub fn enable_pan(mut self, enabled: bool) -> Self {
+        self.pan_enabled = enabled;
+        self
+    }
+
+    /// Set the damping time constant in seconds.
+    ///
+    /// Input is applied gradually, covering about 63% of what's left every
+    /// `smoothing` seconds, so motion eases out after the mouse stops. `0`
+    /// (the default) applies input immediately.
+    pub fn damping(mut self, smoothing: f32) -> Self {
+        self.damping = smoothing.max(0.0);
+        self
+    }
+
     /// Update the camera based on input and delta time.
     pub fn update(&mut self, input: &Input, dt: f32) {
         match self.mode {
@@ -147,27 +198,54 @@ impl OrbitCamera {
                 // Rotate when left mouse button is held
                 if input.mouse_down(MouseButton::Left) {
                     let delta = input.mouse_delta();
-                    self.azimuth -= delta.x * self.sensitivity;
-                    self.elevation += delta.y * self.sensitivity;
-
-                    // Clamp elevation to avoid gimbal lock
-                    self.elevation = self.elevation.clamp(
-                        -std::f32::consts::FRAC_PI_2 + 0.01,
-                        std::f32::consts::FRAC_PI_2 - 0.01,
-                    );
+                    self.pending.rotation += Vec2::new(-delta.x, delta.y) * self.sensitivity;
                 }
 
-                // Zoom with scroll wheel
-                let scroll = input.scroll_delta();
-                if scroll.y.abs() > 0.0 {
-                    self.distance -= scroll.y * self.zoom_sensitivity;
-                    self.distance = self.distance.clamp(self.min_distance, self.max_distance);
+                // Pan across the view plane when middle mouse button is held,
+                // scaled by distance so the target tracks the cursor
+                if self.pan_enabled && input.mouse_down(MouseButton::Middle) {
+                    let delta = input.mouse_delta();
+                    let camera = self.camera();
+                    let right = camera.forward.cross(Vec3::Y).normalize_or(Vec3::X);
+                    let up = right.cross(camera.forward);
+                    let scale = self.sensitivity * self.distance;
+                    self.pending.pan += (up * delta.y - right * delta.x) * scale;
                 }
+
+                // Zoom with scroll wheel
+                self.pending.zoom -= input.scroll_delta().y * self.zoom_sensitivity;
             }
             OrbitMode::AutoRotate { speed } => {
                 self.azimuth += speed * dt;
             }
         }
+
+        self.apply_pending(damping(self.damping, dt));
+    }
+
+    /// Applies `fraction` of the pending motion, keeping the rest for later updates.
+    fn apply_pending(&mut self, fraction: f32) {
+        let rotation = self.pending.rotation * fraction;
+        let zoom = self.pending.zoom * fraction;
+        let pan = self.pending.pan * fraction;
+        self.pending.rotation -= rotation;
+        self.pending.zoom -= zoom;
+        self.pending.pan -= pan;
+
+        self.azimuth += rotation.x;
+        self.target += pan;
+
+        // Motion past a limit is dropped rather than saved up against it
+        let elevation = self.elevation + rotation.y;
+        self.elevation = elevation.clamp(-MAX_ELEVATION, MAX_ELEVATION);
+        if self.elevation != elevation {
+            self.pending.rotation.y = 0.0;
+        }
+        let distance = self.distance + zoom;
+        self.distance = distance.clamp(self.min_distance, self.max_distance);
+        if self.distance != distance {
+            self.pending.zoom = 0.0;
+        }
     }
 
     /// Get the current camera state.
@@ -188,6 +266,86 @@ impl OrbitCamera {
             fov: self.fov,
             near: 0.1,
             far: 1000.0,
+            projection: ProjectionMode::Perspective,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::{ElementState, MouseScrollDelta, WindowEvent};
+
+    fn scroll(input: &mut Input, lines: f32) {
+        input.handle_event(&WindowEvent::MouseWheel {
+            device_id: winit::event::DeviceId::dummy(),
+            delta: MouseScrollDelta::LineDelta(0.0, lines),
+            phase: winit::event::TouchPhase::Moved,
+        });
+    }
+
+    fn cursor_to(input: &mut Input, x: f64, y: f64) {
+        input.handle_event(&WindowEvent::CursorMoved {
+            device_id: winit::event::DeviceId::dummy(),
+            position: winit::dpi::PhysicalPosition::new(x, y),
+        });
+    }
+
+    #[test]
+    fn scroll_zoom_stops_at_the_limits() {
+        let mut orbit = OrbitCamera::new().distance(5.0).zoom_limits(2.0, 8.0);
+        let mut input = Input::new();
+
+        scroll(&mut input, 20.0);
+        orbit.update(&input, 0.016);
+        assert_eq!(orbit.distance, 2.0);
+
+        input.begin_frame();
+        scroll(&mut input, -2.0);
+        orbit.update(&input, 0.016);
+        assert_eq!(orbit.distance, 3.0);
+
+        input.begin_frame();
+        scroll(&mut input, -100.0);
+        orbit.update(&input, 0.016);
+        assert_eq!(orbit.distance, 8.0);
+    }
+
+    #[test]
+    fn middle_drag_pans_only_when_enabled() {
+        let mut input = Input::new();
+        cursor_to(&mut input, 100.0, 100.0);
+        input.handle_mouse_button(MouseButton::Middle, ElementState::Pressed);
+        input.begin_frame();
+        cursor_to(&mut input, 110.0, 100.0);
+
+        let mut fixed = OrbitCamera::new();
+        fixed.update(&input, 0.016);
+        assert_eq!(fixed.target, Vec3::ZERO);
+
+        // Looking down -Z, dragging right slides the target toward -X
+        let mut orbit = OrbitCamera::new().elevation(0.0).enable_pan(true);
+        orbit.update(&input, 0.016);
+        let expected = -10.0 * orbit.sensitivity * orbit.distance;
+        assert!((orbit.target - Vec3::new(expected, 0.0, 0.0)).length() < 1e-5);
+        assert_eq!(orbit.azimuth, 0.0);
+    }
+
+    #[test]
+    fn damping_eases_out_to_the_full_motion() {
+        let mut orbit = OrbitCamera::new().distance(10.0).damping(0.1);
+        let mut input = Input::new();
+
+        scroll(&mut input, 4.0);
+        orbit.update(&input, 0.1);
+        let expected = 10.0 - 2.0 * (1.0 - (-1.0f32).exp());
+        assert!((orbit.distance - expected).abs() < 1e-4);
+
+        // Keeps moving after input stops, settling on the undamped result
+        input.begin_frame();
+        for _ in 0..100 {
+            orbit.update(&input, 0.016);
         }
+        assert!((orbit.distance - 8.0).abs() < 1e-4);
     }
 }
@@ -0,0 +1,318 @@
+//! CPU particle systems drawn with instanced sprites.
+//!
+//! A [`ParticleSystem`] owns a pool of particles that spawn at an emitter,
+//! fly under a constant velocity and gravity, fade between two colors, and
+//! die after a fixed lifetime. Dead particles are recycled for new ones, so a
+//! steady stream doesn't allocate once the pool has grown to fit it. Draw the
+//! whole system in one instanced call with
+//! [`Frame::draw_particles`](crate::Frame::draw_particles).
+//!
+//! # Example
+//!
+//! ```
+//! use hoplite::{Color, ParticleSystem, SpriteId};
+//!
+//! let mut sparks = ParticleSystem::new(SpriteId(0))
+//!     .rate(100.0)
+//!     .lifetime(0.5)
+//!     .velocity([0.0, -200.0])
+//!     .gravity([0.0, 400.0])
+//!     .colors(Color::WHITE, Color::rgba(1.0, 0.5, 0.0, 0.0));
+//!
+//! sparks.emit([320.0, 240.0]);
+//! sparks.update(0.1);
+//! assert_eq!(sparks.len(), 10);
+//! ```
+
+use glam::Vec2;
+
+use crate::draw2d::{Color, SpriteId, SpriteInstance};
+use crate::rng::Rng;
+
+/// Default cap on the number of live particles.
+const DEFAULT_MAX_PARTICLES: usize = 10_000;
+
+#[derive(Clone, Copy, Debug)]
+struct Particle {
+    position: Vec2,
+    velocity: Vec2,
+    /// Seconds since the particle spawned.
+    age: f32,
+}
+
+/// A pool of short-lived sprites spawned from an emitter.
+///
+/// Configure it with the builder methods, then each frame call
+/// [`emit`](Self::emit) while the emitter should be active, [`update`](Self::update)
+/// with the frame's delta time, and draw it with
+/// [`Frame::draw_particles`](crate::Frame::draw_particles).
+#[derive(Clone, Debug)]
+pub struct ParticleSystem {
+    sprite: SpriteId,
+    rate: f32,
+    lifetime: f32,
+    start_color: Color,
+    end_color: Color,
+    start_scale: f32,
+    end_scale: f32,
+    velocity: Vec2,
+    spread: f32,
+    gravity: Vec2,
+    max_particles: usize,
+    /// Live particles are `particles[..alive]`; the rest are dead and reused.
+    particles: Vec<Particle>,
+    alive: usize,
+    /// Emitter position requested since the last update.
+    emitter: Option<Vec2>,
+    /// Fraction of a particle carried over between updates.
+    pending: f32,
+    rng: Rng,
+}
+
+impl ParticleSystem {
+    /// Creates an empty system that draws `sprite`.
+    ///
+    /// Defaults to 50 particles per second living one second each, moving
+    /// nowhere, with no gravity, in white fading to transparent.
+    pub fn new(sprite: SpriteId) -> Self {
+        Self {
+            sprite,
+            rate: 50.0,
+            lifetime: 1.0,
+            start_color: Color::WHITE,
+            end_color: Color::TRANSPARENT,
+            start_scale: 1.0,
+            end_scale: 1.0,
+            velocity: Vec2::ZERO,
+            spread: 0.0,
+            gravity: Vec2::ZERO,
+            max_particles: DEFAULT_MAX_PARTICLES,
+            particles: Vec::new(),
+            alive: 0,
+            emitter: None,
+            pending: 0.0,
+            rng: Rng::default(),
+        }
+    }
+
+    /// Sets how many particles spawn per second while emitting.
+    pub fn rate(mut self, particles_per_second: f32) -> Self {
+        self.rate = particles_per_second.max(0.0);
+        self
+    }
+
+    /// Sets how long each particle lives, in seconds.
+    pub fn lifetime(mut self, seconds: f32) -> Self {
+        self.lifetime = seconds;
+        self
+    }
+
+    /// Sets the tint at birth and at death; particles blend linearly between them.
+    pub fn colors(mut self, start: Color, end: Color) -> Self {
+        self.start_color = start;
+        self.end_color = end;
+        self
+    }
+
+    /// Sets the size at birth and at death, relative to the sprite's native size.
+    pub fn scales(mut self, start: f32, end: f32) -> Self {
+        self.start_scale = start;
+        self.end_scale = end;
+        self
+    }
+
+    /// Sets the initial velocity of each particle in pixels per second.
+    pub fn velocity(mut self, velocity: impl Into<Vec2>) -> Self {
+        self.velocity = velocity.into();
+        self
+    }
+
+    /// Randomly rotates each particle's initial velocity by up to `radians`
+    /// either way. A spread of `PI` sprays particles in every direction.
+    pub fn spread(mut self, radians: f32) -> Self {
+        self.spread = radians.abs();
+        self
+    }
+
+    /// Sets a constant acceleration in pixels per second squared.
+    pub fn gravity(mut self, gravity: impl Into<Vec2>) -> Self {
+        self.gravity = gravity.into();
+        self
+    }
+
+    /// Caps the number of live particles; spawns beyond it are dropped.
+    pub fn max_particles(mut self, max: usize) -> Self {
+        self.max_particles = max;
+        self
+    }
+
+    /// Seeds the random spread so runs are reproducible per seed.
+    pub fn seed(mut self, seed: u32) -> Self {
+        self.rng = Rng::new(seed.into());
+        self
+    }
+
+    /// The sprite drawn for each particle.
+    pub fn sprite(&self) -> SpriteId {
+        self.sprite
+    }
+
+    /// Emits particles from `position` during the next [`update`](Self::update).
+    ///
+    /// The number spawned follows the configured rate and that update's delta
+    /// time, so call this every frame the emitter should be running.
+    pub fn emit(&mut self, position: impl Into<Vec2>) {
+        self.emitter = Some(position.into());
+    }
+
+    /// Spawns `count` particles at `position` immediately, ignoring the rate.
+    pub fn burst(&mut self, position: impl Into<Vec2>, count: usize) {
+        let position = position.into();
+        for _ in 0..count {
+            self.spawn(position);
+        }
+    }
+
+    /// Ages and moves every particle by `dt` seconds, retires the ones past
+    /// their lifetime, and spawns new ones at the emitter if [`emit`](Self::emit)
+    /// was called since the last update.
+    pub fn update(&mut self, dt: f32) {
+        let mut i = 0;
+        while i < self.alive {
+            let particle = &mut self.particles[i];
+            particle.age += dt;
+            if particle.age >= self.lifetime {
+                self.alive -= 1;
+                self.particles.swap(i, self.alive);
+                continue;
+            }
+            particle.velocity += self.gravity * dt;
+            particle.position += particle.velocity * dt;
+            i += 1;
+        }
+
+        match self.emitter.take() {
+            Some(position) => {
+                self.pending += self.rate * dt;
+                while self.pending >= 1.0 {
+                    self.pending -= 1.0;
+                    self.spawn(position);
+                }
+            }
+            None => self.pending = 0.0,
+        }
+    }
+
+    /// Removes every live particle.
+    pub fn clear(&mut self) {
+        self.alive = 0;
+        self.pending = 0.0;
+    }
+
+    /// Number of live particles.
+    pub fn len(&self) -> usize {
+        self.alive
+    }
+
+    /// Whether there are no live particles.
+    pub fn is_empty(&self) -> bool {
+        self.alive == 0
+    }
+
+    /// Live particles as sprite instances, tinted and scaled by age.
+    pub fn instances(&self) -> impl Iterator<Item = SpriteInstance> + '_ {
+        self.particles[..self.alive].iter().map(|particle| {
+            let t = (particle.age / self.lifetime).clamp(0.0, 1.0);
+            SpriteInstance::new(particle.position.x, particle.position.y)
+                .scale(self.start_scale + (self.end_scale - self.start_scale) * t)
+                .color(Color::lerp(self.start_color, self.end_color, t))
+        })
+    }
+
+    fn spawn(&mut self, position: Vec2) {
+        if self.alive >= self.max_particles {
+            return;
+        }
+        let angle = self.rng.range(-self.spread, self.spread);
+        let particle = Particle {
+            position,
+            velocity: Vec2::from_angle(angle).rotate(self.velocity),
+            age: 0.0,
+        };
+        if self.alive < self.particles.len() {
+            self.particles[self.alive] = particle;
+        } else {
+            self.particles.push(particle);
+        }
+        self.alive += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emission_follows_rate_and_carries_fractions() {
+        let mut system = ParticleSystem::new(SpriteId(0)).rate(10.0).lifetime(10.0);
+        for _ in 0..4 {
+            system.emit([0.0, 0.0]);
+            system.update(0.25);
+        }
+        assert_eq!(system.len(), 10);
+
+        // Without emit, nothing new spawns
+        system.update(1.0);
+        assert_eq!(system.len(), 10);
+    }
+
+    #[test]
+    fn dead_particles_are_recycled() {
+        let mut system = ParticleSystem::new(SpriteId(0)).lifetime(1.0);
+        system.burst([0.0, 0.0], 8);
+        system.update(0.5);
+        system.burst([0.0, 0.0], 4);
+        assert_eq!(system.len(), 12);
+
+        // The first burst dies, leaving its slots for the next one
+        system.update(0.6);
+        assert_eq!(system.len(), 4);
+        system.burst([0.0, 0.0], 8);
+        assert_eq!(system.len(), 12);
+        assert_eq!(system.particles.len(), 12);
+    }
+
+    #[test]
+    fn particles_move_fade_and_respect_the_cap() {
+        let mut system = ParticleSystem::new(SpriteId(0))
+            .lifetime(2.0)
+            .velocity([10.0, 0.0])
+            .gravity([0.0, 20.0])
+            .colors(Color::WHITE, Color::rgba(1.0, 1.0, 1.0, 0.0))
+            .scales(1.0, 3.0)
+            .max_particles(3);
+        system.burst([5.0, 5.0], 5);
+        assert_eq!(system.len(), 3);
+
+        system.update(1.0);
+        let instance = system.instances().next().unwrap();
+        assert_eq!(instance.position, Vec2::new(15.0, 25.0));
+        assert!((instance.color.a - 0.5).abs() < 1e-6);
+        assert!((instance.scale - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn spread_stays_within_the_cone() {
+        let mut system = ParticleSystem::new(SpriteId(0))
+            .velocity([0.0, -100.0])
+            .spread(0.5)
+            .seed(7);
+        system.burst([0.0, 0.0], 200);
+        system.update(0.01);
+        for instance in system.instances() {
+            let direction = instance.position.normalize();
+            let angle = direction.angle_to(Vec2::new(0.0, -1.0)).abs();
+            assert!(angle <= 0.5 + 1e-4, "angle {}", angle);
+        }
+    }
+}
@@ -4,8 +4,12 @@
 //! in 3D space. It includes:
 //!
 //! - [`Ray`] — A 3D ray with origin and direction for raycasting
-//! - [`Collider`] — Collision shapes (box, sphere) that can be attached to entities
-//! - [`RayHit`] — Information about a ray-collider intersection
+//! - [`Collider`] — Collision shapes (box, sphere) on layers, attached to entities
+//! - [`RayHit`] — Information about a ray-collider or ray-mesh intersection
+//!
+//! Colliders are cheap approximate shapes. For exact picking against the
+//! rendered geometry, [`raycast_meshes`] tests the triangles of every entity
+//! with a [`RenderMesh`](crate::RenderMesh).
 //!
 //! # Example
 //!
@@ -38,7 +42,11 @@
 //! });
 //! ```
 
-use glam::{Mat4, Vec3};
+use glam::{Mat4, Vec2, Vec3};
+
+use crate::camera::Camera;
+use crate::mesh::{Mesh, Transform};
+use crate::render_graph::MeshQueue;
 
 /// A ray in 3D space, used for raycasting and picking.
 ///
@@ -98,7 +106,7 @@ impl Ray {
     ///     mouse_x, mouse_y,
     ///     frame.width() as f32, frame.height() as f32,
     ///     frame.camera.view_matrix(),
-    ///     frame.camera.projection_matrix(aspect, 0.1, 1000.0),
+    ///     frame.camera.projection_matrix(aspect, frame.camera.near, frame.camera.far),
     /// );
     /// ```
     pub fn from_screen(
@@ -226,6 +234,62 @@ impl Ray {
             None
         }
     }
+
+    /// Test intersection with an infinite plane.
+    ///
+    /// Returns the distance along the ray to the intersection point, or `None`
+    /// if the ray is parallel to the plane or the plane is behind the origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Any point on the plane
+    /// * `normal` - Plane normal; either side is hit and it needn't be normalized
+    pub fn intersect_plane(&self, point: Vec3, normal: Vec3) -> Option<f32> {
+        let denom = normal.dot(self.direction);
+        if denom.abs() < f32::EPSILON {
+            // Ray is parallel to the plane
+            return None;
+        }
+
+        let t = (point - self.origin).dot(normal) / denom;
+        (t > 0.0).then_some(t)
+    }
+
+    /// Test intersection with a triangle using the Möller–Trumbore algorithm.
+    ///
+    /// Both faces are hit regardless of winding order.
+    ///
+    /// # Returns
+    ///
+    /// `(t, u, v)` where `t` is the distance along the ray and `u`, `v` are the
+    /// barycentric weights of `b` and `c` (the weight of `a` is `1 - u - v`),
+    /// or `None` if the ray misses the triangle or hits it behind the origin.
+    pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<(f32, f32, f32)> {
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+        if det.abs() < f32::EPSILON {
+            // Ray is parallel to the triangle
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+        let s = self.origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(q) * inv_det;
+        (t > 0.0).then_some((t, u, v))
+    }
 }
 
 /// A collision shape for picking and hit detection.
@@ -238,6 +302,13 @@ impl Ray {
 /// - **Box**: Axis-aligned bounding box with half-extents
 /// - **Sphere**: Simple sphere with radius
 ///
+/// # Layers
+///
+/// Each collider belongs to one or more layers, stored as a bitmask. Raycasts
+/// take a mask and only hit colliders whose layer shares a bit with it, e.g.
+/// [`Frame::pick_collider_masked`](crate::Frame::pick_collider_masked). New
+/// colliders are on [`Collider::DEFAULT_LAYER`].
+///
 /// # Example
 ///
 /// ```
@@ -246,11 +317,20 @@ impl Ray {
 /// // Box collider for a 2x1x2 object (half-extents are 1, 0.5, 1)
 /// let box_collider = Collider::box_collider(Vec3::new(2.0, 1.0, 2.0));
 ///
-/// // Sphere collider with radius 1.5
-/// let sphere_collider = Collider::sphere(1.5);
+/// // Sphere collider with radius 1.5, on layer 2
+/// let sphere_collider = Collider::sphere(1.5).with_layer(1 << 2);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub enum Collider {
+pub struct Collider {
+    /// The geometric shape tested against rays.
+    pub shape: ColliderShape,
+    /// Bitmask of the layers this collider belongs to.
+    pub layer: u32,
+}
+
+/// The geometric shape of a [`Collider`].
+#[derive(Clone, Copy, Debug)]
+pub enum ColliderShape {
     /// Axis-aligned bounding box defined by half-extents.
     /// A box with half_extents (1, 1, 1) spans from (-1, -1, -1) to (1, 1, 1).
     Box {
@@ -265,6 +345,43 @@ pub enum Collider {
 }
 
 impl Collider {
+    /// Layer bitmask given to new colliders.
+    pub const DEFAULT_LAYER: u32 = 1;
+
+    /// Mask matching colliders on every layer.
+    pub const ALL_LAYERS: u32 = !0;
+
+    /// Create a collider with the given shape on [`DEFAULT_LAYER`](Self::DEFAULT_LAYER).
+    pub fn new(shape: ColliderShape) -> Self {
+        Self {
+            shape,
+            layer: Self::DEFAULT_LAYER,
+        }
+    }
+
+    /// Set the layer bitmask this collider belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hoplite::Collider;
+    ///
+    /// const ENEMIES: u32 = 1 << 1;
+    /// let collider = Collider::unit_box().with_layer(ENEMIES);
+    /// assert!(collider.matches(ENEMIES));
+    /// assert!(!collider.matches(1 << 3));
+    /// ```
+    pub fn with_layer(mut self, layer: u32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Returns `true` if this collider's layer shares any bit with `mask`.
+    #[inline]
+    pub fn matches(&self, mask: u32) -> bool {
+        self.layer & mask != 0
+    }
+
     /// Create a box collider from full dimensions.
     ///
     /// The collider will be centered at the entity's position.
@@ -282,9 +399,7 @@ impl Collider {
     /// let collider = Collider::box_collider(Vec3::ONE);
     /// ```
     pub fn box_collider(size: Vec3) -> Self {
-        Self::Box {
-            half_extents: size * 0.5,
-        }
+        Self::box_half_extents(size * 0.5)
     }
 
     /// Create a box collider from half-extents.
@@ -295,7 +410,7 @@ impl Collider {
     ///
     /// * `half_extents` - Half the size on each axis
     pub fn box_half_extents(half_extents: Vec3) -> Self {
-        Self::Box { half_extents }
+        Self::new(ColliderShape::Box { half_extents })
     }
 
     /// Create a sphere collider.
@@ -313,7 +428,7 @@ impl Collider {
     /// let collider = Collider::sphere(0.5);
     /// ```
     pub fn sphere(radius: f32) -> Self {
-        Self::Sphere { radius }
+        Self::new(ColliderShape::Sphere { radius })
     }
 
     /// Create a unit box collider (1x1x1).
@@ -327,7 +442,7 @@ impl Collider {
     ///
     /// Matches the dimensions of `Mesh::sphere()`.
     pub fn unit_sphere() -> Self {
-        Self::Sphere { radius: 0.5 }
+        Self::sphere(0.5)
     }
 
     /// Test if a ray intersects this collider at the given transform.
@@ -340,14 +455,14 @@ impl Collider {
     /// * `position` - World position of the collider
     /// * `scale` - Scale of the collider (from Transform)
     pub fn intersect(&self, ray: &Ray, position: Vec3, scale: Vec3) -> Option<f32> {
-        match self {
-            Collider::Box { half_extents } => {
-                let scaled_half = *half_extents * scale;
+        match self.shape {
+            ColliderShape::Box { half_extents } => {
+                let scaled_half = half_extents * scale;
                 let min = position - scaled_half;
                 let max = position + scaled_half;
                 ray.intersect_aabb(min, max)
             }
-            Collider::Sphere { radius } => {
+            ColliderShape::Sphere { radius } => {
                 // Use the average scale for sphere radius
                 let avg_scale = (scale.x + scale.y + scale.z) / 3.0;
                 ray.intersect_sphere(position, radius * avg_scale)
@@ -362,9 +477,9 @@ impl Default for Collider {
     }
 }
 
-/// Information about a ray-collider intersection.
+/// Information about a ray-collider or ray-mesh intersection.
 ///
-/// Returned by picking methods when a ray hits a collider.
+/// Returned by picking methods when a ray hits a collider or mesh.
 #[derive(Clone, Copy, Debug)]
 pub struct RayHit {
     /// The entity that was hit.
@@ -373,6 +488,21 @@ pub struct RayHit {
     pub distance: f32,
     /// World-space position of the hit point.
     pub point: Vec3,
+    /// The triangle that was hit. Only set by mesh picking.
+    pub triangle: Option<TriangleHit>,
+}
+
+/// The triangle of a mesh hit by a ray.
+///
+/// Useful for placing decals or sampling textures at the hit point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleHit {
+    /// Index of the triangle in the mesh (its first index is at `3 * index`).
+    pub index: usize,
+    /// Barycentric weights of the triangle's three vertices at the hit point.
+    pub barycentric: Vec3,
+    /// Texture coordinates interpolated at the hit point.
+    pub uv: Vec2,
 }
 
 /// Result of a raycast against all colliders in the world.
@@ -383,39 +513,46 @@ pub type PickResult = Option<RayHit>;
 /// Cast a ray against all entities with colliders and return hits.
 ///
 /// This is the core picking function. It tests the ray against all entities
-/// that have both a `Transform` and `Collider` component.
+/// that have both a `Transform` and `Collider` component, skipping colliders
+/// whose layer doesn't match `mask`.
 ///
 /// # Arguments
 ///
 /// * `world` - The ECS world to query
 /// * `ray` - The ray to cast
+/// * `mask` - Layer bitmask; pass [`Collider::ALL_LAYERS`] to test every collider
 ///
 /// # Returns
 ///
 /// A vector of all hits, sorted by distance (closest first).
-pub fn raycast_all(world: &hecs::World, ray: &Ray) -> Vec<RayHit> {
-    use crate::mesh::Transform;
-
+pub fn raycast_all(world: &hecs::World, ray: &Ray, mask: u32) -> Vec<RayHit> {
     let mut hits = Vec::new();
 
     for (entity, (transform, collider)) in world.query::<(&Transform, &Collider)>().iter() {
+        if !collider.matches(mask) {
+            continue;
+        }
         if let Some(distance) = collider.intersect(ray, transform.position, transform.scale) {
             hits.push(RayHit {
                 entity,
                 distance,
                 point: ray.point_at(distance),
+                triangle: None,
             });
         }
     }
 
-    // Sort by distance (closest first)
+    sort_by_distance(&mut hits);
+    hits
+}
+
+/// Sorts hits by distance (closest first).
+fn sort_by_distance(hits: &mut [RayHit]) {
     hits.sort_by(|a, b| {
         a.distance
             .partial_cmp(&b.distance)
             .unwrap_or(std::cmp::Ordering::Equal)
     });
-
-    hits
 }
 
 /// Cast a ray and return the closest hit.
@@ -424,10 +561,280 @@ pub fn raycast_all(world: &hecs::World, ray: &Ray) -> Vec<RayHit> {
 ///
 /// * `world` - The ECS world to query
 /// * `ray` - The ray to cast
+/// * `mask` - Layer bitmask; pass [`Collider::ALL_LAYERS`] to test every collider
 ///
 /// # Returns
 ///
 /// The closest hit, or `None` if nothing was hit.
-pub fn raycast(world: &hecs::World, ray: &Ray) -> PickResult {
-    raycast_all(world, ray).into_iter().next()
+pub fn raycast(world: &hecs::World, ray: &Ray, mask: u32) -> PickResult {
+    raycast_all(world, ray, mask).into_iter().next()
+}
+
+/// Find all entities with colliders whose centers appear inside a screen rectangle.
+///
+/// Each collider's position is projected through `camera`; entities behind the
+/// camera are excluded. The rectangle's corners may be given in any order, so
+/// the start and end points of a mouse drag can be passed directly.
+///
+/// # Arguments
+///
+/// * `world` - The ECS world to query
+/// * `camera` - Camera used to project positions to the screen
+/// * `screen_size` - Screen dimensions in pixels
+/// * `corner_a`, `corner_b` - Opposite corners of the rectangle in pixels
+/// * `mask` - Layer bitmask; pass [`Collider::ALL_LAYERS`] to test every collider
+///
+/// # Returns
+///
+/// The selected entities, sorted by distance from the camera (closest first).
+pub fn select_in_rect(
+    world: &hecs::World,
+    camera: &Camera,
+    screen_size: Vec2,
+    corner_a: Vec2,
+    corner_b: Vec2,
+    mask: u32,
+) -> Vec<hecs::Entity> {
+    let min = corner_a.min(corner_b);
+    let max = corner_a.max(corner_b);
+
+    let mut selected: Vec<(f32, hecs::Entity)> = world
+        .query::<(&Transform, &Collider)>()
+        .iter()
+        .filter(|(_, (_, collider))| collider.matches(mask))
+        .filter_map(|(entity, (transform, _))| {
+            let screen = camera.world_to_screen(transform.position, screen_size)?;
+            let inside = screen.cmpge(min).all() && screen.cmple(max).all();
+            inside.then(|| (camera.position.distance(transform.position), entity))
+        })
+        .collect();
+
+    selected.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    selected.into_iter().map(|(_, entity)| entity).collect()
+}
+
+/// Cast a ray against the triangles of every rendered mesh and return hits.
+///
+/// Tests all entities that have both a `Transform` and
+/// [`RenderMesh`](crate::RenderMesh) component. The ray is moved into each
+/// mesh's model space and tested against its actual triangles, after a quick
+/// rejection against the mesh's bounding sphere. Hits include the
+/// [`TriangleHit`] with barycentric coordinates and UV.
+///
+/// # Arguments
+///
+/// * `world` - The ECS world to query
+/// * `meshes` - The mesh queue holding the entities' meshes
+/// * `ray` - The ray to cast
+///
+/// # Returns
+///
+/// A vector of all hits (at most one per entity), sorted by distance (closest first).
+pub fn raycast_meshes_all(world: &hecs::World, meshes: &MeshQueue, ray: &Ray) -> Vec<RayHit> {
+    use crate::ecs::RenderMesh;
+
+    let mut hits = Vec::new();
+
+    for (entity, (transform, render_mesh)) in world.query::<(&Transform, &RenderMesh)>().iter() {
+        let Some(mesh) = meshes.mesh(render_mesh.mesh) else {
+            continue;
+        };
+        if let Some((distance, triangle)) = intersect_mesh(ray, mesh, transform) {
+            hits.push(RayHit {
+                entity,
+                distance,
+                point: ray.point_at(distance),
+                triangle: Some(triangle),
+            });
+        }
+    }
+
+    sort_by_distance(&mut hits);
+    hits
+}
+
+/// Cast a ray against the triangles of every rendered mesh and return the closest hit.
+///
+/// See [`raycast_meshes_all`] for details.
+pub fn raycast_meshes(world: &hecs::World, meshes: &MeshQueue, ray: &Ray) -> PickResult {
+    raycast_meshes_all(world, meshes, ray).into_iter().next()
+}
+
+/// Tests a world-space ray against a mesh placed with `transform`.
+fn intersect_mesh(ray: &Ray, mesh: &Mesh, transform: &Transform) -> Option<(f32, TriangleHit)> {
+    let matrix = transform.matrix();
+    let (center, radius) = mesh.bounding_sphere();
+    let world_center = matrix.transform_point3(center);
+    let world_radius = radius * transform.scale.abs().max_element();
+    ray.intersect_sphere(world_center, world_radius)?;
+
+    // Keep the model-space direction unnormalized so distances stay in world units
+    let inverse = matrix.inverse();
+    let local_ray = Ray {
+        origin: inverse.transform_point3(ray.origin),
+        direction: inverse.transform_vector3(ray.direction),
+    };
+    mesh.intersect_ray(&local_ray)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw2d::Color;
+    use crate::ecs::RenderMesh;
+    use crate::mesh::Vertex3d;
+
+    #[test]
+    fn raycast_skips_colliders_outside_mask() {
+        const ENEMIES: u32 = 1 << 1;
+        let mut world = hecs::World::new();
+        let near = world.spawn((
+            Transform::new().position(Vec3::new(0.0, 0.0, -2.0)),
+            Collider::unit_box(),
+        ));
+        let far = world.spawn((
+            Transform::new().position(Vec3::new(0.0, 0.0, -5.0)),
+            Collider::unit_sphere().with_layer(ENEMIES | Collider::DEFAULT_LAYER),
+        ));
+        let ray = Ray::new(Vec3::ZERO, Vec3::NEG_Z);
+
+        assert_eq!(
+            raycast(&world, &ray, Collider::ALL_LAYERS).unwrap().entity,
+            near
+        );
+        assert_eq!(raycast(&world, &ray, ENEMIES).unwrap().entity, far);
+        assert_eq!(raycast_all(&world, &ray, Collider::DEFAULT_LAYER).len(), 2);
+        assert!(raycast(&world, &ray, 1 << 5).is_none());
+    }
+
+    #[test]
+    fn rect_selection_projects_centers_and_sorts_by_distance() {
+        let mut world = hecs::World::new();
+        let spawn = |world: &mut hecs::World, position: Vec3| {
+            world.spawn((Transform::new().position(position), Collider::unit_box()))
+        };
+        let far = spawn(&mut world, Vec3::new(0.0, 0.0, -10.0));
+        let near = spawn(&mut world, Vec3::new(0.0, 0.0, -3.0));
+        let _behind = spawn(&mut world, Vec3::new(0.0, 0.0, 5.0));
+        let _off_center = spawn(&mut world, Vec3::new(3.0, 0.0, -3.0));
+
+        let camera = Camera::new().at([0.0, 0.0, 0.0]).with_fov(90.0);
+        let screen = Vec2::new(200.0, 200.0);
+
+        // Dragged up-left from the bottom-right, around the screen center
+        let selected = select_in_rect(
+            &world,
+            &camera,
+            screen,
+            Vec2::new(120.0, 120.0),
+            Vec2::new(80.0, 80.0),
+            Collider::ALL_LAYERS,
+        );
+        assert_eq!(selected, vec![near, far]);
+    }
+
+    #[test]
+    fn mesh_picking_hits_transformed_triangles() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        // Unit quad in the XY plane with UVs matching X/Y
+        let vertices = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+            .map(|[x, y]| Vertex3d::new([x, y, 0.0], [0.0, 0.0, 1.0], [x, y]));
+        let indices = [0u32, 1, 2, 2, 3, 0];
+        let mesh = Mesh::from_index_bytes(
+            &gpu.device,
+            &vertices,
+            bytemuck::cast_slice(&indices),
+            indices.to_vec(),
+            wgpu::IndexFormat::Uint32,
+        );
+        let mut meshes = MeshQueue::new();
+        let quad = meshes.add_mesh(mesh);
+
+        let mut world = hecs::World::new();
+        let transform = Transform::new()
+            .position(Vec3::new(10.0, 0.0, -5.0))
+            .scale(Vec3::splat(4.0));
+        let entity = world.spawn((transform, RenderMesh::new(quad, Color::WHITE)));
+
+        // Aim at (1, 3) on the 4x4 quad, i.e. uv (0.25, 0.75)
+        let ray = Ray::new(Vec3::new(11.0, 3.0, 0.0), Vec3::NEG_Z);
+        let hit = raycast_meshes(&world, &meshes, &ray).unwrap();
+        assert_eq!(hit.entity, entity);
+        assert!((hit.distance - 5.0).abs() < 1e-4);
+        assert!(hit.point.distance(Vec3::new(11.0, 3.0, -5.0)) < 1e-4);
+        let triangle = hit.triangle.unwrap();
+        assert_eq!(triangle.index, 1);
+        assert!(triangle.uv.distance(Vec2::new(0.25, 0.75)) < 1e-4);
+
+        // Inside the bounding sphere but off the quad
+        let miss = Ray::new(Vec3::new(9.5, 2.0, 0.0), Vec3::NEG_Z);
+        assert!(raycast_meshes(&world, &meshes, &miss).is_none());
+    }
+
+    #[test]
+    fn triangle_hit_reports_distance_and_barycentrics() {
+        let (a, b, c) = (Vec3::ZERO, Vec3::X, Vec3::Y);
+        let ray = Ray::new(Vec3::new(0.25, 0.5, 2.0), Vec3::NEG_Z);
+
+        let (t, u, v) = ray.intersect_triangle(a, b, c).unwrap();
+        assert!((t - 2.0).abs() < 1e-6);
+        assert!((u - 0.25).abs() < 1e-6);
+        assert!((v - 0.5).abs() < 1e-6);
+
+        // Winding doesn't matter, but the triangle must be in front of the ray
+        assert!(ray.intersect_triangle(a, c, b).is_some());
+        assert!(
+            Ray::new(ray.origin, Vec3::Z)
+                .intersect_triangle(a, b, c)
+                .is_none()
+        );
+        assert!(
+            Ray::new(Vec3::new(0.8, 0.8, 2.0), Vec3::NEG_Z)
+                .intersect_triangle(a, b, c)
+                .is_none()
+        );
+
+        // A ray in the triangle's plane is parallel to it
+        assert!(
+            Ray::new(Vec3::new(-1.0, 0.25, 0.0), Vec3::X)
+                .intersect_triangle(a, b, c)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn plane_hit_reports_distance_from_either_side() {
+        let ray = Ray::new(Vec3::new(1.0, 5.0, -2.0), Vec3::new(0.0, -1.0, 0.0));
+
+        let t = ray
+            .intersect_plane(Vec3::new(7.0, 1.0, 3.0), Vec3::Y)
+            .unwrap();
+        assert!((t - 4.0).abs() < 1e-6);
+        assert!(ray.point_at(t).distance(Vec3::new(1.0, 1.0, -2.0)) < 1e-6);
+
+        // The normal's sign and length don't matter
+        let flipped = ray
+            .intersect_plane(Vec3::ZERO, Vec3::new(0.0, -3.0, 0.0))
+            .unwrap();
+        assert!((flipped - 5.0).abs() < 1e-6);
+
+        // Oblique ray hits the plane farther along
+        let oblique = Ray::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+        let t = oblique.intersect_plane(Vec3::ZERO, Vec3::Y).unwrap();
+        assert!((t - 2f32.sqrt()).abs() < 1e-5);
+
+        // Parallel rays and planes behind the origin miss
+        assert!(
+            Ray::new(Vec3::Y, Vec3::X)
+                .intersect_plane(Vec3::ZERO, Vec3::Y)
+                .is_none()
+        );
+        assert!(
+            ray.intersect_plane(Vec3::new(0.0, 6.0, 0.0), Vec3::Y)
+                .is_none()
+        );
+    }
 }
@@ -50,11 +50,15 @@
 //!     }
 //! "#;
 //!
-//! // let pass = PostProcessPass::new(&gpu, shader_source);
+//! // let pass = PostProcessPass::new(&gpu, shader_source)?;
 //! ```
 
-use crate::camera::Camera;
+use crate::camera::{Camera, ProjectionMode};
+use crate::error::{Error, capture_shader_errors};
 use crate::gpu::GpuContext;
+use crate::shader_include;
+use crate::texture::Texture;
+use crate::uniforms::{UniformHandle, UserUniforms};
 
 /// Standard uniforms for post-processing passes.
 ///
@@ -87,8 +91,9 @@ pub struct WorldPostProcessUniforms {
     pub fov: f32,
     /// Camera position in world space.
     pub camera_pos: [f32; 3],
-    /// Padding for 16-byte alignment.
-    pub _pad1: f32,
+    /// Visible view height in world units for orthographic cameras,
+    /// or `0.0` for perspective cameras.
+    pub ortho_height: f32,
     /// Camera forward direction (normalized).
     pub camera_forward: [f32; 3],
     /// Padding for 16-byte alignment.
@@ -101,6 +106,13 @@ pub struct WorldPostProcessUniforms {
     pub camera_up: [f32; 3],
     /// Aspect ratio (width / height).
     pub aspect: f32,
+    /// Inverse of the camera's view-projection matrix, mapping NDC (with
+    /// depth in `[0, 1]`) back to world space.
+    pub inv_view_proj: [[f32; 4]; 4],
+    /// Ray directions through the top-left, top-right, bottom-left, and
+    /// bottom-right screen corners, from [`Camera::frustum_corners`].
+    /// The `w` components are zero.
+    pub frustum_corners: [[f32; 4]; 4],
 }
 
 /// A post-processing pass that samples from an input texture.
@@ -118,14 +130,56 @@ pub struct WorldPostProcessUniforms {
 /// | 1 | `texture_2d<f32>` | Input texture from the previous pass |
 /// | 2 | `sampler` | Linear filtering sampler for the input texture |
 ///
+/// Passes created with [`new_with_textures`](Self::new_with_textures) also
+/// receive each extra texture and its sampler as consecutive binding pairs:
+///
+/// | Binding | Type | Description |
+/// |---------|------|-------------|
+/// | 3 | `texture_2d<f32>` | First extra texture |
+/// | 4 | `sampler` | Sampler of the first extra texture |
+/// | 5, 6, ... | | Further textures and samplers, in order |
+///
+/// Passes created with [`with_uniforms`](Self::with_uniforms) also receive a
+/// user uniform struct at `@group(1) @binding(0)`.
+///
 pub struct PostProcessPass {
     pipeline: wgpu::RenderPipeline,
     uniform_buffer: wgpu::Buffer,
     bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
+    format: wgpu::TextureFormat,
+    /// Extra uniforms at `@group(1) @binding(0)` (see [`with_uniforms`](Self::with_uniforms)).
+    user: Option<UserUniforms>,
+    /// Extra textures and their samplers, bound from binding 3 on (see [`new_with_textures`](Self::new_with_textures)).
+    textures: Vec<(wgpu::TextureView, wgpu::Sampler)>,
 }
 
 impl PostProcessPass {
+    /// Creates the built-in ACES filmic tonemapping pass.
+    ///
+    /// Maps HDR color from an [`HDR_FORMAT`](crate::HDR_FORMAT) input into the
+    /// displayable 0-1 range and writes to the surface format. Add it as the
+    /// last node of a graph built with [`hdr`](crate::RenderGraphBuilder::hdr).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let graph = RenderGraph::builder()
+    ///     .hdr()
+    ///     .node(EffectNode::new(EffectPass::new_with_format(&gpu, scene, HDR_FORMAT)?))
+    ///     .node(PostProcessNode::new(PostProcessPass::aces_tonemap(&gpu)))
+    ///     .build(&gpu);
+    /// ```
+    pub fn aces_tonemap(gpu: &GpuContext) -> Self {
+        Self::build(
+            gpu,
+            include_str!("shaders/tonemap.wgsl"),
+            gpu.config.format,
+            None,
+            Vec::new(),
+        )
+    }
+
     /// Creates a new post-processing pass from WGSL shader source.
     ///
     /// # Arguments
@@ -150,11 +204,131 @@ impl PostProcessPass {
     /// @group(0) @binding(2) var input_sampler: sampler;
     /// ```
     ///
-    /// # Panics
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new(gpu: &GpuContext, shader_source: &str) -> Result<Self, Error> {
+        Self::new_with_format(gpu, shader_source, gpu.config.format)
+    }
+
+    /// Creates a post-processing pass that renders into targets of `format`.
+    ///
+    /// [`new`](Self::new) targets the surface format; use this for passes that
+    /// write into intermediate targets of another format, such as
+    /// [`HDR_FORMAT`](crate::HDR_FORMAT).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new_with_format(
+        gpu: &GpuContext,
+        shader_source: &str,
+        format: wgpu::TextureFormat,
+    ) -> Result<Self, Error> {
+        let shader_source = shader_include::expand_source(shader_source)?;
+        capture_shader_errors(&gpu.device, || {
+            Self::build(gpu, &shader_source, format, None, Vec::new())
+        })
+    }
+
+    /// Creates a post-processing pass that also samples extra textures.
+    ///
+    /// Each texture is bound after the standard bindings together with its own
+    /// sampler: the first at bindings 3 (texture) and 4 (sampler), the second
+    /// at 5 and 6, and so on. Useful for lens dirt overlays, noise, or masks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    ///
+    /// # Example
     ///
-    /// Panics if the shader source fails to compile.
-    pub fn new(gpu: &GpuContext, shader_source: &str) -> Self {
+    /// ```ignore
+    /// // WGSL:
+    /// // @group(0) @binding(3) var dirt_texture: texture_2d<f32>;
+    /// // @group(0) @binding(4) var dirt_sampler: sampler;
+    /// let dirt = Texture::from_file(&gpu, "assets/lens_dirt.png")?;
+    /// let pass = PostProcessPass::new_with_textures(&gpu, LENS_DIRT_SHADER, &[&dirt])?;
+    /// ```
+    pub fn new_with_textures(
+        gpu: &GpuContext,
+        shader_source: &str,
+        textures: &[&Texture],
+    ) -> Result<Self, Error> {
+        let shader_source = shader_include::expand_source(shader_source)?;
+        capture_shader_errors(&gpu.device, || {
+            let textures = textures
+                .iter()
+                .map(|texture| (texture.view.clone(), texture.sampler.clone()))
+                .collect();
+            Self::build(gpu, &shader_source, gpu.config.format, None, textures)
+        })
+    }
+
+    /// Creates a color grading pass that samples a lookup table texture.
+    ///
+    /// Shorthand for [`new_with_textures`](Self::new_with_textures) with a
+    /// single texture, so the LUT is at binding 3 and its sampler at binding 4.
+    /// A common layout is a 2D strip of 16 slices of 16x16 (a 256x16 texture),
+    /// with red across each slice, green down it, and blue selecting the slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn with_lut(gpu: &GpuContext, shader_source: &str, lut: &Texture) -> Result<Self, Error> {
+        Self::new_with_textures(gpu, shader_source, &[lut])
+    }
+
+    /// Creates a post-processing pass with a user uniform buffer of type `T`.
+    ///
+    /// Besides the standard bindings, the shader declares a struct matching
+    /// `T` at `@group(1) @binding(0)`. The buffer starts zeroed; upload values
+    /// with [`set_uniforms`](Self::set_uniforms), or through
+    /// [`uniform_handle`](Self::uniform_handle) once the pass is in a render graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    /// struct Bloom { threshold: f32, intensity: f32, _pad: [f32; 2] }
+    ///
+    /// // WGSL: @group(1) @binding(0) var<uniform> bloom: Bloom;
+    /// let pass = PostProcessPass::with_uniforms::<Bloom>(&gpu, BLOOM_SHADER)?;
+    /// pass.set_uniforms(&gpu, &Bloom { threshold: 1.0, intensity: 0.6, _pad: [0.0; 2] });
+    /// ```
+    pub fn with_uniforms<T: bytemuck::Pod>(
+        gpu: &GpuContext,
+        shader_source: &str,
+    ) -> Result<Self, Error> {
+        let shader_source = shader_include::expand_source(shader_source)?;
+        capture_shader_errors(&gpu.device, || {
+            Self::build(
+                gpu,
+                &shader_source,
+                gpu.config.format,
+                Some(std::mem::size_of::<T>()),
+                Vec::new(),
+            )
+        })
+    }
+
+    /// Creates the shader module, uniform buffer, sampler, and pipeline.
+    ///
+    /// `textures` are the extra texture views and samplers bound from binding 3 on.
+    pub(crate) fn build(
+        gpu: &GpuContext,
+        shader_source: &str,
+        format: wgpu::TextureFormat,
+        user_size: Option<usize>,
+        textures: Vec<(wgpu::TextureView, wgpu::Sampler)>,
+    ) -> Self {
         let device = &gpu.device;
+        let user = user_size.map(|size| UserUniforms::new(device, size));
 
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("PostProcess Shader"),
@@ -179,44 +353,69 @@ impl PostProcessPass {
             ..Default::default()
         });
 
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("PostProcess Bind Group Layout"),
-            entries: &[
-                // Uniforms
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
+        let mut entries = vec![
+            // Uniforms
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
                 },
-                // Input texture
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
-                    },
-                    count: None,
+                count: None,
+            },
+            // Input texture
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
                 },
-                // Sampler
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                    count: None,
+                count: None,
+            },
+            // Sampler
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ];
+        // Extra textures, each followed by its sampler
+        for index in 0..textures.len() {
+            let (texture_binding, sampler_binding) = extra_texture_bindings(index);
+            entries.push(wgpu::BindGroupLayoutEntry {
+                binding: texture_binding,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
                 },
-            ],
+                count: None,
+            });
+            entries.push(wgpu::BindGroupLayoutEntry {
+                binding: sampler_binding,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            });
+        }
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("PostProcess Bind Group Layout"),
+            entries: &entries,
         });
 
+        let bind_group_layouts: Vec<&wgpu::BindGroupLayout> = std::iter::once(&bind_group_layout)
+            .chain(user.as_ref().map(|user| &user.bind_group_layout))
+            .collect();
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("PostProcess Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
+            bind_group_layouts: &bind_group_layouts,
             push_constant_ranges: &[],
         });
 
@@ -233,7 +432,7 @@ impl PostProcessPass {
                 module: &shader,
                 entry_point: Some("fs"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
+                    format,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -254,9 +453,41 @@ impl PostProcessPass {
             uniform_buffer,
             bind_group_layout,
             sampler,
+            format,
+            user,
+            textures,
         }
     }
 
+    /// Swaps the view of extra texture `index`, e.g. after the texture was reallocated.
+    pub(crate) fn set_extra_texture(&mut self, index: usize, view: wgpu::TextureView) {
+        self.textures[index].0 = view;
+    }
+
+    /// Upload this pass's user uniforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pass wasn't created with [`with_uniforms`](Self::with_uniforms),
+    /// or if `T` isn't the size of the type it was created with.
+    pub fn set_uniforms<T: bytemuck::Pod>(&self, gpu: &GpuContext, value: &T) {
+        self.uniform_handle()
+            .expect("This pass has no user uniforms. Create it with with_uniforms().")
+            .set(gpu, value);
+    }
+
+    /// Handle for updating user uniforms after the pass has moved into a render graph.
+    ///
+    /// Returns `None` unless the pass was created with [`with_uniforms`](Self::with_uniforms).
+    pub fn uniform_handle(&self) -> Option<UniformHandle> {
+        self.user.as_ref().map(|user| user.handle.clone())
+    }
+
+    /// The texture format this pass renders into.
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
     /// Creates a bind group for the given input texture.
     ///
     /// This is useful when you need to manage bind groups manually,
@@ -271,23 +502,36 @@ impl PostProcessPass {
         gpu: &GpuContext,
         input_view: &wgpu::TextureView,
     ) -> wgpu::BindGroup {
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: self.uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(input_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(&self.sampler),
+            },
+        ];
+        for (index, (view, sampler)) in self.textures.iter().enumerate() {
+            let (texture_binding, sampler_binding) = extra_texture_bindings(index);
+            entries.push(wgpu::BindGroupEntry {
+                binding: texture_binding,
+                resource: wgpu::BindingResource::TextureView(view),
+            });
+            entries.push(wgpu::BindGroupEntry {
+                binding: sampler_binding,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            });
+        }
+
         gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("PostProcess Bind Group"),
             layout: &self.bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: self.uniform_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(input_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler),
-                },
-            ],
+            entries: &entries,
         })
     }
 
@@ -311,7 +555,7 @@ impl PostProcessPass {
         input_view: &wgpu::TextureView,
     ) {
         let uniforms = PostProcessUniforms {
-            resolution: [gpu.width() as f32, gpu.height() as f32],
+            resolution: [gpu.render_width() as f32, gpu.render_height() as f32],
             time,
             _padding: 0.0,
         };
@@ -322,6 +566,9 @@ impl PostProcessPass {
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &bind_group, &[]);
+        if let Some(user) = &self.user {
+            render_pass.set_bind_group(1, &user.bind_group, &[]);
+        }
         render_pass.draw(0..3, 0..1);
     }
 }
@@ -347,19 +594,57 @@ impl PostProcessPass {
 /// | 0 | `uniform` | Uniforms with resolution, time, and camera data |
 /// | 1 | `texture_2d<f32>` | Input texture from the previous pass |
 /// | 2 | `sampler` | Linear filtering sampler for the input texture |
+/// | 3 | `texture_depth_2d` | Scene depth from the last [`MeshNode`](crate::MeshNode) this frame |
+/// | 4 | `sampler` | Non-filtering sampler for the depth texture |
+///
+/// Bindings 3 and 4 are optional; shaders that don't declare them still work.
+/// Without a mesh node earlier in the graph, depth reads as 1.0 (the far plane).
+///
+/// # Example: Linearizing Depth
+///
+/// Meshes use a perspective projection between the camera's `near` and `far`
+/// planes (0.1 and 1000.0 by default), storing depth in the 0-1 range:
+///
+/// ```wgsl
+/// @group(0) @binding(3) var depth_texture: texture_depth_2d;
+/// @group(0) @binding(4) var depth_sampler: sampler;
+///
+/// fn linearize_depth(depth: f32) -> f32 {
+///     let near = 0.1;
+///     let far = 1000.0;
+///     return near * far / (far - depth * (far - near));
+/// }
+///
+/// @fragment
+/// fn fs(@builtin(position) pos: vec4f) -> @location(0) vec4f {
+///     let uv = pos.xy / u.resolution;
+///     let color = textureSample(input_texture, input_sampler, uv);
+///     let distance = linearize_depth(textureSample(depth_texture, depth_sampler, uv));
+///     let fog = 1.0 - exp(-distance * 0.02);
+///     return mix(color, vec4f(0.6, 0.7, 0.8, 1.0), fog);
+/// }
+/// ```
 ///
 /// # Example: Computing View Rays
 ///
+/// `frustum_corners` holds the rays through the four screen corners, so
+/// interpolating them with the pixel's UV gives its exact ray:
+///
 /// ```wgsl
 /// fn get_ray_direction(uv: vec2f) -> vec3f {
-///     let ndc = uv * 2.0 - 1.0;
-///     let half_height = tan(u.fov * 0.5);
-///     let half_width = half_height * u.aspect;
-///     return normalize(
-///         u.camera_forward +
-///         u.camera_right * ndc.x * half_width +
-///         u.camera_up * ndc.y * half_height
-///     );
+///     let top = mix(u.frustum_corners[0].xyz, u.frustum_corners[1].xyz, uv.x);
+///     let bottom = mix(u.frustum_corners[2].xyz, u.frustum_corners[3].xyz, uv.x);
+///     return normalize(mix(top, bottom, uv.y));
+/// }
+/// ```
+///
+/// # Example: World Position from Depth
+///
+/// ```wgsl
+/// fn world_position(uv: vec2f, depth: f32) -> vec3f {
+///     let ndc = vec4f(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+///     let world = u.inv_view_proj * ndc;
+///     return world.xyz / world.w;
 /// }
 /// ```
 pub struct WorldPostProcessPass {
@@ -367,6 +652,10 @@ pub struct WorldPostProcessPass {
     uniform_buffer: wgpu::Buffer,
     bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
+    format: wgpu::TextureFormat,
+    depth_sampler: wgpu::Sampler,
+    /// 1x1 depth cleared to the far plane, bound when no scene depth is available.
+    default_depth: wgpu::TextureView,
 }
 
 impl WorldPostProcessPass {
@@ -390,23 +679,48 @@ impl WorldPostProcessPass {
     ///     time: f32,
     ///     fov: f32,
     ///     camera_pos: vec3f,
-    ///     _pad1: f32,
+    ///     ortho_height: f32,
     ///     camera_forward: vec3f,
     ///     _pad2: f32,
     ///     camera_right: vec3f,
     ///     _pad3: f32,
     ///     camera_up: vec3f,
     ///     aspect: f32,
+    ///     inv_view_proj: mat4x4f,
+    ///     frustum_corners: array<vec4f, 4>,
     /// }
     /// @group(0) @binding(0) var<uniform> u: Uniforms;
     /// @group(0) @binding(1) var input_texture: texture_2d<f32>;
     /// @group(0) @binding(2) var input_sampler: sampler;
     /// ```
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the shader source fails to compile.
-    pub fn new(gpu: &GpuContext, shader_source: &str) -> Self {
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new(gpu: &GpuContext, shader_source: &str) -> Result<Self, Error> {
+        Self::new_with_format(gpu, shader_source, gpu.config.format)
+    }
+
+    /// Creates a post-processing pass that renders into targets of `format`.
+    ///
+    /// [`new`](Self::new) targets the surface format; use this for passes that
+    /// write into intermediate targets of another format, such as
+    /// [`HDR_FORMAT`](crate::HDR_FORMAT).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Shader`] if the shader fails to compile or validate.
+    pub fn new_with_format(
+        gpu: &GpuContext,
+        shader_source: &str,
+        format: wgpu::TextureFormat,
+    ) -> Result<Self, Error> {
+        let shader_source = shader_include::expand_source(shader_source)?;
+        capture_shader_errors(&gpu.device, || Self::build(gpu, &shader_source, format))
+    }
+
+    /// Creates the shader module, uniform buffer, sampler, and pipeline.
+    fn build(gpu: &GpuContext, shader_source: &str, format: wgpu::TextureFormat) -> Self {
         let device = &gpu.device;
 
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
@@ -464,9 +778,39 @@ impl WorldPostProcessPass {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                // Scene depth
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Depth sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
+                    count: None,
+                },
             ],
         });
 
+        let depth_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("WorldPostProcess Depth Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let default_depth = far_depth_texture(gpu);
+
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("WorldPostProcess Pipeline Layout"),
             bind_group_layouts: &[&bind_group_layout],
@@ -486,7 +830,7 @@ impl WorldPostProcessPass {
                 module: &shader,
                 entry_point: Some("fs"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
+                    format,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -507,13 +851,22 @@ impl WorldPostProcessPass {
             uniform_buffer,
             bind_group_layout,
             sampler,
+            format,
+            depth_sampler,
+            default_depth,
         }
     }
 
+    /// The texture format this pass renders into.
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
     /// Creates a bind group for the given input texture.
     ///
     /// This is useful when you need to manage bind groups manually,
     /// for example when caching them across frames for performance.
+    /// Depth reads as the far plane; see [`create_bind_group_with_depth`](Self::create_bind_group_with_depth).
     ///
     /// # Arguments
     ///
@@ -524,6 +877,23 @@ impl WorldPostProcessPass {
         gpu: &GpuContext,
         input_view: &wgpu::TextureView,
     ) -> wgpu::BindGroup {
+        self.create_bind_group_with_depth(gpu, input_view, None)
+    }
+
+    /// Creates a bind group for the given input texture and scene depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `gpu` - The GPU context
+    /// * `input_view` - Texture view to sample from in the shader
+    /// * `depth_view` - `Depth32Float` view bound at binding 3, or `None` for the far plane
+    pub fn create_bind_group_with_depth(
+        &self,
+        gpu: &GpuContext,
+        input_view: &wgpu::TextureView,
+        depth_view: Option<&wgpu::TextureView>,
+    ) -> wgpu::BindGroup {
+        let depth_view = depth_view.unwrap_or(&self.default_depth);
         gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("WorldPostProcess Bind Group"),
             layout: &self.bind_group_layout,
@@ -540,6 +910,14 @@ impl WorldPostProcessPass {
                     binding: 2,
                     resource: wgpu::BindingResource::Sampler(&self.sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(&self.depth_sampler),
+                },
             ],
         })
     }
@@ -564,27 +942,129 @@ impl WorldPostProcessPass {
         time: f32,
         camera: &Camera,
         input_view: &wgpu::TextureView,
+    ) {
+        self.render_with_depth(gpu, render_pass, time, camera, input_view, None);
+    }
+
+    /// Renders the post-processing effect with camera data and scene depth.
+    ///
+    /// Like [`render`](Self::render), but binds `depth_view` (usually
+    /// [`RenderContext::depth`](crate::RenderContext::depth)) at binding 3.
+    /// `None` binds a texture that reads as the far plane.
+    pub fn render_with_depth(
+        &self,
+        gpu: &GpuContext,
+        render_pass: &mut wgpu::RenderPass,
+        time: f32,
+        camera: &Camera,
+        input_view: &wgpu::TextureView,
+        depth_view: Option<&wgpu::TextureView>,
     ) {
         let uniforms = WorldPostProcessUniforms {
-            resolution: [gpu.width() as f32, gpu.height() as f32],
+            resolution: [gpu.render_width() as f32, gpu.render_height() as f32],
             time,
             fov: camera.fov,
             camera_pos: camera.position.to_array(),
-            _pad1: 0.0,
+            ortho_height: match camera.projection {
+                ProjectionMode::Perspective => 0.0,
+                ProjectionMode::Orthographic { height } => height,
+            },
             camera_forward: camera.forward.to_array(),
             _pad2: 0.0,
             camera_right: camera.right().to_array(),
             _pad3: 0.0,
             camera_up: camera.orthogonal_up().to_array(),
             aspect: gpu.aspect(),
+            inv_view_proj: camera
+                .view_projection_matrix(gpu.aspect())
+                .inverse()
+                .to_cols_array_2d(),
+            frustum_corners: camera
+                .frustum_corners(gpu.aspect())
+                .map(|corner| corner.extend(0.0).to_array()),
         };
         gpu.queue
             .write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
 
-        let bind_group = self.create_bind_group(gpu, input_view);
+        let bind_group = self.create_bind_group_with_depth(gpu, input_view, depth_view);
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &bind_group, &[]);
         render_pass.draw(0..3, 0..1);
     }
 }
+
+/// Texture and sampler bindings of the `index`-th extra post-process texture.
+fn extra_texture_bindings(index: usize) -> (u32, u32) {
+    let texture = 3 + 2 * index as u32;
+    (texture, texture + 1)
+}
+
+/// Creates a 1x1 depth texture cleared to the far plane (1.0).
+fn far_depth_texture(gpu: &GpuContext) -> wgpu::TextureView {
+    let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("WorldPostProcess Default Depth"),
+        size: wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Depth32Float,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut encoder = gpu
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("WorldPostProcess Depth Clear"),
+        });
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("WorldPostProcess Depth Clear"),
+        color_attachments: &[],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: &view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    gpu.queue.submit(std::iter::once(encoder.finish()));
+    view
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_textures_follow_the_standard_bindings_in_pairs() {
+        assert_eq!(extra_texture_bindings(0), (3, 4));
+        assert_eq!(extra_texture_bindings(1), (5, 6));
+        assert_eq!(extra_texture_bindings(2), (7, 8));
+    }
+
+    #[test]
+    fn tonemap_shader_compiles() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        let result = capture_shader_errors(&gpu.device, || {
+            gpu.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(include_str!("shaders/tonemap.wgsl").into()),
+                })
+        });
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Built-in multi-pass bloom composed from post-process passes.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::gpu::GpuContext;
+use crate::post_process::PostProcessPass;
+use crate::render_graph::{HDR_FORMAT, RenderContext, RenderNode};
+
+/// Tunable bloom parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BloomSettings {
+    /// Brightness (max RGB channel) above which pixels start to glow.
+    pub threshold: f32,
+    /// Strength of the glow added back onto the scene.
+    pub intensity: f32,
+    /// Number of horizontal + vertical blur rounds; more rounds give a wider glow.
+    pub iterations: u32,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            intensity: 0.6,
+            iterations: 4,
+        }
+    }
+}
+
+/// Cloneable handle for tuning a [`BloomNode`] after it joins a render graph.
+///
+/// Changes take effect the next time the node renders.
+///
+/// # Example
+///
+/// ```ignore
+/// let bloom = ctx.bloom(0.8, 0.6, 4);
+///
+/// move |frame| {
+///     if frame.input.key_pressed(KeyCode::ArrowUp) {
+///         bloom.set_intensity(bloom.settings().intensity + 0.1);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BloomHandle {
+    settings: Rc<Cell<BloomSettings>>,
+}
+
+impl BloomHandle {
+    /// The current settings.
+    pub fn settings(&self) -> BloomSettings {
+        self.settings.get()
+    }
+
+    /// Replaces all settings at once.
+    pub fn set(&self, settings: BloomSettings) {
+        self.settings.set(settings);
+    }
+
+    /// Sets the brightness threshold.
+    pub fn set_threshold(&self, threshold: f32) {
+        self.set(BloomSettings {
+            threshold,
+            ..self.settings()
+        });
+    }
+
+    /// Sets the glow strength.
+    pub fn set_intensity(&self, intensity: f32) {
+        self.set(BloomSettings {
+            intensity,
+            ..self.settings()
+        });
+    }
+
+    /// Sets the number of blur rounds.
+    pub fn set_iterations(&self, iterations: u32) {
+        self.set(BloomSettings {
+            iterations,
+            ..self.settings()
+        });
+    }
+}
+
+/// User uniforms shared by the bloom shaders at `@group(1) @binding(0)`.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct BloomUniforms {
+    threshold: f32,
+    intensity: f32,
+    /// Blur step in texels; unused by the bright and composite passes.
+    direction: [f32; 2],
+}
+
+/// Render node that adds a glow around bright parts of the previous pass.
+///
+/// Internally this chains four [`PostProcessPass`]es over two half-resolution
+/// [`HDR_FORMAT`] targets:
+///
+/// 1. A bright pass keeps color above the threshold and downsamples it
+/// 2. `iterations` rounds of horizontal then vertical Gaussian blur ping-pong
+///    between the targets
+/// 3. A composite pass adds the blurred glow onto the input, scaled by the intensity
+///
+/// The glow stays in HDR until the composite, so thresholds above 1.0 work
+/// with graphs built with [`hdr`](crate::RenderGraphBuilder::hdr).
+///
+/// # Requirements
+///
+/// This node **requires** a previous pass in the render graph. It will panic
+/// if used as the first node, since there's no input texture to sample.
+///
+/// # Example
+///
+/// ```ignore
+/// let bloom = BloomNode::new(&gpu, BloomSettings::default());
+/// let handle = bloom.handle();
+///
+/// let graph = RenderGraph::builder()
+///     .node(EffectNode::new(scene))
+///     .node(bloom)
+///     .build(&gpu);
+///
+/// handle.set_threshold(0.6);
+/// ```
+pub struct BloomNode {
+    bright: PostProcessPass,
+    blur_horizontal: PostProcessPass,
+    blur_vertical: PostProcessPass,
+    composite: PostProcessPass,
+    /// Half-resolution targets; `targets[0]` holds the finished glow.
+    targets: [BloomTarget; 2],
+    settings: Rc<Cell<BloomSettings>>,
+}
+
+impl BloomNode {
+    /// Creates a bloom node that writes to the surface format.
+    pub fn new(gpu: &GpuContext, settings: BloomSettings) -> Self {
+        Self::new_with_format(gpu, settings, gpu.config.format)
+    }
+
+    /// Creates a bloom node whose composite writes to `format`.
+    ///
+    /// Use [`HDR_FORMAT`] for nodes that aren't last in a graph built with
+    /// [`hdr`](crate::RenderGraphBuilder::hdr).
+    pub fn new_with_format(
+        gpu: &GpuContext,
+        settings: BloomSettings,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let uniforms_size = Some(std::mem::size_of::<BloomUniforms>());
+        let targets = [BloomTarget::new(gpu), BloomTarget::new(gpu)];
+
+        let blur = |direction: [f32; 2]| {
+            let pass = PostProcessPass::build(
+                gpu,
+                include_str!("../shaders/bloom_blur.wgsl"),
+                HDR_FORMAT,
+                uniforms_size,
+                Vec::new(),
+            );
+            pass.set_uniforms(
+                gpu,
+                &BloomUniforms {
+                    threshold: 0.0,
+                    intensity: 0.0,
+                    direction,
+                },
+            );
+            pass
+        };
+
+        Self {
+            bright: PostProcessPass::build(
+                gpu,
+                include_str!("../shaders/bloom_bright.wgsl"),
+                HDR_FORMAT,
+                uniforms_size,
+                Vec::new(),
+            ),
+            blur_horizontal: blur([1.0, 0.0]),
+            blur_vertical: blur([0.0, 1.0]),
+            composite: PostProcessPass::build(
+                gpu,
+                include_str!("../shaders/bloom_composite.wgsl"),
+                format,
+                uniforms_size,
+                vec![(targets[0].view.clone(), targets[0].sampler.clone())],
+            ),
+            targets,
+            settings: Rc::new(Cell::new(settings)),
+        }
+    }
+
+    /// Handle for changing this node's settings after it moves into a graph.
+    pub fn handle(&self) -> BloomHandle {
+        BloomHandle {
+            settings: Rc::clone(&self.settings),
+        }
+    }
+}
+
+impl RenderNode for BloomNode {
+    fn execute(
+        &self,
+        ctx: &mut RenderContext,
+        target: &wgpu::TextureView,
+        input: Option<&wgpu::TextureView>,
+    ) {
+        let input_view = input.expect("BloomNode requires an input from a previous pass");
+        let settings = self.settings.get();
+        let uniforms = BloomUniforms {
+            threshold: settings.threshold,
+            intensity: settings.intensity,
+            direction: [0.0, 0.0],
+        };
+        let [glow, scratch] = &self.targets;
+
+        self.bright.set_uniforms(ctx.gpu, &uniforms);
+        let mut pass = begin_pass(ctx.encoder, &glow.view, "Bloom Bright Pass");
+        self.bright.render(ctx.gpu, &mut pass, ctx.time, input_view);
+        drop(pass);
+
+        for _ in 0..settings.iterations {
+            let mut pass = begin_pass(ctx.encoder, &scratch.view, "Bloom Blur Pass");
+            self.blur_horizontal
+                .render(ctx.gpu, &mut pass, ctx.time, &glow.view);
+            drop(pass);
+
+            let mut pass = begin_pass(ctx.encoder, &glow.view, "Bloom Blur Pass");
+            self.blur_vertical
+                .render(ctx.gpu, &mut pass, ctx.time, &scratch.view);
+        }
+
+        self.composite.set_uniforms(ctx.gpu, &uniforms);
+        let mut pass = begin_pass(ctx.encoder, target, "Bloom Composite Pass");
+        self.composite
+            .render(ctx.gpu, &mut pass, ctx.time, input_view);
+    }
+
+    fn check_hot_reload(&mut self, gpu: &GpuContext) {
+        if self.targets[0].size != half_size(gpu.render_width(), gpu.render_height()) {
+            self.targets = [BloomTarget::new(gpu), BloomTarget::new(gpu)];
+            self.composite
+                .set_extra_texture(0, self.targets[0].view.clone());
+        }
+    }
+}
+
+/// Half-resolution HDR texture the glow is blurred in.
+struct BloomTarget {
+    view: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+    size: (u32, u32),
+}
+
+impl BloomTarget {
+    fn new(gpu: &GpuContext) -> Self {
+        let size = half_size(gpu.render_width(), gpu.render_height());
+        let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Bloom Target"),
+            size: wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HDR_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Bloom Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self {
+            view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            sampler,
+            size,
+        }
+    }
+}
+
+/// Size of the bloom targets for a `width` x `height` render resolution.
+fn half_size(width: u32, height: u32) -> (u32, u32) {
+    ((width / 2).max(1), (height / 2).max(1))
+}
+
+/// Begins a render pass that clears `target` and draws into it.
+fn begin_pass<'e>(
+    encoder: &'e mut wgpu::CommandEncoder,
+    target: &wgpu::TextureView,
+    label: &str,
+) -> wgpu::RenderPass<'e> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::capture_shader_errors;
+
+    #[test]
+    fn targets_are_half_resolution_and_never_empty() {
+        assert_eq!(half_size(1920, 1080), (960, 540));
+        assert_eq!(half_size(801, 601), (400, 300));
+        assert_eq!(half_size(1, 1), (1, 1));
+    }
+
+    #[test]
+    fn handle_updates_shared_settings() {
+        let settings = Rc::new(Cell::new(BloomSettings::default()));
+        let handle = BloomHandle {
+            settings: Rc::clone(&settings),
+        };
+
+        handle.set_threshold(1.5);
+        handle.set_iterations(2);
+        assert_eq!(
+            settings.get(),
+            BloomSettings {
+                threshold: 1.5,
+                intensity: 0.6,
+                iterations: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn bloom_shaders_compile() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        for source in [
+            include_str!("../shaders/bloom_bright.wgsl"),
+            include_str!("../shaders/bloom_blur.wgsl"),
+            include_str!("../shaders/bloom_composite.wgsl"),
+        ] {
+            let result = capture_shader_errors(&gpu.device, || {
+                gpu.device
+                    .create_shader_module(wgpu::ShaderModuleDescriptor {
+                        label: None,
+                        source: wgpu::ShaderSource::Wgsl(source.into()),
+                    })
+            });
+            assert!(result.is_ok(), "{:?}", result.err());
+        }
+    }
+}
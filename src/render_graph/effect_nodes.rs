@@ -23,7 +23,7 @@ use crate::render_graph::{RenderContext, RenderNode};
 /// # Example
 ///
 /// ```ignore
-/// let scene = EffectPass::new(&gpu, include_str!("shaders/scene.wgsl"));
+/// let scene = EffectPass::new(&gpu, include_str!("shaders/scene.wgsl"))?;
 /// let node = EffectNode::new(scene)
 ///     .with_clear(wgpu::Color::BLUE);
 ///
@@ -242,4 +242,8 @@ impl RenderNode for HotEffectNode {
     fn check_hot_reload(&mut self, gpu: &GpuContext) {
         self.effect.check_reload(gpu);
     }
+
+    fn shader_error(&self) -> Option<&str> {
+        self.effect.last_error()
+    }
 }
@@ -2,7 +2,9 @@
 
 use crate::camera::Camera;
 use crate::gpu::GpuContext;
-use crate::render_graph::{RenderContext, RenderNode, RenderTarget};
+use crate::render_graph::profiler::GpuProfiler;
+use crate::render_graph::upscale::Upscaler;
+use crate::render_graph::{HDR_FORMAT, RenderContext, RenderNode, RenderTarget};
 
 /// Builder for constructing render graphs with a fluent API.
 ///
@@ -25,13 +27,71 @@ use crate::render_graph::{RenderContext, RenderNode, RenderTarget};
 /// (`input` is `None`), while subsequent nodes receive the previous
 /// node's output. The final node renders directly to the screen.
 pub struct RenderGraphBuilder {
-    nodes: Vec<Box<dyn RenderNode>>,
+    nodes: Vec<GraphNode>,
+    /// Whether intermediate targets use [`HDR_FORMAT`].
+    hdr: bool,
+}
+
+/// A node in a [`RenderGraph`], with its optional name and enabled flag.
+struct GraphNode {
+    /// Name for lookup with [`RenderGraph::remove_node`] and friends.
+    name: Option<String>,
+    node: Box<dyn RenderNode>,
+    /// Disabled nodes are skipped during execution.
+    enabled: bool,
+}
+
+impl GraphNode {
+    fn new(name: Option<String>, node: Box<dyn RenderNode>) -> Self {
+        Self {
+            name,
+            node,
+            enabled: true,
+        }
+    }
+
+    /// The graph name if one was given, otherwise the node's own name.
+    fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or_else(|| self.node.name())
+    }
+}
+
+/// Index of the first node registered under `name`.
+fn index_of(nodes: &[GraphNode], name: &str) -> Option<usize> {
+    nodes.iter().position(|n| n.name.as_deref() == Some(name))
+}
+
+/// Where a pass writes in the ping-pong chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PassTarget {
+    A,
+    B,
+    Final,
+}
+
+impl PassTarget {
+    /// Target for the `pass`-th enabled node out of `passes`.
+    ///
+    /// The last pass writes to the final target; earlier passes alternate
+    /// A, B, A, ... so each reads what the previous one wrote.
+    fn for_pass(pass: usize, passes: usize) -> Self {
+        if pass + 1 == passes {
+            PassTarget::Final
+        } else if pass.is_multiple_of(2) {
+            PassTarget::A
+        } else {
+            PassTarget::B
+        }
+    }
 }
 
 impl RenderGraphBuilder {
     /// Creates a new empty render graph builder.
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            hdr: false,
+        }
     }
 
     /// Adds a render node to the graph.
@@ -51,7 +111,55 @@ impl RenderGraphBuilder {
     ///
     /// * `N` - Any type implementing `RenderNode + 'static`
     pub fn node<N: RenderNode + 'static>(mut self, node: N) -> Self {
-        self.nodes.push(Box::new(node));
+        self.nodes.push(GraphNode::new(None, Box::new(node)));
+        self
+    }
+
+    /// Adds a render node under a name so it can be changed after building.
+    ///
+    /// Named nodes can later be toggled with [`RenderGraph::set_enabled`],
+    /// swapped with [`RenderGraph::replace_node`], or dropped with
+    /// [`RenderGraph::remove_node`]. The name is also used for
+    /// [`RenderGraph::last_timings`]. If several nodes share a name, lookups
+    /// find the first one.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let graph = RenderGraph::builder()
+    ///     .node(EffectNode::new(scene))
+    ///     .named_node("bloom", PostProcessNode::new(bloom))
+    ///     .build(&gpu);
+    /// ```
+    pub fn named_node<N: RenderNode + 'static>(mut self, name: impl Into<String>, node: N) -> Self {
+        self.nodes
+            .push(GraphNode::new(Some(name.into()), Box::new(node)));
+        self
+    }
+
+    /// Allocates the intermediate ping-pong targets in [`HDR_FORMAT`].
+    ///
+    /// Every node except the last then renders into half-float targets, so
+    /// lighting and bloom can exceed 1.0 without clipping. The last node still
+    /// renders to the surface and should map HDR values back into range, e.g.
+    /// with [`PostProcessPass::aces_tonemap`](crate::PostProcessPass::aces_tonemap).
+    ///
+    /// Node pipelines must match the target they write: build the earlier
+    /// passes with their `new_with_format(.., HDR_FORMAT)` constructors and the
+    /// last one with the surface format.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let graph = RenderGraph::builder()
+    ///     .hdr()
+    ///     .node(MeshNode::new_with_format(&gpu, queue, HDR_FORMAT))
+    ///     .node(PostProcessNode::new(PostProcessPass::new_with_format(&gpu, bloom, HDR_FORMAT)?))
+    ///     .node(PostProcessNode::new(PostProcessPass::aces_tonemap(&gpu)))
+    ///     .build(&gpu);
+    /// ```
+    pub fn hdr(mut self) -> Self {
+        self.hdr = true;
         self
     }
 
@@ -59,7 +167,8 @@ impl RenderGraphBuilder {
     ///
     /// This method finalizes the graph and creates the intermediate render
     /// targets needed for multi-pass rendering. Two render targets are
-    /// allocated at the current GPU surface size.
+    /// allocated at the current GPU surface size, in the surface format or
+    /// [`HDR_FORMAT`] if [`hdr`](Self::hdr) was called.
     ///
     /// # Arguments
     ///
@@ -70,13 +179,22 @@ impl RenderGraphBuilder {
     /// A ready-to-use [`RenderGraph`].
     pub fn build(self, gpu: &GpuContext) -> RenderGraph {
         // Create ping-pong buffers for multi-pass rendering
-        let target_a = RenderTarget::new(gpu, "RenderGraph Target A");
-        let target_b = RenderTarget::new(gpu, "RenderGraph Target B");
+        let format = if self.hdr {
+            HDR_FORMAT
+        } else {
+            gpu.config.format
+        };
+        let target_a = RenderTarget::new_with_format(gpu, "RenderGraph Target A", format);
+        let target_b = RenderTarget::new_with_format(gpu, "RenderGraph Target B", format);
 
         RenderGraph {
             nodes: self.nodes,
             target_a,
             target_b,
+            profiler: None,
+            timings: Vec::new(),
+            upscaler: None,
+            clear_color: None,
         }
     }
 }
@@ -97,6 +215,7 @@ impl Default for RenderGraphBuilder {
 /// - Hot-reload checking for all nodes
 /// - Final presentation to the screen
 /// - Optional UI overlay compositing
+/// - Optional per-node GPU timings (see [`last_timings`](Self::last_timings))
 ///
 /// # Buffer Management
 ///
@@ -111,7 +230,12 @@ impl Default for RenderGraphBuilder {
 /// Pass 3: Target A → Screen
 /// ```
 ///
-/// For single-node graphs, no intermediate buffers are used.
+/// For single-node graphs, no intermediate buffers are used. When
+/// [`GpuContext::render_scale`] is below 1.0, the targets are allocated at the
+/// reduced size and the final pass writes into a scaled target that is then
+/// upscaled to the screen. Disabled nodes
+/// (see [`set_enabled`](Self::set_enabled)) are skipped entirely, and the
+/// remaining nodes ping-pong as if the disabled ones weren't there.
 ///
 /// # Example
 ///
@@ -132,11 +256,20 @@ impl Default for RenderGraphBuilder {
 /// ```
 pub struct RenderGraph {
     /// The sequence of render nodes to execute.
-    nodes: Vec<Box<dyn RenderNode>>,
+    nodes: Vec<GraphNode>,
     /// First ping-pong buffer for intermediate results.
     target_a: RenderTarget,
     /// Second ping-pong buffer for intermediate results.
     target_b: RenderTarget,
+    /// Timestamp queries, present while GPU profiling is enabled.
+    profiler: Option<GpuProfiler>,
+    /// Node names and GPU durations in milliseconds from the last profiled execution.
+    timings: Vec<(String, f32)>,
+    /// Present while rendering below native resolution (see [`GpuContext::set_render_scale`]).
+    upscaler: Option<Upscaler>,
+    /// Background the first node clears to (see [`set_clear_color`](Self::set_clear_color)),
+    /// or `None` for the default.
+    clear_color: Option<wgpu::Color>,
 }
 
 impl RenderGraph {
@@ -178,11 +311,110 @@ impl RenderGraph {
     /// graph = graph.with_node(PostProcessNode::new(new_effect), &gpu);
     /// ```
     pub fn with_node<N: RenderNode + 'static>(mut self, node: N, gpu: &GpuContext) -> Self {
-        self.nodes.push(Box::new(node));
-        // Ensure we have render targets
+        self.push_node(node, gpu);
+        self
+    }
+
+    /// Appends a node to the end of the graph in place.
+    ///
+    /// Existing nodes, their state, and the intermediate targets are kept; the
+    /// targets are only reallocated if the surface size has changed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// graph.push_node(PostProcessNode::new(vignette), &gpu);
+    /// ```
+    pub fn push_node<N: RenderNode + 'static>(&mut self, node: N, gpu: &GpuContext) {
+        self.nodes.push(GraphNode::new(None, Box::new(node)));
         self.target_a.ensure_size(gpu, "RenderGraph Target A");
         self.target_b.ensure_size(gpu, "RenderGraph Target B");
-        self
+    }
+
+    /// Format of the intermediate targets every node but the last renders into.
+    ///
+    /// [`HDR_FORMAT`] for graphs built with [`hdr`](RenderGraphBuilder::hdr),
+    /// otherwise the surface format.
+    pub fn target_format(&self) -> wgpu::TextureFormat {
+        self.target_a.format()
+    }
+
+    /// Removes the node registered under `name`, returning it.
+    ///
+    /// Returns `None` if no node has that name. The remaining nodes keep
+    /// their order.
+    pub fn remove_node(&mut self, name: &str) -> Option<Box<dyn RenderNode>> {
+        let index = index_of(&self.nodes, name)?;
+        Some(self.nodes.remove(index).node)
+    }
+
+    /// Replaces the node registered under `name`, keeping its position and enabled state.
+    ///
+    /// Returns `false` (and drops `node`) if no node has that name.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// graph.replace_node("tonemap", PostProcessNode::new(filmic));
+    /// ```
+    pub fn replace_node<N: RenderNode + 'static>(&mut self, name: &str, node: N) -> bool {
+        match index_of(&self.nodes, name) {
+            Some(index) => {
+                self.nodes[index].node = Box::new(node);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Enables or disables the node registered under `name`.
+    ///
+    /// Disabled nodes are skipped during execution but stay in the graph and
+    /// still hot-reload. Returns `false` if no node has that name.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// if frame.input.key_pressed(KeyCode::KeyB) {
+    ///     bloom_on = !bloom_on;
+    ///     graph.set_enabled("bloom", bloom_on);
+    /// }
+    /// ```
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match index_of(&self.nodes, name) {
+            Some(index) => {
+                self.nodes[index].enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether the node registered under `name` is enabled, or `None` if there is none.
+    pub fn is_enabled(&self, name: &str) -> Option<bool> {
+        index_of(&self.nodes, name).map(|index| self.nodes[index].enabled)
+    }
+
+    /// Sets the color the frame is cleared to before the first node draws.
+    ///
+    /// Nodes that start without an input, like a [`MeshNode`](super::MeshNode)
+    /// first in the graph, clear to this color, and a graph with every node
+    /// disabled shows it alone. Nodes that cover the whole target, such as
+    /// effects and skyboxes, draw over it. Black by default, or transparent
+    /// for a scene pushed over other scenes so they show through it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// graph.set_clear_color(Color::rgb(0.1, 0.1, 0.15).into());
+    /// ```
+    pub fn set_clear_color(&mut self, color: wgpu::Color) {
+        self.clear_color = Some(color);
+    }
+
+    /// Returns the color the frame is cleared to before the first node draws.
+    pub fn clear_color(&self) -> wgpu::Color {
+        self.clear_color.unwrap_or(wgpu::Color::BLACK)
     }
 
     /// Executes the render graph and presents to the screen.
@@ -216,11 +448,21 @@ impl RenderGraph {
     ///
     /// * `gpu` - GPU context for shader recompilation
     pub fn check_hot_reload(&mut self, gpu: &GpuContext) {
-        for node in &mut self.nodes {
-            node.check_hot_reload(gpu);
+        for entry in &mut self.nodes {
+            entry.node.check_hot_reload(gpu);
         }
     }
 
+    /// Current shader errors from hot-reloadable nodes, in node order.
+    ///
+    /// An error stays listed until the node's shader compiles again, while the
+    /// node keeps rendering with its last working shader.
+    pub fn shader_errors(&self) -> impl Iterator<Item = &str> {
+        self.nodes
+            .iter()
+            .filter_map(|entry| entry.node.shader_error())
+    }
+
     /// Executes the render graph with a UI overlay pass.
     ///
     /// Similar to [`execute`](Self::execute), but allows rendering UI elements
@@ -264,10 +506,8 @@ impl RenderGraph {
         self.target_a.ensure_size(gpu, "RenderGraph Target A");
         self.target_b.ensure_size(gpu, "RenderGraph Target B");
 
-        let output = gpu.surface.get_current_texture().unwrap();
-        let screen_view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        let output = gpu.acquire_output().unwrap();
+        let screen_view = output.view();
 
         let mut encoder = gpu
             .device
@@ -275,47 +515,17 @@ impl RenderGraph {
                 label: Some("RenderGraph Encoder"),
             });
 
-        let node_count = self.nodes.len();
-
-        {
-            let mut ctx = RenderContext {
+        self.run_nodes(
+            &mut RenderContext {
                 gpu,
                 encoder: &mut encoder,
                 time,
                 camera,
-            };
-
-            // For single node, render directly to screen
-            if node_count == 1 {
-                self.nodes[0].execute(&mut ctx, &screen_view, None);
-            } else {
-                // Multi-pass: ping-pong between targets, final pass goes to screen
-                let mut current_input: Option<&wgpu::TextureView> = None;
-
-                for (i, node) in self.nodes.iter().enumerate() {
-                    let is_last = i == node_count - 1;
-
-                    let target = if is_last {
-                        &screen_view
-                    } else if i % 2 == 0 {
-                        &self.target_a.view
-                    } else {
-                        &self.target_b.view
-                    };
-
-                    node.execute(&mut ctx, target, current_input);
-
-                    // Set up input for next pass
-                    if !is_last {
-                        current_input = Some(if i % 2 == 0 {
-                            &self.target_a.view
-                        } else {
-                            &self.target_b.view
-                        });
-                    }
-                }
-            }
-        }
+                depth: None,
+                clear_color: self.clear_color(),
+            },
+            &screen_view,
+        );
 
         // Render UI on top (if any)
         {
@@ -338,7 +548,8 @@ impl RenderGraph {
         }
 
         gpu.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        gpu.present_output(output);
+        self.collect_timings(gpu);
     }
 
     /// Executes the render graph to an arbitrary target texture (not the screen).
@@ -371,6 +582,36 @@ impl RenderGraph {
         time: f32,
         camera: &Camera,
         target: &wgpu::TextureView,
+    ) {
+        let clear_color = self.clear_color();
+        self.render_to_target(gpu, time, camera, target, clear_color);
+    }
+
+    /// Executes the render graph to `target` as a layer to composite over
+    /// other output.
+    ///
+    /// Like [`execute_to_target`](Self::execute_to_target), but unless a clear
+    /// color was [set](Self::set_clear_color) the background is transparent,
+    /// so only what the nodes draw covers the layers below.
+    pub(crate) fn execute_overlay_to_target(
+        &mut self,
+        gpu: &GpuContext,
+        time: f32,
+        camera: &Camera,
+        target: &wgpu::TextureView,
+    ) {
+        let clear_color = self.clear_color.unwrap_or(wgpu::Color::TRANSPARENT);
+        self.render_to_target(gpu, time, camera, target, clear_color);
+    }
+
+    /// Runs the nodes into `target` with `clear_color` as the background and submits them.
+    fn render_to_target(
+        &mut self,
+        gpu: &GpuContext,
+        time: f32,
+        camera: &Camera,
+        target: &wgpu::TextureView,
+        clear_color: wgpu::Color,
     ) {
         // Check for hot-reload changes before rendering
         self.check_hot_reload(gpu);
@@ -385,48 +626,228 @@ impl RenderGraph {
                 label: Some("RenderGraph To Target Encoder"),
             });
 
-        let node_count = self.nodes.len();
-
-        {
-            let mut ctx = RenderContext {
+        self.run_nodes(
+            &mut RenderContext {
                 gpu,
                 encoder: &mut encoder,
                 time,
                 camera,
+                depth: None,
+                clear_color,
+            },
+            target,
+        );
+
+        gpu.queue.submit(std::iter::once(encoder.finish()));
+        self.collect_timings(gpu);
+    }
+
+    /// GPU time spent in each node during the last profiled execution.
+    ///
+    /// Returns `(name, milliseconds)` pairs for the enabled nodes in execution
+    /// order, using the name given to [`RenderGraphBuilder::named_node`] or else
+    /// the node's [`RenderNode::name`]. Empty unless profiling is enabled with
+    /// [`GpuContext::set_profiling`] and the device
+    /// [supports timestamps](GpuContext::supports_timestamps).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// gpu.set_profiling(true);
+    /// graph.execute(&gpu, time, &camera);
+    /// for (name, ms) in graph.last_timings() {
+    ///     println!("{name}: {ms:.3} ms");
+    /// }
+    /// ```
+    pub fn last_timings(&self) -> &[(String, f32)] {
+        &self.timings
+    }
+
+    /// Enabled nodes in execution order.
+    fn active_nodes(&self) -> impl Iterator<Item = &GraphNode> {
+        self.nodes.iter().filter(|entry| entry.enabled)
+    }
+
+    /// Runs every enabled node in order, ping-ponging between the intermediate targets.
+    ///
+    /// The first node receives no input and the last node renders to `final_target`.
+    /// If every node is disabled, `final_target` is cleared to the clear color instead.
+    /// When profiling, timestamps are written around each node and resolved at the end.
+    fn run_nodes(&mut self, ctx: &mut RenderContext, final_target: &wgpu::TextureView) {
+        self.prepare_profiler(ctx.gpu);
+        self.prepare_upscaler(ctx.gpu);
+
+        let passes = self.active_nodes().count();
+        if passes == 0 {
+            ctx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("RenderGraph Clear Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: final_target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(ctx.clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            return;
+        }
+
+        // Below native resolution the last node renders into a scaled target
+        // that is stretched over the final target afterwards
+        let last_target = match &self.upscaler {
+            Some(upscaler) => &upscaler.target.view,
+            None => final_target,
+        };
+        let mut current_input: Option<&wgpu::TextureView> = None;
+
+        for (pass, entry) in self.active_nodes().enumerate() {
+            let target = match PassTarget::for_pass(pass, passes) {
+                PassTarget::A => &self.target_a.view,
+                PassTarget::B => &self.target_b.view,
+                PassTarget::Final => last_target,
             };
 
-            // For single node, render directly to provided target
-            if node_count == 1 {
-                self.nodes[0].execute(&mut ctx, target, None);
-            } else {
-                // Multi-pass: ping-pong between targets, final pass goes to provided target
-                let mut current_input: Option<&wgpu::TextureView> = None;
-
-                for (i, node) in self.nodes.iter().enumerate() {
-                    let is_last = i == node_count - 1;
-
-                    let node_target = if is_last {
-                        target
-                    } else if i % 2 == 0 {
-                        &self.target_a.view
-                    } else {
-                        &self.target_b.view
-                    };
-
-                    node.execute(&mut ctx, node_target, current_input);
-
-                    // Set up input for next pass
-                    if !is_last {
-                        current_input = Some(if i % 2 == 0 {
-                            &self.target_a.view
-                        } else {
-                            &self.target_b.view
-                        });
-                    }
-                }
+            if let Some(profiler) = &self.profiler {
+                profiler.begin(ctx.encoder, pass);
+            }
+            entry.node.execute(ctx, target, current_input);
+            if let Some(profiler) = &self.profiler {
+                profiler.end(ctx.encoder, pass);
             }
+
+            // This pass's output is the next pass's input
+            current_input = Some(target);
         }
 
-        gpu.queue.submit(std::iter::once(encoder.finish()));
+        if let Some(upscaler) = &self.upscaler {
+            upscaler.blit(ctx.gpu, ctx.encoder, final_target);
+        }
+        if let Some(profiler) = &self.profiler {
+            profiler.resolve(ctx.encoder, passes);
+        }
+    }
+
+    /// Creates, resizes, or drops the upscaler to match the GPU's render scale.
+    fn prepare_upscaler(&mut self, gpu: &GpuContext) {
+        if gpu.render_scale() >= 1.0 {
+            self.upscaler = None;
+            return;
+        }
+        match &mut self.upscaler {
+            Some(upscaler) => upscaler
+                .target
+                .ensure_size(gpu, "RenderGraph Upscale Source"),
+            None => self.upscaler = Some(Upscaler::new(gpu)),
+        }
+    }
+
+    /// Creates, grows, or drops the profiler to match the GPU's profiling setting.
+    fn prepare_profiler(&mut self, gpu: &GpuContext) {
+        if !gpu.profiling() {
+            self.profiler = None;
+            self.timings.clear();
+            return;
+        }
+        let nodes = self.active_nodes().count();
+        if self.profiler.as_ref().is_none_or(|p| p.capacity() < nodes) {
+            self.profiler = Some(GpuProfiler::new(gpu, nodes));
+        }
+    }
+
+    /// Reads back this frame's timestamps after submission and publishes them to the GPU context.
+    fn collect_timings(&mut self, gpu: &GpuContext) {
+        let Some(profiler) = &self.profiler else {
+            return;
+        };
+        let durations = profiler.read(gpu, self.active_nodes().count());
+        self.timings = self
+            .active_nodes()
+            .zip(durations)
+            .map(|(entry, ms)| (entry.label().to_string(), ms))
+            .collect();
+        gpu.timings.borrow_mut().clone_from(&self.timings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Blur;
+
+    impl RenderNode for Blur {
+        fn execute(
+            &self,
+            _: &mut RenderContext,
+            _: &wgpu::TextureView,
+            _: Option<&wgpu::TextureView>,
+        ) {
+        }
+    }
+
+    #[test]
+    fn passes_alternate_and_end_on_the_final_target() {
+        use PassTarget::*;
+
+        let targets = |passes| {
+            (0..passes)
+                .map(|pass| PassTarget::for_pass(pass, passes))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(targets(1), [Final]);
+        assert_eq!(targets(2), [A, Final]);
+        assert_eq!(targets(3), [A, B, Final]);
+        assert_eq!(targets(4), [A, B, A, Final]);
+    }
+
+    #[test]
+    fn named_nodes_are_found_by_name_and_labelled() {
+        let builder = RenderGraph::builder()
+            .node(Blur)
+            .named_node("bloom", Blur)
+            .named_node("bloom", Blur);
+
+        assert_eq!(index_of(&builder.nodes, "bloom"), Some(1));
+        assert_eq!(index_of(&builder.nodes, "missing"), None);
+        assert_eq!(builder.nodes[0].label(), "Blur");
+        assert_eq!(builder.nodes[1].label(), "bloom");
+    }
+
+    #[test]
+    fn clear_color_fills_the_first_pass() {
+        use crate::Color;
+
+        // Top-left pixel with the clear color set without a graph, and before
+        // and after the graph is created
+        let render = |meshes: bool, before: bool| {
+            let result = crate::run_headless_fallible(4, 4, 1, move |ctx| {
+                if before {
+                    ctx.clear_color(Color::rgb(0.0, 1.0, 0.0));
+                }
+                if meshes {
+                    ctx.enable_mesh_rendering();
+                }
+                if !before {
+                    ctx.clear_color(Color::rgb(0.0, 1.0, 0.0));
+                }
+                |_: &mut crate::Frame| {}
+            });
+            result.map(|pixels| pixels[..4].to_vec())
+        };
+        for (meshes, before) in [(false, true), (true, true), (true, false)] {
+            match render(meshes, before) {
+                Ok(pixel) => assert_eq!(pixel, [0, 255, 0, 255]),
+                Err(crate::Error::Gpu(e)) => {
+                    crate::test_support::skip_without_gpu(e);
+                    return;
+                }
+                Err(e) => panic!("{}", e),
+            }
+        }
     }
 }
@@ -1,14 +1,19 @@
 //! Mesh rendering system with deferred draw queuing.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use crate::billboard::{Billboard, BillboardPass};
+use crate::debug_lines::{DebugLinePass, DebugLines};
 use crate::draw2d::Color;
-use crate::ecs::{MeshId, TextureId};
+use crate::ecs::{MaterialId, MeshId, TextureId};
+use crate::frustum::Frustum;
 use crate::gpu::GpuContext;
-use crate::mesh::{Mesh, Transform};
-use crate::mesh_pass::{DrawCall, MeshPass};
+use crate::lighting::Lighting;
+use crate::mesh::{Mesh, Transform, UvTransform};
+use crate::mesh_pass::{CullMode, DrawCall, MeshPass};
 use crate::render_graph::{RenderContext, RenderNode};
+use crate::render_stats::RenderStats;
 use crate::texture::Texture;
 
 /// A queued mesh draw call stored in the shared mesh queue.
@@ -23,6 +28,12 @@ use crate::texture::Texture;
 /// * `transform` - World-space transformation (position, rotation, scale)
 /// * `color` - RGBA color tint applied to the mesh
 /// * `texture` - Optional type-safe texture handle
+/// * `layer` - Layer to sample when `texture` is a texture array
+/// * `uv_transform` - Offset, scale, and rotation applied to the mesh's UVs
+/// * `normal_map` - Optional type-safe normal map handle
+/// * `material` - Optional custom material handle
+/// * `cull_mode` - Which triangle faces to discard
+/// * `alpha_cutoff` - Alpha below which fragments are discarded, or `0.0` to blend
 pub struct QueuedMesh {
     /// Handle to the mesh in the queue's mesh array.
     pub mesh: MeshId,
@@ -32,6 +43,19 @@ pub struct QueuedMesh {
     pub color: Color,
     /// Optional texture handle. `None` uses vertex colors only.
     pub texture: Option<TextureId>,
+    /// Layer of `texture` to sample if it is a texture array; ignored otherwise.
+    pub layer: u32,
+    /// Transform applied to the mesh's UVs. [`UvTransform::IDENTITY`] leaves them unchanged.
+    pub uv_transform: UvTransform,
+    /// Optional normal map handle. `None` keeps the mesh's own normals.
+    pub normal_map: Option<TextureId>,
+    /// Optional material handle. `None` uses the built-in mesh shader.
+    pub material: Option<MaterialId>,
+    /// Which faces to discard. [`CullMode::Back`] suits most closed meshes.
+    pub cull_mode: CullMode,
+    /// Fragments with alpha below this are discarded and the rest drawn opaque.
+    /// `0.0` disables the cutout and blends by alpha instead.
+    pub alpha_cutoff: f32,
 }
 
 /// Shared storage for meshes, textures, and the per-frame draw queue.
@@ -65,11 +89,34 @@ pub struct QueuedMesh {
 /// applications or `Arc<Mutex<>>` for multi-threaded scenarios.
 pub struct MeshQueue {
     /// Registered meshes, indexed by the values returned from [`add_mesh`](Self::add_mesh).
-    pub meshes: Vec<Mesh>,
+    /// `None` where a mesh was [removed](Self::remove_mesh) or hasn't been set yet.
+    pub meshes: Vec<Option<Mesh>>,
     /// Registered textures, indexed by the values returned from [`add_texture`](Self::add_texture).
-    pub textures: Vec<Texture>,
+    /// `None` where a texture was [removed](Self::remove_texture) or hasn't been set yet.
+    pub textures: Vec<Option<Texture>>,
+    /// WGSL sources of registered materials, indexed by the values returned from
+    /// [`add_material`](Self::add_material). Compiled lazily by each [`MeshNode`].
+    pub materials: Vec<String>,
     /// Per-frame draw queue, cleared at the end of each frame.
     pub draw_queue: Vec<QueuedMesh>,
+    /// Lights applied to all meshes. Persists across frames.
+    pub lighting: Lighting,
+    /// World-space lines drawn over the meshes, cleared with the draw queue.
+    pub debug_lines: DebugLines,
+    /// Camera-facing quads drawn after the meshes, cleared with the draw queue.
+    pub billboards: Vec<Billboard>,
+    /// Skip meshes whose bounding spheres lie entirely outside the camera frustum.
+    pub frustum_culling: bool,
+    /// Lay down opaque meshes' depth before shading them, so each pixel is shaded once.
+    ///
+    /// See [`MeshPass::set_depth_prepass`].
+    pub depth_prepass: bool,
+    /// Number of draw calls dropped by frustum culling in the last rendered frame.
+    culled: Cell<usize>,
+    /// Work submitted by mesh nodes so far this frame.
+    rendering_stats: Cell<RenderStats>,
+    /// Work submitted by mesh nodes in the last rendered frame.
+    stats: RenderStats,
 }
 
 impl MeshQueue {
@@ -78,14 +125,24 @@ impl MeshQueue {
         Self {
             meshes: Vec::new(),
             textures: Vec::new(),
+            materials: Vec::new(),
             draw_queue: Vec::new(),
+            lighting: Lighting::default(),
+            debug_lines: DebugLines::new(),
+            billboards: Vec::new(),
+            frustum_culling: false,
+            depth_prepass: false,
+            culled: Cell::new(0),
+            rendering_stats: Cell::new(RenderStats::default()),
+            stats: RenderStats::default(),
         }
     }
 
     /// Registers a mesh and returns a type-safe handle for later use.
     ///
-    /// Meshes are stored permanently until the queue is dropped. Use the
-    /// returned [`MeshId`] with [`draw`](Self::draw) or [`draw_textured`](Self::draw_textured).
+    /// Meshes are stored until the queue is dropped or the mesh is freed with
+    /// [`remove_mesh`](Self::remove_mesh). Use the returned [`MeshId`] with
+    /// [`draw`](Self::draw) or [`draw_textured`](Self::draw_textured).
     ///
     /// # Arguments
     ///
@@ -96,14 +153,44 @@ impl MeshQueue {
     /// A [`MeshId`] that can be used to reference this mesh in draw calls.
     pub fn add_mesh(&mut self, mesh: Mesh) -> MeshId {
         let idx = self.meshes.len();
-        self.meshes.push(mesh);
+        self.meshes.push(Some(mesh));
+        MeshId(idx)
+    }
+
+    /// Reserves a mesh handle to be filled later with [`set_mesh`](Self::set_mesh).
+    ///
+    /// Draws of the handle are skipped until it holds a mesh.
+    pub fn reserve_mesh(&mut self) -> MeshId {
+        let idx = self.meshes.len();
+        self.meshes.push(None);
         MeshId(idx)
     }
 
+    /// Puts `mesh` behind an existing handle, freeing any mesh it held.
+    pub fn set_mesh(&mut self, id: MeshId, mesh: Mesh) {
+        if let Some(slot) = self.meshes.get_mut(id.0) {
+            *slot = Some(mesh);
+        }
+    }
+
+    /// Removes a mesh, freeing its GPU buffers once nothing else holds them.
+    ///
+    /// The handle stays reserved: draws of it are skipped until
+    /// [`set_mesh`](Self::set_mesh) gives it a mesh again.
+    pub fn remove_mesh(&mut self, id: MeshId) -> Option<Mesh> {
+        self.meshes.get_mut(id.0).and_then(Option::take)
+    }
+
+    /// Returns the mesh behind a handle, if it holds one.
+    pub fn mesh(&self, id: MeshId) -> Option<&Mesh> {
+        self.meshes.get(id.0).and_then(Option::as_ref)
+    }
+
     /// Registers a texture and returns a type-safe handle for later use.
     ///
-    /// Textures are stored permanently until the queue is dropped. Use the
-    /// returned [`TextureId`] with [`draw_textured`](Self::draw_textured).
+    /// Textures are stored until the queue is dropped or the texture is freed
+    /// with [`remove_texture`](Self::remove_texture). Use the returned
+    /// [`TextureId`] with [`draw_textured`](Self::draw_textured).
     ///
     /// # Arguments
     ///
@@ -114,10 +201,54 @@ impl MeshQueue {
     /// A [`TextureId`] that can be used to reference this texture in draw calls.
     pub fn add_texture(&mut self, texture: Texture) -> TextureId {
         let idx = self.textures.len();
-        self.textures.push(texture);
+        self.textures.push(Some(texture));
+        TextureId(idx)
+    }
+
+    /// Reserves a texture handle to be filled later with [`set_texture`](Self::set_texture).
+    ///
+    /// Draws of the handle render untextured until it holds a texture.
+    pub fn reserve_texture(&mut self) -> TextureId {
+        let idx = self.textures.len();
+        self.textures.push(None);
         TextureId(idx)
     }
 
+    /// Puts `texture` behind an existing handle, freeing any texture it held.
+    pub fn set_texture(&mut self, id: TextureId, texture: Texture) {
+        if let Some(slot) = self.textures.get_mut(id.0) {
+            *slot = Some(texture);
+        }
+    }
+
+    /// Removes a texture, freeing its GPU memory once nothing else holds it.
+    ///
+    /// The handle stays reserved: draws of it render untextured until
+    /// [`set_texture`](Self::set_texture) gives it a texture again.
+    pub fn remove_texture(&mut self, id: TextureId) -> Option<Texture> {
+        self.textures.get_mut(id.0).and_then(Option::take)
+    }
+
+    /// Returns the texture behind a handle, if it holds one.
+    pub fn texture(&self, id: TextureId) -> Option<&Texture> {
+        self.textures.get(id.0).and_then(Option::as_ref)
+    }
+
+    /// Registers a custom material shader and returns a type-safe handle for later use.
+    ///
+    /// The WGSL source is compiled by each [`MeshNode`] before it next renders.
+    /// Pass the handle to [`MeshBuilder::material`](crate::MeshBuilder::material) or
+    /// [`RenderMesh::with_material`](crate::RenderMesh::with_material) to draw a mesh with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `wgsl` - Shader source with the same entry points and bindings as the built-in mesh shader
+    pub fn add_material(&mut self, wgsl: impl Into<String>) -> MaterialId {
+        let idx = self.materials.len();
+        self.materials.push(wgsl.into());
+        MaterialId(idx)
+    }
+
     /// Queues a mesh for rendering this frame without a texture.
     ///
     /// The mesh will be rendered using vertex colors multiplied by the
@@ -134,6 +265,12 @@ impl MeshQueue {
             transform,
             color,
             texture: None,
+            layer: 0,
+            uv_transform: UvTransform::IDENTITY,
+            normal_map: None,
+            material: None,
+            cull_mode: CullMode::Back,
+            alpha_cutoff: 0.0,
         });
     }
 
@@ -160,15 +297,86 @@ impl MeshQueue {
             transform,
             color,
             texture: Some(texture),
+            layer: 0,
+            uv_transform: UvTransform::IDENTITY,
+            normal_map: None,
+            material: None,
+            cull_mode: CullMode::Back,
+            alpha_cutoff: 0.0,
+        });
+    }
+
+    /// Queues a mesh textured with one layer of a texture array.
+    ///
+    /// Draws of the same mesh and array are rendered together in one
+    /// instanced call, whichever layers they use.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh` - Handle from [`add_mesh`](Self::add_mesh)
+    /// * `transform` - World-space transformation
+    /// * `color` - Color tint (use `Color::WHITE` for no tinting)
+    /// * `texture` - Handle of a [`Texture::array_from_files`] texture
+    /// * `layer` - Index of the layer to sample
+    pub fn draw_texture_layer(
+        &mut self,
+        mesh: MeshId,
+        transform: Transform,
+        color: Color,
+        texture: TextureId,
+        layer: u32,
+    ) {
+        self.draw_queue.push(QueuedMesh {
+            mesh,
+            transform,
+            color,
+            texture: Some(texture),
+            layer,
+            uv_transform: UvTransform::IDENTITY,
+            normal_map: None,
+            material: None,
+            cull_mode: CullMode::Back,
+            alpha_cutoff: 0.0,
         });
     }
 
-    /// Clears the draw queue for the next frame.
+    /// Returns how many queued meshes frustum culling skipped in the last rendered frame.
+    ///
+    /// Always zero while [`frustum_culling`](Self::frustum_culling) is disabled.
+    pub fn culled_count(&self) -> usize {
+        self.culled.get()
+    }
+
+    /// Returns the draw calls, triangles, and mesh instances submitted in the last
+    /// rendered frame, summed over every [`MeshNode`] drawing this queue.
+    ///
+    /// `vertices_2d` is always zero here; see [`Frame::stats`](crate::Frame::stats)
+    /// for totals that include 2D drawing.
+    pub fn render_stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    /// Returns `true` if the queued mesh's bounding sphere touches the frustum.
+    fn is_visible(&self, queued: &QueuedMesh, frustum: &Frustum) -> bool {
+        let Some(mesh) = self.mesh(queued.mesh) else {
+            return false;
+        };
+        let (center, radius) = mesh.bounding_sphere();
+        let center = queued.transform.matrix().transform_point3(center);
+        let radius = radius * queued.transform.scale.abs().max_element();
+        frustum.intersects_sphere(center, radius)
+    }
+
+    /// Clears the draw queue, debug lines, and billboards for the next frame.
     ///
     /// Call this at the end of each frame after the render graph has executed.
-    /// Registered meshes and textures are preserved.
+    /// Registered meshes and textures are preserved, and the frame's
+    /// [`render_stats`](Self::render_stats) become readable.
     pub fn clear_queue(&mut self) {
         self.draw_queue.clear();
+        self.debug_lines.clear();
+        self.billboards.clear();
+        self.stats = self.rendering_stats.take();
     }
 }
 
@@ -186,6 +394,8 @@ impl Default for MeshQueue {
 /// - Depth testing for correct occlusion
 /// - Per-instance transforms and color tints
 /// - Optional texturing
+/// - Depth-tested [`DebugLines`] from the queue, drawn after the meshes
+/// - Camera-facing [`Billboard`]s from the queue, drawn last
 /// - Compositing over previous pass output (background blitting)
 ///
 /// # Integration with Render Graph
@@ -223,8 +433,13 @@ pub struct MeshNode {
     pub pass: MeshPass,
     /// Shared queue containing meshes, textures, and draw calls.
     pub queue: Rc<RefCell<MeshQueue>>,
-    /// Optional clear color. `None` preserves previous pass output.
+    /// Optional clear color. `None` preserves previous pass output, or uses the
+    /// graph's [clear color](crate::RenderGraph::set_clear_color) when first in the graph.
     pub clear_color: Option<wgpu::Color>,
+    /// Draws the queue's debug lines into the mesh render pass.
+    lines: DebugLinePass,
+    /// Draws the queue's billboards into the mesh render pass.
+    billboards: BillboardPass,
 }
 
 impl MeshNode {
@@ -238,10 +453,24 @@ impl MeshNode {
     /// * `gpu` - GPU context for creating the mesh pass and depth buffer
     /// * `queue` - Shared mesh queue (typically `Rc<RefCell<MeshQueue>>`)
     pub fn new(gpu: &GpuContext, queue: Rc<RefCell<MeshQueue>>) -> Self {
+        Self::new_with_format(gpu, queue, gpu.config.format)
+    }
+
+    /// Creates a mesh render node that renders into targets of `format`.
+    ///
+    /// Use [`HDR_FORMAT`](crate::HDR_FORMAT) when the node isn't last in a
+    /// graph built with [`hdr`](crate::RenderGraphBuilder::hdr).
+    pub fn new_with_format(
+        gpu: &GpuContext,
+        queue: Rc<RefCell<MeshQueue>>,
+        format: wgpu::TextureFormat,
+    ) -> Self {
         Self {
-            pass: MeshPass::new(gpu),
+            pass: MeshPass::new_with_format(gpu, format),
             queue,
             clear_color: None, // Don't clear by default - render on top of previous pass
+            lines: DebugLinePass::new(&gpu.device, format),
+            billboards: BillboardPass::new(&gpu.device, format),
         }
     }
 
@@ -272,19 +501,39 @@ impl RenderNode for MeshNode {
     ) {
         let queue = self.queue.borrow();
 
+        // Drop meshes that are entirely off screen
+        let frustum = queue.frustum_culling.then(|| {
+            Frustum::from_view_projection(&MeshPass::view_projection(ctx.gpu, ctx.camera))
+        });
+        let mut culled = 0;
+
         // Build draw calls from the queue
         let draw_calls: Vec<DrawCall> = queue
             .draw_queue
             .iter()
+            .filter(|q| match &frustum {
+                Some(frustum) if !queue.is_visible(q, frustum) => {
+                    culled += 1;
+                    false
+                }
+                _ => true,
+            })
             .filter_map(|q| {
-                queue.meshes.get(q.mesh.0).map(|mesh| DrawCall {
+                queue.mesh(q.mesh).map(|mesh| DrawCall {
                     mesh,
                     transform: q.transform,
                     color: q.color,
-                    texture: q.texture.and_then(|t| queue.textures.get(t.0)),
+                    texture: q.texture.and_then(|t| queue.texture(t)),
+                    layer: q.layer,
+                    uv_transform: q.uv_transform,
+                    normal_map: q.normal_map.and_then(|t| queue.texture(t)),
+                    material: q.material,
+                    cull_mode: q.cull_mode,
+                    alpha_cutoff: q.alpha_cutoff,
                 })
             })
             .collect();
+        queue.culled.set(culled);
 
         // If there's an input texture, we need to blit it first as the background
         if let Some(input_view) = input {
@@ -307,8 +556,8 @@ impl RenderNode for MeshNode {
             self.pass.blit(ctx.gpu, &mut blit_pass, input_view);
         }
 
-        // If no meshes to draw, we're done (background is already blitted)
-        if draw_calls.is_empty() {
+        // If nothing to draw, we're done (background is already blitted)
+        if draw_calls.is_empty() && queue.debug_lines.is_empty() && queue.billboards.is_empty() {
             // If there was no input either, we need to at least clear the target
             if input.is_none() {
                 let _clear_pass = ctx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -317,9 +566,7 @@ impl RenderNode for MeshNode {
                         view: target,
                         resolve_target: None,
                         ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(
-                                self.clear_color.unwrap_or(wgpu::Color::BLACK),
-                            ),
+                            load: wgpu::LoadOp::Clear(self.clear_color.unwrap_or(ctx.clear_color)),
                             store: wgpu::StoreOp::Store,
                         },
                         depth_slice: None,
@@ -333,14 +580,11 @@ impl RenderNode for MeshNode {
         }
 
         // Second pass: render meshes on top with depth testing
-        // Use Load since we already blitted (or Clear if no input and clear_color is set)
+        // Use Load since we already blitted, or clear to the node's or graph's clear color
         let load_op = if input.is_some() {
             wgpu::LoadOp::Load
         } else {
-            match self.clear_color {
-                Some(color) => wgpu::LoadOp::Clear(color),
-                None => wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-            }
+            wgpu::LoadOp::Clear(self.clear_color.unwrap_or(ctx.clear_color))
         };
 
         let mut render_pass = ctx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -366,11 +610,43 @@ impl RenderNode for MeshNode {
             occlusion_query_set: None,
         });
 
-        self.pass
+        self.pass.set_lighting(ctx.gpu, &queue.lighting);
+        let stats = self
+            .pass
             .render(ctx.gpu, &mut render_pass, ctx.camera, ctx.time, &draw_calls);
+        queue
+            .rendering_stats
+            .set(queue.rendering_stats.get() + stats);
+        let view_proj = MeshPass::view_projection(ctx.gpu, ctx.camera);
+        self.lines.render(
+            &ctx.gpu.queue,
+            &mut render_pass,
+            view_proj,
+            &queue.debug_lines,
+        );
+        self.billboards.render(
+            &ctx.gpu.device,
+            &ctx.gpu.queue,
+            &mut render_pass,
+            ctx.camera,
+            view_proj,
+            &queue.billboards,
+            &queue.textures,
+        );
+
+        // Later world post-process passes can read this frame's depth
+        ctx.depth = Some(self.pass.depth_view.clone());
     }
 
     fn check_hot_reload(&mut self, gpu: &GpuContext) {
         self.pass.ensure_depth_size(gpu);
+        let queue = self.queue.borrow();
+        self.pass.set_depth_prepass(gpu, queue.depth_prepass);
+        self.pass
+            .ensure_instance_capacity(gpu, queue.draw_queue.len());
+        self.pass.ensure_materials(gpu, &queue.materials);
+        self.lines.ensure_capacity(&gpu.device, &queue.debug_lines);
+        self.billboards
+            .ensure_capacity(&gpu.device, queue.billboards.len());
     }
 }
@@ -25,6 +25,8 @@
 //! - [`PostProcessNode`] / [`HotPostProcessNode`]: Screen-space post-processing (blur, bloom, color grading)
 //! - [`WorldPostProcessNode`] / [`HotWorldPostProcessNode`]: Post-processing with camera/world data (raymarching, fog)
 //! - [`MeshNode`]: 3D mesh rendering with depth testing
+//! - [`BloomNode`]: Built-in multi-pass bloom
+//! - [`SkyboxNode`]: Cubemap background that follows the camera's orientation
 //!
 //! Hot-reload variants automatically watch shader files and recompile on changes.
 //!
@@ -54,18 +56,25 @@
 //! });
 //! ```
 
+mod bloom;
 mod effect_nodes;
 mod graph;
 mod mesh_queue;
 mod post_process_nodes;
+mod profiler;
 mod render_node;
 mod render_target;
+mod skybox;
+mod upscale;
 
+pub use bloom::{BloomHandle, BloomNode, BloomSettings};
 pub use effect_nodes::{EffectNode, HotEffectNode};
 pub use graph::{RenderGraph, RenderGraphBuilder};
+pub(crate) use mesh_queue::QueuedMesh;
 pub use mesh_queue::{MeshNode, MeshQueue};
 pub use post_process_nodes::{
     HotPostProcessNode, HotWorldPostProcessNode, PostProcessNode, WorldPostProcessNode,
 };
 pub use render_node::RenderNode;
-pub use render_target::{RenderContext, RenderTarget};
+pub use render_target::{HDR_FORMAT, RenderContext, RenderTarget};
+pub use skybox::SkyboxNode;
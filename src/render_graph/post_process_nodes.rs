@@ -24,8 +24,8 @@ use crate::render_graph::{RenderContext, RenderNode};
 /// # Example
 ///
 /// ```ignore
-/// let bloom = PostProcessPass::new(&gpu, include_str!("shaders/bloom.wgsl"));
-/// let tonemap = PostProcessPass::new(&gpu, include_str!("shaders/tonemap.wgsl"));
+/// let bloom = PostProcessPass::new(&gpu, include_str!("shaders/bloom.wgsl"))?;
+/// let tonemap = PostProcessPass::new(&gpu, include_str!("shaders/tonemap.wgsl"))?;
 ///
 /// let graph = RenderGraph::builder()
 ///     .node(EffectNode::new(scene))         // First: render scene
@@ -105,7 +105,7 @@ impl RenderNode for PostProcessNode {
 /// # Example
 ///
 /// ```ignore
-/// let fog = WorldPostProcessPass::new(&gpu, include_str!("shaders/volumetric_fog.wgsl"));
+/// let fog = WorldPostProcessPass::new(&gpu, include_str!("shaders/volumetric_fog.wgsl"))?;
 ///
 /// let graph = RenderGraph::builder()
 ///     .node(EffectNode::new(scene))
@@ -154,8 +154,14 @@ impl RenderNode for WorldPostProcessNode {
             occlusion_query_set: None,
         });
 
-        self.pass
-            .render(ctx.gpu, &mut render_pass, ctx.time, ctx.camera, input_view);
+        self.pass.render_with_depth(
+            ctx.gpu,
+            &mut render_pass,
+            ctx.time,
+            ctx.camera,
+            input_view,
+            ctx.depth.as_ref(),
+        );
     }
 }
 
@@ -243,6 +249,10 @@ impl RenderNode for HotPostProcessNode {
     fn check_hot_reload(&mut self, gpu: &GpuContext) {
         self.pass.check_reload(gpu);
     }
+
+    fn shader_error(&self) -> Option<&str> {
+        self.pass.last_error()
+    }
 }
 
 /// Hot-reloadable render node for world-aware post-processing.
@@ -319,11 +329,21 @@ impl RenderNode for HotWorldPostProcessNode {
             occlusion_query_set: None,
         });
 
-        self.pass
-            .render(ctx.gpu, &mut render_pass, ctx.time, ctx.camera, input_view);
+        self.pass.render_with_depth(
+            ctx.gpu,
+            &mut render_pass,
+            ctx.time,
+            ctx.camera,
+            input_view,
+            ctx.depth.as_ref(),
+        );
     }
 
     fn check_hot_reload(&mut self, gpu: &GpuContext) {
         self.pass.check_reload(gpu);
     }
+
+    fn shader_error(&self) -> Option<&str> {
+        self.pass.last_error()
+    }
 }
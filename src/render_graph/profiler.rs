@@ -0,0 +1,139 @@
+//! GPU timestamp queries for per-node render graph timings.
+
+use crate::gpu::GpuContext;
+
+/// Records a start and end timestamp around each render node and reads them back.
+///
+/// Requires [`GpuContext::supports_timestamps`]. Timestamps are written directly
+/// into the command encoder, so they bracket every pass a node records. Reading
+/// them back waits for the GPU to finish the frame, which costs some throughput
+/// while profiling is enabled.
+pub(crate) struct GpuProfiler {
+    query_set: wgpu::QuerySet,
+    /// Destination of `resolve_query_set`; not mappable.
+    resolve_buffer: wgpu::Buffer,
+    /// CPU-readable copy of the resolved timestamps.
+    readback_buffer: wgpu::Buffer,
+    /// Number of nodes the buffers can hold.
+    capacity: usize,
+}
+
+impl GpuProfiler {
+    /// Creates a profiler with room for `nodes` render nodes.
+    pub(crate) fn new(gpu: &GpuContext, nodes: usize) -> Self {
+        let capacity = nodes.max(1);
+        let query_count = (capacity * 2) as u32;
+        let size = query_count as u64 * wgpu::QUERY_SIZE as u64;
+
+        let query_set = gpu.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("RenderGraph Timestamps"),
+            ty: wgpu::QueryType::Timestamp,
+            count: query_count,
+        });
+        let resolve_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("RenderGraph Timestamp Resolve"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("RenderGraph Timestamp Readback"),
+            size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            capacity,
+        }
+    }
+
+    /// Number of nodes this profiler can time per frame.
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Writes the start timestamp for node `index`.
+    pub(crate) fn begin(&self, encoder: &mut wgpu::CommandEncoder, index: usize) {
+        encoder.write_timestamp(&self.query_set, (index * 2) as u32);
+    }
+
+    /// Writes the end timestamp for node `index`.
+    pub(crate) fn end(&self, encoder: &mut wgpu::CommandEncoder, index: usize) {
+        encoder.write_timestamp(&self.query_set, (index * 2 + 1) as u32);
+    }
+
+    /// Resolves the timestamps of the first `nodes` nodes into the readback buffer.
+    pub(crate) fn resolve(&self, encoder: &mut wgpu::CommandEncoder, nodes: usize) {
+        let query_count = (nodes * 2) as u32;
+        if query_count == 0 {
+            return;
+        }
+        encoder.resolve_query_set(&self.query_set, 0..query_count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            query_count as u64 * wgpu::QUERY_SIZE as u64,
+        );
+    }
+
+    /// Reads back resolved timestamps, returning each node's duration in milliseconds.
+    ///
+    /// Call after submitting the encoder passed to [`resolve`](Self::resolve).
+    /// Blocks until the GPU has finished. Returns an empty list if the readback fails.
+    pub(crate) fn read(&self, gpu: &GpuContext, nodes: usize) -> Vec<f32> {
+        if nodes == 0 {
+            return Vec::new();
+        }
+        let size = (nodes * 2) as u64 * wgpu::QUERY_SIZE as u64;
+        let slice = self.readback_buffer.slice(..size);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        if gpu
+            .device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .is_err()
+        {
+            return Vec::new();
+        }
+
+        let period = gpu.queue.get_timestamp_period();
+        let durations = {
+            let data = slice.get_mapped_range();
+            let ticks: &[u64] = bytemuck::cast_slice(&data);
+            ticks_to_millis(ticks, period)
+        };
+        self.readback_buffer.unmap();
+        durations
+    }
+}
+
+/// Converts `[start, end, start, end, ...]` tick pairs into durations in milliseconds.
+///
+/// `period` is the number of nanoseconds per tick.
+fn ticks_to_millis(ticks: &[u64], period: f32) -> Vec<f32> {
+    ticks
+        .chunks_exact(2)
+        .map(|pair| pair[1].saturating_sub(pair[0]) as f64 * period as f64 / 1_000_000.0)
+        .map(|ms| ms as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_pairs_become_milliseconds() {
+        // 1ns ticks: 2ms, then 0.5ms; a wrapped pair clamps to zero
+        let ticks = [1_000, 2_001_000, 5_000_000, 5_500_000, 10, 5];
+        assert_eq!(ticks_to_millis(&ticks, 1.0), vec![2.0, 0.5, 0.0]);
+
+        // 10ns ticks scale up
+        assert_eq!(ticks_to_millis(&[0, 100_000], 10.0), vec![1.0]);
+    }
+}
@@ -72,4 +72,54 @@ pub trait RenderNode {
     ///
     /// * `gpu` - GPU context for recompiling shaders if changes are detected
     fn check_hot_reload(&mut self, _gpu: &GpuContext) {}
+
+    /// The current shader error of a hot-reloadable node, if any.
+    ///
+    /// Hot-reload nodes return the error that kept their latest shader edit
+    /// from compiling, until a later edit compiles. The default implementation
+    /// returns `None`.
+    fn shader_error(&self) -> Option<&str> {
+        None
+    }
+
+    /// Human-readable name used in profiling output.
+    ///
+    /// Defaults to the implementing type's name without its module path
+    /// (e.g. `"PostProcessNode"`). Override to tell apart several nodes of the
+    /// same type.
+    fn name(&self) -> &str {
+        short_type_name(std::any::type_name::<Self>())
+    }
+}
+
+/// Strips the module path from a type name, keeping generic arguments intact.
+fn short_type_name(full: &str) -> &str {
+    let generics = full.find('<').unwrap_or(full.len());
+    let start = full[..generics].rfind("::").map_or(0, |i| i + 2);
+    &full[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_graph::RenderContext;
+
+    struct Blur;
+
+    impl RenderNode for Blur {
+        fn execute(
+            &self,
+            _: &mut RenderContext,
+            _: &wgpu::TextureView,
+            _: Option<&wgpu::TextureView>,
+        ) {
+        }
+    }
+
+    #[test]
+    fn default_name_is_the_short_type_name() {
+        assert_eq!(Blur.name(), "Blur");
+        assert_eq!(short_type_name("a::b::Node<c::D>"), "Node<c::D>");
+        assert_eq!(short_type_name("Plain"), "Plain");
+    }
 }
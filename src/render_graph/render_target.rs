@@ -3,6 +3,12 @@
 use crate::camera::Camera;
 use crate::gpu::GpuContext;
 
+/// Format of HDR intermediate targets (see [`RenderGraphBuilder::hdr`](crate::RenderGraphBuilder::hdr)).
+///
+/// Half-float channels keep values above 1.0 so bright lighting and bloom don't
+/// clip before the final tonemapping pass.
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
 /// An off-screen render target used for intermediate pass results.
 ///
 /// Render targets are GPU textures that can be both rendered to (as a color attachment)
@@ -17,8 +23,8 @@ use crate::gpu::GpuContext;
 ///
 /// * `texture` - The underlying wgpu texture resource
 /// * `view` - A texture view for binding as either render target or sampler input
-/// * `width` - Current width in pixels (tracks GPU surface size)
-/// * `height` - Current height in pixels (tracks GPU surface size)
+/// * `width` - Current width in pixels (tracks the GPU render width)
+/// * `height` - Current height in pixels (tracks the GPU render height)
 pub struct RenderTarget {
     /// The underlying GPU texture that stores pixel data.
     pub texture: wgpu::Texture,
@@ -26,10 +32,13 @@ pub struct RenderTarget {
     pub view: wgpu::TextureView,
     width: u32,
     height: u32,
+    format: wgpu::TextureFormat,
 }
 
 impl RenderTarget {
-    /// Creates a new render target matching the current GPU surface dimensions.
+    /// Creates a new render target matching the current GPU render resolution.
+    ///
+    /// This is the surface size scaled by [`GpuContext::render_scale`].
     ///
     /// The texture is created with:
     /// - Same format as the surface (typically `Bgra8UnormSrgb`)
@@ -41,17 +50,24 @@ impl RenderTarget {
     /// * `gpu` - The GPU context providing device and surface configuration
     /// * `label` - Debug label for the texture (visible in GPU debuggers like RenderDoc)
     pub fn new(gpu: &GpuContext, label: &str) -> Self {
+        Self::new_with_format(gpu, label, gpu.config.format)
+    }
+
+    /// Creates a render target of the given format, such as [`HDR_FORMAT`].
+    ///
+    /// Passes that render into it must build their pipelines for the same format.
+    pub fn new_with_format(gpu: &GpuContext, label: &str, format: wgpu::TextureFormat) -> Self {
         let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
             label: Some(label),
             size: wgpu::Extent3d {
-                width: gpu.width(),
-                height: gpu.height(),
+                width: gpu.render_width(),
+                height: gpu.render_height(),
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: gpu.config.format,
+            format,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
         });
@@ -59,23 +75,30 @@ impl RenderTarget {
         Self {
             texture,
             view,
-            width: gpu.width(),
-            height: gpu.height(),
+            width: gpu.render_width(),
+            height: gpu.render_height(),
+            format,
         }
     }
 
-    /// Checks if the target dimensions match the GPU surface and recreates if needed.
+    /// The texture format of this target.
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// Checks if the target dimensions match the GPU render resolution and recreates if needed.
     ///
     /// This should be called at the start of each frame to handle window resizes.
-    /// If the dimensions differ, a new texture is allocated and the old one is dropped.
+    /// If the dimensions differ, a new texture of the same format is allocated and
+    /// the old one is dropped.
     ///
     /// # Arguments
     ///
     /// * `gpu` - The GPU context to check dimensions against
     /// * `label` - Debug label for the recreated texture
     pub fn ensure_size(&mut self, gpu: &GpuContext, label: &str) {
-        if self.width != gpu.width() || self.height != gpu.height() {
-            *self = Self::new(gpu, label);
+        if self.width != gpu.render_width() || self.height != gpu.render_height() {
+            *self = Self::new_with_format(gpu, label, self.format);
         }
     }
 }
@@ -97,6 +120,7 @@ impl RenderTarget {
 /// * `encoder` - Command encoder for recording GPU commands
 /// * `time` - Elapsed time in seconds (for animations and effects)
 /// * `camera` - Current camera state for view/projection matrices
+/// * `depth` - Scene depth written by a [`MeshNode`](crate::MeshNode) earlier in the frame
 pub struct RenderContext<'a> {
     /// GPU context providing access to device, queue, and configuration.
     pub gpu: &'a GpuContext,
@@ -109,4 +133,10 @@ pub struct RenderContext<'a> {
     /// Current camera providing view and projection matrices.
     /// Available for nodes that need world-space or screen-space transformations.
     pub camera: &'a Camera,
+    /// Depth buffer (`Depth32Float`) of the most recent mesh node that drew this frame.
+    /// `None` until one has. World post-process nodes bind it for depth-aware effects.
+    pub depth: Option<wgpu::TextureView>,
+    /// Background for nodes that start without an input to draw over.
+    /// See [`RenderGraph::set_clear_color`](super::RenderGraph::set_clear_color).
+    pub clear_color: wgpu::Color,
 }
@@ -0,0 +1,245 @@
+//! Cubemap skybox drawn behind the rest of the scene.
+
+use glam::Mat4;
+
+use crate::camera::Camera;
+use crate::gpu::GpuContext;
+use crate::render_graph::{RenderContext, RenderNode};
+use crate::texture::Texture;
+
+/// Uniforms for `skybox.wgsl` at `@group(0) @binding(0)`.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct SkyboxUniforms {
+    inv_view_proj: [[f32; 4]; 4],
+}
+
+/// Render node that fills the target with a cubemap sampled along the view direction.
+///
+/// Only the camera's orientation matters, so the sky stays infinitely far
+/// away as the camera moves. The node clears and covers the whole target, so
+/// add it as the first node and draw meshes on top with a later [`MeshNode`](crate::MeshNode).
+///
+/// # Example
+///
+/// ```ignore
+/// let sky = Texture::cubemap_from_files(&gpu, [
+///     "sky/px.png", "sky/nx.png",
+///     "sky/py.png", "sky/ny.png",
+///     "sky/pz.png", "sky/nz.png",
+/// ])?;
+///
+/// let graph = RenderGraph::builder()
+///     .node(SkyboxNode::new(&gpu, sky))
+///     .node(MeshNode::new(&gpu, Rc::clone(&queue)))
+///     .build(&gpu);
+/// ```
+pub struct SkyboxNode {
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl SkyboxNode {
+    /// Creates a skybox node that writes to the surface format.
+    ///
+    /// `cubemap` must have a cube view, as made by [`Texture::cubemap_from_files`].
+    pub fn new(gpu: &GpuContext, cubemap: Texture) -> Self {
+        Self::new_with_format(gpu, cubemap, gpu.config.format)
+    }
+
+    /// Creates a skybox node that writes to `format`.
+    ///
+    /// Use [`HDR_FORMAT`](crate::HDR_FORMAT) for nodes that aren't last in a
+    /// graph built with [`hdr`](crate::RenderGraphBuilder::hdr).
+    pub fn new_with_format(
+        gpu: &GpuContext,
+        cubemap: Texture,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        let device = &gpu.device;
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Skybox Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/skybox.wgsl").into()),
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Skybox Uniforms"),
+            size: std::mem::size_of::<SkyboxUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Skybox Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::Cube,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Skybox Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&cubemap.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&cubemap.sampler),
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Skybox Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Skybox Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+}
+
+impl RenderNode for SkyboxNode {
+    fn execute(
+        &self,
+        ctx: &mut RenderContext,
+        target: &wgpu::TextureView,
+        _input: Option<&wgpu::TextureView>,
+    ) {
+        let uniforms = SkyboxUniforms {
+            inv_view_proj: sky_inverse_view_projection(ctx.camera, ctx.gpu.aspect())
+                .to_cols_array_2d(),
+        };
+        ctx.gpu
+            .queue
+            .write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+
+        let mut pass = ctx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Skybox Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+/// Inverse view-projection with the camera's translation removed, so unprojected
+/// points give view directions from the origin.
+fn sky_inverse_view_projection(camera: &Camera, aspect: f32) -> Mat4 {
+    let rotation_only = Camera {
+        position: glam::Vec3::ZERO,
+        ..*camera
+    };
+    let view_proj =
+        camera.projection_matrix(aspect, camera.near, camera.far) * rotation_only.view_matrix();
+    view_proj.inverse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::capture_shader_errors;
+    use glam::{Vec3, Vec4};
+
+    #[test]
+    fn screen_center_looks_along_camera_forward_from_anywhere() {
+        let camera = Camera::new()
+            .at([100.0, -40.0, 7.0])
+            .looking_at([101.0, -40.0, 6.0]);
+
+        let far = sky_inverse_view_projection(&camera, 16.0 / 9.0) * Vec4::new(0.0, 0.0, 1.0, 1.0);
+        let direction = (far.truncate() / far.w).normalize();
+        assert!((direction - Vec3::new(1.0, 0.0, -1.0).normalize()).length() < 1e-3);
+    }
+
+    #[test]
+    fn skybox_shader_compiles() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        let result = capture_shader_errors(&gpu.device, || {
+            gpu.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/skybox.wgsl").into()),
+                })
+        });
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+}
@@ -0,0 +1,44 @@
+//! Per-frame counts of the work submitted to the GPU.
+
+use std::ops::{Add, AddAssign};
+
+/// What the last rendered frame drew, for performance overlays and budgets.
+///
+/// Counts cover the built-in mesh and 2D renderers, [`MeshNode`](crate::MeshNode)
+/// and [`Draw2d`](crate::Draw2d). Work done by custom render nodes, post-process
+/// passes, and the skybox isn't included.
+///
+/// Stats add together, so totals over several renderers or frames are just sums.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Draw calls issued, counting each instanced draw once.
+    ///
+    /// Meshes drawn again by the [depth prepass](crate::MeshPass::set_depth_prepass)
+    /// count twice.
+    pub draw_calls: usize,
+    /// Mesh triangles submitted, summed over every instance and draw.
+    pub triangles: usize,
+    /// Vertices submitted by [`Draw2d`](crate::Draw2d) for shapes, text, and sprites.
+    pub vertices_2d: usize,
+    /// Mesh instances drawn, after frustum culling.
+    pub mesh_count: usize,
+}
+
+impl Add for RenderStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            draw_calls: self.draw_calls + other.draw_calls,
+            triangles: self.triangles + other.triangles,
+            vertices_2d: self.vertices_2d + other.vertices_2d,
+            mesh_count: self.mesh_count + other.mesh_count,
+        }
+    }
+}
+
+impl AddAssign for RenderStats {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
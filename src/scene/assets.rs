@@ -0,0 +1,78 @@
+//! Meshes and textures owned by a single scene.
+
+use crate::ecs::{MeshId, TextureId};
+use crate::gpu::GpuContext;
+use crate::mesh::Mesh;
+use crate::render_graph::MeshQueue;
+use crate::texture::Texture;
+
+/// Builds a scene-local mesh each time its scene is loaded.
+type MeshLoader = Box<dyn Fn(&GpuContext) -> Mesh>;
+
+/// Builds a scene-local texture each time its scene is loaded.
+type TextureLoader = Box<dyn Fn(&GpuContext) -> Texture>;
+
+/// Scene-local meshes and textures, created when their scene comes into use
+/// and freed when it leaves.
+///
+/// Each asset reserves its handle in the shared [`MeshQueue`] when it's
+/// registered, so frame closures can capture the handle during setup. Only
+/// the GPU resources behind the handles come and go.
+#[derive(Default)]
+pub(crate) struct SceneAssets {
+    meshes: Vec<(MeshId, MeshLoader)>,
+    textures: Vec<(TextureId, TextureLoader)>,
+    loaded: bool,
+}
+
+impl SceneAssets {
+    /// Reserves a mesh handle that `load` fills whenever the scene is loaded.
+    pub(crate) fn add_mesh(
+        &mut self,
+        queue: &mut MeshQueue,
+        load: impl Fn(&GpuContext) -> Mesh + 'static,
+    ) -> MeshId {
+        let id = queue.reserve_mesh();
+        self.meshes.push((id, Box::new(load)));
+        id
+    }
+
+    /// Reserves a texture handle that `load` fills whenever the scene is loaded.
+    pub(crate) fn add_texture(
+        &mut self,
+        queue: &mut MeshQueue,
+        load: impl Fn(&GpuContext) -> Texture + 'static,
+    ) -> TextureId {
+        let id = queue.reserve_texture();
+        self.textures.push((id, Box::new(load)));
+        id
+    }
+
+    /// Creates every asset behind its handle, unless they're already loaded.
+    pub(crate) fn load(&mut self, gpu: &GpuContext, queue: &mut MeshQueue) {
+        if self.loaded {
+            return;
+        }
+        for (id, load) in &self.meshes {
+            queue.set_mesh(*id, load(gpu));
+        }
+        for (id, load) in &self.textures {
+            queue.set_texture(*id, load(gpu));
+        }
+        self.loaded = true;
+    }
+
+    /// Frees every asset, keeping the handles for the next [`load`](Self::load).
+    pub(crate) fn unload(&mut self, queue: &mut MeshQueue) {
+        if !self.loaded {
+            return;
+        }
+        for (id, _) in &self.meshes {
+            queue.remove_mesh(*id);
+        }
+        for (id, _) in &self.textures {
+            queue.remove_texture(*id);
+        }
+        self.loaded = false;
+    }
+}
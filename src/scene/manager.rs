@@ -8,18 +8,47 @@ use crate::camera::Camera;
 use crate::draw2d::Draw2d;
 use crate::gpu::GpuContext;
 use crate::render_graph::{MeshQueue, RenderTarget};
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
+/// A scene pushed on top of the active scene.
+struct Overlay {
+    name: String,
+    /// Whether scenes below this one stop receiving input and time.
+    pause_below: bool,
+}
+
+/// A requested switch: target scene name, transition, and data payload.
+pub(crate) type SceneSwitch = (String, Transition, Option<Box<dyn Any>>);
+
+/// A queued change to the scene stack (processed at start of next frame).
+pub(crate) enum StackChange {
+    Push { name: String, pause_below: bool },
+    Pop,
+}
+
 /// Manages multiple scenes and transitions between them.
 ///
 /// The scene manager is responsible for:
 /// - Storing registered scenes
-/// - Tracking the active scene
+/// - Tracking the active scene and any scenes pushed on top of it
 /// - Handling scene transitions with various effects
 /// - Coordinating rendering during transitions
 ///
+/// # Scene Stack
+///
+/// [`push_scene`](Self::push_scene) layers a scene over the active one, for
+/// pause menus, HUDs, and modal dialogs. Every scene on the stack runs each
+/// frame from the bottom up, so the top scene draws over the ones below. Scenes
+/// with a render graph render in the same order, and all 2D drawing goes on top.
+/// Scenes below one pushed with [`push_scene_paused`](Self::push_scene_paused)
+/// keep drawing but get a zero `dt` and no input.
+///
+/// Pushing a scene calls its `on_enter` and popping it calls its `on_exit`;
+/// the scenes below stay entered. Switching scenes pops every pushed scene first.
+///
 /// # Example
 ///
 /// Scene management is typically done through the `run_with_scenes` API,
@@ -43,8 +72,17 @@ pub struct SceneManager {
     /// Second render target for the other scene during crossfade.
     crossfade_capture_2: Option<RenderTarget>,
 
-    /// Queued scene switch (processed at start of next frame).
-    pending_switch: Option<(String, Transition)>,
+    /// Queued scene switch and its data payload (processed at start of next frame).
+    pending_switch: Option<SceneSwitch>,
+
+    /// Scenes pushed on top of the active scene, bottom first.
+    overlays: Vec<Overlay>,
+
+    /// Queued pushes and pops (processed at start of next frame, after any switch).
+    pending_stack: Vec<StackChange>,
+
+    /// Input given to paused scenes.
+    idle_input: crate::Input,
 }
 
 impl SceneManager {
@@ -58,6 +96,9 @@ impl SceneManager {
             crossfade_capture: None,
             crossfade_capture_2: None,
             pending_switch: None,
+            overlays: Vec::new(),
+            pending_stack: Vec::new(),
+            idle_input: crate::Input::idle(),
         }
     }
 
@@ -88,15 +129,56 @@ impl SceneManager {
             }
             self.active_scene = Some(name);
         } else {
-            eprintln!("[scene] Warning: Scene '{}' not found", name);
+            self.warn_missing(&name);
         }
     }
 
+    /// Returns `true` if a scene named `name` is registered.
+    pub fn scene_exists(&self, name: &str) -> bool {
+        self.scenes.contains_key(name)
+    }
+
+    /// Names of every registered scene, in alphabetical order.
+    pub fn scene_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.scenes.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Reports a request for an unregistered scene, listing the ones that exist.
+    fn warn_missing(&self, name: &str) {
+        eprintln!(
+            "[scene] Warning: Scene '{}' not found (registered scenes: {})",
+            name,
+            self.scene_ids().join(", ")
+        );
+    }
+
     /// Get the name of the currently active scene.
+    ///
+    /// This is the bottom of the scene stack; see [`top_scene`](Self::top_scene)
+    /// for the scene pushed last.
     pub fn active_scene(&self) -> Option<&str> {
         self.active_scene.as_deref()
     }
 
+    /// Get the name of the topmost scene: the last pushed scene, or the active scene.
+    pub fn top_scene(&self) -> Option<&str> {
+        self.overlays
+            .last()
+            .map(|overlay| overlay.name.as_str())
+            .or(self.active_scene())
+    }
+
+    /// Names of all scenes on the stack, starting with the active scene.
+    pub fn scene_stack(&self) -> Vec<&str> {
+        self.active_scene
+            .iter()
+            .map(String::as_str)
+            .chain(self.overlays.iter().map(|overlay| overlay.name.as_str()))
+            .collect()
+    }
+
     /// Get a mutable reference to the active scene.
     pub fn active_scene_mut(&mut self) -> Option<&mut Scene> {
         self.active_scene
@@ -129,18 +211,134 @@ impl SceneManager {
     ///
     /// The switch is queued and processed at the start of the next frame.
     pub fn switch_to_with(&mut self, scene_name: impl Into<String>, transition: Transition) {
-        let name = scene_name.into();
+        self.queue_switch(scene_name.into(), transition, None);
+    }
+
+    /// Request an instant scene switch that hands `data` to the target scene.
+    ///
+    /// The payload is stored on the target scene before its `on_enter` runs,
+    /// and its frame logic retrieves it with
+    /// [`Frame::take_scene_data`](crate::Frame::take_scene_data).
+    pub fn switch_to_with_data(&mut self, scene_name: impl Into<String>, data: Box<dyn Any>) {
+        self.queue_switch(scene_name.into(), Transition::instant(), Some(data));
+    }
+
+    pub(crate) fn queue_switch(
+        &mut self,
+        name: String,
+        transition: Transition,
+        data: Option<Box<dyn Any>>,
+    ) {
         if !self.scenes.contains_key(&name) {
-            eprintln!("[scene] Warning: Scene '{}' not found", name);
+            self.warn_missing(&name);
+            return;
+        }
+
+        // Don't switch if we're already on this scene and there's nothing to deliver
+        if self.active_scene.as_ref() == Some(&name)
+            && self.transition.is_none()
+            && self.overlays.is_empty()
+            && data.is_none()
+        {
             return;
         }
 
-        // Don't switch if we're already on this scene and there's no transition
-        if self.active_scene.as_ref() == Some(&name) && self.transition.is_none() {
+        self.pending_switch = Some((name, transition, data));
+    }
+
+    /// Request pushing a scene on top of the stack.
+    ///
+    /// The scenes below keep updating and rendering underneath it, showing
+    /// through wherever it draws nothing. The push is queued and processed at
+    /// the start of the next frame.
+    pub fn push_scene(&mut self, scene_name: impl Into<String>) {
+        self.queue_push(scene_name.into(), false);
+    }
+
+    /// Request pushing a scene on top of the stack, pausing the scenes below.
+    ///
+    /// Paused scenes still run their frame logic so they keep drawing, but with
+    /// a zero `dt` and no input.
+    pub fn push_scene_paused(&mut self, scene_name: impl Into<String>) {
+        self.queue_push(scene_name.into(), true);
+    }
+
+    /// Request popping the topmost pushed scene.
+    ///
+    /// The active scene at the bottom of the stack is never popped; use
+    /// [`switch_to`](Self::switch_to) to replace it.
+    pub fn pop_scene(&mut self) {
+        self.pending_stack.push(StackChange::Pop);
+    }
+
+    fn queue_push(&mut self, name: String, pause_below: bool) {
+        if !self.scenes.contains_key(&name) {
+            self.warn_missing(&name);
             return;
         }
+        self.pending_stack
+            .push(StackChange::Push { name, pause_below });
+    }
+
+    /// Apply a queued push or pop, calling the lifecycle hooks.
+    fn apply_stack_change(&mut self, change: StackChange) {
+        match change {
+            StackChange::Push { name, pause_below } => {
+                if self.scene_stack().contains(&name.as_str()) {
+                    eprintln!("[scene] Warning: Scene '{}' is already on the stack", name);
+                    return;
+                }
+                if let Some(scene) = self.scenes.get_mut(&name) {
+                    scene.enter();
+                }
+                self.overlays.push(Overlay { name, pause_below });
+            }
+            StackChange::Pop => match self.overlays.pop() {
+                Some(overlay) => {
+                    if let Some(scene) = self.scenes.get_mut(&overlay.name) {
+                        scene.exit();
+                    }
+                }
+                None => eprintln!("[scene] Warning: No pushed scene to pop"),
+            },
+        }
+    }
+
+    /// Pop every pushed scene, top first.
+    fn clear_overlays(&mut self) {
+        while !self.overlays.is_empty() {
+            self.apply_stack_change(StackChange::Pop);
+        }
+    }
 
-        self.pending_switch = Some((name, transition));
+    /// Names of the scenes still being drawn: the stack plus both sides of any
+    /// transition.
+    fn scenes_in_use(&self) -> Vec<&str> {
+        let mut names = self.scene_stack();
+        if let Some(active) = &self.transition {
+            for name in [&active.source_scene, &active.target_scene] {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Load the scene-local assets of every scene in use and free the rest.
+    ///
+    /// Call after [`update`](Self::update), before running the frame. A scene
+    /// that exits keeps its assets until any transition away from it ends.
+    pub(crate) fn sync_scene_assets(&mut self, gpu: &GpuContext) {
+        let in_use: Vec<String> = self.scenes_in_use().into_iter().map(String::from).collect();
+        for (name, scene) in &mut self.scenes {
+            let mut queue = scene.mesh_queue.borrow_mut();
+            if in_use.contains(name) {
+                scene.assets.load(gpu, &mut queue);
+            } else {
+                scene.assets.unload(&mut queue);
+            }
+        }
     }
 
     /// Check if a transition is currently in progress.
@@ -155,14 +353,22 @@ impl SceneManager {
         let mut scene_changed = false;
 
         // Process pending switch
-        if let Some((target_name, transition)) = self.pending_switch.take() {
+        if let Some((target_name, transition, data)) = self.pending_switch.take() {
             let source_name = self.active_scene.clone().unwrap_or_default();
 
+            // Pushed scenes leave before the scene they were stacked on
+            self.clear_overlays();
+
             // Call on_exit for current scene (before transition starts)
             if let Some(current) = self.active_scene_mut() {
                 current.exit();
             }
 
+            // Hand over the payload, replacing any the target never took
+            if let Some(target) = self.scenes.get_mut(&target_name) {
+                target.data = data;
+            }
+
             // Start the transition
             self.transition = Some(ActiveTransition::new(
                 transition,
@@ -172,6 +378,11 @@ impl SceneManager {
             ));
         }
 
+        // Process pending pushes and pops
+        for change in std::mem::take(&mut self.pending_stack) {
+            self.apply_stack_change(change);
+        }
+
         // Update active transition
         if let Some(ref mut active) = self.transition {
             let completed = active.update(time);
@@ -215,47 +426,75 @@ impl SceneManager {
         scene_changed
     }
 
-    /// Execute the active scene's frame logic.
-    pub fn run_frame(
+    /// Execute the frame logic of every scene on the stack, bottom first.
+    ///
+    /// Returns `true` if a scene called [`Frame::exit`](crate::Frame::exit).
+    pub(crate) fn run_frame(
         &mut self,
         gpu: &GpuContext,
         assets: &Assets,
         draw: &mut Draw2d,
         input: &crate::Input,
         world: &mut hecs::World,
-        time: f32,
-        dt: f32,
+        clock: &mut crate::clock::Clock,
         mesh_queue: &Rc<RefCell<MeshQueue>>,
         window: &winit::window::Window,
         default_font: Option<crate::assets::FontId>,
-    ) {
-        if let Some(scene_name) = self.active_scene.clone() {
-            if let Some(scene) = self.scenes.get_mut(&scene_name) {
-                // Create frame context with scene's camera
-                let mut frame = crate::Frame {
-                    gpu,
-                    assets,
-                    draw,
-                    camera: &mut scene.camera,
-                    input,
-                    world,
-                    time,
-                    dt,
-                    default_font,
-                    mesh_queue: Rc::clone(mesh_queue),
-                    window,
-                    scene_switch: None,
-                };
-
-                // Run scene's frame function
-                (scene.frame_fn)(&mut frame);
-
-                // Check if scene requested a switch
-                if let Some((target, transition)) = frame.scene_switch.take() {
-                    self.switch_to_with(target, transition);
+        fixed_alpha: f32,
+    ) -> bool {
+        let stack: Vec<String> = self.scene_stack().into_iter().map(String::from).collect();
+        let scene_ids: Vec<String> = self.scene_ids().into_iter().map(String::from).collect();
+        let paused = paused_scene_count(&self.overlays);
+        let mut exit_requested = false;
+
+        for (index, scene_name) in stack.iter().enumerate() {
+            let Some(scene) = self.scenes.get_mut(scene_name) else {
+                continue;
+            };
+            let is_paused = index < paused;
+
+            // Create frame context with scene's camera
+            let mut frame = crate::Frame {
+                gpu,
+                assets,
+                draw: &mut *draw,
+                camera: &mut scene.camera,
+                input: if is_paused { &self.idle_input } else { input },
+                world: &mut *world,
+                time: clock.time(),
+                dt: if is_paused { 0.0 } else { clock.dt() },
+                clock: &mut *clock,
+                default_font,
+                font_stack: Vec::new(),
+                mesh_queue: Rc::clone(mesh_queue),
+                window: Some(window),
+                scene_switch: None,
+                scene_data: Some(&mut scene.data),
+                current_scene: Some(scene_name),
+                scene_ids: &scene_ids,
+                stack_changes: Vec::new(),
+                fixed_alpha,
+                exit_requested: false,
+            };
+
+            // Run scene's frame function
+            (scene.frame_fn)(&mut frame);
+
+            // Check if scene requested a switch or stack change
+            exit_requested |= frame.exit_requested;
+            let switch = frame.scene_switch.take();
+            let changes = std::mem::take(&mut frame.stack_changes);
+            if let Some((target, transition, data)) = switch {
+                self.queue_switch(target, transition, data);
+            }
+            for change in changes {
+                match change {
+                    StackChange::Push { name, pause_below } => self.queue_push(name, pause_below),
+                    StackChange::Pop => self.pop_scene(),
                 }
             }
         }
+        exit_requested
     }
 
     /// Render the scene(s) with transition effects if active.
@@ -269,16 +508,14 @@ impl SceneManager {
         }
 
         // Get the output texture
-        let output = match gpu.surface.get_current_texture() {
+        let output = match gpu.acquire_output() {
             Ok(output) => output,
             Err(e) => {
                 eprintln!("[scene] Failed to get surface texture: {}", e);
                 return;
             }
         };
-        let screen_view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        let screen_view = output.view();
 
         // Check if we're in a transition - extract data before mutable borrows
         let transition_info = self.transition.as_ref().map(|t| {
@@ -327,7 +564,7 @@ impl SceneManager {
             }
         }
 
-        output.present();
+        gpu.present_output(output);
     }
 
     /// Render without any transition effects.
@@ -339,43 +576,68 @@ impl SceneManager {
         draw_2d: &Draw2d,
         assets: &Assets,
     ) {
-        if let Some(scene_name) = &self.active_scene {
-            if let Some(scene) = self.scenes.get_mut(scene_name) {
-                if let Some(ref mut graph) = scene.render_graph {
-                    // Render scene to screen directly using execute_to_target
-                    graph.execute_to_target(gpu, time, &scene.camera, screen_view);
+        if self.active_scene.is_none() {
+            return;
+        }
 
-                    // Render UI on top
+        // Render the stack bottom-up: the lowest graph straight to the screen,
+        // then each graph above it into a capture composited over the result
+        let stack: Vec<String> = self.scene_stack().into_iter().map(String::from).collect();
+        let mut rendered_graph = false;
+        for scene_name in &stack {
+            let Some(scene) = self.scenes.get_mut(scene_name) else {
+                continue;
+            };
+            let Some(ref mut graph) = scene.render_graph else {
+                continue;
+            };
+            match (&self.crossfade_capture, &self.transition_pass) {
+                (Some(capture), Some(pass)) if rendered_graph => {
+                    graph.execute_overlay_to_target(gpu, time, &scene.camera, &capture.view);
                     let mut encoder =
                         gpu.device
                             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                                label: Some("Scene UI Encoder"),
+                                label: Some("Scene Overlay Encoder"),
                             });
-                    {
-                        let mut ui_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                            label: Some("Scene UI Pass"),
-                            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                                view: screen_view,
-                                resolve_target: None,
-                                ops: wgpu::Operations {
-                                    load: wgpu::LoadOp::Load,
-                                    store: wgpu::StoreOp::Store,
-                                },
-                                depth_slice: None,
-                            })],
-                            depth_stencil_attachment: None,
-                            timestamp_writes: None,
-                            occlusion_query_set: None,
-                        });
-                        draw_2d.render(gpu, &mut ui_pass, assets);
-                    }
+                    pass.render_overlay(gpu, &mut encoder, screen_view, &capture.view);
                     gpu.queue.submit(std::iter::once(encoder.finish()));
-                } else {
-                    // No render graph - just render 2D
-                    render_2d_only(gpu, screen_view, draw_2d, assets);
                 }
+                _ => graph.execute_to_target(gpu, time, &scene.camera, screen_view),
             }
+            rendered_graph = true;
+        }
+
+        if !rendered_graph {
+            // No render graph - just render 2D
+            render_2d_only(gpu, screen_view, draw_2d, assets);
+            return;
+        }
+
+        // Render UI on top
+        let mut encoder = gpu
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Scene UI Encoder"),
+            });
+        {
+            let mut ui_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Scene UI Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: screen_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            draw_2d.render(gpu, &mut ui_pass, assets);
         }
+        gpu.queue.submit(std::iter::once(encoder.finish()));
     }
 
     /// Render with fade-to-color transition.
@@ -494,6 +756,15 @@ impl SceneManager {
             .map(|t| t.get_crossfade_blend())
             .unwrap_or(0.0);
 
+        // Compile a custom transition shader the first time it's used
+        let shader = self
+            .transition
+            .as_ref()
+            .and_then(|t| t.shader_source().cloned());
+        if let (Some(source), Some(pass)) = (&shader, self.transition_pass.as_mut()) {
+            pass.prepare_shader(gpu, source);
+        }
+
         let capture_1 = self.crossfade_capture.as_ref().unwrap();
         let capture_2 = self.crossfade_capture_2.as_ref().unwrap();
 
@@ -519,14 +790,25 @@ impl SceneManager {
             });
 
         if let Some(ref pass) = self.transition_pass {
-            pass.render_crossfade(
-                gpu,
-                &mut encoder,
-                screen_view,
-                &capture_1.view,
-                &capture_2.view,
-                blend,
-            );
+            if shader.is_some() {
+                pass.render_shader(
+                    gpu,
+                    &mut encoder,
+                    screen_view,
+                    &capture_1.view,
+                    &capture_2.view,
+                    blend,
+                );
+            } else {
+                pass.render_crossfade(
+                    gpu,
+                    &mut encoder,
+                    screen_view,
+                    &capture_1.view,
+                    &capture_2.view,
+                    blend,
+                );
+            }
         }
 
         // Render UI on top
@@ -633,6 +915,17 @@ impl Default for SceneManager {
     }
 }
 
+/// Number of scenes at the bottom of the stack paused by a pushed scene above them.
+///
+/// Stack index 0 is the active scene and overlay `i` is at index `i + 1`, so a
+/// pausing overlay at `i` pauses indices `0..=i`.
+fn paused_scene_count(overlays: &[Overlay]) -> usize {
+    overlays
+        .iter()
+        .rposition(|overlay| overlay.pause_below)
+        .map_or(0, |i| i + 1)
+}
+
 /// Fallback renderer for scenes without a render graph.
 fn render_2d_only(gpu: &GpuContext, target: &wgpu::TextureView, draw_2d: &Draw2d, assets: &Assets) {
     let mut encoder = gpu
@@ -663,3 +956,282 @@ fn render_2d_only(gpu: &GpuContext, target: &wgpu::TextureView, draw_2d: &Draw2d
 
     gpu.queue.submit(std::iter::once(encoder.finish()));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Mesh;
+    use crate::scene::{SceneAssets, SceneSetupContext};
+
+    /// Registers scenes that log their lifecycle hooks as `"enter name"` / `"exit name"`.
+    fn manager_with(names: &[&'static str]) -> (SceneManager, Rc<RefCell<Vec<String>>>) {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mesh_queue = Rc::new(RefCell::new(MeshQueue::new()));
+        let mut manager = SceneManager::new();
+        for &name in names {
+            let mut scene = Scene::new(
+                SceneId::new(name),
+                None,
+                Rc::clone(&mesh_queue),
+                Box::new(|_| {}),
+            );
+            let (enter_log, exit_log) = (Rc::clone(&log), Rc::clone(&log));
+            scene.on_enter = Some(Box::new(move || {
+                enter_log.borrow_mut().push(format!("enter {name}"))
+            }));
+            scene.on_exit = Some(Box::new(move || {
+                exit_log.borrow_mut().push(format!("exit {name}"))
+            }));
+            manager.register(scene);
+        }
+        (manager, log)
+    }
+
+    fn overlay(name: &str, pause_below: bool) -> Overlay {
+        Overlay {
+            name: name.to_string(),
+            pause_below,
+        }
+    }
+
+    #[test]
+    fn push_and_pop_fire_hooks_on_the_top_scene_only() {
+        let (mut manager, log) = manager_with(&["game", "pause", "confirm"]);
+        manager.set_active("game");
+
+        manager.push_scene_paused("pause");
+        manager.update(0.0);
+        manager.push_scene("confirm");
+        manager.update(0.0);
+        assert_eq!(manager.scene_stack(), ["game", "pause", "confirm"]);
+        assert_eq!(manager.top_scene(), Some("confirm"));
+
+        manager.pop_scene();
+        manager.update(0.0);
+        assert_eq!(manager.scene_stack(), ["game", "pause"]);
+        assert_eq!(
+            *log.borrow(),
+            ["enter game", "enter pause", "enter confirm", "exit confirm"]
+        );
+    }
+
+    #[test]
+    fn scenes_already_on_the_stack_are_not_pushed_again() {
+        let (mut manager, log) = manager_with(&["game", "hud"]);
+        manager.set_active("game");
+
+        manager.push_scene("hud");
+        manager.push_scene("hud");
+        manager.push_scene("game");
+        manager.update(0.0);
+        assert_eq!(manager.scene_stack(), ["game", "hud"]);
+        assert_eq!(*log.borrow(), ["enter game", "enter hud"]);
+    }
+
+    #[test]
+    fn popping_never_removes_the_active_scene() {
+        let (mut manager, log) = manager_with(&["game"]);
+        manager.set_active("game");
+
+        manager.pop_scene();
+        manager.update(0.0);
+        assert_eq!(manager.scene_stack(), ["game"]);
+        assert_eq!(*log.borrow(), ["enter game"]);
+    }
+
+    #[test]
+    fn switching_pops_pushed_scenes_first() {
+        let (mut manager, log) = manager_with(&["game", "pause", "menu"]);
+        manager.set_active("game");
+        manager.push_scene("pause");
+        manager.update(0.0);
+
+        manager.switch_to("menu");
+        manager.update(0.0);
+        assert_eq!(manager.scene_stack(), ["menu"]);
+        assert_eq!(
+            *log.borrow(),
+            [
+                "enter game",
+                "enter pause",
+                "exit pause",
+                "exit game",
+                "enter menu"
+            ]
+        );
+    }
+
+    #[test]
+    fn switch_data_reaches_the_target_scene() {
+        let (mut manager, _log) = manager_with(&["menu", "game"]);
+        manager.set_active("menu");
+
+        manager.switch_to_with_data("game", Box::new(3u32));
+        manager.update(0.0);
+        let game = manager.active_scene_mut().unwrap();
+        assert_eq!(game.take_data::<String>(), None);
+        assert_eq!(game.take_data::<u32>(), Some(3));
+        assert_eq!(game.take_data::<u32>(), None);
+    }
+
+    #[test]
+    fn plain_switches_clear_untaken_data() {
+        let (mut manager, _log) = manager_with(&["menu", "game"]);
+        manager.set_active("menu");
+        manager.switch_to_with_data("game", Box::new(3u32));
+        manager.update(0.0);
+
+        manager.switch_to("menu");
+        manager.update(0.0);
+        manager.switch_to("game");
+        manager.update(0.0);
+        assert_eq!(manager.active_scene_mut().unwrap().take_data::<u32>(), None);
+    }
+
+    #[test]
+    fn scenes_stay_in_use_until_their_transition_out_ends() {
+        let (mut manager, _log) = manager_with(&["level_1", "level_2", "pause"]);
+        manager.set_active("level_1");
+        manager.push_scene("pause");
+        manager.update(0.0);
+        assert_eq!(manager.scenes_in_use(), ["level_1", "pause"]);
+
+        manager.switch_to_with("level_2", Transition::fade_to_black(1.0));
+        manager.update(0.0);
+        assert_eq!(manager.scenes_in_use(), ["level_1", "level_2"]);
+        manager.update(0.6);
+        assert_eq!(manager.scenes_in_use(), ["level_2", "level_1"]);
+        manager.update(0.7);
+        manager.update(1.5);
+        assert_eq!(manager.scenes_in_use(), ["level_2"]);
+    }
+
+    #[test]
+    fn scene_assets_load_in_use_and_free_when_left() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+        let (mut manager, _log) = manager_with(&["level", "menu"]);
+        let queue = Rc::clone(&manager.scenes["level"].mesh_queue);
+        let level = manager.scenes.get_mut("level").unwrap();
+        let cube = level.assets.add_mesh(&mut queue.borrow_mut(), Mesh::cube);
+        assert!(queue.borrow().mesh(cube).is_none(), "loaded during setup");
+
+        manager.set_active("level");
+        manager.sync_scene_assets(&gpu);
+        assert!(queue.borrow().mesh(cube).is_some());
+
+        manager.switch_to("menu");
+        manager.update(0.0);
+        manager.sync_scene_assets(&gpu);
+        assert!(queue.borrow().mesh(cube).is_none(), "not freed on leaving");
+
+        // The same handle reloads on return
+        manager.switch_to("level");
+        manager.update(0.0);
+        manager.sync_scene_assets(&gpu);
+        assert!(queue.borrow().mesh(cube).is_some());
+    }
+
+    #[test]
+    fn unknown_scenes_are_listed_and_never_switched_to() {
+        let (mut manager, log) = manager_with(&["menu", "game"]);
+        manager.set_active("menu");
+        assert!(manager.scene_exists("game"));
+        assert!(!manager.scene_exists("gmae"));
+        assert_eq!(manager.scene_ids(), ["game", "menu"]);
+
+        manager.switch_to("gmae");
+        manager.push_scene("hdu");
+        manager.update(0.0);
+        assert_eq!(manager.scene_stack(), ["menu"]);
+        assert_eq!(*log.borrow(), ["enter menu"]);
+    }
+
+    #[test]
+    fn pausing_overlays_pause_everything_below_them() {
+        assert_eq!(paused_scene_count(&[]), 0);
+        assert_eq!(paused_scene_count(&[overlay("hud", false)]), 0);
+        assert_eq!(paused_scene_count(&[overlay("pause", true)]), 1);
+        assert_eq!(
+            paused_scene_count(&[overlay("pause", true), overlay("confirm", false)]),
+            1
+        );
+        assert_eq!(
+            paused_scene_count(&[overlay("hud", false), overlay("pause", true)]),
+            2
+        );
+    }
+
+    #[test]
+    fn scene_textures_are_decoded_during_setup() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+        let path = std::env::temp_dir().join(format!("hoplite-scene-{}.png", std::process::id()));
+        image::RgbaImage::new(2, 2).save(&path).unwrap();
+
+        let mesh_queue = Rc::new(RefCell::new(MeshQueue::new()));
+        let (mut graph, mut assets) = (None, SceneAssets::default());
+        let mut scene = SceneSetupContext::new(&gpu, &mut graph, &mesh_queue, &mut assets);
+        assert!(scene.texture_from_file("missing/texture.png").is_err());
+        let id = scene.texture_from_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Entering the scene uploads the pixels decoded before the file was removed
+        assert!(mesh_queue.borrow().texture(id).is_none());
+        assets.load(&gpu, &mut mesh_queue.borrow_mut());
+        assert!(mesh_queue.borrow().texture(id).is_some());
+    }
+
+    #[test]
+    fn pushed_graph_scenes_draw_over_the_scenes_below() {
+        use crate::render_graph::RenderGraph;
+
+        let Some(gpu) = crate::test_support::headless_gpu(4, 4) else {
+            return;
+        };
+        let mesh_queue = Rc::new(RefCell::new(MeshQueue::new()));
+        let mut manager = SceneManager::new();
+        manager.init_gpu_resources(&gpu);
+        for (name, clear_color) in [("game", Some(wgpu::Color::RED)), ("hud", None)] {
+            let mut graph = RenderGraph::builder().build(&gpu);
+            if let Some(color) = clear_color {
+                graph.set_clear_color(color);
+            }
+            let scene = Scene::new(
+                SceneId::new(name),
+                Some(graph),
+                Rc::clone(&mesh_queue),
+                Box::new(|_| {}),
+            );
+            manager.register(scene);
+        }
+        manager.set_active("game");
+        manager.push_scene("hud");
+        manager.update(0.0);
+
+        let (draw, assets) = (Draw2d::new(&gpu), Assets::new());
+        let render = |manager: &mut SceneManager| {
+            manager.render(&gpu, 0.0, &draw, &assets);
+            gpu.read_offscreen().unwrap().get_pixel(0, 0).0
+        };
+
+        // The HUD's graph has no background of its own, so the game shows through
+        assert_eq!(render(&mut manager), [255, 0, 0, 255]);
+
+        // A translucent background tints the game instead of replacing it
+        let hud = manager.scenes.get_mut("hud").unwrap();
+        hud.render_graph
+            .as_mut()
+            .unwrap()
+            .set_clear_color(wgpu::Color {
+                r: 0.0,
+                g: 0.0,
+                b: 1.0,
+                a: 0.5,
+            });
+        let [r, g, b, a] = render(&mut manager);
+        assert!(r > 0 && g == 0 && b > 0 && a == 255, "{:?}", [r, g, b, a]);
+    }
+}
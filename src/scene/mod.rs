@@ -11,6 +11,19 @@
 //! - Its own frame logic (update closure)
 //! - Optional lifecycle hooks (`on_enter`, `on_exit`)
 //!
+//! Besides switching, scenes can be stacked: [`Frame::push_scene`](crate::Frame::push_scene)
+//! layers a scene such as a pause menu or HUD over the current one, and
+//! [`Frame::pop_scene`](crate::Frame::pop_scene) removes it again.
+//!
+//! # Assets
+//!
+//! Meshes and textures created through the main setup context are shared by
+//! all scenes and live as long as the app. Those created through a scene's
+//! [`SceneSetupContext`], like `scene.mesh_cube()`, are scene-local: they are
+//! loaded when the scene is entered and freed once it has left the stack and
+//! finished transitioning out, keeping memory flat across many levels.
+//! Scene-local handles stay valid and reload whenever the scene returns.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -61,13 +74,16 @@
 //! }
 //! ```
 
+mod assets;
 mod manager;
 pub mod scene;
 mod setup;
 mod transition;
 mod transition_pass;
 
+pub(crate) use assets::SceneAssets;
 pub use manager::SceneManager;
+pub(crate) use manager::{SceneSwitch, StackChange};
 pub use scene::{Scene, SceneBuilder, SceneId};
 pub use setup::SceneSetupContext;
 pub use transition::{ActiveTransition, Easing, Transition, TransitionKind};
@@ -1,8 +1,10 @@
 //! Scene definition and identifier types.
 
+use super::assets::SceneAssets;
 use crate::Frame;
 use crate::camera::Camera;
 use crate::render_graph::{MeshQueue, RenderGraph};
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -68,6 +70,12 @@ pub struct Scene {
 
     /// Optional callback when exiting this scene.
     pub(crate) on_exit: Option<Box<dyn FnMut()>>,
+
+    /// Payload from the switch that entered this scene, until taken.
+    pub(crate) data: Option<Box<dyn Any>>,
+
+    /// Meshes and textures loaded only while this scene is in use.
+    pub(crate) assets: SceneAssets,
 }
 
 impl Scene {
@@ -86,9 +94,19 @@ impl Scene {
             frame_fn,
             on_enter: None,
             on_exit: None,
+            data: None,
+            assets: SceneAssets::default(),
         }
     }
 
+    /// Take the data passed by the switch that entered this scene.
+    ///
+    /// Returns `None` if there is no data or it isn't a `T`; data of another
+    /// type stays in place.
+    pub fn take_data<T: 'static>(&mut self) -> Option<T> {
+        take_data(&mut self.data)
+    }
+
     /// Call the scene's enter callback if set.
     pub(crate) fn enter(&mut self) {
         if let Some(ref mut callback) = self.on_enter {
@@ -104,6 +122,17 @@ impl Scene {
     }
 }
 
+/// Takes `slot`'s payload if it holds a `T`.
+pub(crate) fn take_data<T: 'static>(slot: &mut Option<Box<dyn Any>>) -> Option<T> {
+    match slot.take()?.downcast::<T>() {
+        Ok(data) => Some(*data),
+        Err(data) => {
+            *slot = Some(data);
+            None
+        }
+    }
+}
+
 /// Builder for configuring scene lifecycle hooks.
 ///
 /// Returned by [`SetupContext::scene`] to allow chaining `on_enter` and `on_exit` hooks.
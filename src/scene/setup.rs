@@ -1,23 +1,39 @@
 //! Scene-specific setup context for configuring per-scene render pipelines.
 
+use super::assets::SceneAssets;
 use crate::draw2d::Color;
+use crate::ecs::{MeshId, TextureId};
 use crate::effect_pass::EffectPass;
+use crate::error::Error;
 use crate::gpu::GpuContext;
 use crate::hot_shader::{HotEffectPass, HotPostProcessPass, HotWorldPostProcessPass};
+use crate::mesh::Mesh;
 use crate::post_process::{PostProcessPass, WorldPostProcessPass};
 use crate::render_graph::{
-    EffectNode, HotEffectNode, HotPostProcessNode, HotWorldPostProcessNode, MeshNode, MeshQueue,
-    PostProcessNode, RenderGraph, WorldPostProcessNode,
+    BloomHandle, BloomNode, BloomSettings, EffectNode, HotEffectNode, HotPostProcessNode,
+    HotWorldPostProcessNode, MeshNode, MeshQueue, PostProcessNode, RenderGraph,
+    WorldPostProcessNode,
 };
+use crate::texture::Texture;
+use crate::uniforms::UniformHandle;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 /// Context for configuring a scene's render pipeline during setup.
 ///
-/// This is similar to [`SetupContext`](crate::SetupContext) but only provides
-/// methods for configuring the render graph. Asset loading (meshes, textures,
-/// sprites) is done through the main `SetupContext` since assets are shared
-/// across all scenes.
+/// This is similar to [`SetupContext`](crate::SetupContext) but mostly provides
+/// methods for configuring the render graph.
+///
+/// # Shared and Scene-Local Assets
+///
+/// Meshes, textures, sprites, and fonts loaded through the main `SetupContext`
+/// are shared by every scene and live for the whole app. Meshes and textures
+/// created here, such as with [`mesh_cube`](Self::mesh_cube) or
+/// [`texture_from_file`](Self::texture_from_file), belong to this scene: they
+/// are loaded when it is entered and their GPU memory is freed once it's no
+/// longer on the scene stack or in a transition, so a game with many levels
+/// only holds the current level's assets. The handles stay valid throughout,
+/// and the assets are loaded again if the scene is re-entered.
 ///
 /// # Example
 ///
@@ -40,6 +56,8 @@ pub struct SceneSetupContext<'a> {
     graph_builder: &'a mut Option<RenderGraph>,
     /// Shared mesh queue (for creating MeshNode).
     mesh_queue: &'a Rc<RefCell<MeshQueue>>,
+    /// Meshes and textures owned by this scene.
+    assets: &'a mut SceneAssets,
 }
 
 impl<'a> SceneSetupContext<'a> {
@@ -48,11 +66,13 @@ impl<'a> SceneSetupContext<'a> {
         gpu: &'a GpuContext,
         graph_builder: &'a mut Option<RenderGraph>,
         mesh_queue: &'a Rc<RefCell<MeshQueue>>,
+        assets: &'a mut SceneAssets,
     ) -> Self {
         Self {
             gpu,
             graph_builder,
             mesh_queue,
+            assets,
         }
     }
 
@@ -60,53 +80,33 @@ impl<'a> SceneSetupContext<'a> {
     // Background Color
     // ========================================================================
 
-    /// Set a solid background color for this scene.
-    ///
-    /// This is the simplest way to set a background - no shader required.
-    /// Use this when you just want a solid color behind your content.
-    ///
-    /// # Arguments
+    /// Set the color this scene is cleared to at the start of each frame.
     ///
-    /// * `color` - Background color
+    /// The scene's first render node clears to this color instead of black when
+    /// it has no input to draw over, such as
+    /// [`enable_mesh_rendering`](Self::enable_mesh_rendering). No shader or render
+    /// node is created, so it can be called at any point during setup.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// scene.background_color(Color::rgb(0.1, 0.1, 0.15));
+    /// scene.clear_color(Color::rgb(0.1, 0.1, 0.15));
     /// scene.enable_mesh_rendering();
     /// ```
-    pub fn background_color(&mut self, color: Color) -> &mut Self {
-        let shader = format!(
-            r#"
-@group(0) @binding(0) var<uniform> u: Uniforms;
-
-struct Uniforms {{
-    resolution: vec2f,
-    time: f32,
-}}
-
-@vertex
-fn vs(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4f {{
-    var pos = array<vec2f, 3>(
-        vec2f(-1.0, -1.0),
-        vec2f(3.0, -1.0),
-        vec2f(-1.0, 3.0)
-    );
-    return vec4f(pos[vertex_index], 0.0, 1.0);
-}}
-
-@fragment
-fn fs() -> @location(0) vec4f {{
-    return vec4f({:.6}, {:.6}, {:.6}, {:.6});
-}}
-"#,
-            color.r, color.g, color.b, color.a
-        );
-        let effect = EffectPass::new(self.gpu, &shader);
-        self.add_node(EffectNode::new(effect));
+    pub fn clear_color(&mut self, color: Color) -> &mut Self {
+        self.graph_builder
+            .get_or_insert_with(|| RenderGraph::builder().build(self.gpu))
+            .set_clear_color(color.into());
         self
     }
 
+    /// Set a solid background color for this scene.
+    ///
+    /// Same as [`clear_color`](Self::clear_color).
+    pub fn background_color(&mut self, color: Color) -> &mut Self {
+        self.clear_color(color)
+    }
+
     // ========================================================================
     // Shader Effect Methods (Embedded)
     // ========================================================================
@@ -120,12 +120,48 @@ fn fs() -> @location(0) vec4f {{
     /// # Arguments
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
+    ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
     pub fn effect(&mut self, shader: &str) -> &mut Self {
-        let effect = EffectPass::new(self.gpu, shader);
-        self.add_node(EffectNode::new(effect));
+        match EffectPass::new(self.gpu, shader) {
+            Ok(effect) => self.add_node(EffectNode::new(effect)),
+            Err(e) => eprintln!("[shader] Failed to create effect: {}", e),
+        }
         self
     }
 
+    /// Add a screen-space shader effect with a user uniform buffer.
+    ///
+    /// Like [`Self::effect`], but the shader also declares a struct matching
+    /// `T` at `@group(1) @binding(0)`, initialized to `initial`. Returns a
+    /// handle for updating the values each frame with [`Frame::set_uniforms`](crate::Frame::set_uniforms),
+    /// or `None` if the shader fails to compile (the error is printed to stderr).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let waves = ctx.effect_with_uniforms(include_str!("shaders/waves.wgsl"), &Waves::default());
+    /// ```
+    pub fn effect_with_uniforms<T: bytemuck::Pod>(
+        &mut self,
+        shader: &str,
+        initial: &T,
+    ) -> Option<UniformHandle> {
+        match EffectPass::with_uniforms::<T>(self.gpu, shader) {
+            Ok(effect) => {
+                effect.set_uniforms(self.gpu, initial);
+                let handle = effect.uniform_handle();
+                self.add_node(EffectNode::new(effect));
+                handle
+            }
+            Err(e) => {
+                eprintln!("[shader] Failed to create effect: {}", e);
+                None
+            }
+        }
+    }
+
     /// Add a fullscreen world-space shader effect.
     ///
     /// World-space effects receive camera uniforms (view matrix, projection matrix,
@@ -135,9 +171,14 @@ fn fs() -> @location(0) vec4f {{
     /// # Arguments
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
+    ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
     pub fn effect_world(&mut self, shader: &str) -> &mut Self {
-        let effect = EffectPass::new_world(self.gpu, shader);
-        self.add_node(EffectNode::new(effect));
+        match EffectPass::new_world(self.gpu, shader) {
+            Ok(effect) => self.add_node(EffectNode::new(effect)),
+            Err(e) => eprintln!("[shader] Failed to create effect: {}", e),
+        }
         self
     }
 
@@ -150,12 +191,116 @@ fn fs() -> @location(0) vec4f {{
     /// # Arguments
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
+    ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
     pub fn post_process(&mut self, shader: &str) -> &mut Self {
-        let pass = PostProcessPass::new(self.gpu, shader);
-        self.add_node(PostProcessNode::new(pass));
+        match PostProcessPass::new(self.gpu, shader) {
+            Ok(pass) => self.add_node(PostProcessNode::new(pass)),
+            Err(e) => eprintln!("[shader] Failed to create post-process pass: {}", e),
+        }
+        self
+    }
+
+    /// Add a screen-space post-processing effect with a user uniform buffer.
+    ///
+    /// Like [`Self::post_process`], but the shader also declares a struct
+    /// matching `T` at `@group(1) @binding(0)`, initialized to `initial`.
+    /// Returns a handle for updating the values each frame with
+    /// [`Frame::set_uniforms`](crate::Frame::set_uniforms), or `None` if the shader fails to compile (the
+    /// error is printed to stderr).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let bloom = ctx.post_process_with_uniforms(
+    ///     include_str!("shaders/bloom.wgsl"),
+    ///     &Bloom { threshold: 1.0, intensity: 0.6, _pad: [0.0; 2] },
+    /// );
+    /// ```
+    pub fn post_process_with_uniforms<T: bytemuck::Pod>(
+        &mut self,
+        shader: &str,
+        initial: &T,
+    ) -> Option<UniformHandle> {
+        match PostProcessPass::with_uniforms::<T>(self.gpu, shader) {
+            Ok(pass) => {
+                pass.set_uniforms(self.gpu, initial);
+                let handle = pass.uniform_handle();
+                self.add_node(PostProcessNode::new(pass));
+                handle
+            }
+            Err(e) => {
+                eprintln!("[shader] Failed to create post-process pass: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Add a screen-space post-processing effect that samples extra textures.
+    ///
+    /// Like [`Self::post_process`], but each texture (registered with
+    /// [`SetupContext::add_texture`](crate::SetupContext::add_texture)) is bound after the
+    /// standard bindings together with its sampler: the first at bindings 3
+    /// and 4, the next at 5 and 6, and so on. Useful for color grading LUTs,
+    /// lens dirt, or noise.
+    ///
+    /// If a texture id is unknown or the shader fails to compile, the error is
+    /// printed to stderr and the pass is skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let lut = ctx.texture_from_file("assets/grade_lut.png")?;
+    /// ctx.post_process_with_textures(include_str!("shaders/grade.wgsl"), &[lut]);
+    /// ```
+    pub fn post_process_with_textures(
+        &mut self,
+        shader: &str,
+        textures: &[TextureId],
+    ) -> &mut Self {
+        let result = {
+            let queue = self.mesh_queue.borrow();
+            let resolved: Option<Vec<&Texture>> =
+                textures.iter().map(|id| queue.texture(*id)).collect();
+            resolved.map(|resolved| PostProcessPass::new_with_textures(self.gpu, shader, &resolved))
+        };
+        match result {
+            Some(Ok(pass)) => self.add_node(PostProcessNode::new(pass)),
+            Some(Err(e)) => eprintln!("[shader] Failed to create post-process pass: {}", e),
+            None => eprintln!("[shader] Failed to create post-process pass: unknown texture id"),
+        }
         self
     }
 
+    /// Add a built-in bloom effect.
+    ///
+    /// Bright parts of the previous pass (brightness above `threshold`) are
+    /// blurred over `iterations` rounds at half resolution and added back,
+    /// scaled by `intensity`. Returns a handle for tuning the parameters live.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.effect_world(include_str!("shaders/scene.wgsl"));
+    /// let bloom = ctx.bloom(0.8, 0.6, 4);
+    /// // later, in the frame closure:
+    /// bloom.set_intensity(1.0);
+    /// ```
+    pub fn bloom(&mut self, threshold: f32, intensity: f32, iterations: u32) -> BloomHandle {
+        let node = BloomNode::new(
+            self.gpu,
+            BloomSettings {
+                threshold,
+                intensity,
+                iterations,
+            },
+        );
+        let handle = node.handle();
+        self.add_node(node);
+        handle
+    }
+
     /// Add a world-space post-processing effect.
     ///
     /// Similar to [`Self::post_process`], but also receives camera uniforms.
@@ -165,9 +310,14 @@ fn fs() -> @location(0) vec4f {{
     /// # Arguments
     ///
     /// * `shader` - WGSL shader source code (typically via `include_str!`)
+    ///
+    /// If the shader fails to compile, the error is printed to stderr and the
+    /// pass is skipped.
     pub fn post_process_world(&mut self, shader: &str) -> &mut Self {
-        let pass = WorldPostProcessPass::new(self.gpu, shader);
-        self.add_node(WorldPostProcessNode::new(pass));
+        match WorldPostProcessPass::new(self.gpu, shader) {
+            Ok(pass) => self.add_node(WorldPostProcessNode::new(pass)),
+            Err(e) => eprintln!("[shader] Failed to create post-process pass: {}", e),
+        }
         self
     }
 
@@ -255,13 +405,92 @@ fn fs() -> @location(0) vec4f {{
         self
     }
 
+    // ========================================================================
+    // Scene-Local Assets
+    // ========================================================================
+
+    /// Create a unit cube mesh owned by this scene.
+    ///
+    /// Unlike [`SetupContext::mesh_cube`](crate::SetupContext::mesh_cube), the
+    /// cube only occupies GPU memory while the scene is in use.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// ctx.scene("level_1", |scene| {
+    ///     scene.enable_mesh_rendering();
+    ///     let crate_mesh = scene.mesh_cube();
+    ///
+    ///     move |frame| {
+    ///         frame.mesh(crate_mesh).draw();
+    ///     }
+    /// });
+    /// ```
+    pub fn mesh_cube(&mut self) -> MeshId {
+        self.mesh_with(Mesh::cube)
+    }
+
+    /// Create a UV sphere mesh owned by this scene.
+    ///
+    /// See [`SetupContext::mesh_sphere`](crate::SetupContext::mesh_sphere).
+    pub fn mesh_sphere(&mut self, segments: u32, rings: u32) -> MeshId {
+        self.mesh_with(move |gpu| Mesh::sphere(gpu, segments, rings))
+    }
+
+    /// Create a flat plane mesh owned by this scene.
+    ///
+    /// See [`SetupContext::mesh_plane`](crate::SetupContext::mesh_plane).
+    pub fn mesh_plane(&mut self, size: f32) -> MeshId {
+        self.mesh_with(move |gpu| Mesh::plane(gpu, size))
+    }
+
+    /// Register any mesh as owned by this scene.
+    ///
+    /// `load` builds the mesh each time the scene is entered, so it isn't
+    /// called during setup.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let arena = scene.mesh_with(|gpu| Mesh::cylinder(gpu, 20.0, 1.0, 48));
+    /// ```
+    pub fn mesh_with(&mut self, load: impl Fn(&GpuContext) -> Mesh + 'static) -> MeshId {
+        self.assets
+            .add_mesh(&mut self.mesh_queue.borrow_mut(), load)
+    }
+
+    /// Load a texture file as owned by this scene.
+    ///
+    /// The file is decoded during setup, so a missing or corrupt file is
+    /// reported here. The decoded pixels are kept and uploaded to the GPU
+    /// each time the scene is entered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded.
+    pub fn texture_from_file(&mut self, path: &str) -> Result<TextureId, Error> {
+        let image = image::open(path)?.to_rgba8();
+        let label = path.to_string();
+        Ok(self.texture_with(move |gpu| {
+            Texture::from_rgba(gpu, &image, image.width(), image.height(), &label)
+        }))
+    }
+
+    /// Register any texture as owned by this scene.
+    ///
+    /// `load` creates the texture each time the scene is entered, so it isn't
+    /// called during setup. Do any fallible work, like reading files, before
+    /// registering it.
+    pub fn texture_with(&mut self, load: impl Fn(&GpuContext) -> Texture + 'static) -> TextureId {
+        self.assets
+            .add_texture(&mut self.mesh_queue.borrow_mut(), load)
+    }
+
     /// Internal helper to add a render node to the graph.
     fn add_node<N: crate::render_graph::RenderNode + 'static>(&mut self, node: N) {
-        if self.graph_builder.is_none() {
-            *self.graph_builder = Some(RenderGraph::builder().node(node).build(self.gpu));
-        } else {
-            let old = self.graph_builder.take().unwrap();
-            *self.graph_builder = Some(old.with_node(node, self.gpu));
+        match self.graph_builder {
+            Some(graph) => graph.push_node(node, self.gpu),
+            None => *self.graph_builder = Some(RenderGraph::builder().node(node).build(self.gpu)),
         }
     }
 }
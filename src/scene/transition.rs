@@ -1,10 +1,14 @@
 //! Transition types and easing functions for scene switching.
 
 use crate::draw2d::Color;
+use std::sync::Arc;
 
 /// Easing functions for smooth transitions.
 ///
-/// These control the acceleration curve of transition animations.
+/// These control the acceleration curve of transition animations, and work
+/// just as well for UI animation via [`apply`](Self::apply). Every curve maps
+/// 0.0 to 0.0 and 1.0 to 1.0; [`EaseOutBack`](Self::EaseOutBack) and
+/// [`EaseOutElastic`](Self::EaseOutElastic) overshoot above 1.0 in between.
 #[derive(Clone, Copy, Debug, Default)]
 pub enum Easing {
     /// Constant speed throughout.
@@ -16,6 +20,16 @@ pub enum Easing {
     EaseOut,
     /// Start slow, speed up, then slow down.
     EaseInOut,
+    /// Like [`EaseInOut`](Self::EaseInOut) with a steeper cubic middle.
+    EaseInOutCubic,
+    /// Decelerate past the end, then settle back.
+    EaseOutBack,
+    /// Spring past the end and oscillate into place.
+    EaseOutElastic,
+    /// Bounce against the end like a dropped ball.
+    EaseOutBounce,
+    /// A user-supplied curve, called with the clamped progress.
+    Custom(fn(f32) -> f32),
 }
 
 impl Easing {
@@ -33,6 +47,43 @@ impl Easing {
                     1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
                 }
             }
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::EaseOutBack => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+            Easing::EaseOutElastic => {
+                const C4: f32 = std::f32::consts::TAU / 3.0;
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * C4).sin() + 1.0
+                }
+            }
+            Easing::EaseOutBounce => {
+                const N1: f32 = 7.5625;
+                const D1: f32 = 2.75;
+                if t < 1.0 / D1 {
+                    N1 * t * t
+                } else if t < 2.0 / D1 {
+                    let t = t - 1.5 / D1;
+                    N1 * t * t + 0.75
+                } else if t < 2.5 / D1 {
+                    let t = t - 2.25 / D1;
+                    N1 * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D1;
+                    N1 * t * t + 0.984375
+                }
+            }
+            Easing::Custom(curve) => curve(t),
         }
     }
 }
@@ -46,6 +97,8 @@ pub enum TransitionKind {
     FadeToColor { color: Color },
     /// Crossfade: blend old scene out while blending new scene in.
     Crossfade,
+    /// Blend the two scenes with a user WGSL shader (see [`Transition::shader`]).
+    Shader { source: Arc<str> },
 }
 
 /// Configuration for a scene transition.
@@ -152,6 +205,76 @@ impl Transition {
         }
     }
 
+    /// Create a transition that blends the two scenes with a custom WGSL shader.
+    ///
+    /// `path_or_source` is either WGSL source or, if it's a single line ending
+    /// in `.wgsl`, the path of a shader file to read. Like a crossfade, both
+    /// scenes render for the whole transition. If the file can't be read or
+    /// the shader fails to compile, the error is printed to stderr and a
+    /// crossfade is used instead.
+    ///
+    /// # Shader Requirements
+    ///
+    /// The shader must define `vs` and `fs` entry points (a fullscreen
+    /// triangle, as in post-process shaders) and these bindings:
+    ///
+    /// ```wgsl
+    /// struct Uniforms {
+    ///     resolution: vec2f,
+    ///     progress: f32,   // eased, 0.0 = all old scene, 1.0 = all new scene
+    ///     _pad: f32,
+    ///     color: vec4f,    // unused
+    /// }
+    /// @group(0) @binding(0) var<uniform> u: Uniforms;
+    /// @group(0) @binding(1) var old_texture: texture_2d<f32>;
+    /// @group(0) @binding(2) var new_texture: texture_2d<f32>;
+    /// @group(0) @binding(3) var tex_sampler: sampler;
+    /// ```
+    ///
+    /// See `src/shaders/radial_wipe.wgsl` (used by [`radial_wipe`](Self::radial_wipe))
+    /// for a complete example.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// frame.switch_to_with(
+    ///     "game",
+    ///     Transition::shader("shaders/dissolve.wgsl", 1.0, Easing::EaseInOut),
+    /// );
+    /// ```
+    pub fn shader(path_or_source: &str, duration: f32, easing: Easing) -> Self {
+        let source = if looks_like_path(path_or_source) {
+            std::fs::read_to_string(path_or_source.trim()).unwrap_or_else(|e| {
+                eprintln!(
+                    "[scene] Failed to read transition shader '{}': {}",
+                    path_or_source, e
+                );
+                String::new()
+            })
+        } else {
+            path_or_source.to_string()
+        };
+
+        Self {
+            kind: TransitionKind::Shader {
+                source: source.into(),
+            },
+            duration,
+            easing,
+        }
+    }
+
+    /// Create a radial wipe that reveals the new scene in a growing circle.
+    ///
+    /// A built-in [`shader`](Self::shader) transition.
+    pub fn radial_wipe(duration: f32) -> Self {
+        Self::shader(
+            include_str!("../shaders/radial_wipe.wgsl"),
+            duration,
+            Easing::EaseInOut,
+        )
+    }
+
     /// Set the easing function for this transition.
     pub fn easing(mut self, easing: Easing) -> Self {
         self.easing = easing;
@@ -206,7 +329,9 @@ impl ActiveTransition {
         let phase = match transition.kind {
             TransitionKind::Instant => TransitionPhase::Midpoint,
             TransitionKind::FadeToColor { .. } => TransitionPhase::FadingOut,
-            TransitionKind::Crossfade => TransitionPhase::Crossfading,
+            TransitionKind::Crossfade | TransitionKind::Shader { .. } => {
+                TransitionPhase::Crossfading
+            }
         };
 
         Self {
@@ -259,7 +384,7 @@ impl ActiveTransition {
                     TransitionPhase::Crossfading => unreachable!(),
                 }
             }
-            TransitionKind::Crossfade => {
+            TransitionKind::Crossfade | TransitionKind::Shader { .. } => {
                 let raw_progress = (elapsed / self.transition.duration).clamp(0.0, 1.0);
                 self.progress = self.transition.easing.apply(raw_progress);
                 self.phase = TransitionPhase::Crossfading;
@@ -275,10 +400,12 @@ impl ActiveTransition {
     /// - scene_alpha is how visible the scene should be
     /// - overlay_alpha is how visible the fade color should be
     pub fn get_fade_alpha(&self) -> (f32, f32) {
+        // Overshooting curves would push the overlay past fully opaque
+        let progress = self.progress.clamp(0.0, 1.0);
         match self.phase {
             TransitionPhase::FadingOut => {
                 // Scene fades out: 1.0 -> 0.0, overlay fades in: 0.0 -> 1.0
-                (1.0 - self.progress, self.progress)
+                (1.0 - progress, progress)
             }
             TransitionPhase::Midpoint => {
                 // Fully faded to color
@@ -286,7 +413,7 @@ impl ActiveTransition {
             }
             TransitionPhase::FadingIn => {
                 // Scene fades in: 0.0 -> 1.0, overlay fades out: 1.0 -> 0.0
-                (self.progress, 1.0 - self.progress)
+                (progress, 1.0 - progress)
             }
             TransitionPhase::Crossfading => {
                 // Not used for fade-to-color
@@ -297,9 +424,10 @@ impl ActiveTransition {
 
     /// Get the crossfade blend factor.
     ///
-    /// Returns how much of the new scene to show (0.0 = all old, 1.0 = all new).
+    /// Returns how much of the new scene to show (0.0 = all old, 1.0 = all new),
+    /// clamped so overshooting easing curves don't extrapolate past either scene.
     pub fn get_crossfade_blend(&self) -> f32 {
-        self.progress
+        self.progress.clamp(0.0, 1.0)
     }
 
     /// Check if we're at the midpoint (time to swap scenes).
@@ -307,9 +435,20 @@ impl ActiveTransition {
         matches!(self.phase, TransitionPhase::Midpoint)
     }
 
-    /// Check if this is a crossfade transition.
+    /// Check if this transition blends both scenes (a crossfade or a custom shader).
     pub fn is_crossfade(&self) -> bool {
-        matches!(self.transition.kind, TransitionKind::Crossfade)
+        matches!(
+            self.transition.kind,
+            TransitionKind::Crossfade | TransitionKind::Shader { .. }
+        )
+    }
+
+    /// Get the WGSL source if this is a custom shader transition.
+    pub fn shader_source(&self) -> Option<&Arc<str>> {
+        match &self.transition.kind {
+            TransitionKind::Shader { source } => Some(source),
+            _ => None,
+        }
     }
 
     /// Get the fade color if this is a fade-to-color transition.
@@ -320,3 +459,94 @@ impl ActiveTransition {
         }
     }
 }
+
+/// Whether a [`Transition::shader`] argument names a file rather than holding WGSL.
+fn looks_like_path(path_or_source: &str) -> bool {
+    let trimmed = path_or_source.trim();
+    !trimmed.contains('\n') && trimmed.ends_with(".wgsl")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easing_curves_start_at_zero_end_at_one_and_hit_known_midpoints() {
+        let cases = [
+            (Easing::Linear, 0.5),
+            (Easing::EaseIn, 0.25),
+            (Easing::EaseOut, 0.75),
+            (Easing::EaseInOut, 0.5),
+            (Easing::EaseInOutCubic, 0.5),
+            (Easing::EaseOutBack, 1.087_697_5),
+            (Easing::EaseOutElastic, 1.015_625),
+            (Easing::EaseOutBounce, 0.765_625),
+            (Easing::Custom(|t| t * t * t), 0.125),
+        ];
+
+        for (easing, midpoint) in cases {
+            assert!(easing.apply(0.0).abs() < 1e-6, "{easing:?} at 0");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-6, "{easing:?} at 1");
+            assert!(
+                (easing.apply(0.5) - midpoint).abs() < 1e-5,
+                "{easing:?} at 0.5: {}",
+                easing.apply(0.5)
+            );
+        }
+    }
+
+    #[test]
+    fn easing_clamps_progress_outside_zero_to_one() {
+        assert_eq!(Easing::EaseOutBounce.apply(-1.0), 0.0);
+        assert!((Easing::EaseOutElastic.apply(2.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn overshooting_curves_keep_blends_within_both_scenes() {
+        let transition = Transition::crossfade(1.0).easing(Easing::EaseOutBack);
+        let mut active = ActiveTransition::new(transition, "a".into(), "b".into(), 0.0);
+        active.update(0.5);
+        assert!(active.progress > 1.0);
+        assert_eq!(active.get_crossfade_blend(), 1.0);
+    }
+
+    #[test]
+    fn radial_wipe_shader_compiles() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        let result = crate::error::capture_shader_errors(&gpu.device, || {
+            gpu.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(
+                        include_str!("../shaders/radial_wipe.wgsl").into(),
+                    ),
+                })
+        });
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
+    #[test]
+    fn shader_argument_is_a_path_only_when_it_names_a_wgsl_file() {
+        assert!(looks_like_path("shaders/wipe.wgsl"));
+        assert!(looks_like_path("  wipe.wgsl\n"));
+        assert!(!looks_like_path("@fragment fn fs() {}"));
+        assert!(!looks_like_path(
+            "// see wipe.wgsl\nfn fs() {}\n// wipe.wgsl"
+        ));
+    }
+
+    #[test]
+    fn shader_transitions_blend_both_scenes_like_a_crossfade() {
+        let transition = Transition::shader("fn fs() {}", 1.0, Easing::Linear);
+        let mut active = ActiveTransition::new(transition, "a".into(), "b".into(), 0.0);
+        assert!(active.is_crossfade());
+        assert_eq!(active.shader_source().map(|s| &**s), Some("fn fs() {}"));
+
+        assert!(!active.update(0.25));
+        assert_eq!(active.get_crossfade_blend(), 0.25);
+        assert!(active.update(1.0));
+    }
+}
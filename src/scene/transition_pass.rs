@@ -1,10 +1,12 @@
 //! GPU rendering pass for scene transitions.
 //!
 //! This module provides shaders and pipelines for rendering transition effects
-//! between scenes, including fade-to-color and crossfade transitions.
+//! between scenes, including fade-to-color, crossfade, and custom shader transitions.
 
 use crate::draw2d::Color;
+use crate::error::capture_shader_errors;
 use crate::gpu::GpuContext;
+use std::sync::Arc;
 
 /// Uniforms for transition rendering.
 #[repr(C)]
@@ -20,10 +22,18 @@ struct TransitionUniforms {
     color: [f32; 4],
 }
 
+/// A user transition shader and its pipeline, or `None` if it failed to compile.
+struct CustomShader {
+    source: Arc<str>,
+    pipeline: Option<wgpu::RenderPipeline>,
+}
+
 /// GPU resources for rendering scene transitions.
 pub struct TransitionPass {
     /// Pipeline for fade-to-color overlay.
     fade_pipeline: wgpu::RenderPipeline,
+    /// Pipeline that alpha-blends a scene over the target.
+    overlay_pipeline: wgpu::RenderPipeline,
     /// Pipeline for crossfade blending.
     crossfade_pipeline: wgpu::RenderPipeline,
     /// Uniform buffer.
@@ -34,6 +44,8 @@ pub struct TransitionPass {
     crossfade_bind_group_layout: wgpu::BindGroupLayout,
     /// Texture sampler.
     sampler: wgpu::Sampler,
+    /// Most recently used custom transition shader.
+    custom: Option<CustomShader>,
 }
 
 impl TransitionPass {
@@ -194,53 +206,74 @@ impl TransitionPass {
             cache: None,
         });
 
-        // Create crossfade pipeline
-        let crossfade_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Crossfade Transition Pipeline Layout"),
-                bind_group_layouts: &[&crossfade_bind_group_layout],
-                push_constant_ranges: &[],
-            });
+        // The fade shader with no fade passes the scene through; alpha
+        // blending it lets the scenes below show through its background
+        let overlay_pipeline = create_blend_pipeline(
+            gpu,
+            &fade_bind_group_layout,
+            &fade_shader,
+            "Scene Overlay Pipeline",
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
 
-        let crossfade_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Crossfade Transition Pipeline"),
-            layout: Some(&crossfade_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &crossfade_shader,
-                entry_point: Some("vs"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &crossfade_shader,
-                entry_point: Some("fs"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        // Create crossfade pipeline
+        let crossfade_pipeline = create_blend_pipeline(
+            gpu,
+            &crossfade_bind_group_layout,
+            &crossfade_shader,
+            "Crossfade Transition Pipeline",
+            wgpu::BlendState::REPLACE,
+        );
 
         Self {
             fade_pipeline,
+            overlay_pipeline,
             crossfade_pipeline,
             uniform_buffer,
             fade_bind_group_layout,
             crossfade_bind_group_layout,
             sampler,
+            custom: None,
         }
     }
 
+    /// Compile a custom transition shader unless it's already the current one.
+    ///
+    /// Returns `false` if the shader failed to compile; the error is printed to
+    /// stderr once and [`render_shader`](Self::render_shader) falls back to a crossfade.
+    pub fn prepare_shader(&mut self, gpu: &GpuContext, source: &Arc<str>) -> bool {
+        let cached = self
+            .custom
+            .as_ref()
+            .is_some_and(|custom| custom.source == *source);
+        if !cached {
+            let pipeline = capture_shader_errors(&gpu.device, || {
+                let module = gpu
+                    .device
+                    .create_shader_module(wgpu::ShaderModuleDescriptor {
+                        label: Some("Custom Transition Shader"),
+                        source: wgpu::ShaderSource::Wgsl((**source).into()),
+                    });
+                create_blend_pipeline(
+                    gpu,
+                    &self.crossfade_bind_group_layout,
+                    &module,
+                    "Custom Transition Pipeline",
+                    wgpu::BlendState::REPLACE,
+                )
+            })
+            .map_err(|e| eprintln!("[shader] Failed to create transition shader: {}", e))
+            .ok();
+            self.custom = Some(CustomShader {
+                source: Arc::clone(source),
+                pipeline,
+            });
+        }
+        self.custom
+            .as_ref()
+            .is_some_and(|custom| custom.pipeline.is_some())
+    }
+
     /// Render a fade-to-color transition.
     ///
     /// This blends the scene texture with a solid color based on the progress.
@@ -261,6 +294,54 @@ impl TransitionPass {
         scene_view: &wgpu::TextureView,
         color: Color,
         overlay_alpha: f32,
+    ) {
+        self.render_single(
+            gpu,
+            encoder,
+            &self.fade_pipeline,
+            target,
+            scene_view,
+            color,
+            overlay_alpha,
+            wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+        );
+    }
+
+    /// Draw a scene over what `target` already holds, blending by the scene's alpha.
+    ///
+    /// Scenes higher on the stack are composited this way, so the ones below
+    /// show through wherever the upper scene's background is transparent.
+    pub fn render_overlay(
+        &self,
+        gpu: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        scene_view: &wgpu::TextureView,
+    ) {
+        self.render_single(
+            gpu,
+            encoder,
+            &self.overlay_pipeline,
+            target,
+            scene_view,
+            Color::TRANSPARENT,
+            0.0,
+            wgpu::LoadOp::Load,
+        );
+    }
+
+    /// Draw one scene texture into `target` with a fade-layout pipeline.
+    #[allow(clippy::too_many_arguments)]
+    fn render_single(
+        &self,
+        gpu: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::RenderPipeline,
+        target: &wgpu::TextureView,
+        scene_view: &wgpu::TextureView,
+        color: Color,
+        overlay_alpha: f32,
+        load: wgpu::LoadOp<wgpu::Color>,
     ) {
         let uniforms = TransitionUniforms {
             resolution: [gpu.width() as f32, gpu.height() as f32],
@@ -297,7 +378,7 @@ impl TransitionPass {
                 view: target,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    load,
                     store: wgpu::StoreOp::Store,
                 },
                 depth_slice: None,
@@ -307,7 +388,7 @@ impl TransitionPass {
             occlusion_query_set: None,
         });
 
-        pass.set_pipeline(&self.fade_pipeline);
+        pass.set_pipeline(pipeline);
         pass.set_bind_group(0, &bind_group, &[]);
         pass.draw(0..3, 0..1);
     }
@@ -330,6 +411,58 @@ impl TransitionPass {
         old_scene_view: &wgpu::TextureView,
         new_scene_view: &wgpu::TextureView,
         blend: f32,
+    ) {
+        self.render_blend(
+            gpu,
+            encoder,
+            &self.crossfade_pipeline,
+            target,
+            old_scene_view,
+            new_scene_view,
+            blend,
+        );
+    }
+
+    /// Render the custom shader last passed to [`prepare_shader`](Self::prepare_shader).
+    ///
+    /// Falls back to a crossfade if the shader failed to compile. Arguments
+    /// are the same as for [`render_crossfade`](Self::render_crossfade).
+    pub fn render_shader(
+        &self,
+        gpu: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        old_scene_view: &wgpu::TextureView,
+        new_scene_view: &wgpu::TextureView,
+        blend: f32,
+    ) {
+        let pipeline = self
+            .custom
+            .as_ref()
+            .and_then(|custom| custom.pipeline.as_ref())
+            .unwrap_or(&self.crossfade_pipeline);
+        self.render_blend(
+            gpu,
+            encoder,
+            pipeline,
+            target,
+            old_scene_view,
+            new_scene_view,
+            blend,
+        );
+    }
+
+    /// Draw two scene textures into `target` with a two-scene blend pipeline.
+    #[allow(clippy::too_many_arguments)]
+    fn render_blend(
+        &self,
+        gpu: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::RenderPipeline,
+        target: &wgpu::TextureView,
+        old_scene_view: &wgpu::TextureView,
+        new_scene_view: &wgpu::TextureView,
+        blend: f32,
     ) {
         let uniforms = TransitionUniforms {
             resolution: [gpu.width() as f32, gpu.height() as f32],
@@ -380,12 +513,59 @@ impl TransitionPass {
             occlusion_query_set: None,
         });
 
-        pass.set_pipeline(&self.crossfade_pipeline);
+        pass.set_pipeline(pipeline);
         pass.set_bind_group(0, &bind_group, &[]);
         pass.draw(0..3, 0..1);
     }
 }
 
+/// Create a pipeline drawing a fullscreen triangle with `bind_group_layout` and `blend`.
+fn create_blend_pipeline(
+    gpu: &GpuContext,
+    bind_group_layout: &wgpu::BindGroupLayout,
+    shader: &wgpu::ShaderModule,
+    label: &str,
+    blend: wgpu::BlendState,
+) -> wgpu::RenderPipeline {
+    let layout = gpu
+        .device
+        .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: &[bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+    gpu.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: Some("vs"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: Some("fs"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: gpu.config.format,
+                    blend: Some(blend),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        })
+}
+
 /// Fade transition shader - blends scene with solid color.
 const FADE_SHADER: &str = r#"
 struct Uniforms {
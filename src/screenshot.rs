@@ -0,0 +1,193 @@
+//! Framebuffer readback for screenshots.
+//!
+//! Screenshots are requested during a frame and captured from the surface
+//! texture right before it is presented, so they contain exactly what ends up
+//! on screen: 3D content, post-processing, transitions, and 2D overlays.
+//!
+//! Requests are queued on the [`GpuContext`](crate::GpuContext) and serviced by
+//! [`GpuContext::present`](crate::GpuContext::present). File captures are
+//! written immediately; in-memory captures are held until the next frame picks
+//! them up with [`Frame::take_screenshot`](crate::Frame::take_screenshot).
+
+use std::path::PathBuf;
+
+use image::RgbaImage;
+
+/// Where a requested screenshot should go.
+pub(crate) enum CaptureTarget {
+    /// Encode and write to a file; the format is chosen from the extension.
+    File(PathBuf),
+    /// Keep the pixels in memory for [`Frame::take_screenshot`](crate::Frame::take_screenshot).
+    Memory,
+}
+
+/// Screenshot requests waiting for the next present, plus the last in-memory capture.
+#[derive(Default)]
+pub(crate) struct ScreenshotQueue {
+    /// Captures to take when the current frame is presented.
+    pub(crate) pending: Vec<CaptureTarget>,
+    /// Most recent in-memory capture, not yet taken.
+    pub(crate) captured: Option<RgbaImage>,
+}
+
+impl ScreenshotQueue {
+    /// Services all pending requests using pixels read from `texture`.
+    pub(crate) fn capture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+    ) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let pending = std::mem::take(&mut self.pending);
+        let Some(image) = read_texture_rgba(device, queue, texture) else {
+            eprintln!(
+                "[screenshot] Unsupported surface format {:?}",
+                texture.format()
+            );
+            return;
+        };
+
+        for target in pending {
+            match target {
+                CaptureTarget::File(path) => match image.save(&path) {
+                    Ok(()) => println!("[screenshot] Saved {:?}", path),
+                    Err(e) => eprintln!("[screenshot] Failed to save {:?}: {}", path, e),
+                },
+                CaptureTarget::Memory => self.captured = Some(image.clone()),
+            }
+        }
+    }
+}
+
+/// Returns `true` if [`read_texture_rgba`] can convert pixels of this format.
+pub(crate) fn is_readable_format(format: wgpu::TextureFormat) -> bool {
+    matches!(
+        format,
+        wgpu::TextureFormat::Rgba8Unorm
+            | wgpu::TextureFormat::Rgba8UnormSrgb
+            | wgpu::TextureFormat::Bgra8Unorm
+            | wgpu::TextureFormat::Bgra8UnormSrgb
+    )
+}
+
+/// Copies a texture to the CPU as tightly packed RGBA8 pixels.
+///
+/// The texture must have `COPY_SRC` usage. Row padding required by
+/// `copy_texture_to_buffer` is stripped and BGRA formats are swizzled to RGBA.
+/// sRGB textures are returned as stored (already gamma encoded), which is what
+/// image files expect. Returns `None` for formats other than 8-bit RGBA/BGRA.
+///
+/// This blocks until the GPU has finished all submitted work.
+pub(crate) fn read_texture_rgba(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> Option<RgbaImage> {
+    let format = texture.format();
+    if !is_readable_format(format) {
+        return None;
+    }
+
+    let width = texture.width();
+    let height = texture.height();
+    let row_bytes = width * 4;
+    let padded_row_bytes = row_bytes.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Screenshot Readback"),
+        size: (padded_row_bytes * height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Screenshot Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes),
+                rows_per_image: None,
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = readback.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    device.poll(wgpu::PollType::wait_indefinitely()).ok()?;
+
+    let mut pixels = Vec::with_capacity((row_bytes * height) as usize);
+    for row in slice.get_mapped_range().chunks(padded_row_bytes as usize) {
+        pixels.extend_from_slice(&row[..row_bytes as usize]);
+    }
+    readback.unmap();
+
+    if matches!(
+        format,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+    ) {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+
+    RgbaImage::from_raw(width, height, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bgra_readback_strips_row_padding_and_swizzles() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        // 3 pixels wide, so each 12-byte row is padded to 256 bytes in the buffer
+        let size = wgpu::Extent3d {
+            width: 3,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Bgra8Unorm,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let bgra: Vec<u8> = (0..6u8).flat_map(|i| [i, 10 + i, 20 + i, 255]).collect();
+        gpu.queue.write_texture(
+            texture.as_image_copy(),
+            &bgra,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(3 * 4),
+                rows_per_image: None,
+            },
+            size,
+        );
+
+        let image = read_texture_rgba(&gpu.device, &gpu.queue, &texture).unwrap();
+        assert_eq!(image.dimensions(), (3, 2));
+        assert_eq!(image.get_pixel(0, 0).0, [20, 10, 0, 255]);
+        assert_eq!(image.get_pixel(2, 1).0, [25, 15, 5, 255]);
+    }
+
+    #[test]
+    fn only_8bit_color_formats_are_readable() {
+        assert!(is_readable_format(wgpu::TextureFormat::Bgra8UnormSrgb));
+        assert!(!is_readable_format(wgpu::TextureFormat::Rgba16Float));
+    }
+}
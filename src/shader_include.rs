@@ -0,0 +1,212 @@
+//! `#include` preprocessing for WGSL shaders.
+//!
+//! WGSL has no module system, so shared helpers like noise or SDF functions
+//! would otherwise be copied into every shader. A line of the form
+//!
+//! ```text
+//! #include "common/noise.wgsl"
+//! ```
+//!
+//! is replaced with the contents of that file. Paths are resolved relative to
+//! the file containing the directive; for shaders passed as strings, relative
+//! to the working directory. Each file is included at most once, so two
+//! shaders can share a helper that both include. A file that ends up
+//! including itself is an error, as is nesting deeper than
+//! [`MAX_INCLUDE_DEPTH`].
+
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Maximum nesting of `#include` directives.
+pub(crate) const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// A shader with its includes spliced in.
+pub(crate) struct Expanded {
+    /// The preprocessed WGSL source.
+    pub source: String,
+    /// Every file read, root file first.
+    pub files: Vec<PathBuf>,
+}
+
+/// Reads the shader at `path` and expands its includes.
+pub(crate) fn expand_file(path: &Path) -> io::Result<Expanded> {
+    let mut expander = Expander::default();
+    let root = expander.enter(path)?;
+    let source = read(&root)?;
+    let mut out = String::with_capacity(source.len());
+    expander.expand(&source, parent_dir(&root), &mut out)?;
+
+    Ok(Expanded {
+        source: out,
+        files: expander.files,
+    })
+}
+
+/// Expands includes in shader source that isn't backed by a file.
+///
+/// Includes resolve relative to the working directory. Source without any
+/// `#include` lines is returned as is, without touching the filesystem.
+pub(crate) fn expand_source(source: &str) -> io::Result<Cow<'_, str>> {
+    if !source.lines().any(is_directive) {
+        return Ok(Cow::Borrowed(source));
+    }
+    let mut out = String::with_capacity(source.len());
+    Expander::default().expand(source, Path::new("."), &mut out)?;
+    Ok(Cow::Owned(out))
+}
+
+#[derive(Default)]
+struct Expander {
+    /// Files currently being expanded, outermost first.
+    stack: Vec<PathBuf>,
+    /// Files already included.
+    files: Vec<PathBuf>,
+}
+
+impl Expander {
+    /// Marks `path` as being expanded, returning its canonical form.
+    fn enter(&mut self, path: &Path) -> io::Result<PathBuf> {
+        let canonical = fs::canonicalize(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        if self.stack.contains(&canonical) {
+            let chain: Vec<_> = self
+                .stack
+                .iter()
+                .chain([&canonical])
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(invalid(format!("include cycle: {}", chain.join(" -> "))));
+        }
+        if self.stack.len() >= MAX_INCLUDE_DEPTH {
+            return Err(invalid(format!(
+                "includes nested deeper than {} at {}",
+                MAX_INCLUDE_DEPTH,
+                path.display()
+            )));
+        }
+        self.stack.push(canonical.clone());
+        self.files.push(canonical.clone());
+        Ok(canonical)
+    }
+
+    /// Appends `source` to `out`, replacing include directives with file contents.
+    fn expand(&mut self, source: &str, base_dir: &Path, out: &mut String) -> io::Result<()> {
+        for line in source.lines() {
+            let Some(include) = parse_directive(line)? else {
+                out.push_str(line);
+                out.push('\n');
+                continue;
+            };
+
+            let path = base_dir.join(include);
+            let already_included = fs::canonicalize(&path)
+                .map(|canonical| {
+                    self.files.contains(&canonical) && !self.stack.contains(&canonical)
+                })
+                .unwrap_or(false);
+            if already_included {
+                continue;
+            }
+
+            let canonical = self.enter(&path)?;
+            let text = read(&canonical)?;
+            self.expand(&text, parent_dir(&canonical), out)?;
+            self.stack.pop();
+        }
+        Ok(())
+    }
+}
+
+/// Whether `line` is an `#include` directive, well-formed or not.
+fn is_directive(line: &str) -> bool {
+    line.trim_start().starts_with("#include")
+}
+
+/// Returns the quoted path of an `#include` line, or `None` for other lines.
+fn parse_directive(line: &str) -> io::Result<Option<&str>> {
+    let Some(rest) = line.trim().strip_prefix("#include") else {
+        return Ok(None);
+    };
+    rest.trim()
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .filter(|path| !path.is_empty())
+        .map(Some)
+        .ok_or_else(|| invalid(format!("malformed include: {}", line.trim())))
+}
+
+fn read(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    path.parent().unwrap_or(Path::new("."))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an empty scratch directory unique to `name`.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("hoplite-include-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn includes_resolve_relative_to_including_file_once() {
+        let dir = scratch_dir("relative");
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(dir.join("lib/common.wgsl"), "fn common() {}").unwrap();
+        fs::write(
+            dir.join("lib/noise.wgsl"),
+            "#include \"common.wgsl\"\nfn noise() {}",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("main.wgsl"),
+            "#include \"lib/noise.wgsl\"\n  #include \"lib/common.wgsl\"\nfn main() {}",
+        )
+        .unwrap();
+
+        let expanded = expand_file(&dir.join("main.wgsl")).unwrap();
+        assert_eq!(
+            expanded.source,
+            "fn common() {}\nfn noise() {}\nfn main() {}\n"
+        );
+        assert_eq!(expanded.files.len(), 3);
+        assert!(expanded.files[0].ends_with("main.wgsl"));
+    }
+
+    #[test]
+    fn include_cycles_and_bad_directives_are_errors() {
+        let dir = scratch_dir("cycle");
+        fs::write(dir.join("a.wgsl"), "#include \"b.wgsl\"").unwrap();
+        fs::write(dir.join("b.wgsl"), "#include \"a.wgsl\"").unwrap();
+        let err = expand_file(&dir.join("a.wgsl")).err().unwrap();
+        assert!(err.to_string().contains("include cycle"), "{}", err);
+
+        fs::write(dir.join("bad.wgsl"), "#include common.wgsl").unwrap();
+        let err = expand_file(&dir.join("bad.wgsl")).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = expand_file(&dir.join("missing.wgsl")).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn source_without_includes_is_borrowed() {
+        let source = "@fragment fn fs() -> @location(0) vec4f { return vec4f(1.0); }";
+        assert!(matches!(expand_source(source), Ok(Cow::Borrowed(s)) if s == source));
+    }
+}
@@ -0,0 +1,34 @@
+// Billboards: camera-facing textured quads expanded on the CPU
+// Depth-tested against the mesh depth buffer without writing to it
+
+struct BillboardUniforms {
+    view_proj: mat4x4f,
+}
+
+@group(0) @binding(0) var<uniform> billboards: BillboardUniforms;
+@group(1) @binding(0) var t_diffuse: texture_2d<f32>;
+@group(1) @binding(1) var s_diffuse: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+    @location(1) color: vec4f,
+}
+
+@vertex
+fn vs(
+    @location(0) position: vec3f,
+    @location(1) uv: vec2f,
+    @location(2) color: vec4f,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = billboards.view_proj * vec4f(position, 1.0);
+    out.uv = uv;
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    return textureSample(t_diffuse, s_diffuse, in.uv) * in.color;
+}
@@ -0,0 +1,55 @@
+// Bloom blur - one direction of a separable 9-tap Gaussian
+
+struct Bloom {
+    threshold: f32,
+    intensity: f32,
+    direction: vec2f,
+}
+
+struct Uniforms {
+    resolution: vec2f,
+    time: f32,
+}
+
+@group(0) @binding(0) var<uniform> u: Uniforms;
+@group(0) @binding(1) var input_texture: texture_2d<f32>;
+@group(0) @binding(2) var input_sampler: sampler;
+@group(1) @binding(0) var<uniform> bloom: Bloom;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+}
+
+@vertex
+fn vs(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Fullscreen triangle (oversized to cover screen)
+    var positions = array<vec2f, 3>(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0)
+    );
+    let pos = positions[vertex_index];
+
+    var out: VertexOutput;
+    out.position = vec4f(pos, 0.0, 1.0);
+    out.uv = vec2f((pos.x + 1.0) * 0.5, (1.0 - pos.y) * 0.5);
+    return out;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    let texel = bloom.direction / vec2f(textureDimensions(input_texture));
+
+    // 9 Gaussian taps folded into 5 linear samples
+    var offsets = array<f32, 3>(0.0, 1.3846153846, 3.2307692308);
+    var weights = array<f32, 3>(0.2270270270, 0.3162162162, 0.0702702703);
+
+    var color = textureSample(input_texture, input_sampler, in.uv).rgb * weights[0];
+    for (var i = 1; i < 3; i++) {
+        let offset = texel * offsets[i];
+        color += textureSample(input_texture, input_sampler, in.uv + offset).rgb * weights[i];
+        color += textureSample(input_texture, input_sampler, in.uv - offset).rgb * weights[i];
+    }
+    return vec4f(color, 1.0);
+}
@@ -0,0 +1,48 @@
+// Bloom bright pass - keeps only the color above the threshold, at half resolution
+
+struct Bloom {
+    threshold: f32,
+    intensity: f32,
+    direction: vec2f,
+}
+
+struct Uniforms {
+    resolution: vec2f,
+    time: f32,
+}
+
+@group(0) @binding(0) var<uniform> u: Uniforms;
+@group(0) @binding(1) var input_texture: texture_2d<f32>;
+@group(0) @binding(2) var input_sampler: sampler;
+@group(1) @binding(0) var<uniform> bloom: Bloom;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+}
+
+@vertex
+fn vs(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Fullscreen triangle (oversized to cover screen)
+    var positions = array<vec2f, 3>(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0)
+    );
+    let pos = positions[vertex_index];
+
+    var out: VertexOutput;
+    out.position = vec4f(pos, 0.0, 1.0);
+    out.uv = vec2f((pos.x + 1.0) * 0.5, (1.0 - pos.y) * 0.5);
+    return out;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    // The half-resolution target puts each sample between four input texels,
+    // so linear filtering averages them
+    let color = textureSample(input_texture, input_sampler, in.uv).rgb;
+    let brightness = max(color.r, max(color.g, color.b));
+    let contribution = max(brightness - bloom.threshold, 0.0) / max(brightness, 0.0001);
+    return vec4f(color * contribution, 1.0);
+}
@@ -0,0 +1,47 @@
+// Bloom composite - adds the blurred highlights back onto the scene
+
+struct Bloom {
+    threshold: f32,
+    intensity: f32,
+    direction: vec2f,
+}
+
+struct Uniforms {
+    resolution: vec2f,
+    time: f32,
+}
+
+@group(0) @binding(0) var<uniform> u: Uniforms;
+@group(0) @binding(1) var input_texture: texture_2d<f32>;
+@group(0) @binding(2) var input_sampler: sampler;
+@group(0) @binding(3) var bloom_texture: texture_2d<f32>;
+@group(0) @binding(4) var bloom_sampler: sampler;
+@group(1) @binding(0) var<uniform> bloom: Bloom;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+}
+
+@vertex
+fn vs(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Fullscreen triangle (oversized to cover screen)
+    var positions = array<vec2f, 3>(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0)
+    );
+    let pos = positions[vertex_index];
+
+    var out: VertexOutput;
+    out.position = vec4f(pos, 0.0, 1.0);
+    out.uv = vec2f((pos.x + 1.0) * 0.5, (1.0 - pos.y) * 0.5);
+    return out;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    let scene = textureSample(input_texture, input_sampler, in.uv);
+    let glow = textureSample(bloom_texture, bloom_sampler, in.uv).rgb;
+    return vec4f(scene.rgb + glow * bloom.intensity, scene.a);
+}
@@ -0,0 +1,26 @@
+// Debug lines: world-space line segments with per-vertex colors
+// Depth-tested against the mesh depth buffer without writing to it
+
+struct LineUniforms {
+    view_proj: mat4x4f,
+}
+
+@group(0) @binding(0) var<uniform> lines: LineUniforms;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) color: vec4f,
+}
+
+@vertex
+fn vs(@location(0) position: vec3f, @location(1) color: vec4f) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = lines.view_proj * vec4f(position, 1.0);
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    return in.color;
+}
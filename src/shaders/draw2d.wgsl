@@ -2,6 +2,8 @@
 
 struct Uniforms {
     resolution: vec2f,
+    // Coverage gamma for bitmap font glyphs
+    text_gamma: f32,
 }
 
 @group(0) @binding(0) var<uniform> u: Uniforms;
@@ -10,6 +12,18 @@ struct Uniforms {
 @group(1) @binding(0) var t_atlas: texture_2d<f32>;
 @group(1) @binding(1) var s_atlas: sampler;
 
+// Distance field text parameters (group 2) - only used by the SDF pipeline
+struct SdfParams {
+    // Atlas pixels of distance covered by 0.5 of texel value
+    spread: f32,
+    outline_width: f32,
+    glow_radius: f32,
+    outline_color: vec4f,
+    glow_color: vec4f,
+}
+
+@group(2) @binding(0) var<uniform> sdf: SdfParams;
+
 struct VertexInput {
     @location(0) position: vec2f,
     @location(1) uv: vec2f,
@@ -38,6 +52,44 @@ fn vs(in: VertexInput) -> VertexOutput {
     return out;
 }
 
+// Per-instance data for instanced sprites
+struct SpriteInstanceInput {
+    @location(3) center: vec2f,
+    @location(4) size: vec2f,
+    @location(5) rotation: f32,
+    @location(6) color: vec4f,
+}
+
+// Vertex shader for instanced sprites: expands one quad per instance
+@vertex
+fn vs_sprite_instanced(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: SpriteInstanceInput,
+) -> VertexOutput {
+    var corners = array<vec2f, 6>(
+        vec2f(0.0, 0.0), vec2f(1.0, 0.0), vec2f(0.0, 1.0),
+        vec2f(1.0, 0.0), vec2f(1.0, 1.0), vec2f(0.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+
+    // Rotate around the center; with y pointing down, positive angles turn clockwise
+    let local = (corner - 0.5) * instance.size;
+    let c = cos(instance.rotation);
+    let s = sin(instance.rotation);
+    let pixel = instance.center + vec2f(local.x * c - local.y * s, local.x * s + local.y * c);
+
+    var out: VertexOutput;
+    out.position = vec4f(
+        (pixel.x / u.resolution.x) * 2.0 - 1.0,
+        1.0 - (pixel.y / u.resolution.y) * 2.0,
+        0.0,
+        1.0,
+    );
+    out.uv = corner;
+    out.color = instance.color;
+    return out;
+}
+
 // Fragment shader for colored (non-textured) quads
 @fragment
 fn fs_colored(in: VertexOutput) -> @location(0) vec4f {
@@ -45,13 +97,40 @@ fn fs_colored(in: VertexOutput) -> @location(0) vec4f {
 }
 
 // Fragment shader for textured quads (fonts)
-// Uses R8 texture as alpha mask
+// Uses R8 texture as alpha mask, with gamma above 1 lifting partial coverage
+// so thin antialiased stems don't fade out
 @fragment
 fn fs_textured(in: VertexOutput) -> @location(0) vec4f {
-    let alpha = textureSample(t_atlas, s_atlas, in.uv).r;
+    let coverage = textureSample(t_atlas, s_atlas, in.uv).r;
+    let alpha = pow(coverage, 1.0 / u.text_gamma);
     return vec4f(in.color.rgb, in.color.a * alpha);
 }
 
+// Fragment shader for signed distance field text
+// Distances are in atlas pixels, positive inside the glyph
+@fragment
+fn fs_sdf(in: VertexOutput) -> @location(0) vec4f {
+    let value = textureSample(t_atlas, s_atlas, in.uv).r;
+    let distance = (value - 0.5) * 2.0 * sdf.spread;
+    // Antialias over one screen pixel at any scale
+    let aa = max(fwidth(distance), 1e-4);
+
+    let fill = clamp(distance / aa + 0.5, 0.0, 1.0);
+    let outline = clamp((distance + sdf.outline_width) / aa + 0.5, 0.0, 1.0);
+    let falloff = clamp(1.0 - (-sdf.outline_width - distance) / max(sdf.glow_radius, 1e-4), 0.0, 1.0);
+    let glow = falloff * falloff;
+
+    // Composite glow, then outline, then fill, front to back in premultiplied form
+    var color = vec4f(sdf.glow_color.rgb, 1.0) * sdf.glow_color.a * glow;
+    let outline_color = vec4f(sdf.outline_color.rgb, 1.0) * sdf.outline_color.a * outline;
+    color = outline_color + color * (1.0 - outline_color.a);
+    let fill_color = vec4f(in.color.rgb, 1.0) * in.color.a * fill;
+    color = fill_color + color * (1.0 - fill_color.a);
+
+    // Back to straight alpha for the shared blend state
+    return vec4f(color.rgb / max(color.a, 1e-4), color.a);
+}
+
 // Fragment shader for RGBA sprites
 // Samples full color and multiplies by vertex color (tint)
 @fragment
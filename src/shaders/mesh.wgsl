@@ -9,6 +9,27 @@ struct CameraUniforms {
     time: f32,
 }
 
+struct PointLight {
+    position: vec3f,
+    range: f32,
+    color: vec3f,
+    _pad: f32,
+}
+
+const MAX_POINT_LIGHTS: u32 = 8u;
+
+struct LightUniforms {
+    sun_direction: vec3f,  // Toward the sun
+    sun_enabled: u32,
+    sun_color: vec3f,
+    ambient: f32,
+    point_count: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+    point_lights: array<PointLight, MAX_POINT_LIGHTS>,
+}
+
 struct InstanceData {
     model: mat4x4f,
     normal_matrix: mat4x4f,
@@ -16,27 +37,67 @@ struct InstanceData {
 }
 
 @group(0) @binding(0) var<uniform> camera: CameraUniforms;
+@group(0) @binding(1) var<uniform> lights: LightUniforms;
 @group(1) @binding(0) var<storage, read> instances: array<InstanceData>;
+// Texture array layer per instance, read from t_diffuse_array
+@group(1) @binding(1) var<storage, read> instance_layers: array<u32>;
+
+// Per-instance UV transform: scale, then rotate counter-clockwise, then offset
+struct UvTransform {
+    offset: vec2f,
+    scale: vec2f,
+    rotation: f32,
+    _pad: f32,
+}
+
+@group(1) @binding(2) var<storage, read> instance_uvs: array<UvTransform>;
+// Alpha below which each instance's fragments are discarded; 0 blends instead
+@group(1) @binding(3) var<storage, read> instance_alpha_cutoffs: array<f32>;
 @group(2) @binding(0) var t_diffuse: texture_2d<f32>;
 @group(2) @binding(1) var s_diffuse: sampler;
+@group(2) @binding(2) var s_normal: sampler;
+@group(2) @binding(3) var t_normal: texture_2d<f32>;
+// A single white layer unless the mesh is textured from an array, in which
+// case t_diffuse is white instead
+@group(2) @binding(4) var t_diffuse_array: texture_2d_array<f32>;
 
 struct VertexInput {
     @location(0) position: vec3f,
     @location(1) normal: vec3f,
     @location(2) uv: vec2f,
+    @location(3) tangent: vec4f,
+    @builtin(instance_index) instance_idx: u32,
+}
+
+// Vertex3dColored: the same attributes plus a per-vertex color
+struct ColoredVertexInput {
+    @location(0) position: vec3f,
+    @location(1) normal: vec3f,
+    @location(2) uv: vec2f,
+    @location(3) tangent: vec4f,
+    @location(4) color: vec4f,
     @builtin(instance_index) instance_idx: u32,
 }
 
 struct VertexOutput {
-    @builtin(position) clip_position: vec4f,
+    // Invariant so the depth prepass and color pass compute identical depths
+    @builtin(position) @invariant clip_position: vec4f,
     @location(0) world_pos: vec3f,
     @location(1) world_normal: vec3f,
     @location(2) uv: vec2f,
     @location(3) @interpolate(flat) instance_idx: u32,
+    @location(4) world_tangent: vec4f,
+    @location(5) color: vec4f,
 }
 
-@vertex
-fn vs(in: VertexInput) -> VertexOutput {
+fn transform_uv(uv: vec2f, t: UvTransform) -> vec2f {
+    let scaled = uv * t.scale;
+    let c = cos(t.rotation);
+    let s = sin(t.rotation);
+    return vec2f(scaled.x * c - scaled.y * s, scaled.x * s + scaled.y * c) + t.offset;
+}
+
+fn transform_vertex(in: VertexInput, color: vec4f) -> VertexOutput {
     let instance = instances[in.instance_idx];
     let world_pos = instance.model * vec4f(in.position, 1.0);
     let world_normal = normalize((instance.normal_matrix * vec4f(in.normal, 0.0)).xyz);
@@ -45,20 +106,74 @@ fn vs(in: VertexInput) -> VertexOutput {
     out.clip_position = camera.view_proj * world_pos;
     out.world_pos = world_pos.xyz;
     out.world_normal = world_normal;
-    out.uv = in.uv;
+    out.uv = transform_uv(in.uv, instance_uvs[in.instance_idx]);
     out.instance_idx = in.instance_idx;
+    out.world_tangent = vec4f((instance.model * vec4f(in.tangent.xyz, 0.0)).xyz, in.tangent.w);
+    out.color = color;
     return out;
 }
 
-@fragment
-fn fs(in: VertexOutput) -> @location(0) vec4f {
-    let instance = instances[in.instance_idx];
-    let normal = normalize(in.world_normal);
-    let view_dir = normalize(camera.camera_pos - in.world_pos);
+@vertex
+fn vs(in: VertexInput) -> VertexOutput {
+    return transform_vertex(in, vec4f(1.0));
+}
+
+@vertex
+fn vs_colored(in: ColoredVertexInput) -> VertexOutput {
+    let base = VertexInput(in.position, in.normal, in.uv, in.tangent, in.instance_idx);
+    return transform_vertex(base, in.color);
+}
+
+// Perturbs the surface normal by the normal map in tangent space.
+// 128 decodes to exactly zero, so the flat default map leaves the normal unchanged.
+fn apply_normal_map(normal: vec3f, tangent: vec4f, uv: vec2f) -> vec3f {
+    let encoded = textureSample(t_normal, s_normal, uv).xyz;
+    if (dot(tangent.xyz, tangent.xyz) < 1e-8) {
+        return normal;
+    }
+    let t = normalize(tangent.xyz - normal * dot(normal, tangent.xyz));
+    let b = cross(normal, t) * tangent.w;
+    let n = (encoded * 255.0 - 128.0) / 127.0;
+    return normalize(t * n.x + b * n.y + normal * n.z);
+}
 
-    // Sample texture
-    let tex_color = textureSample(t_diffuse, s_diffuse, in.uv);
+// Lambert diffuse + Blinn-Phong specular for one light
+fn shade(normal: vec3f, view_dir: vec3f, light_dir: vec3f, color: vec3f) -> vec3f {
+    let diffuse = dot(normal, light_dir);
+    if (diffuse <= 0.0) {
+        return vec3f(0.0);
+    }
+    let half_vec = normalize(light_dir + view_dir);
+    let spec = pow(max(dot(normal, half_vec), 0.0), 32.0) * 0.3;
+    return color * (diffuse + spec);
+}
+
+// Lighting from the configured sun and point lights
+fn configured_lighting(normal: vec3f, view_dir: vec3f, world_pos: vec3f) -> vec3f {
+    var lighting = vec3f(lights.ambient);
+
+    if (lights.sun_enabled != 0u) {
+        lighting += shade(normal, view_dir, lights.sun_direction, lights.sun_color);
+    }
+
+    for (var i = 0u; i < min(lights.point_count, MAX_POINT_LIGHTS); i++) {
+        let light = lights.point_lights[i];
+        let to_light = light.position - world_pos;
+        let dist = length(to_light);
+        // Smooth falloff that reaches exactly zero at the light's range
+        let falloff = clamp(1.0 - dist / light.range, 0.0, 1.0);
+        let attenuation = falloff * falloff;
+        if (attenuation > 0.0) {
+            let light_dir = to_light / max(dist, 0.0001);
+            lighting += shade(normal, view_dir, light_dir, light.color) * attenuation;
+        }
+    }
+
+    return lighting;
+}
 
+// Default look used when no lights are configured
+fn default_lighting(normal: vec3f, view_dir: vec3f) -> vec3f {
     // Simple directional light from above-right
     let light_dir = normalize(vec3f(0.5, 1.0, 0.3));
     let light_color = vec3f(1.0, 0.98, 0.95);
@@ -77,10 +192,44 @@ fn fs(in: VertexOutput) -> @location(0) vec4f {
     // Rim light for edge definition
     let rim = pow(1.0 - max(dot(normal, view_dir), 0.0), 3.0) * 0.15;
 
-    // Combine texture color with instance color (tint)
-    let base_color = tex_color.rgb * instance.color.rgb;
-    let lighting = ambient + diffuse * light_color + spec + rim;
-    let final_color = base_color * lighting;
+    return ambient + diffuse * light_color + spec + rim;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    let instance = instances[in.instance_idx];
+    let normal = apply_normal_map(normalize(in.world_normal), in.world_tangent, in.uv);
+    let view_dir = normalize(camera.camera_pos - in.world_pos);
 
-    return vec4f(final_color, tex_color.a * instance.color.a);
+    // Sample texture; one of the two is always white
+    let layer = instance_layers[in.instance_idx];
+    let tex_color = textureSample(t_diffuse, s_diffuse, in.uv)
+        * textureSample(t_diffuse_array, s_diffuse, in.uv, layer);
+
+    var lighting: vec3f;
+    if (lights.sun_enabled != 0u || lights.point_count > 0u) {
+        lighting = configured_lighting(normal, view_dir, in.world_pos);
+    } else {
+        lighting = default_lighting(normal, view_dir);
+    }
+
+    // Combine texture color with instance color (tint) and vertex color
+    let tint = instance.color * in.color;
+    let base_color = tex_color.rgb * tint.rgb;
+    let final_color = base_color * lighting;
+    let alpha = tex_color.a * tint.a;
+
+    // Cutouts are alpha tested, leaving fully opaque fragments that need no sorting
+    let cutoff = instance_alpha_cutoffs[in.instance_idx];
+    if (cutoff > 0.0) {
+        if (alpha < cutoff) {
+            discard;
+        }
+        return vec4f(final_color, 1.0);
+    }
+    return vec4f(final_color, alpha);
 }
+
+// Fragment stage of depth prepass pipelines, which write depth only
+@fragment
+fn fs_depth_only() {}
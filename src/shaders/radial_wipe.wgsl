@@ -0,0 +1,50 @@
+// Radial wipe transition - reveals the new scene in a circle growing from the center
+
+struct Uniforms {
+    resolution: vec2f,
+    progress: f32,
+    _pad: f32,
+    color: vec4f,
+}
+
+@group(0) @binding(0) var<uniform> u: Uniforms;
+@group(0) @binding(1) var old_texture: texture_2d<f32>;
+@group(0) @binding(2) var new_texture: texture_2d<f32>;
+@group(0) @binding(3) var tex_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+}
+
+@vertex
+fn vs(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Fullscreen triangle (oversized to cover screen)
+    var positions = array<vec2f, 3>(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0)
+    );
+    let pos = positions[vertex_index];
+
+    var out: VertexOutput;
+    out.position = vec4f(pos, 0.0, 1.0);
+    out.uv = vec2f((pos.x + 1.0) * 0.5, (1.0 - pos.y) * 0.5);
+    return out;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    let old_scene = textureSample(old_texture, tex_sampler, in.uv);
+    let new_scene = textureSample(new_texture, tex_sampler, in.uv);
+
+    // Distance from the center in pixels, normalized so 1.0 reaches the corners
+    let offset = (in.uv - 0.5) * u.resolution;
+    let distance = length(offset) / length(u.resolution * 0.5);
+
+    // Soft edge a few percent wide, fully closed at 0 and fully open at 1
+    let edge = 0.05;
+    let radius = u.progress * (1.0 + edge);
+    let reveal = 1.0 - smoothstep(radius - edge, radius, distance);
+    return mix(old_scene, new_scene, reveal);
+}
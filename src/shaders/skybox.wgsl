@@ -0,0 +1,37 @@
+// Skybox: samples a cubemap along the camera's view direction for every pixel
+// Drawn as a fullscreen triangle, so it works with any projection
+
+struct SkyboxUniforms {
+    // Inverse of projection * view with the camera translation removed
+    inv_view_proj: mat4x4f,
+}
+
+@group(0) @binding(0) var<uniform> sky: SkyboxUniforms;
+@group(0) @binding(1) var t_sky: texture_cube<f32>;
+@group(0) @binding(2) var s_sky: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) ndc: vec2f,
+}
+
+@vertex
+fn vs(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var pos = array<vec2f, 3>(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0)
+    );
+    var out: VertexOutput;
+    out.position = vec4f(pos[vertex_index], 0.0, 1.0);
+    out.ndc = pos[vertex_index];
+    return out;
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    // Unproject a point on the far plane to get the world-space view direction
+    let far = sky.inv_view_proj * vec4f(in.ndc, 1.0, 1.0);
+    let direction = normalize(far.xyz / far.w);
+    return vec4f(textureSample(t_sky, s_sky, direction).rgb, 1.0);
+}
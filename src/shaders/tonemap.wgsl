@@ -0,0 +1,48 @@
+// ACES filmic tonemapping - maps HDR input into the displayable 0-1 range
+
+struct Uniforms {
+    resolution: vec2f,
+    time: f32,
+}
+
+@group(0) @binding(0) var<uniform> u: Uniforms;
+@group(0) @binding(1) var input_texture: texture_2d<f32>;
+@group(0) @binding(2) var input_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4f,
+    @location(0) uv: vec2f,
+}
+
+@vertex
+fn vs(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Fullscreen triangle (oversized to cover screen)
+    var positions = array<vec2f, 3>(
+        vec2f(-1.0, -1.0),
+        vec2f(3.0, -1.0),
+        vec2f(-1.0, 3.0)
+    );
+    let pos = positions[vertex_index];
+
+    var out: VertexOutput;
+    out.position = vec4f(pos, 0.0, 1.0);
+    out.uv = vec2f((pos.x + 1.0) * 0.5, (1.0 - pos.y) * 0.5);
+    return out;
+}
+
+// Krzysztof Narkowicz's fit of the ACES reference rendering transform
+fn aces(x: vec3f) -> vec3f {
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    return clamp((x * (a * x + b)) / (x * (c * x + d) + e), vec3f(0.0), vec3f(1.0));
+}
+
+@fragment
+fn fs(in: VertexOutput) -> @location(0) vec4f {
+    let hdr = textureSample(input_texture, input_sampler, in.uv);
+    // Output stays linear; an sRGB surface applies the transfer curve
+    return vec4f(aces(max(hdr.rgb, vec3f(0.0))), hdr.a);
+}
@@ -0,0 +1,190 @@
+//! Frame-by-frame animation from sprite sheets.
+//!
+//! A [`SpriteAnimation`] tracks playback time and picks the current frame out
+//! of a grid of equally sized frames, so drawing an animation doesn't need any
+//! source-rectangle math. Advance it with [`update`](SpriteAnimation::update)
+//! and draw it with [`Frame::sprite_anim`](crate::Frame::sprite_anim).
+//!
+//! # Example
+//!
+//! ```
+//! use hoplite::{SpriteAnimation, SpriteId};
+//!
+//! // 6 frames of 32x32 in a sheet 4 frames wide, at 10 fps
+//! let mut walk = SpriteAnimation::new(SpriteId(0), 32.0, 32.0, 6, 4, 10.0);
+//! walk.update(0.45);
+//! assert_eq!(walk.current_frame(), 4);
+//! assert_eq!(walk.source_rect(), (0.0, 32.0, 32.0, 32.0));
+//! ```
+
+use crate::draw2d::SpriteId;
+
+/// Playback state of an animation laid out as a grid in a sprite sheet.
+///
+/// Frames are numbered left to right, then top to bottom, starting at the
+/// sheet's top-left corner. Animations loop by default; call
+/// [`one_shot`](Self::one_shot) to stop on the last frame instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpriteAnimation {
+    sprite: SpriteId,
+    frame_width: f32,
+    frame_height: f32,
+    frame_count: u32,
+    columns: u32,
+    fps: f32,
+    looping: bool,
+    /// Playback time in seconds since the start or last [`reset`](Self::reset).
+    elapsed: f32,
+}
+
+impl SpriteAnimation {
+    /// Creates a looping animation.
+    ///
+    /// # Arguments
+    ///
+    /// * `sprite` - The sprite sheet
+    /// * `frame_width`, `frame_height` - Size of one frame in pixels
+    /// * `frame_count` - Number of frames in the animation
+    /// * `columns` - Number of frames per row of the sheet
+    /// * `fps` - Playback speed in frames per second
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_count` or `columns` is zero.
+    pub fn new(
+        sprite: SpriteId,
+        frame_width: f32,
+        frame_height: f32,
+        frame_count: u32,
+        columns: u32,
+        fps: f32,
+    ) -> Self {
+        assert!(frame_count > 0, "sprite animation needs at least one frame");
+        assert!(columns > 0, "sprite animation needs at least one column");
+        Self {
+            sprite,
+            frame_width,
+            frame_height,
+            frame_count,
+            columns,
+            fps,
+            looping: true,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Plays the animation once and holds the last frame.
+    pub fn one_shot(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+
+    /// Advances playback by `dt` seconds.
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed += dt.max(0.0);
+        // Keep looping playback time small so it doesn't lose precision
+        let duration = self.duration();
+        if self.looping && duration > 0.0 {
+            self.elapsed %= duration;
+        }
+    }
+
+    /// Restarts playback from the first frame.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// Whether a one-shot animation has reached its end. Always `false` when looping.
+    pub fn finished(&self) -> bool {
+        !self.looping && self.fps > 0.0 && self.elapsed >= self.duration()
+    }
+
+    /// Index of the frame to draw, from 0 to `frame_count - 1`.
+    pub fn current_frame(&self) -> u32 {
+        let frame = (self.elapsed * self.fps.max(0.0)) as u32;
+        if self.looping {
+            frame % self.frame_count
+        } else {
+            frame.min(self.frame_count - 1)
+        }
+    }
+
+    /// Source rectangle `(src_x, src_y, src_w, src_h)` of the current frame in the sheet.
+    pub fn source_rect(&self) -> (f32, f32, f32, f32) {
+        let frame = self.current_frame();
+        let column = frame % self.columns;
+        let row = frame / self.columns;
+        (
+            column as f32 * self.frame_width,
+            row as f32 * self.frame_height,
+            self.frame_width,
+            self.frame_height,
+        )
+    }
+
+    /// The sprite sheet this animation draws from.
+    pub fn sprite(&self) -> SpriteId {
+        self.sprite
+    }
+
+    /// Size of one frame in pixels as `(width, height)`.
+    pub fn frame_size(&self) -> (f32, f32) {
+        (self.frame_width, self.frame_height)
+    }
+
+    /// Length of one pass through all frames in seconds.
+    fn duration(&self) -> f32 {
+        if self.fps > 0.0 {
+            self.frame_count as f32 / self.fps
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk() -> SpriteAnimation {
+        SpriteAnimation::new(SpriteId(0), 16.0, 24.0, 6, 4, 10.0)
+    }
+
+    #[test]
+    fn frames_fill_the_sheet_row_by_row() {
+        let mut anim = walk();
+        assert_eq!(anim.source_rect(), (0.0, 0.0, 16.0, 24.0));
+
+        anim.update(0.35);
+        assert_eq!(anim.current_frame(), 3);
+        assert_eq!(anim.source_rect(), (48.0, 0.0, 16.0, 24.0));
+
+        anim.update(0.1);
+        assert_eq!(anim.source_rect(), (0.0, 24.0, 16.0, 24.0));
+    }
+
+    #[test]
+    fn looping_wraps_and_never_finishes() {
+        let mut anim = walk();
+        anim.update(0.65);
+        assert_eq!(anim.current_frame(), 0);
+        anim.update(10.0);
+        assert!(!anim.finished());
+    }
+
+    #[test]
+    fn one_shot_holds_the_last_frame_and_finishes() {
+        let mut anim = walk().one_shot();
+        anim.update(0.55);
+        assert_eq!(anim.current_frame(), 5);
+        assert!(!anim.finished());
+
+        anim.update(1.0);
+        assert_eq!(anim.current_frame(), 5);
+        assert!(anim.finished());
+
+        anim.reset();
+        assert_eq!(anim.current_frame(), 0);
+        assert!(!anim.finished());
+    }
+}
@@ -0,0 +1,28 @@
+//! Helpers shared by the crate's GPU tests.
+
+use crate::GpuContext;
+
+/// Environment variable that turns a missing GPU adapter into a test failure,
+/// so CI with a GPU can't pass by skipping every GPU test.
+const REQUIRE_GPU_VAR: &str = "HOPLITE_REQUIRE_GPU";
+
+/// Report a GPU test skipped because no adapter is available.
+///
+/// Panics instead when `HOPLITE_REQUIRE_GPU` is set.
+pub(crate) fn skip_without_gpu(reason: impl std::fmt::Display) {
+    if std::env::var_os(REQUIRE_GPU_VAR).is_some() {
+        panic!("no GPU adapter available with {REQUIRE_GPU_VAR} set: {reason}");
+    }
+    eprintln!("skipping: no GPU adapter available ({reason})");
+}
+
+/// A headless context for a GPU test, or `None` after [`skip_without_gpu`].
+pub(crate) fn headless_gpu(width: u32, height: u32) -> Option<GpuContext> {
+    match GpuContext::try_new_headless(width, height) {
+        Ok(gpu) => Some(gpu),
+        Err(e) => {
+            skip_without_gpu(e);
+            None
+        }
+    }
+}
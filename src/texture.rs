@@ -11,8 +11,8 @@
 //!
 //! | Feature | Texture | Sprite |
 //! |---------|---------|--------|
-//! | Filtering | Nearest (pixelated) | Linear (smooth) or Nearest |
-//! | Address mode | Repeat (tiling) | Clamp to edge |
+//! | Filtering | Linear (smooth) or Nearest | Linear (smooth) or Nearest |
+//! | Address mode | Repeat (tiling) or Clamp to edge | Clamp to edge |
 //! | Use case | 3D world textures | UI elements, HUD |
 //!
 //! # Examples
@@ -21,6 +21,10 @@
 //! // Load a texture from a file
 //! let texture = Texture::from_file(&gpu, "assets/stone.png")?;
 //!
+//! // Pixel art that shouldn't tile
+//! let sign = Texture::from_file_nearest(&gpu, "assets/sign.png")?
+//!     .with_address_mode(&gpu, wgpu::AddressMode::ClampToEdge);
+//!
 //! // Generate a procedural blocky texture
 //! let grass = Texture::blocky_grass(&gpu, 16, 42);
 //!
@@ -28,26 +32,35 @@
 //! let icon = Sprite::from_bytes(&gpu, include_bytes!("icon.png"), "icon")?;
 //! ```
 
+use crate::error::Error;
 use crate::gpu::GpuContext;
+use crate::screenshot::read_texture_rgba;
 
 /// A GPU texture that can be bound to shaders.
 ///
-/// Textures are configured with nearest-neighbor filtering and repeating address mode,
-/// making them ideal for blocky/pixelated 3D rendering where textures
-/// tile across surfaces.
+/// Each texture carries its own sampler. Images loaded with
+/// [`from_file`](Self::from_file) and friends use linear filtering, the
+/// `_nearest` variants and the procedural generators keep pixels crisp, and
+/// both repeat so textures tile across surfaces. Change either afterwards with
+/// [`with_filter`](Self::with_filter) and [`with_address_mode`](Self::with_address_mode).
 #[derive(Debug)]
 pub struct Texture {
     /// The underlying wgpu texture resource.
-    #[allow(dead_code)]
     pub(crate) texture: wgpu::Texture,
     /// View into the texture for shader binding.
     pub(crate) view: wgpu::TextureView,
     /// Sampler defining how the texture is filtered and addressed.
     pub(crate) sampler: wgpu::Sampler,
+    /// Magnification and minification filter of `sampler`.
+    filter: wgpu::FilterMode,
+    /// Address mode of `sampler` on every axis.
+    address_mode: wgpu::AddressMode,
     /// Width of the texture in pixels.
     pub width: u32,
     /// Height of the texture in pixels.
     pub height: u32,
+    /// Whether `view` is a `texture_2d_array` (see [`Texture::array_from_files`]).
+    pub(crate) array: bool,
 }
 
 /// A 2D sprite for UI/HUD rendering.
@@ -63,6 +76,8 @@ pub struct Texture {
 ///   filtering for pixel art that should stay crisp
 #[derive(Debug)]
 pub struct Sprite {
+    /// The underlying wgpu texture resource.
+    pub(crate) texture: wgpu::Texture,
     /// View into the texture for shader binding.
     pub(crate) view: wgpu::TextureView,
     /// Sampler defining how the sprite is filtered and addressed.
@@ -83,7 +98,7 @@ impl Texture {
     ///
     /// * `texture` - The underlying wgpu texture
     /// * `view` - Texture view for shader binding
-    /// * `sampler` - Sampler defining filtering and addressing
+    /// * `sampler` - Sampler with linear filtering that clamps to the edges
     /// * `width` - Texture width in pixels
     /// * `height` - Texture height in pixels
     pub(crate) fn from_wgpu_resources(
@@ -97,12 +112,17 @@ impl Texture {
             texture,
             view,
             sampler,
+            filter: wgpu::FilterMode::Linear,
+            address_mode: wgpu::AddressMode::ClampToEdge,
             width,
             height,
+            array: false,
         }
     }
 
-    /// Create a texture from raw RGBA data.
+    /// Create a texture from raw RGBA data with linear filtering.
+    ///
+    /// For pixel art, use [`from_rgba_nearest`](Self::from_rgba_nearest).
     ///
     /// # Arguments
     ///
@@ -116,6 +136,83 @@ impl Texture {
     ///
     /// Panics if `data.len() != width * height * 4`.
     pub fn from_rgba(gpu: &GpuContext, data: &[u8], width: u32, height: u32, label: &str) -> Self {
+        Self::from_rgba_with_format(
+            gpu,
+            data,
+            width,
+            height,
+            label,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            wgpu::FilterMode::Linear,
+        )
+    }
+
+    /// Create a texture from raw RGBA data with nearest-neighbor filtering.
+    ///
+    /// Keeps texels as sharp squares however large they appear, for
+    /// pixel-art and blocky 3D worlds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != width * height * 4`.
+    pub fn from_rgba_nearest(
+        gpu: &GpuContext,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        label: &str,
+    ) -> Self {
+        Self::from_rgba_with_format(
+            gpu,
+            data,
+            width,
+            height,
+            label,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            wgpu::FilterMode::Nearest,
+        )
+    }
+
+    /// Create a tangent-space normal map from raw RGBA data.
+    ///
+    /// Unlike [`from_rgba`](Self::from_rgba), the data is stored as linear
+    /// values rather than sRGB colors, so the encoded directions come through
+    /// unchanged. Normal maps are expected OpenGL-style, with green pointing up
+    /// in the image. Normal maps are filtered linearly so lighting varies
+    /// smoothly across texels. Apply one with
+    /// [`MeshBuilder::normal_map`](crate::MeshBuilder::normal_map).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != width * height * 4`.
+    pub fn normal_map_from_rgba(
+        gpu: &GpuContext,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        label: &str,
+    ) -> Self {
+        Self::from_rgba_with_format(
+            gpu,
+            data,
+            width,
+            height,
+            label,
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::FilterMode::Linear,
+        )
+    }
+
+    /// Uploads RGBA8 data as a repeating texture of the given 4-byte-per-pixel `format`.
+    fn from_rgba_with_format(
+        gpu: &GpuContext,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        label: &str,
+        format: wgpu::TextureFormat,
+        filter: wgpu::FilterMode,
+    ) -> Self {
         use wgpu::util::DeviceExt;
 
         let texture = gpu.device.create_texture_with_data(
@@ -130,8 +227,10 @@ impl Texture {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST
+                    | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             },
             wgpu::util::TextureDataOrder::LayerMajor,
@@ -139,32 +238,26 @@ impl Texture {
         );
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-        // Use nearest-neighbor filtering for crisp pixel-art look
-        let sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some(&format!("{} Sampler", label)),
-            address_mode_u: wgpu::AddressMode::Repeat,
-            address_mode_v: wgpu::AddressMode::Repeat,
-            address_mode_w: wgpu::AddressMode::Repeat,
-            mag_filter: wgpu::FilterMode::Nearest,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
+        let address_mode = wgpu::AddressMode::Repeat;
+        let sampler = create_sampler(&gpu.device, label, filter, address_mode);
 
         Self {
             texture,
             view,
             sampler,
+            filter,
+            address_mode,
             width,
             height,
+            array: false,
         }
     }
 
-    /// Load a texture from an image file.
+    /// Load a texture from an image file with linear filtering.
     ///
     /// Supports common image formats (PNG, JPEG, etc.) via the `image` crate.
-    /// The image is automatically converted to RGBA8 format.
+    /// The image is automatically converted to RGBA8 format. For pixel art,
+    /// use [`from_file_nearest`](Self::from_file_nearest).
     ///
     /// # Arguments
     ///
@@ -174,13 +267,26 @@ impl Texture {
     /// # Errors
     ///
     /// Returns an error if the file cannot be read or decoded.
-    pub fn from_file(gpu: &GpuContext, path: &str) -> Result<Self, image::ImageError> {
+    pub fn from_file(gpu: &GpuContext, path: &str) -> Result<Self, Error> {
         let img = image::open(path)?.to_rgba8();
         let (width, height) = img.dimensions();
         Ok(Self::from_rgba(gpu, &img, width, height, path))
     }
 
-    /// Load a texture from embedded bytes.
+    /// Load a texture from an image file with nearest-neighbor filtering.
+    ///
+    /// See [`from_rgba_nearest`](Self::from_rgba_nearest).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded.
+    pub fn from_file_nearest(gpu: &GpuContext, path: &str) -> Result<Self, Error> {
+        let img = image::open(path)?.to_rgba8();
+        let (width, height) = img.dimensions();
+        Ok(Self::from_rgba_nearest(gpu, &img, width, height, path))
+    }
+
+    /// Load a texture from embedded bytes with linear filtering.
     ///
     /// Useful for loading textures embedded in the binary via `include_bytes!`.
     /// Supports common image formats (PNG, JPEG, etc.).
@@ -204,14 +310,424 @@ impl Texture {
     ///     "stone",
     /// )?;
     /// ```
-    pub fn from_bytes(
+    pub fn from_bytes(gpu: &GpuContext, bytes: &[u8], label: &str) -> Result<Self, Error> {
+        let img = image::load_from_memory(bytes)?.to_rgba8();
+        let (width, height) = img.dimensions();
+        Ok(Self::from_rgba(gpu, &img, width, height, label))
+    }
+
+    /// Load a texture from embedded bytes with nearest-neighbor filtering.
+    ///
+    /// See [`from_rgba_nearest`](Self::from_rgba_nearest).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes cannot be decoded as an image.
+    pub fn from_bytes_nearest(gpu: &GpuContext, bytes: &[u8], label: &str) -> Result<Self, Error> {
+        let img = image::load_from_memory(bytes)?.to_rgba8();
+        let (width, height) = img.dimensions();
+        Ok(Self::from_rgba_nearest(gpu, &img, width, height, label))
+    }
+
+    /// Returns this texture with its sampler switched to `filter`.
+    ///
+    /// [`FilterMode::Nearest`](wgpu::FilterMode::Nearest) keeps texels crisp
+    /// and [`FilterMode::Linear`](wgpu::FilterMode::Linear) blends between them.
+    /// Meshes sample every texture with its own sampler, so this only affects
+    /// this texture.
+    pub fn with_filter(mut self, gpu: &GpuContext, filter: wgpu::FilterMode) -> Self {
+        self.filter = filter;
+        self.rebuild_sampler(gpu);
+        self
+    }
+
+    /// Returns this texture with its sampler switched to `address_mode` on every axis.
+    ///
+    /// [`AddressMode::Repeat`](wgpu::AddressMode::Repeat) tiles the texture
+    /// when UVs leave `[0, 1]`, while
+    /// [`AddressMode::ClampToEdge`](wgpu::AddressMode::ClampToEdge) stretches
+    /// its border texels and keeps opposite edges from bleeding into each other.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let decal = Texture::from_file(&gpu, "decal.png")?
+    ///     .with_address_mode(&gpu, wgpu::AddressMode::ClampToEdge);
+    /// ```
+    pub fn with_address_mode(mut self, gpu: &GpuContext, address_mode: wgpu::AddressMode) -> Self {
+        self.address_mode = address_mode;
+        self.rebuild_sampler(gpu);
+        self
+    }
+
+    /// Returns the sampler's magnification and minification filter.
+    pub fn filter(&self) -> wgpu::FilterMode {
+        self.filter
+    }
+
+    /// Returns the sampler's address mode.
+    pub fn address_mode(&self) -> wgpu::AddressMode {
+        self.address_mode
+    }
+
+    fn rebuild_sampler(&mut self, gpu: &GpuContext) {
+        self.sampler = create_sampler(&gpu.device, "Texture", self.filter, self.address_mode);
+    }
+
+    /// Load a cubemap from six square images of equal size.
+    ///
+    /// Faces are given in wgpu's layer order: `+X`, `-X`, `+Y`, `-Y`, `+Z`,
+    /// `-Z`. The result has a cube view with linear filtering, ready for
+    /// [`SkyboxNode`](crate::SkyboxNode) or a custom shader's `texture_cube`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or decoded, or if the faces
+    /// aren't square and all the same size.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let sky = Texture::cubemap_from_files(&gpu, [
+    ///     "sky/px.png", "sky/nx.png",
+    ///     "sky/py.png", "sky/ny.png",
+    ///     "sky/pz.png", "sky/nz.png",
+    /// ])?;
+    /// ```
+    pub fn cubemap_from_files(gpu: &GpuContext, paths: [&str; 6]) -> Result<Self, Error> {
+        let faces = paths
+            .iter()
+            .map(|path| Ok(image::open(path)?.to_rgba8()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let size = cubemap_face_size(faces.iter().map(|face| face.dimensions()))?;
+
+        let data: Vec<u8> = faces.into_iter().flat_map(|face| face.into_raw()).collect();
+        Ok(Self::cubemap_from_rgba(gpu, &data, size, paths[0]))
+    }
+
+    /// Create a cubemap from raw RGBA data for six `size × size` faces.
+    ///
+    /// `data` holds the faces back to back in the order `+X`, `-X`, `+Y`,
+    /// `-Y`, `+Z`, `-Z`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != size * size * 4 * 6`.
+    pub fn cubemap_from_rgba(gpu: &GpuContext, data: &[u8], size: u32, label: &str) -> Self {
+        use wgpu::util::DeviceExt;
+
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: size,
+                    height: size,
+                    depth_or_array_layers: 6,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            data,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::Cube),
+            ..Default::default()
+        });
+
+        // Smooth filtering and no wrapping, so face edges meet without seams
+        let (filter, address_mode) = (wgpu::FilterMode::Linear, wgpu::AddressMode::ClampToEdge);
+        let sampler = create_sampler(&gpu.device, label, filter, address_mode);
+
+        Self {
+            texture,
+            view,
+            sampler,
+            filter,
+            address_mode,
+            width: size,
+            height: size,
+            array: false,
+        }
+    }
+
+    /// Load a texture array from images of equal size, one layer per file.
+    ///
+    /// Every layer shares one bind group, so meshes textured from the same
+    /// array batch into a single instanced draw even when each instance shows
+    /// a different layer. Pick the layer per draw with
+    /// [`MeshBuilder::texture_layer`](crate::MeshBuilder::texture_layer). This
+    /// is the scalable way to texture thousands of voxels or tiles.
+    ///
+    /// Layers keep the order of `paths` and use the same nearest-neighbor,
+    /// repeating sampler as [`from_file_nearest`](Self::from_file_nearest). Array textures are
+    /// for meshes only; billboards and custom materials sampling
+    /// `@group(2) @binding(0)` need a regular texture.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or decoded, if `paths` is
+    /// empty, or if the images aren't all the same size.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let blocks = Texture::array_from_files(&gpu, &[
+    ///     "blocks/dirt.png",
+    ///     "blocks/grass.png",
+    ///     "blocks/stone.png",
+    /// ])?;
+    /// ```
+    pub fn array_from_files(gpu: &GpuContext, paths: &[&str]) -> Result<Self, Error> {
+        let layers = paths
+            .iter()
+            .map(|path| Ok(image::open(path)?.to_rgba8()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let (width, height) = layer_size(layers.iter().map(|layer| layer.dimensions()))?;
+
+        let count = layers.len() as u32;
+        let data: Vec<u8> = layers
+            .into_iter()
+            .flat_map(|layer| layer.into_raw())
+            .collect();
+        Ok(Self::array_from_rgba(
+            gpu, &data, width, height, count, paths[0],
+        ))
+    }
+
+    /// Create a texture array from raw RGBA data for `layers` images of `width × height`.
+    ///
+    /// `data` holds the layers back to back. See
+    /// [`array_from_files`](Self::array_from_files).
+    ///
+    /// A single layer is stored twice, because the GL backend can't view a
+    /// one-layer texture as an array; [`layer_count`](Self::layer_count)
+    /// reports 2 for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != width * height * 4 * layers`.
+    pub fn array_from_rgba(
+        gpu: &GpuContext,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        layers: u32,
+        label: &str,
+    ) -> Self {
+        use wgpu::util::DeviceExt;
+
+        let padded;
+        let (data, layers) = if layers == 1 {
+            padded = data.repeat(2);
+            (padded.as_slice(), 2)
+        } else {
+            (data, layers)
+        };
+
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: layers,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            data,
+        );
+
+        // Explicit, since a texture with few layers may otherwise get a plain 2D view
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+
+        // Crisp and tiling, for the tile sets and block atlases arrays usually hold
+        let (filter, address_mode) = (wgpu::FilterMode::Nearest, wgpu::AddressMode::Repeat);
+        let sampler = create_sampler(&gpu.device, label, filter, address_mode);
+
+        Self {
+            texture,
+            view,
+            sampler,
+            filter,
+            address_mode,
+            width,
+            height,
+            array: true,
+        }
+    }
+
+    /// Returns `true` for texture arrays created with
+    /// [`array_from_files`](Self::array_from_files) or [`array_from_rgba`](Self::array_from_rgba).
+    pub fn is_array(&self) -> bool {
+        self.array
+    }
+
+    /// Number of layers: the array size for texture arrays (at least 2, see
+    /// [`array_from_rgba`](Self::array_from_rgba)), 6 for cubemaps, otherwise 1.
+    pub fn layer_count(&self) -> u32 {
+        self.texture.depth_or_array_layers()
+    }
+
+    /// Create a floating-point texture from raw RGBA `f32` data.
+    ///
+    /// Keeps values outside `[0, 1]` and the full precision of procedural
+    /// data, for lookup tables, height or displacement maps, and HDR
+    /// environments. The texture samples with linear filtering and clamps at
+    /// the edges, so it binds anywhere a regular texture does, including
+    /// [`MeshBuilder::texture`](crate::MeshBuilder::texture).
+    ///
+    /// The data is stored as `Rgba32Float` when the device can filter 32-bit
+    /// floats. Otherwise it is converted to `Rgba16Float`, which every device
+    /// can filter, at the cost of precision beyond about three decimal digits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != width * height * 4`.
+    pub fn from_f32_rgba(
+        gpu: &GpuContext,
+        data: &[f32],
+        width: u32,
+        height: u32,
+        label: &str,
+    ) -> Self {
+        use wgpu::util::DeviceExt;
+
+        assert_eq!(
+            data.len(),
+            (width * height * 4) as usize,
+            "expected {}x{} RGBA f32 pixels",
+            width,
+            height
+        );
+        let filterable = gpu
+            .device
+            .features()
+            .contains(wgpu::Features::FLOAT32_FILTERABLE);
+        let (format, bytes) = float_texture_data(data, filterable);
+
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
+            &wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &bytes,
+        );
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Smooth and unwrapped, so lookup tables don't bleed across edges
+        let (filter, address_mode) = (wgpu::FilterMode::Linear, wgpu::AddressMode::ClampToEdge);
+        let sampler = create_sampler(&gpu.device, label, filter, address_mode);
+
+        Self {
+            texture,
+            view,
+            sampler,
+            filter,
+            address_mode,
+            width,
+            height,
+            array: false,
+        }
+    }
+
+    /// Load a Radiance HDR (`.hdr`) image as a floating-point texture.
+    ///
+    /// See [`from_f32_rgba`](Self::from_f32_rgba) for how the data is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded.
+    pub fn from_hdr_file(gpu: &GpuContext, path: &str) -> Result<Self, Error> {
+        Self::from_float_image_file(gpu, path, image::ImageFormat::Hdr)
+    }
+
+    /// Load an OpenEXR (`.exr`) image as a floating-point texture.
+    ///
+    /// See [`from_f32_rgba`](Self::from_f32_rgba) for how the data is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded.
+    pub fn from_exr_file(gpu: &GpuContext, path: &str) -> Result<Self, Error> {
+        Self::from_float_image_file(gpu, path, image::ImageFormat::OpenExr)
+    }
+
+    fn from_float_image_file(
+        gpu: &GpuContext,
+        path: &str,
+        format: image::ImageFormat,
+    ) -> Result<Self, Error> {
+        let img = load_float_image(path, format)?;
+        let (width, height) = img.dimensions();
+        Ok(Self::from_f32_rgba(gpu, &img, width, height, path))
+    }
+
+    /// Load a tangent-space normal map from an image file.
+    ///
+    /// See [`normal_map_from_rgba`](Self::normal_map_from_rgba) for how the
+    /// data is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or decoded.
+    pub fn normal_map_from_file(gpu: &GpuContext, path: &str) -> Result<Self, Error> {
+        let img = image::open(path)?.to_rgba8();
+        let (width, height) = img.dimensions();
+        Ok(Self::normal_map_from_rgba(gpu, &img, width, height, path))
+    }
+
+    /// Load a tangent-space normal map from embedded bytes.
+    ///
+    /// See [`normal_map_from_rgba`](Self::normal_map_from_rgba) for how the
+    /// data is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes cannot be decoded as an image.
+    pub fn normal_map_from_bytes(
         gpu: &GpuContext,
         bytes: &[u8],
         label: &str,
-    ) -> Result<Self, image::ImageError> {
+    ) -> Result<Self, Error> {
         let img = image::load_from_memory(bytes)?.to_rgba8();
         let (width, height) = img.dimensions();
-        Ok(Self::from_rgba(gpu, &img, width, height, label))
+        Ok(Self::normal_map_from_rgba(gpu, &img, width, height, label))
     }
 
     /// Generate a procedural blocky noise texture.
@@ -265,7 +781,7 @@ impl Texture {
             }
         }
 
-        Self::from_rgba(gpu, &data, size, size, "Blocky Noise Texture")
+        Self::from_rgba_nearest(gpu, &data, size, size, "Blocky Noise Texture")
     }
 
     /// Generate a procedural grass-top block texture.
@@ -306,7 +822,7 @@ impl Texture {
             }
         }
 
-        Self::from_rgba(gpu, &data, size, size, "Blocky Grass Texture")
+        Self::from_rgba_nearest(gpu, &data, size, size, "Blocky Grass Texture")
     }
 
     /// Generate a procedural stone texture.
@@ -360,7 +876,7 @@ impl Texture {
             }
         }
 
-        Self::from_rgba(gpu, &data, size, size, "Blocky Stone Texture")
+        Self::from_rgba_nearest(gpu, &data, size, size, "Blocky Stone Texture")
     }
 
     /// Simple hash function for procedural generation.
@@ -379,6 +895,28 @@ impl Texture {
         h ^= h >> 16;
         h
     }
+
+    /// Save the texture to a PNG file.
+    ///
+    /// Reads the pixels back from the GPU, blocking until all submitted work
+    /// has finished, so keep this out of per-frame code. Useful for keeping
+    /// the output of procedural generators like [`blocky_noise`](Self::blocky_noise).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for textures that can't be read back, such as
+    /// cubemaps, floating-point textures, and render targets, or if the file
+    /// cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let stone = Texture::blocky_stone(&gpu, 16, 42);
+    /// stone.save_png(&gpu, "stone.png")?;
+    /// ```
+    pub fn save_png(&self, gpu: &GpuContext, path: &str) -> Result<(), Error> {
+        save_texture_png(&gpu.device, &gpu.queue, &self.texture, path)
+    }
 }
 
 impl Sprite {
@@ -414,7 +952,9 @@ impl Sprite {
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
                 format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST
+                    | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             },
             wgpu::util::TextureDataOrder::LayerMajor,
@@ -436,6 +976,7 @@ impl Sprite {
         });
 
         Self {
+            texture,
             view,
             sampler,
             width,
@@ -481,7 +1022,9 @@ impl Sprite {
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
                 format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_DST
+                    | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             },
             wgpu::util::TextureDataOrder::LayerMajor,
@@ -502,6 +1045,7 @@ impl Sprite {
         });
 
         Self {
+            texture,
             view,
             sampler,
             width,
@@ -519,7 +1063,7 @@ impl Sprite {
     /// # Errors
     ///
     /// Returns an error if the file cannot be read or decoded.
-    pub fn from_file(gpu: &GpuContext, path: &str) -> Result<Self, image::ImageError> {
+    pub fn from_file(gpu: &GpuContext, path: &str) -> Result<Self, Error> {
         let img = image::open(path)?.to_rgba8();
         let (width, height) = img.dimensions();
         Ok(Self::from_rgba(gpu, &img, width, height, path))
@@ -537,7 +1081,7 @@ impl Sprite {
     /// # Errors
     ///
     /// Returns an error if the file cannot be read or decoded.
-    pub fn from_file_nearest(gpu: &GpuContext, path: &str) -> Result<Self, image::ImageError> {
+    pub fn from_file_nearest(gpu: &GpuContext, path: &str) -> Result<Self, Error> {
         let img = image::open(path)?.to_rgba8();
         let (width, height) = img.dimensions();
         Ok(Self::from_rgba_nearest(gpu, &img, width, height, path))
@@ -556,11 +1100,7 @@ impl Sprite {
     /// # Errors
     ///
     /// Returns an error if the bytes cannot be decoded as an image.
-    pub fn from_bytes(
-        gpu: &GpuContext,
-        bytes: &[u8],
-        label: &str,
-    ) -> Result<Self, image::ImageError> {
+    pub fn from_bytes(gpu: &GpuContext, bytes: &[u8], label: &str) -> Result<Self, Error> {
         let img = image::load_from_memory(bytes)?.to_rgba8();
         let (width, height) = img.dimensions();
         Ok(Self::from_rgba(gpu, &img, width, height, label))
@@ -580,13 +1120,253 @@ impl Sprite {
     /// # Errors
     ///
     /// Returns an error if the bytes cannot be decoded as an image.
-    pub fn from_bytes_nearest(
-        gpu: &GpuContext,
-        bytes: &[u8],
-        label: &str,
-    ) -> Result<Self, image::ImageError> {
+    pub fn from_bytes_nearest(gpu: &GpuContext, bytes: &[u8], label: &str) -> Result<Self, Error> {
         let img = image::load_from_memory(bytes)?.to_rgba8();
         let (width, height) = img.dimensions();
         Ok(Self::from_rgba_nearest(gpu, &img, width, height, label))
     }
+
+    /// Save the sprite to a PNG file.
+    ///
+    /// Reads the pixels back from the GPU, blocking until all submitted work
+    /// has finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sprite can't be read back or the file cannot
+    /// be written.
+    pub fn save_png(&self, gpu: &GpuContext, path: &str) -> Result<(), Error> {
+        save_texture_png(&gpu.device, &gpu.queue, &self.texture, path)
+    }
+}
+
+/// Reads back a single-layer 8-bit RGBA texture and writes it to `path` as PNG.
+fn save_texture_png(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    path: &str,
+) -> Result<(), Error> {
+    let unreadable = || {
+        Error::Screenshot(format!(
+            "{}: can't read back {:?} texture with {} layers",
+            path,
+            texture.format(),
+            texture.depth_or_array_layers()
+        ))
+    };
+    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC)
+        || texture.depth_or_array_layers() != 1
+    {
+        return Err(unreadable());
+    }
+    let image = read_texture_rgba(device, queue, texture).ok_or_else(unreadable)?;
+    image.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+/// Creates a texture sampler with `filter` for magnification and minification
+/// and `address_mode` on every axis.
+fn create_sampler(
+    device: &wgpu::Device,
+    label: &str,
+    filter: wgpu::FilterMode,
+    address_mode: wgpu::AddressMode,
+) -> wgpu::Sampler {
+    device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some(&format!("{} Sampler", label)),
+        address_mode_u: address_mode,
+        address_mode_v: address_mode,
+        address_mode_w: address_mode,
+        mag_filter: filter,
+        min_filter: filter,
+        mipmap_filter: wgpu::FilterMode::Nearest,
+        ..Default::default()
+    })
+}
+
+/// Decodes the image at `path` as `format` into RGBA `f32` pixels.
+fn load_float_image(path: &str, format: image::ImageFormat) -> Result<image::Rgba32FImage, Error> {
+    let reader = image::ImageReader::with_format(
+        std::io::BufReader::new(std::fs::File::open(path)?),
+        format,
+    );
+    Ok(reader.decode()?.into_rgba32f())
+}
+
+/// Picks the texture format for RGBA `f32` data and encodes the data for it.
+///
+/// Uses `Rgba32Float` when the device can filter it, else `Rgba16Float`.
+fn float_texture_data(data: &[f32], filterable: bool) -> (wgpu::TextureFormat, Vec<u8>) {
+    if filterable {
+        (
+            wgpu::TextureFormat::Rgba32Float,
+            bytemuck::cast_slice(data).to_vec(),
+        )
+    } else {
+        let halves: Vec<u16> = data
+            .iter()
+            .map(|value| half::f16::from_f32(*value).to_bits())
+            .collect();
+        (
+            wgpu::TextureFormat::Rgba16Float,
+            bytemuck::cast_slice(&halves).to_vec(),
+        )
+    }
+}
+
+/// Returns the edge length shared by six cubemap faces.
+///
+/// Fails unless every face is square and the same size.
+fn cubemap_face_size(sizes: impl Iterator<Item = (u32, u32)>) -> Result<u32, Error> {
+    let (width, height) = layer_size(sizes)?;
+    if width != height {
+        return Err(dimension_mismatch());
+    }
+    Ok(width)
+}
+
+/// Returns the shared size of texture layers, or an error if there are none
+/// or their sizes differ.
+fn layer_size(mut sizes: impl Iterator<Item = (u32, u32)>) -> Result<(u32, u32), Error> {
+    let size = sizes.next().ok_or_else(dimension_mismatch)?;
+    if !sizes.all(|other| other == size) {
+        return Err(dimension_mismatch());
+    }
+    Ok(size)
+}
+
+fn dimension_mismatch() -> Error {
+    use image::error::{ImageError, ParameterError, ParameterErrorKind};
+
+    Error::Image(ImageError::Parameter(ParameterError::from_kind(
+        ParameterErrorKind::DimensionMismatch,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cubemap_faces_must_be_square_and_equal() {
+        assert_eq!(cubemap_face_size([(64, 64); 6].into_iter()).unwrap(), 64);
+
+        let mut sizes = [(64, 64); 6];
+        sizes[3] = (32, 32);
+        assert!(matches!(
+            cubemap_face_size(sizes.into_iter()),
+            Err(Error::Image(_))
+        ));
+        assert!(cubemap_face_size([(64, 32); 6].into_iter()).is_err());
+    }
+
+    #[test]
+    fn array_layers_must_share_a_size() {
+        assert_eq!(layer_size([(16, 8); 3].into_iter()).unwrap(), (16, 8));
+        assert!(layer_size([(16, 8), (16, 8), (8, 8)].into_iter()).is_err());
+        assert!(layer_size(std::iter::empty()).is_err());
+    }
+
+    #[test]
+    fn textures_carry_their_sampler_modes() {
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+        let pixel = [255; 4];
+
+        let smooth = Texture::from_rgba(&gpu, &pixel, 1, 1, "smooth");
+        assert_eq!(smooth.filter(), wgpu::FilterMode::Linear);
+        assert_eq!(smooth.address_mode(), wgpu::AddressMode::Repeat);
+
+        let crisp = Texture::from_rgba_nearest(&gpu, &pixel, 1, 1, "crisp");
+        assert_eq!(crisp.filter(), wgpu::FilterMode::Nearest);
+        assert_eq!(
+            Texture::blocky_stone(&gpu, 4, 1).filter(),
+            wgpu::FilterMode::Nearest
+        );
+
+        let clamped = crisp
+            .with_filter(&gpu, wgpu::FilterMode::Linear)
+            .with_address_mode(&gpu, wgpu::AddressMode::ClampToEdge);
+        assert_eq!(clamped.filter(), wgpu::FilterMode::Linear);
+        assert_eq!(clamped.address_mode(), wgpu::AddressMode::ClampToEdge);
+    }
+
+    #[test]
+    fn saved_png_round_trips_pixels() {
+        use wgpu::util::DeviceExt;
+
+        let Some(gpu) = crate::test_support::headless_gpu(1, 1) else {
+            return;
+        };
+
+        // 3 pixels wide, so rows need padding in the readback buffer
+        let pixels: Vec<u8> = (0..3 * 2 * 4).map(|i| (i * 10) as u8).collect();
+        let descriptor = wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 3,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        };
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
+            &descriptor,
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &pixels,
+        );
+
+        let path = std::env::temp_dir().join(format!("hoplite-save-{}.png", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_texture_png(&gpu.device, &gpu.queue, &texture, path).unwrap();
+        assert_eq!(image::open(path).unwrap().to_rgba8().into_raw(), pixels);
+
+        let hidden = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            ..descriptor
+        });
+        assert!(matches!(
+            save_texture_png(&gpu.device, &gpu.queue, &hidden, path),
+            Err(Error::Screenshot(_))
+        ));
+    }
+
+    #[test]
+    fn float_data_falls_back_to_half_precision() {
+        let data = [0.5, 2.0, -1.0, 1000.0];
+        let (format, bytes) = float_texture_data(&data, true);
+        assert_eq!(format, wgpu::TextureFormat::Rgba32Float);
+        assert_eq!(bytes, bytemuck::cast_slice::<f32, u8>(&data));
+
+        let (format, bytes) = float_texture_data(&data, false);
+        assert_eq!(format, wgpu::TextureFormat::Rgba16Float);
+        let halves: Vec<f32> = bytes
+            .chunks(2)
+            .map(|b| half::f16::from_le_bytes([b[0], b[1]]).to_f32())
+            .collect();
+        assert_eq!(halves, data);
+    }
+
+    #[test]
+    fn hdr_files_decode_values_above_one() {
+        let path = std::env::temp_dir().join(format!("hoplite-{}.hdr", std::process::id()));
+        let pixels = [image::Rgb([4.0f32, 0.5, 0.0]), image::Rgb([1.0, 1.0, 1.0])];
+        let file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+        image::codecs::hdr::HdrEncoder::new(file)
+            .encode(&pixels, 2, 1)
+            .unwrap();
+
+        let img = load_float_image(path.to_str().unwrap(), image::ImageFormat::Hdr).unwrap();
+        assert_eq!(img.dimensions(), (2, 1));
+        assert_eq!(img.get_pixel(0, 0).0, [4.0, 0.5, 0.0, 1.0]);
+        assert!(load_float_image(path.to_str().unwrap(), image::ImageFormat::OpenExr).is_err());
+    }
 }
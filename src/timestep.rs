@@ -0,0 +1,125 @@
+//! Fixed-timestep updates decoupled from the render rate.
+//!
+//! Physics and other deterministic logic should advance in constant steps no
+//! matter how fast frames are rendered. [`FixedTimestep`] accumulates frame
+//! time and reports how many whole steps are due; the leftover fraction is the
+//! interpolation alpha used to blend the last two simulation states when drawing.
+//!
+//! Most apps register a callback with
+//! [`SetupContext::fixed_update`](crate::SetupContext::fixed_update) and read
+//! [`Frame::fixed_alpha`](crate::Frame::fixed_alpha) while rendering.
+//!
+//! # Example
+//!
+//! ```
+//! use hoplite::FixedTimestep;
+//!
+//! let mut timestep = FixedTimestep::new(0.1);
+//! assert_eq!(timestep.advance(0.25), 2);
+//! assert!((timestep.alpha() - 0.5).abs() < 1e-5);
+//! ```
+
+/// Most fixed steps run in one frame.
+///
+/// After a long stall (a breakpoint, a dragged window) the backlog is dropped
+/// instead of running hundreds of steps and stalling again.
+pub const MAX_FIXED_STEPS_PER_FRAME: u32 = 8;
+
+/// Accumulator that turns variable frame times into whole fixed steps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedTimestep {
+    step: f32,
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    /// Creates a timestep that advances in increments of `step` seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive.
+    pub fn new(step: f32) -> Self {
+        assert!(step > 0.0, "fixed timestep must be positive, got {step}");
+        Self {
+            step,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Length of one fixed step in seconds.
+    pub fn step(&self) -> f32 {
+        self.step
+    }
+
+    /// Adds a frame's `dt` and returns how many fixed steps are now due.
+    ///
+    /// At most [`MAX_FIXED_STEPS_PER_FRAME`] steps are returned; any further
+    /// backlog is discarded.
+    pub fn advance(&mut self, dt: f32) -> u32 {
+        self.accumulator += dt.max(0.0);
+        let due = (self.accumulator / self.step) as u32;
+        let steps = due.min(MAX_FIXED_STEPS_PER_FRAME);
+        self.accumulator = if due > steps {
+            0.0
+        } else {
+            self.accumulator - steps as f32 * self.step
+        };
+        steps
+    }
+
+    /// Fraction of a step left in the accumulator, from 0.0 up to 1.0.
+    ///
+    /// Blend between the previous and current simulation state with this to
+    /// render smoothly between fixed steps.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.step).clamp(0.0, 1.0)
+    }
+}
+
+/// Callback run once per fixed step with the ECS world and the step length.
+pub(crate) type FixedUpdateFn = Box<dyn FnMut(&mut hecs::World, f32)>;
+
+/// A fixed-update callback registered during setup, with its accumulator.
+pub(crate) struct FixedUpdate {
+    pub(crate) timestep: FixedTimestep,
+    pub(crate) callback: FixedUpdateFn,
+}
+
+impl FixedUpdate {
+    /// Runs every step due after `dt` seconds and returns the interpolation alpha.
+    pub(crate) fn run(&mut self, world: &mut hecs::World, dt: f32) -> f32 {
+        let step = self.timestep.step();
+        for _ in 0..self.timestep.advance(dt) {
+            (self.callback)(world, step);
+        }
+        self.timestep.alpha()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_partial_frames_into_whole_steps() {
+        let mut timestep = FixedTimestep::new(0.25);
+
+        // Two half-step frames make one step
+        assert_eq!(timestep.advance(0.125), 0);
+        assert_eq!(timestep.alpha(), 0.5);
+        assert_eq!(timestep.advance(0.125), 1);
+        assert_eq!(timestep.alpha(), 0.0);
+
+        // A long frame runs several steps and keeps the remainder
+        assert_eq!(timestep.advance(0.625), 2);
+        assert_eq!(timestep.alpha(), 0.5);
+    }
+
+    #[test]
+    fn long_stalls_drop_the_backlog() {
+        let mut timestep = FixedTimestep::new(0.01);
+        assert_eq!(timestep.advance(5.0), MAX_FIXED_STEPS_PER_FRAME);
+        assert_eq!(timestep.alpha(), 0.0);
+        assert_eq!(timestep.advance(0.0), 0);
+    }
+}
@@ -0,0 +1,256 @@
+//! Immediate-mode widgets: buttons, sliders, and checkboxes.
+//!
+//! Widgets keep no state of their own. Each frame they are drawn from the
+//! caller's values and hit-tested against the left mouse button, so the code
+//! that draws a widget is also the code that reacts to it. A widget becomes
+//! active when the button goes down over it and stays active until release,
+//! even if the cursor wanders off, which keeps slider drags from dropping.
+//!
+//! Use them through [`Frame::button`](crate::Frame::button),
+//! [`Frame::slider`](crate::Frame::slider), and
+//! [`Frame::checkbox`](crate::Frame::checkbox).
+
+use glam::Vec2;
+use winit::event::MouseButton;
+
+use crate::assets::{Assets, FontId};
+use crate::draw2d::{Color, Draw2d, Rect, TextAlign};
+use crate::input::Input;
+
+const IDLE: Color = Color::rgba(0.22, 0.22, 0.26, 0.9);
+const HOVERED: Color = Color::rgba(0.32, 0.32, 0.38, 0.9);
+const ACTIVE: Color = Color::rgba(0.16, 0.16, 0.2, 0.9);
+const ACCENT: Color = Color::rgba(0.35, 0.6, 0.95, 1.0);
+
+/// Width of a slider's handle in pixels.
+const HANDLE_WIDTH: f32 = 6.0;
+
+/// How the mouse relates to a widget this frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WidgetState {
+    Idle,
+    /// Under the cursor with the mouse button up, or held down elsewhere.
+    Hovered,
+    /// The mouse button went down on the widget and is still held, or was
+    /// released this frame.
+    Active,
+}
+
+impl WidgetState {
+    fn of(input: &Input, rect: Rect) -> Self {
+        let pressed_here = input
+            .mouse_press_position(MouseButton::Left)
+            .is_some_and(|at| rect.contains(at));
+        if pressed_here {
+            Self::Active
+        } else if rect.contains(input.mouse_position()) {
+            Self::Hovered
+        } else {
+            Self::Idle
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Idle => IDLE,
+            Self::Hovered => HOVERED,
+            Self::Active => ACTIVE,
+        }
+    }
+}
+
+/// Whether the mouse button was pressed and released over `rect`.
+fn clicked(input: &Input, rect: Rect) -> bool {
+    input.mouse_released(MouseButton::Left)
+        && WidgetState::of(input, rect) == WidgetState::Active
+        && rect.contains(input.mouse_position())
+}
+
+/// Value of a slider across `rect` with the cursor at `x`.
+fn slider_value(rect: Rect, x: f32, min: f32, max: f32) -> f32 {
+    let t = if rect.width > 0.0 {
+        ((x - rect.x) / rect.width).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    min + (max - min) * t
+}
+
+/// Draws `text` centered in `rect`.
+fn centered_label(draw: &mut Draw2d, assets: &Assets, font: FontId, rect: Rect, text: &str) {
+    let size = draw.measure_text(assets, font, text);
+    draw.text_aligned(
+        assets,
+        font,
+        rect.x + rect.width * 0.5,
+        rect.y + (rect.height - size.y) * 0.5,
+        text,
+        Color::WHITE,
+        TextAlign::Center,
+    );
+}
+
+/// Draws a button and returns `true` on the frame it's clicked.
+pub(crate) fn button(
+    draw: &mut Draw2d,
+    assets: &Assets,
+    font: FontId,
+    input: &Input,
+    rect: Rect,
+    label: &str,
+) -> bool {
+    let state = WidgetState::of(input, rect);
+    draw.panel(rect.x, rect.y, rect.width, rect.height)
+        .background(state.color())
+        .draw(assets);
+    centered_label(draw, assets, font, rect, label);
+    clicked(input, rect)
+}
+
+/// Draws a horizontal slider and drags `value` while it's active.
+///
+/// Returns `true` if `value` changed. The value is printed on the slider
+/// when `font` is given.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn slider(
+    draw: &mut Draw2d,
+    assets: &Assets,
+    font: Option<FontId>,
+    input: &Input,
+    rect: Rect,
+    value: &mut f32,
+    min: f32,
+    max: f32,
+) -> bool {
+    let state = WidgetState::of(input, rect);
+    let old = *value;
+    if state == WidgetState::Active && input.mouse_down(MouseButton::Left) {
+        *value = slider_value(rect, input.mouse_position().x, min, max);
+    }
+
+    let t = if max != min {
+        ((*value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    draw.panel(rect.x, rect.y, rect.width, rect.height)
+        .background(state.color())
+        .draw(assets);
+    let fill = rect.width * t;
+    draw.rect(
+        rect.x,
+        rect.y,
+        fill,
+        rect.height,
+        Color::rgba(ACCENT.r, ACCENT.g, ACCENT.b, 0.35),
+    );
+    let handle_x = (rect.x + fill - HANDLE_WIDTH * 0.5)
+        .clamp(rect.x, rect.x + (rect.width - HANDLE_WIDTH).max(0.0));
+    draw.rect(handle_x, rect.y, HANDLE_WIDTH, rect.height, ACCENT);
+    if let Some(font) = font {
+        centered_label(draw, assets, font, rect, &format!("{:.2}", *value));
+    }
+
+    *value != old
+}
+
+/// Draws a checkbox and flips `checked` when it's clicked.
+///
+/// Returns `true` if `checked` changed.
+pub(crate) fn checkbox(
+    draw: &mut Draw2d,
+    assets: &Assets,
+    input: &Input,
+    rect: Rect,
+    checked: &mut bool,
+) -> bool {
+    let state = WidgetState::of(input, rect);
+    let toggled = clicked(input, rect);
+    if toggled {
+        *checked = !*checked;
+    }
+
+    draw.panel(rect.x, rect.y, rect.width, rect.height)
+        .background(state.color())
+        .draw(assets);
+    if *checked {
+        let inset = Vec2::new(rect.width, rect.height) * 0.25;
+        draw.rect(
+            rect.x + inset.x,
+            rect.y + inset.y,
+            rect.width - inset.x * 2.0,
+            rect.height - inset.y * 2.0,
+            ACCENT,
+        );
+    }
+
+    toggled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::{ElementState, WindowEvent};
+
+    fn cursor_to(input: &mut Input, x: f64, y: f64) {
+        input.handle_event(&WindowEvent::CursorMoved {
+            device_id: winit::event::DeviceId::dummy(),
+            position: winit::dpi::PhysicalPosition::new(x, y),
+        });
+    }
+
+    fn left(input: &mut Input, state: ElementState) {
+        input.handle_mouse_button(MouseButton::Left, state);
+    }
+
+    const RECT: Rect = Rect {
+        x: 10.0,
+        y: 10.0,
+        width: 100.0,
+        height: 20.0,
+    };
+
+    #[test]
+    fn clicks_need_press_and_release_on_the_widget() {
+        let mut input = Input::new();
+        cursor_to(&mut input, 50.0, 20.0);
+        assert_eq!(WidgetState::of(&input, RECT), WidgetState::Hovered);
+
+        left(&mut input, ElementState::Pressed);
+        assert_eq!(WidgetState::of(&input, RECT), WidgetState::Active);
+        assert!(!clicked(&input, RECT));
+        input.begin_frame();
+        left(&mut input, ElementState::Released);
+        assert!(clicked(&input, RECT));
+
+        // Pressing elsewhere and releasing over the widget isn't a click
+        input.begin_frame();
+        cursor_to(&mut input, 200.0, 20.0);
+        left(&mut input, ElementState::Pressed);
+        input.begin_frame();
+        cursor_to(&mut input, 50.0, 20.0);
+        left(&mut input, ElementState::Released);
+        assert!(!clicked(&input, RECT));
+
+        // Neither is pressing on it and releasing off it
+        input.begin_frame();
+        left(&mut input, ElementState::Pressed);
+        input.begin_frame();
+        cursor_to(&mut input, 200.0, 20.0);
+        left(&mut input, ElementState::Released);
+        assert!(!clicked(&input, RECT));
+        input.begin_frame();
+        assert_eq!(WidgetState::of(&input, RECT), WidgetState::Idle);
+    }
+
+    #[test]
+    fn slider_values_follow_the_cursor_and_clamp() {
+        assert_eq!(slider_value(RECT, 60.0, 0.0, 1.0), 0.5);
+        assert_eq!(slider_value(RECT, 0.0, -2.0, 2.0), -2.0);
+        assert_eq!(slider_value(RECT, 500.0, -2.0, 2.0), 2.0);
+        assert_eq!(
+            slider_value(Rect::new(0.0, 0.0, 0.0, 0.0), 5.0, 1.0, 2.0),
+            1.0
+        );
+    }
+}
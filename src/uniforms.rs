@@ -0,0 +1,130 @@
+//! User-defined uniform buffers for effect and post-process shaders.
+//!
+//! Passes created with [`EffectPass::with_uniforms`](crate::EffectPass::with_uniforms)
+//! or [`PostProcessPass::with_uniforms`](crate::PostProcessPass::with_uniforms)
+//! get an extra uniform buffer bound at `@group(1) @binding(0)`, sized for a
+//! `#[repr(C)]` Pod type whose layout matches a WGSL struct in the shader:
+//!
+//! ```wgsl
+//! struct Params {
+//!     threshold: f32,
+//!     intensity: f32,
+//! }
+//! @group(1) @binding(0) var<uniform> params: Params;
+//! ```
+//!
+//! Values are uploaded with `set_uniforms` on the pass, or through a
+//! [`UniformHandle`] once the pass has moved into a render graph.
+
+use crate::gpu::GpuContext;
+
+/// Cloneable handle to a pass's user uniform buffer.
+///
+/// Obtained from a pass's `uniform_handle()` or returned by
+/// [`SetupContext::effect_with_uniforms`](crate::SetupContext::effect_with_uniforms),
+/// so parameters can change every frame after the pass joins a render graph.
+///
+/// # Example
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct Fog { density: f32, _pad: [f32; 3] }
+///
+/// let fog = ctx.post_process_with_uniforms(FOG_SHADER, &Fog { density: 0.02, _pad: [0.0; 3] });
+///
+/// move |frame| {
+///     if let Some(fog) = &fog {
+///         frame.set_uniforms(fog, &Fog { density: 0.02 + frame.time.sin() * 0.01, _pad: [0.0; 3] });
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct UniformHandle {
+    buffer: wgpu::Buffer,
+    /// Size of the Pod type the buffer was created for.
+    size: usize,
+}
+
+impl UniformHandle {
+    /// Uploads `value`; it takes effect the next time the pass renders.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is not the size of the type the pass was created with.
+    pub fn set<T: bytemuck::Pod>(&self, gpu: &GpuContext, value: &T) {
+        assert_eq!(
+            std::mem::size_of::<T>(),
+            self.size,
+            "uniform type size doesn't match the type the pass was created with"
+        );
+        gpu.queue
+            .write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+    }
+}
+
+/// GPU resources for a pass's user uniforms at `@group(1) @binding(0)`.
+pub(crate) struct UserUniforms {
+    pub(crate) handle: UniformHandle,
+    pub(crate) bind_group_layout: wgpu::BindGroupLayout,
+    pub(crate) bind_group: wgpu::BindGroup,
+}
+
+impl UserUniforms {
+    /// Allocates a zeroed uniform buffer for `size` bytes of user data.
+    pub(crate) fn new(device: &wgpu::Device, size: usize) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("User Uniforms"),
+            size: buffer_size(size),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("User Uniforms Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("User Uniforms Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        Self {
+            handle: UniformHandle { buffer, size },
+            bind_group_layout,
+            bind_group,
+        }
+    }
+}
+
+/// Rounds a uniform struct size up to WGSL's 16-byte uniform alignment.
+fn buffer_size(size: usize) -> u64 {
+    (size.max(1) as u64).next_multiple_of(16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_size_rounds_up_to_sixteen_bytes() {
+        assert_eq!(buffer_size(0), 16);
+        assert_eq!(buffer_size(4), 16);
+        assert_eq!(buffer_size(16), 16);
+        assert_eq!(buffer_size(20), 32);
+    }
+}